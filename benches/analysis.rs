@@ -0,0 +1,55 @@
+// SPDX-FileCopyrightText: 2026 RAprogramm
+// SPDX-License-Identifier: MIT
+
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use sql_query_analyzer::{
+    query::{SqlDialect, parse_queries},
+    rules::RuleRunner
+};
+
+/// Statement shapes covering joins, subqueries, DML, and DDL
+const CORPUS: [&str; 8] = [
+    "SELECT * FROM users WHERE email LIKE '%@example.com'",
+    "SELECT u.id, o.total FROM users u JOIN orders o ON o.user_id = u.id WHERE o.status = 'paid' ORDER BY o.created_at DESC LIMIT 20",
+    "SELECT status, COUNT(*) FROM orders GROUP BY status HAVING COUNT(*) > 10",
+    "SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM bans)",
+    "UPDATE users SET name = 'x' WHERE id = $1",
+    "DELETE FROM sessions WHERE expires_at < NOW()",
+    "INSERT INTO orders (id, user_id, status) VALUES (1, 2, 'new')",
+    "SELECT id FROM users ORDER BY RAND() LIMIT 1"
+];
+
+fn corpus(size: usize) -> String {
+    CORPUS
+        .iter()
+        .cycle()
+        .take(size)
+        .map(|q| format!("{};\n", q))
+        .collect()
+}
+
+fn bench_parse(c: &mut Criterion) {
+    let sql = corpus(1000);
+    let mut group = c.benchmark_group("parse_queries");
+    group.throughput(Throughput::Elements(1000));
+    group.bench_function("1000_statements", |b| {
+        b.iter(|| parse_queries(black_box(&sql), SqlDialect::Generic).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let queries = parse_queries(&corpus(1000), SqlDialect::Generic).unwrap();
+    let runner = RuleRunner::new();
+    let mut group = c.benchmark_group("rule_runner");
+    group.throughput(Throughput::Elements(queries.len() as u64));
+    group.bench_function("analyze_1000_statements", |b| {
+        b.iter(|| runner.analyze(black_box(&queries)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_parse, bench_analyze);
+criterion_main!(benches);
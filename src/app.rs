@@ -12,6 +12,13 @@
 //! - `convert`: Type conversion between CLI and internal representations
 //! - `helpers`: Utility functions for common operations
 //! - `analyze`: SQL analysis execution logic
+//! - `impact`: Column impact analysis
+//! - `catalog`: Rule catalog export
+//! - `changed`: Per-file analysis for pre-commit hooks
+//! - `report`: Report upload to a dashboard endpoint
+//! - `stats`: Table and column usage statistics
+//! - `validate`: Config file validation
+//! - `validate_schema`: Schema conformance check of the queries
 //!
 //! # Architecture
 //!
@@ -51,17 +58,49 @@
 //! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 //! // Parse CLI arguments and execute the command
 //! let command = Commands::Analyze {
-//!     schema:        "schema.sql".into(),
-//!     queries:       "queries.sql".into(),
-//!     provider:      sql_query_analyzer::cli::Provider::Ollama,
-//!     api_key:       None,
-//!     model:         None,
-//!     ollama_url:    "http://localhost:11434".to_string(),
-//!     dialect:       sql_query_analyzer::cli::Dialect::Generic,
-//!     output_format: sql_query_analyzer::cli::Format::Text,
-//!     verbose:       false,
-//!     dry_run:       false,
-//!     no_color:      false
+//!     schema:               "schema.sql".into(),
+//!     queries:              Some("queries.sql".into()),
+//!     provider:             sql_query_analyzer::cli::Provider::Ollama,
+//!     api_key:              None,
+//!     model:                None,
+//!     ollama_url:           "http://localhost:11434".to_string(),
+//!     dialect:              sql_query_analyzer::cli::Dialect::Generic,
+//!     template:             None,
+//!     input_format:         sql_query_analyzer::cli::InputFormat::Raw,
+//!     output_format:        sql_query_analyzer::cli::Format::Text,
+//!     verbose:              false,
+//!     dry_run:              false,
+//!     no_color:             false,
+//!     compact:              false,
+//!     quiet:                false,
+//!     summary_only:         false,
+//!     stats:                false,
+//!     select:               None,
+//!     fail_on_selected:     false,
+//!     timings:              false,
+//!     no_llm_cache:         false,
+//!     llm_cache_ttl:        86400,
+//!     llm_timeout:          None,
+//!     llm_chunk_tokens:     8000,
+//!     audit_log:            None,
+//!     audit_include_prompt: false,
+//!     deadline:             None,
+//!     concurrency:          None,
+//!     lang:                 "en".to_string(),
+//!     incremental:          false,
+//!     state_file:           ".sql-analyzer-state.json".into(),
+//!     report_url:           None,
+//!     report_header:        Vec::new(),
+//!     also_output:          Vec::new(),
+//!     text_template:        None,
+//!     changed_only:         false,
+//!     files_from:           None,
+//!     since:                None,
+//!     watch:                false,
+//!     min_severity:         None,
+//!     profile:              None,
+//!     fail_on:              sql_query_analyzer::cli::FailOn::Warning,
+//!     no_llm:               false
 //! };
 //!
 //! let config = Config::default();
@@ -72,23 +111,60 @@
 //! ```
 
 mod analyze;
+mod catalog;
+mod changed;
 mod convert;
 mod helpers;
+mod impact;
+mod print_schema;
+mod report;
+mod stats;
 mod types;
+mod validate;
+mod validate_schema;
+mod watch;
 
 #[allow(unused_imports)]
 pub use analyze::run_analyze;
 #[allow(unused_imports)]
-pub use convert::{convert_dialect, convert_format};
+pub use catalog::run_export_catalog;
+#[allow(unused_imports)]
+pub use changed::{git_changed_lines, parse_changed_lines, run_changed_files, run_since};
+#[allow(unused_imports)]
+pub use convert::{
+    convert_catalog_format, convert_dialect, convert_fail_on, convert_format,
+    convert_input_format, convert_profile, convert_schema_format, convert_stats_format,
+    convert_template
+};
 #[allow(unused_imports)]
 pub use helpers::{
-    build_llm_provider, calculate_exit_code, create_output_options, get_effective_model,
-    get_effective_ollama_url, has_llm_access, parse_queries_cached, read_queries_input
+    build_llm_provider, calculate_exit_code, calculate_exit_code_at, create_output_options,
+    get_effective_model, get_effective_ollama_url, has_llm_access, parse_queries_cached,
+    read_file_list, read_queries_input
 };
 #[allow(unused_imports)]
+pub use impact::run_impact;
+#[allow(unused_imports)]
+pub use print_schema::run_print_schema;
+#[allow(unused_imports)]
+pub use report::post_report;
+#[allow(unused_imports)]
+pub use stats::run_stats;
+#[allow(unused_imports)]
 pub use types::{AnalyzeParams, AnalyzeResult, CommandOutput, DryRunInfo};
+#[allow(unused_imports)]
+pub use validate::run_validate_config;
+#[allow(unused_imports)]
+pub use validate_schema::run_validate_schema;
+#[allow(unused_imports)]
+pub use watch::{ChangeSource, DEBOUNCE, run_watch, watch_files};
 
-use crate::{cli::Commands, config::Config, error::AppResult};
+use crate::{
+    cli::{Commands, Format},
+    config::Config,
+    error::{AppResult, config_error},
+    output::format_summary_line
+};
 
 /// Executes a CLI command and produces output ready for display.
 ///
@@ -137,17 +213,49 @@ use crate::{cli::Commands, config::Config, error::AppResult};
 ///
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let command = Commands::Analyze {
-///     schema:        PathBuf::from("schema.sql"),
-///     queries:       PathBuf::from("queries.sql"),
-///     provider:      Provider::Ollama,
-///     api_key:       None,
-///     model:         None,
-///     ollama_url:    "http://localhost:11434".to_string(),
-///     dialect:       Dialect::Generic,
-///     output_format: Format::Text,
-///     verbose:       false,
-///     dry_run:       false,
-///     no_color:      false
+///     schema:               PathBuf::from("schema.sql"),
+///     queries:              Some(PathBuf::from("queries.sql")),
+///     provider:             Provider::Ollama,
+///     api_key:              None,
+///     model:                None,
+///     ollama_url:           "http://localhost:11434".to_string(),
+///     dialect:              Dialect::Generic,
+///     template:             None,
+///     input_format:         sql_query_analyzer::cli::InputFormat::Raw,
+///     output_format:        Format::Text,
+///     verbose:              false,
+///     dry_run:              false,
+///     no_color:             false,
+///     compact:              false,
+///     quiet:                false,
+///     summary_only:         false,
+///     stats:                false,
+///     select:               None,
+///     fail_on_selected:     false,
+///     timings:              false,
+///     no_llm_cache:         false,
+///     llm_cache_ttl:        86400,
+///     llm_timeout:          None,
+///     llm_chunk_tokens:     8000,
+///     audit_log:            None,
+///     audit_include_prompt: false,
+///     deadline:             None,
+///     concurrency:          None,
+///     lang:                 "en".to_string(),
+///     incremental:          false,
+///     state_file:           PathBuf::from(".sql-analyzer-state.json"),
+///     report_url:           None,
+///     report_header:        Vec::new(),
+///     also_output:          Vec::new(),
+///     text_template:        None,
+///     changed_only:         false,
+///     files_from:           None,
+///     since:                None,
+///     watch:                false,
+///     min_severity:         None,
+///     profile:              None,
+///     fail_on:              sql_query_analyzer::cli::FailOn::Warning,
+///     no_llm:               false
 /// };
 ///
 /// let config = Config::default();
@@ -171,28 +279,116 @@ pub async fn execute_command(command: Commands, config: Config) -> AppResult<Com
             model,
             ollama_url,
             dialect,
+            template,
+            input_format,
             output_format,
             verbose,
             dry_run,
-            no_color
+            no_color,
+            compact,
+            quiet,
+            summary_only,
+            stats,
+            select,
+            fail_on_selected,
+            timings,
+            no_llm_cache,
+            llm_cache_ttl,
+            llm_timeout,
+            llm_chunk_tokens,
+            audit_log,
+            audit_include_prompt,
+            deadline,
+            concurrency,
+            lang,
+            incremental,
+            state_file,
+            report_url,
+            report_header,
+            also_output,
+            text_template,
+            changed_only,
+            files_from,
+            since,
+            watch,
+            fail_on,
+            min_severity,
+            profile,
+            no_llm
         } => {
+            let mut config = config;
+            if let Some(profile) = profile {
+                config.rules = config.rules.over_profile(convert_profile(profile));
+            }
             let params = AnalyzeParams {
                 schema_path: schema.display().to_string(),
-                queries_path: if queries.to_str() == Some("-") {
-                    "-".to_string()
-                } else {
-                    queries.display().to_string()
-                },
+                queries_path: queries
+                    .map(|queries| queries.display().to_string())
+                    .unwrap_or_default(),
                 provider,
                 api_key,
                 model,
                 ollama_url,
                 dialect,
+                template,
+                input_format,
                 output_format,
                 verbose,
                 dry_run,
-                no_color
+                no_color,
+                compact,
+                quiet,
+                summary_only,
+                stats,
+                select,
+                fail_on_selected,
+                timings,
+                no_llm_cache,
+                llm_cache_ttl,
+                llm_timeout,
+                llm_chunk_tokens,
+                audit_log,
+                audit_include_prompt,
+                deadline,
+                concurrency,
+                lang,
+                incremental,
+                state_file: state_file.display().to_string(),
+                report_url,
+                report_headers: report_header,
+                also_outputs: also_output,
+                text_template,
+                min_severity,
+                fail_on,
+                skip_llm: changed_only || since.is_some() || watch || no_llm,
+                changed_lines: None
             };
+            if let Some(since) = since {
+                return run_since(params, &since, config).await;
+            }
+            if watch {
+                if !matches!(params.output_format, Format::Text) {
+                    return Err(config_error("--watch only supports text output"));
+                }
+                if params.schema_path == "-" || params.queries_path == "-" {
+                    return Err(config_error(
+                        "--watch needs the schema and queries as files, not stdin"
+                    ));
+                }
+                let changes = watch_files(
+                    vec![
+                        params.schema_path.clone().into(),
+                        params.queries_path.clone().into(),
+                    ],
+                    DEBOUNCE
+                )?;
+                return run_watch(params, config, changes, &mut std::io::stdout()).await;
+            }
+            if changed_only {
+                let files_from = files_from.map(|path| path.display().to_string());
+                let files = read_file_list(files_from.as_deref())?;
+                return run_changed_files(params, &files, config).await;
+            }
             let result = run_analyze(params, config).await?;
             let mut stdout = vec![result.static_output];
             if let Some(dry_run_info) = result.dry_run_info {
@@ -205,7 +401,13 @@ pub async fn execute_command(command: Commands, config: Config) -> AppResult<Com
                     "Queries Summary:\n{}",
                     dry_run_info.queries_summary
                 ));
-            } else if result.llm_output.is_none() && !dry_run {
+            } else if result.llm_output.is_none()
+                && !result.timed_out
+                && !dry_run
+                && !summary_only
+                && !stats
+                && !no_llm
+            {
                 stdout.push(
                     "Note: Set LLM_API_KEY for additional AI-powered analysis\n".to_string()
                 );
@@ -213,11 +415,68 @@ pub async fn execute_command(command: Commands, config: Config) -> AppResult<Com
             if let Some(llm_output) = result.llm_output {
                 stdout.push(llm_output);
             }
+            let mut stderr = Vec::new();
+            if result.timed_out {
+                stderr.push(format!(
+                    "Warning: deadline of {}s reached; showing static analysis only",
+                    deadline.unwrap_or_default()
+                ));
+            }
+            if !quiet {
+                stderr.push(format_summary_line(
+                    &result.summary,
+                    result.queries_count,
+                    result.exit_code
+                ));
+            }
             Ok(CommandOutput {
                 exit_code: result.exit_code,
-                stdout
+                stdout,
+                stderr
             })
         }
+        Commands::ExportCatalog {
+            format
+        } => Ok(run_export_catalog(format)),
+        Commands::PrintSchema {
+            format
+        } => Ok(run_print_schema(format)),
+        Commands::ValidateConfig {
+            path
+        } => run_validate_config(path.as_deref()),
+        Commands::Stats {
+            queries,
+            schema,
+            dialect,
+            format
+        } => run_stats(
+            &queries.display().to_string(),
+            schema.map(|path| path.display().to_string()).as_deref(),
+            dialect,
+            format
+        ),
+        Commands::Impact {
+            table,
+            column,
+            schema,
+            queries,
+            dialect
+        } => run_impact(
+            &schema.display().to_string(),
+            &queries.display().to_string(),
+            dialect,
+            &table,
+            &column
+        ),
+        Commands::ValidateSchema {
+            schema,
+            queries,
+            dialect
+        } => run_validate_schema(
+            &schema.display().to_string(),
+            &queries.display().to_string(),
+            dialect
+        )
     }
 }
 
@@ -228,7 +487,7 @@ mod tests {
     use tempfile::NamedTempFile;
 
     use super::*;
-    use crate::cli::{Dialect, Format, Provider};
+    use crate::cli::{CatalogFormat, Dialect, FailOn, InputFormat, Provider, SchemaFormat};
 
     #[tokio::test]
     async fn test_execute_command_success() {
@@ -237,17 +496,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM users;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -255,6 +546,243 @@ mod tests {
         assert!(!result.stdout.is_empty());
     }
 
+    #[tokio::test]
+    async fn test_execute_command_summary_only() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "DELETE FROM users;").unwrap();
+        let command = Commands::Analyze {
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Json,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         true,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
+        };
+        let config = Config::default();
+        let result = execute_command(command, config).await.unwrap();
+        assert_eq!(result.exit_code, 2);
+        assert_eq!(result.stdout.len(), 1);
+        assert!(!result.stdout[0].contains("violations"));
+        assert!(result.stdout[0].contains("\"errors\": 1"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_no_llm_with_api_key() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "SELECT * FROM users;").unwrap();
+        let command = Commands::Analyze {
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              Some("sk-test".to_string()),
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         true,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               true
+        };
+        let config = Config::default();
+        let result = execute_command(command, config).await.unwrap();
+        assert_eq!(result.stdout.len(), 1);
+        assert!(result.stdout[0].contains("PERF001"));
+        assert!(!result.stdout.concat().contains("LLM_API_KEY"));
+    }
+
+    #[tokio::test]
+    async fn test_run_analyze_skip_llm_with_api_key() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "SELECT * FROM users;").unwrap();
+        let params = AnalyzeParams {
+            schema_path:          schema_file.path().display().to_string(),
+            queries_path:         queries_file.path().display().to_string(),
+            provider:             Provider::OpenAI,
+            api_key:              Some("sk-test".to_string()),
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         true,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           ".sql-analyzer-state.json".to_string(),
+            report_url:           None,
+            report_headers:       Vec::new(),
+            also_outputs:         Vec::new(),
+            text_template:        None,
+            min_severity:         None,
+            fail_on:              FailOn::Warning,
+            skip_llm:             true,
+            changed_lines:        None
+        };
+        let result = run_analyze(params, Config::default()).await.unwrap();
+        assert!(result.llm_output.is_none());
+        assert!(result.dry_run_info.is_none());
+        assert!(result.static_output.contains("PERF001"));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_select_with_fail_on_selected() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "DELETE FROM users; SELECT * FROM users;").unwrap();
+        let analyze = |select: &str, fail_on_selected: bool| Commands::Analyze {
+            schema: schema_file.path().to_path_buf(),
+            queries: Some(queries_file.path().to_path_buf()),
+            provider: Provider::OpenAI,
+            api_key: None,
+            model: None,
+            ollama_url: "http://localhost:11434".to_string(),
+            dialect: Dialect::Generic,
+            template: None,
+            input_format: InputFormat::Raw,
+            output_format: Format::Json,
+            verbose: false,
+            dry_run: false,
+            no_color: true,
+            compact: false,
+            quiet: false,
+            summary_only: false,
+            stats: false,
+            select: Some(select.to_string()),
+            fail_on_selected,
+            timings: false,
+            no_llm_cache: false,
+            llm_cache_ttl: 86400,
+            llm_timeout: None,
+            llm_chunk_tokens: 8000,
+            audit_log: None,
+            audit_include_prompt: false,
+            deadline: None,
+            concurrency: None,
+            lang: "en".to_string(),
+            incremental: false,
+            state_file: PathBuf::from(".sql-analyzer-state.json"),
+            report_url: None,
+            report_header: Vec::new(),
+            also_output: Vec::new(),
+            text_template: None,
+            changed_only: false,
+            files_from: None,
+            since: None,
+            watch: false,
+            min_severity: None,
+            profile: None,
+            fail_on: FailOn::Warning,
+            no_llm: false
+        };
+        let result = execute_command(analyze("query=2", false), Config::default())
+            .await
+            .unwrap();
+        assert_eq!(result.exit_code, 2);
+        assert!(!result.stdout[0].contains("SEC002"));
+        let result = execute_command(analyze("query=2", true), Config::default())
+            .await
+            .unwrap();
+        assert_eq!(result.exit_code, 1);
+        assert!(
+            execute_command(analyze("bogus", false), Config::default())
+                .await
+                .is_err()
+        );
+    }
+
     #[tokio::test]
     async fn test_execute_command_dry_run() {
         let mut schema_file = NamedTempFile::new().unwrap();
@@ -262,17 +790,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM test;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       true,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              true,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -285,17 +845,49 @@ mod tests {
     #[tokio::test]
     async fn test_execute_command_file_not_found() {
         let command = Commands::Analyze {
-            schema:        PathBuf::from("/nonexistent/schema.sql"),
-            queries:       PathBuf::from("/nonexistent/queries.sql"),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               PathBuf::from("/nonexistent/schema.sql"),
+            queries:              Some(PathBuf::from("/nonexistent/queries.sql")),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await;
@@ -309,17 +901,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT * FROM orders;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -333,17 +957,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM items;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Json,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Json,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -358,17 +1014,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM logs;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       true,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              true,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -382,17 +1070,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM events;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Yaml,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Yaml,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -406,17 +1126,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM metrics;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Sarif,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Sarif,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -429,17 +1181,49 @@ mod tests {
         let mut schema_file = NamedTempFile::new().unwrap();
         writeln!(schema_file, "CREATE TABLE stdin_test (id INT);").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       PathBuf::from("-"),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       true,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(PathBuf::from("-")),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              true,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await;
@@ -453,17 +1237,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM t;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Mysql,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Mysql,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -477,17 +1293,49 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM t;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Postgresql,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Postgresql,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
@@ -501,20 +1349,391 @@ mod tests {
         let mut queries_file = NamedTempFile::new().unwrap();
         writeln!(queries_file, "SELECT id FROM t;").unwrap();
         let command = Commands::Analyze {
-            schema:        schema_file.path().to_path_buf(),
-            queries:       queries_file.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Sqlite,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Sqlite,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let config = Config::default();
         let result = execute_command(command, config).await.unwrap();
         assert_eq!(result.exit_code, 0);
     }
+
+    #[tokio::test]
+    async fn test_execute_command_export_catalog() {
+        let command = Commands::ExportCatalog {
+            format: CatalogFormat::Json
+        };
+        let result = execute_command(command, Config::default()).await.unwrap();
+        assert_eq!(result.exit_code, 0);
+        assert!(result.stdout.join("").contains("\"PERF001\""));
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_print_schema() {
+        let command = Commands::PrintSchema {
+            format: SchemaFormat::Sarif
+        };
+        let result = execute_command(command, Config::default()).await.unwrap();
+        assert_eq!(result.exit_code, 0);
+        assert!(result.stdout.join("").contains("\"ruleId\""));
+    }
+
+    /// Analyze params that send `queries_sql` to an Ollama server at
+    /// `ollama_url` and give up after `deadline` seconds
+    fn deadline_params(
+        schema_file: &NamedTempFile,
+        queries_file: &NamedTempFile,
+        ollama_url: String,
+        deadline: u64
+    ) -> AnalyzeParams {
+        AnalyzeParams {
+            schema_path: schema_file.path().display().to_string(),
+            queries_path: queries_file.path().display().to_string(),
+            provider: Provider::Ollama,
+            api_key: None,
+            model: None,
+            ollama_url,
+            dialect: Dialect::Generic,
+            template: None,
+            input_format: InputFormat::Raw,
+            output_format: Format::Text,
+            verbose: false,
+            dry_run: false,
+            no_color: true,
+            compact: false,
+            quiet: true,
+            summary_only: false,
+            stats: false,
+            select: None,
+            fail_on_selected: false,
+            timings: false,
+            no_llm_cache: true,
+            llm_cache_ttl: 86400,
+            llm_timeout: None,
+            llm_chunk_tokens: 8000,
+            audit_log: None,
+            audit_include_prompt: false,
+            deadline: Some(deadline),
+            concurrency: None,
+            lang: "en".to_string(),
+            incremental: false,
+            state_file: ".sql-analyzer-state.json".to_string(),
+            report_url: None,
+            report_headers: Vec::new(),
+            also_outputs: Vec::new(),
+            text_template: None,
+            min_severity: None,
+            fail_on: FailOn::Warning,
+            skip_llm: false,
+            changed_lines: None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_analyze_deadline_returns_static_results() {
+        // Accepts connections but never answers, like a stalled LLM server
+        let slow_provider = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", slow_provider.local_addr().unwrap());
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "SELECT * FROM users;").unwrap();
+        let params = deadline_params(&schema_file, &queries_file, url, 1);
+        let started = std::time::Instant::now();
+        let result = run_analyze(params, Config::default()).await.unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(10));
+        assert!(result.timed_out);
+        assert!(result.llm_output.is_none());
+        assert!(result.static_output.contains("PERF001"));
+        assert_eq!(result.exit_code, 1);
+    }
+
+    #[tokio::test]
+    async fn test_execute_command_deadline_prints_notice() {
+        let slow_provider = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "SELECT * FROM users;").unwrap();
+        let command = Commands::Analyze {
+            schema:               schema_file.path().to_path_buf(),
+            queries:              Some(queries_file.path().to_path_buf()),
+            provider:             Provider::Ollama,
+            api_key:              None,
+            model:                None,
+            ollama_url:           format!("http://{}", slow_provider.local_addr().unwrap()),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                true,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         true,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             Some(1),
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
+        };
+        let result = execute_command(command, Config::default()).await.unwrap();
+        assert_eq!(
+            result.stderr,
+            vec!["Warning: deadline of 1s reached; showing static analysis only"]
+        );
+        assert!(result.stdout.concat().contains("PERF001"));
+        assert!(!result.stdout.concat().contains("LLM_API_KEY"));
+    }
+
+    #[tokio::test]
+    async fn test_run_analyze_deadline_before_static_analysis_fails() {
+        let schema_file = NamedTempFile::new().unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        for i in 0..5000 {
+            writeln!(
+                queries_file,
+                "SELECT * FROM t{} WHERE a LIKE '%x' OR b = 1;",
+                i
+            )
+            .unwrap();
+        }
+        let params = deadline_params(&schema_file, &queries_file, String::new(), 0);
+        let err = run_analyze(params, Config::default()).await.unwrap_err();
+        assert!(err.render_message().contains("deadline of 0s"));
+    }
+
+    #[test]
+    fn test_parse_changed_lines_from_unified_diff() {
+        let diff = "diff --git a/db/q.sql b/db/q.sql\n\
+                    index 1b2c3d4..5e6f7a8 100644\n\
+                    --- a/db/q.sql\n\
+                    +++ b/db/q.sql\n\
+                    @@ -2 +2 @@ SELECT 1;\n\
+                    -SELECT * FROM users;\n\
+                    +SELECT * FROM users LIMIT 5;\n\
+                    @@ -9,0 +10,3 @@\n\
+                    +SELECT 1;\n\
+                    @@ -20,2 +22,0 @@\n\
+                    -SELECT 2;\n\
+                    diff --git a/old.sql b/old.sql\n\
+                    deleted file mode 100644\n\
+                    --- a/old.sql\n\
+                    +++ /dev/null\n\
+                    @@ -1 +0,0 @@\n\
+                    -SELECT 3;\n";
+        let files = parse_changed_lines(diff);
+        assert_eq!(
+            files,
+            vec![("db/q.sql".to_string(), vec![2..=2, 10..=12, 22..=23])]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_analyze_reports_only_changed_statements() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(
+            queries_file,
+            "SELECT * FROM users;\nSELECT *\n  FROM users;\nSELECT * FROM users;"
+        )
+        .unwrap();
+        let params = AnalyzeParams {
+            output_format: Format::Json,
+            deadline: None,
+            skip_llm: true,
+            changed_lines: Some(vec![3..=3]),
+            ..deadline_params(&schema_file, &queries_file, String::new(), 0)
+        };
+        let result = run_analyze(params, Config::default()).await.unwrap();
+        let report: serde_json::Value = serde_json::from_str(&result.static_output).unwrap();
+        let indices: Vec<u64> = report["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v["query_index"].as_u64().unwrap())
+            .collect();
+        assert!(!indices.is_empty());
+        assert!(indices.iter().all(|&index| index == 1), "{:?}", indices);
+    }
+
+    #[tokio::test]
+    async fn test_run_analyze_single_thread_matches_default() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(
+            schema_file,
+            "CREATE TABLE users (id INT PRIMARY KEY, email TEXT);"
+        )
+        .unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        for i in 0..50 {
+            writeln!(
+                queries_file,
+                "SELECT * FROM users WHERE email LIKE '%{}';\nDELETE FROM users;",
+                i
+            )
+            .unwrap();
+        }
+        let params = |concurrency| AnalyzeParams {
+            output_format: Format::Json,
+            deadline: None,
+            concurrency,
+            skip_llm: true,
+            ..deadline_params(&schema_file, &queries_file, String::new(), 0)
+        };
+        let pinned = run_analyze(params(Some(1)), Config::default())
+            .await
+            .unwrap();
+        let default = run_analyze(params(None), Config::default()).await.unwrap();
+        assert_eq!(pinned.static_output, default.static_output);
+        assert_eq!(pinned.exit_code, 2);
+        let report: serde_json::Value = serde_json::from_str(&pinned.static_output).unwrap();
+        assert_eq!(report["queries_count"], 100);
+    }
+
+    /// Change source that rewrites `path` with `sql` on its first call and
+    /// ends the watch on the second
+    struct EditOnce {
+        path: PathBuf,
+        sql:  Option<&'static str>
+    }
+
+    impl ChangeSource for EditOnce {
+        async fn next_change(&mut self) -> Option<Vec<PathBuf>> {
+            let sql = self.sql.take()?;
+            std::fs::write(&self.path, sql).unwrap();
+            Some(vec![self.path.clone()])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_watch_reanalyzes_after_change() {
+        let mut schema_file = NamedTempFile::new().unwrap();
+        writeln!(schema_file, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+        let mut queries_file = NamedTempFile::new().unwrap();
+        writeln!(queries_file, "SELECT id FROM users;").unwrap();
+        let params = AnalyzeParams {
+            deadline: None,
+            skip_llm: true,
+            ..deadline_params(&schema_file, &queries_file, String::new(), 0)
+        };
+        let changes = EditOnce {
+            path: queries_file.path().to_path_buf(),
+            sql:  Some("DELETE FROM users;")
+        };
+        let mut out = Vec::new();
+        let output = run_watch(params, Config::default(), changes, &mut out)
+            .await
+            .unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let runs: Vec<&str> = out.split("\x1b[2J\x1b[H").skip(1).collect();
+        assert_eq!(runs.len(), 2);
+        assert!(!runs[0].contains("SEC002"), "{}", runs[0]);
+        assert!(runs[1].contains("SEC002"), "{}", runs[1]);
+        assert!(runs[1].contains("for changes"));
+        assert_eq!(output.exit_code, 2);
+    }
+
+    #[tokio::test]
+    async fn test_watch_files_sends_one_batch_per_burst() {
+        let queries_file = NamedTempFile::new().unwrap();
+        let path = queries_file.path().to_path_buf();
+        let mut changes =
+            watch_files(vec![path.clone()], std::time::Duration::from_millis(100)).unwrap();
+        for sql in ["SELECT 1;", "SELECT 1;\nSELECT 2;"] {
+            std::fs::write(&path, sql).unwrap();
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let batch = tokio::time::timeout(std::time::Duration::from_secs(5), changes.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(batch, vec![path]);
+        let next =
+            tokio::time::timeout(std::time::Duration::from_millis(300), changes.recv()).await;
+        assert!(next.is_err(), "second batch for the same burst");
+    }
+
+    #[tokio::test]
+    async fn test_watch_files_follows_save_by_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("queries.sql");
+        std::fs::write(&path, "SELECT 1;").unwrap();
+        let mut changes =
+            watch_files(vec![path.clone()], std::time::Duration::from_millis(50)).unwrap();
+        for sql in ["SELECT 2;", "SELECT 3;"] {
+            let staged = dir.path().join("queries.sql.tmp");
+            std::fs::write(&staged, sql).unwrap();
+            std::fs::rename(&staged, &path).unwrap();
+            let batch = tokio::time::timeout(std::time::Duration::from_secs(5), changes.recv())
+                .await
+                .unwrap()
+                .unwrap();
+            assert_eq!(batch, vec![path.clone()]);
+        }
+    }
 }
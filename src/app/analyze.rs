@@ -4,24 +4,40 @@
 //! the complete SQL analysis pipeline, including schema parsing, query
 //! analysis, static rule checking, and optional LLM-powered analysis.
 
-use std::{fs::read_to_string, time::Duration};
+use std::{
+    ops::RangeInclusive,
+    sync::{Mutex, PoisonError},
+    time::Duration
+};
 
-use indicatif::{ProgressBar, ProgressStyle};
+use indicatif::{ProgressBar, ProgressFinish, ProgressStyle};
+use rayon::ThreadPoolBuilder;
+use tokio::{task::spawn_blocking, time::timeout};
 
 use super::{
-    convert::convert_dialect,
+    convert::{convert_dialect, convert_fail_on, convert_input_format, convert_template},
     helpers::{
-        build_llm_provider, calculate_exit_code, create_output_options, get_effective_model,
-        get_effective_ollama_url, has_llm_access, parse_queries_cached, read_queries_input
+        build_llm_provider, create_output_options, get_effective_model, get_effective_ollama_url,
+        has_llm_access, parse_extra_output, parse_queries_cached, read_analyze_inputs,
+        write_extra_outputs
     },
+    report::post_report,
     types::{AnalyzeParams, AnalyzeResult, DryRunInfo}
 };
 use crate::{
     config::Config,
-    error::{AppResult, file_read_error},
-    llm::LlmClient,
-    output::{format_analysis_result, format_queries_summary, format_static_analysis},
-    rules::RuleRunner,
+    error::{AppError, AppResult, deadline_error},
+    incremental::{IncrementalState, analysis_key},
+    llm::{LlmAuditLog, LlmCache, LlmClient, split_batches},
+    locale::Locale,
+    output::{
+        OutputOptions, SourcePaths, format_analysis_result, format_queries_summary,
+        format_queries_summary_from, format_report_statistics, format_report_summary,
+        format_static_analysis, validate_template
+    },
+    preprocessor::{strip_template, unwrap_queries},
+    query::{Query, SqlDialect},
+    rules::{AnalysisReport, RuleRunner, ViolationFilter},
     schema::Schema
 };
 
@@ -34,6 +50,23 @@ use crate::{
 /// 3. **Static Analysis**: Runs all enabled rules against the queries
 /// 4. **LLM Analysis** (optional): Sends schema and queries to LLM for analysis
 ///
+/// With `summary_only` set, the pipeline stops after static analysis and
+/// the output holds only the violation counts; `stats` does the same with
+/// [`Statistics`](crate::rules::Statistics) in place of the counts. A
+/// `select` expression drops non-matching violations before formatting; the
+/// exit code still reflects every violation unless `fail_on_selected` is
+/// set. Likewise, `min_severity` only hides lower-severity violations from the
+/// output. With `incremental` set, a report stored in the state file for
+/// identical inputs replaces the rule engine run; queries read from stdin are
+/// always analyzed. With `report_url` set, the final report is POSTed there;
+/// upload failures are printed to stderr and leave the exit code alone. Only
+/// violations at or above `fail_on` count toward the exit code, and `skip_llm`
+/// ends the run after static analysis. Each `also_outputs` entry writes the
+/// static report to a file in its own format, next to the regular output. A
+/// `text_template` replaces the layout of each violation line in text
+/// output. When `deadline` expires after static analysis, the static result
+/// is returned with `timed_out` set and in-flight HTTP requests are dropped.
+///
 /// # Arguments
 ///
 /// * `params` - Analysis parameters including file paths and options
@@ -46,13 +79,16 @@ use crate::{
 /// - Formatted static analysis output
 /// - Optional LLM analysis output
 /// - Optional dry-run information
+/// - Violation totals for the stderr summary line
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - Schema or query files cannot be read
 /// - SQL parsing fails
+/// - The `select` expression is malformed
 /// - LLM API call fails (when LLM is enabled)
+/// - `deadline` expires before static analysis finishes
 ///
 /// # Example
 ///
@@ -65,17 +101,45 @@ use crate::{
 ///
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// let params = AnalyzeParams {
-///     schema_path:   "schema.sql".to_string(),
-///     queries_path:  "queries.sql".to_string(),
-///     provider:      Provider::Ollama,
-///     api_key:       None,
-///     model:         None,
-///     ollama_url:    "http://localhost:11434".to_string(),
-///     dialect:       Dialect::Generic,
-///     output_format: Format::Text,
-///     verbose:       false,
-///     dry_run:       false,
-///     no_color:      false
+///     schema_path:          "schema.sql".to_string(),
+///     queries_path:         "queries.sql".to_string(),
+///     provider:             Provider::Ollama,
+///     api_key:              None,
+///     model:                None,
+///     ollama_url:           "http://localhost:11434".to_string(),
+///     dialect:              Dialect::Generic,
+///     template:             None,
+///     input_format:         sql_query_analyzer::cli::InputFormat::Raw,
+///     output_format:        Format::Text,
+///     verbose:              false,
+///     dry_run:              false,
+///     no_color:             false,
+///     compact:              false,
+///     quiet:                false,
+///     summary_only:         false,
+///     stats:                false,
+///     select:               None,
+///     fail_on_selected:     false,
+///     timings:              false,
+///     no_llm_cache:         false,
+///     llm_cache_ttl:        86400,
+///     llm_timeout:          None,
+///     llm_chunk_tokens:     8000,
+///     audit_log:            None,
+///     audit_include_prompt: false,
+///     deadline:             None,
+///     concurrency:          None,
+///     lang:                 "en".to_string(),
+///     incremental:          false,
+///     state_file:           ".sql-analyzer-state.json".to_string(),
+///     report_url:           None,
+///     report_headers:       Vec::new(),
+///     also_outputs:         Vec::new(),
+///     text_template:        None,
+///     min_severity:         None,
+///     fail_on:              sql_query_analyzer::cli::FailOn::Warning,
+///     skip_llm:             false,
+///     changed_lines:        None
 /// };
 ///
 /// let config = Config::default();
@@ -85,40 +149,79 @@ use crate::{
 /// # }
 /// ```
 pub async fn run_analyze(params: AnalyzeParams, config: Config) -> AppResult<AnalyzeResult> {
-    let schema_sql = read_to_string(&params.schema_path)
-        .map_err(|e| file_read_error(&params.schema_path, e))?;
-    let queries_sql = read_queries_input(&params.queries_path)?;
-    let sql_dialect = convert_dialect(params.dialect);
-    let parsed_schema = Schema::parse(&schema_sql, sql_dialect)?;
-    let parsed_queries = parse_queries_cached(&queries_sql, sql_dialect)?;
-    let schema_summary = parsed_schema.to_summary();
-    let output_opts = create_output_options(params.output_format, params.no_color, params.verbose);
-    let runner = RuleRunner::with_schema_and_config(parsed_schema.clone(), config.rules.clone());
-    let static_report = runner.analyze(&parsed_queries);
-    let static_output = format_static_analysis(&static_report, &output_opts);
-    let exit_code = calculate_exit_code(&static_report);
+    let completed = Mutex::new(None);
+    let Some(secs) = params.deadline else {
+        return run_pipeline(params, config, &completed).await;
+    };
+    match timeout(
+        Duration::from_secs(secs),
+        run_pipeline(params, config, &completed)
+    )
+    .await
+    {
+        Ok(result) => result,
+        Err(_) => match completed
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+        {
+            Some(result) => Ok(AnalyzeResult {
+                timed_out: true,
+                ..result
+            }),
+            None => Err(deadline_error(secs))
+        }
+    }
+}
+
+/// Static analysis outcome, before the report upload and the LLM step
+struct StaticStage {
+    /// Result to return when nothing after static analysis runs
+    result:         AnalyzeResult,
+    report:         AnalysisReport,
+    queries:        Vec<Query>,
+    schema_summary: String,
+    output_opts:    OutputOptions
+}
+
+/// Run the whole analysis, storing the static-only result in `completed`
+/// as soon as it exists so a deadline can fall back to it
+async fn run_pipeline(
+    params: AnalyzeParams,
+    config: Config,
+    completed: &Mutex<Option<AnalyzeResult>>
+) -> AppResult<AnalyzeResult> {
+    let stage = {
+        let (params, config) = (params.clone(), config.clone());
+        spawn_blocking(move || {
+            with_concurrency(params.concurrency, || run_static(&params, &config))
+        })
+        .await
+        .map_err(|e| AppError::internal(format!("Static analysis aborted: {}", e)))???
+    };
+    *completed.lock().unwrap_or_else(PoisonError::into_inner) = Some(stage.result.clone());
+    if let Some(url) = &params.report_url
+        && let Err(e) = post_report(url, &params.report_headers, &stage.report).await
+    {
+        eprintln!("Warning: {}", e.render_message());
+    }
+    if params.summary_only || params.stats {
+        return Ok(stage.result);
+    }
     if params.dry_run {
-        let queries_summary = format_queries_summary(&parsed_queries, &output_opts);
+        let queries_summary = format_queries_summary(&stage.queries, &stage.output_opts);
         return Ok(AnalyzeResult {
-            exit_code,
-            static_output,
-            llm_output: None,
             dry_run_info: Some(DryRunInfo {
-                schema_summary,
+                schema_summary: stage.schema_summary,
                 queries_summary
-            })
+            }),
+            ..stage.result
         });
     }
     let effective_api_key = params.api_key.or(config.llm.api_key.clone());
     let effective_ollama_url =
         get_effective_ollama_url(params.ollama_url, config.llm.ollama_url.clone());
-    if !has_llm_access(&effective_api_key, &params.provider) {
-        return Ok(AnalyzeResult {
-            exit_code,
-            static_output,
-            llm_output: None,
-            dry_run_info: None
-        });
+    if params.skip_llm || !has_llm_access(&effective_api_key, &params.provider) {
+        return Ok(stage.result);
     }
     let model_name = get_effective_model(params.model, config.llm.model.clone(), &params.provider);
     let llm_provider = build_llm_provider(
@@ -127,21 +230,233 @@ pub async fn run_analyze(params: AnalyzeParams, config: Config) -> AppResult<Ana
         model_name,
         effective_ollama_url
     )?;
-    let pb = ProgressBar::new_spinner();
+    let pb = ProgressBar::new_spinner().with_finish(ProgressFinish::AndClear);
     if let Ok(style) = ProgressStyle::default_spinner().template("{spinner:.green} {msg}") {
         pb.set_style(style);
     }
     pb.set_message("Analyzing queries with LLM...");
     pb.enable_steady_tick(Duration::from_millis(100));
-    let queries_summary = format_queries_summary(&parsed_queries, &output_opts);
-    let client = LlmClient::with_retry_config(llm_provider, config.retry);
-    let analysis = client.analyze(&schema_summary, &queries_summary).await?;
+    let batches = split_batches(stage.queries.len(), params.llm_chunk_tokens, |range| {
+        format_queries_summary_from(
+            &stage.queries[range.clone()],
+            range.start + 1,
+            &stage.output_opts
+        )
+    });
+    let mut retry = config.retry;
+    if let Some(timeout_secs) = params.llm_timeout {
+        retry.timeout_secs = timeout_secs;
+    }
+    let mut client = LlmClient::with_retry_config(llm_provider, retry);
+    if !params.no_llm_cache
+        && let Some(cache) = LlmCache::in_user_cache_dir(Duration::from_secs(params.llm_cache_ttl))
+    {
+        client = client.with_cache(cache);
+    }
+    if let Some(path) = &params.audit_log {
+        client =
+            client.with_audit_log(LlmAuditLog::new(path).with_prompt(params.audit_include_prompt));
+    }
+    let analysis = client
+        .analyze_batches(&stage.schema_summary, &batches)
+        .await?;
     pb.finish_and_clear();
-    let llm_output = format_analysis_result(&parsed_queries, &analysis, &output_opts);
+    let llm_output = format_analysis_result(&stage.queries, &analysis, &stage.output_opts);
     Ok(AnalyzeResult {
-        exit_code,
-        static_output,
         llm_output: Some(llm_output),
-        dry_run_info: None
+        ..stage.result
+    })
+}
+
+/// Run `f` on a thread pool of `threads` workers, or on the global pool
+/// with one worker per CPU when `None`
+fn with_concurrency<T: Send>(
+    threads: Option<usize>,
+    f: impl FnOnce() -> T + Send
+) -> AppResult<T> {
+    let Some(threads) = threads else {
+        return Ok(f());
+    };
+    ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build()
+        .map(|pool| pool.install(f))
+        .map_err(|e| AppError::internal(format!("Failed to start analysis threads: {}", e)))
+}
+
+/// Parse the inputs, run the rules, and format the static report
+fn run_static(params: &AnalyzeParams, config: &Config) -> AppResult<StaticStage> {
+    let extra_outputs = params
+        .also_outputs
+        .iter()
+        .map(|spec| parse_extra_output(spec))
+        .collect::<AppResult<Vec<_>>>()?;
+    if let Some(template) = &params.text_template {
+        validate_template(template)?;
+    }
+    let (schema_sql, mut queries_sql) =
+        read_analyze_inputs(&params.schema_path, &params.queries_path)?;
+    let unwrapped = convert_input_format(params.input_format)
+        .map(|format| unwrap_queries(&queries_sql, format))
+        .transpose()?;
+    // Spans of unwrapped queries point into the wrapped file
+    let mut wrapped_sql = None;
+    if let Some(unwrapped) = &unwrapped {
+        wrapped_sql = Some(std::mem::replace(&mut queries_sql, unwrapped.sql.clone()));
+    }
+    let stripped = params
+        .template
+        .map(|template| strip_template(&queries_sql, convert_template(template)));
+    if let Some(stripped) = &stripped {
+        queries_sql = stripped.sql.clone();
+    }
+    let mut sql_dialect = convert_dialect(params.dialect.clone());
+    if sql_dialect == SqlDialect::Generic {
+        let detected = SqlDialect::detect(&format!("{}\n{}", schema_sql, queries_sql));
+        if detected != SqlDialect::Generic {
+            if !params.quiet {
+                eprintln!(
+                    "Detected {:?} dialect from SQL markers; pass --dialect to override",
+                    detected
+                );
+            }
+            sql_dialect = detected;
+        }
+    }
+    let mut parsed_schema = Schema::parse(&schema_sql, sql_dialect)?;
+    parsed_schema.deprecate_columns(&config.schema.deprecated);
+    parsed_schema.mark_large_tables(&config.schema.large_tables);
+    let mut parsed_queries = parse_queries_cached(&queries_sql, sql_dialect)?;
+    if let Some(stripped) = &stripped {
+        stripped.mark(&mut parsed_queries);
+    }
+    if let Some(unwrapped) = &unwrapped {
+        unwrapped.apply(&mut parsed_queries);
+    }
+    let schema_summary = parsed_schema.to_summary();
+    let mut output_opts = create_output_options(
+        params.output_format.clone(),
+        params.no_color,
+        params.verbose
+    );
+    output_opts.template = params.text_template.clone();
+    output_opts.compact = params.compact;
+    output_opts.min_severity = params.min_severity.map(convert_fail_on);
+    output_opts.sources = Some(SourcePaths {
+        schema:  source_name(&params.schema_path, "schema.sql"),
+        queries: source_name(&params.queries_path, "queries.sql")
+    });
+    let runner = RuleRunner::with_schema_and_config(parsed_schema.clone(), config.rules.clone())
+        .with_dialect(sql_dialect)
+        .with_timings(params.timings);
+    let mut static_report = if params.incremental && params.queries_path != "-" {
+        let key = analysis_key(&schema_sql, &queries_sql, sql_dialect, config);
+        let mut state = IncrementalState::load(&params.state_file);
+        match state.lookup(&params.queries_path, &key) {
+            Some(mut report) => {
+                // Names and wrapped-file positions are not part of the key, so
+                // take them from this run
+                for violation in &mut report.violations {
+                    let query = parsed_queries.get(violation.query_index);
+                    violation.origin = query.and_then(|query| query.origin.clone());
+                    violation.location = query.and_then(|query| query.span);
+                }
+                report
+            }
+            None => {
+                let report = runner.analyze(&parsed_queries);
+                state.record(&params.queries_path, key, &report);
+                state.save()?;
+                report
+            }
+        }
+    } else {
+        runner.analyze(&parsed_queries)
+    };
+    Locale::resolve(&params.lang)?.translate(&mut static_report);
+    note_templated(&mut static_report, &parsed_queries);
+    if let Some(lines) = &params.changed_lines {
+        let sql = wrapped_sql.as_deref().unwrap_or(&queries_sql);
+        keep_changed_statements(&mut static_report, &parsed_queries, sql, lines);
+    }
+    let fail_on = convert_fail_on(params.fail_on);
+    let mut exit = static_report.exit_status(fail_on);
+    if let Some(select) = &params.select {
+        let filter = ViolationFilter::parse(select)?;
+        static_report.violations.retain(|v| filter.matches(v));
+        static_report
+            .schema_violations
+            .retain(|v| filter.matches(v));
+        if params.fail_on_selected {
+            exit = static_report.exit_status(fail_on);
+        }
+    }
+    let exit_code = exit.exit_code;
+    output_opts.exit = Some(exit);
+    write_extra_outputs(&static_report, &extra_outputs, &output_opts)?;
+    let static_output = if params.summary_only {
+        format_report_summary(&static_report, &output_opts)
+    } else if params.stats {
+        format_report_statistics(&static_report, &parsed_queries, &output_opts)
+    } else {
+        format_static_analysis(&static_report, &output_opts)
+    };
+    Ok(StaticStage {
+        result: AnalyzeResult {
+            exit_code,
+            static_output,
+            llm_output: None,
+            dry_run_info: None,
+            summary: static_report.summary(),
+            queries_count: static_report.queries_count,
+            timed_out: false
+        },
+        report: static_report,
+        queries: parsed_queries,
+        schema_summary,
+        output_opts
     })
 }
+
+/// `path` as annotations name it; `fallback` for stdin (`-`)
+fn source_name(path: &str, fallback: &str) -> String {
+    if path == "-" { fallback } else { path }.to_string()
+}
+
+/// Drop violations of statements that overlap none of the `lines` ranges,
+/// and the schema lint results, which belong to another file
+fn keep_changed_statements(
+    report: &mut AnalysisReport,
+    queries: &[Query],
+    sql: &str,
+    lines: &[RangeInclusive<usize>]
+) {
+    report.violations.retain(|violation| {
+        let Some(span) = queries.get(violation.query_index).and_then(|q| q.span) else {
+            return true;
+        };
+        let last_line = span.line
+            + sql
+                .get(span.start..span.end)
+                .map_or(0, |text| text.matches('\n').count());
+        lines
+            .iter()
+            .any(|range| *range.start() <= last_line && span.line <= *range.end())
+    });
+    report.schema_violations.clear();
+}
+
+/// Mark violations of statements rebuilt from a template, whose placeholders
+/// may hide what the rendered SQL does
+fn note_templated(report: &mut AnalysisReport, queries: &[Query]) {
+    for violation in &mut report.violations {
+        if queries
+            .get(violation.query_index)
+            .is_some_and(|query| query.templated)
+        {
+            violation
+                .message
+                .push_str(" (templated statement; reduced confidence)");
+        }
+    }
+}
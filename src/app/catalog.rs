@@ -0,0 +1,35 @@
+//! Rule catalog export logic.
+//!
+//! This module renders the catalog of built-in rules for the
+//! `export-catalog` subcommand.
+
+use super::{convert::convert_catalog_format, types::CommandOutput};
+use crate::{cli::CatalogFormat, output::format_catalog, rules::rule_catalog};
+
+/// Renders the full rule catalog in the requested format.
+///
+/// # Arguments
+///
+/// * `format` - Catalog format (JSON or Markdown)
+///
+/// # Returns
+///
+/// A `CommandOutput` with exit code `0` and the rendered catalog.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{app::run_export_catalog, cli::CatalogFormat};
+///
+/// let output = run_export_catalog(CatalogFormat::Markdown);
+/// assert_eq!(output.exit_code, 0);
+/// assert!(output.stdout[0].contains("PERF001"));
+/// ```
+pub fn run_export_catalog(format: CatalogFormat) -> CommandOutput {
+    let catalog = rule_catalog();
+    CommandOutput {
+        exit_code: 0,
+        stdout:    vec![format_catalog(&catalog, convert_catalog_format(format))],
+        stderr:    Vec::new()
+    }
+}
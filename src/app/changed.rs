@@ -0,0 +1,193 @@
+//! Changed-files mode for pre-commit hooks and `--since`.
+//!
+//! Hooks pass the staged files as a list of paths. Each file is analyzed as
+//! its own queries input against the shared schema, and the worst exit code
+//! across files becomes the result. The LLM step is skipped so the hook
+//! stays fast and works offline.
+//!
+//! `--since <REF>` takes the files and changed line ranges from
+//! `git diff <REF>` instead, and reports only violations of statements that
+//! overlap those lines.
+
+use std::{ops::RangeInclusive, process::Command};
+
+use super::{
+    analyze::run_analyze,
+    types::{AnalyzeParams, CommandOutput}
+};
+use crate::{
+    cli::Format,
+    config::Config,
+    error::{AppResult, config_error, git_error},
+    output::format_summary_line,
+    rules::ReportSummary
+};
+
+/// Analyzes every file of `files` with `params`, one run per file.
+///
+/// Each file's output is preceded by a `==> path <==` header line; the
+/// stderr summary line totals all files.
+///
+/// # Errors
+///
+/// Returns an error if the schema is read from stdin (the file list may
+/// occupy it), if the output is not plain text (see [`run_files`]), or if
+/// any file fails to read or parse.
+pub async fn run_changed_files(
+    params: AnalyzeParams,
+    files: &[String],
+    config: Config
+) -> AppResult<CommandOutput> {
+    if params.schema_path == "-" {
+        return Err(config_error(
+            "--changed-only needs the schema as a file, not stdin"
+        ));
+    }
+    let files: Vec<(String, Option<Vec<RangeInclusive<usize>>>)> =
+        files.iter().map(|file| (file.clone(), None)).collect();
+    run_files(params, files, config).await
+}
+
+/// Analyzes the `.sql` files changed since the git ref `since`, reporting
+/// only violations of statements that overlap changed lines.
+///
+/// The schema file itself is not analyzed as a queries input.
+///
+/// # Errors
+///
+/// Returns an error if the schema is read from stdin, if the output is not
+/// plain text, if `git diff` fails, or if any file fails to read or parse.
+pub async fn run_since(
+    params: AnalyzeParams,
+    since: &str,
+    config: Config
+) -> AppResult<CommandOutput> {
+    if params.schema_path == "-" {
+        return Err(config_error(
+            "--since needs the schema as a file, not stdin"
+        ));
+    }
+    let files = git_changed_lines(since)?
+        .into_iter()
+        .filter(|(path, _)| *path != params.schema_path)
+        .map(|(path, lines)| (path, Some(lines)))
+        .collect();
+    run_files(params, files, config).await
+}
+
+/// Runs `git diff` against `since` in the current directory and returns
+/// the changed line ranges of each `.sql` file.
+///
+/// # Errors
+///
+/// Returns an error if git cannot be run or rejects the ref.
+pub fn git_changed_lines(since: &str) -> AppResult<Vec<(String, Vec<RangeInclusive<usize>>)>> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--relative",
+            "--diff-filter=d",
+            since,
+            "--",
+            "*.sql"
+        ])
+        .output()
+        .map_err(|e| git_error(e.to_string()))?;
+    if !output.status.success() {
+        return Err(git_error(
+            String::from_utf8_lossy(&output.stderr).trim().to_string()
+        ));
+    }
+    Ok(parse_changed_lines(&String::from_utf8_lossy(
+        &output.stdout
+    )))
+}
+
+/// Extracts the changed line ranges of each file from a `--unified=0`
+/// diff.
+///
+/// Ranges are 1-based and inclusive, in the new version of the file. A hunk
+/// that only deletes lines marks the lines on either side of the deletion.
+/// Deleted files are skipped.
+pub fn parse_changed_lines(diff: &str) -> Vec<(String, Vec<RangeInclusive<usize>>)> {
+    let mut files: Vec<(String, Vec<RangeInclusive<usize>>)> = Vec::new();
+    let mut current = false;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            current = path != "/dev/null";
+            if current {
+                let path = path.trim_end_matches('\t');
+                let path = path.strip_prefix("b/").unwrap_or(path);
+                files.push((path.to_string(), Vec::new()));
+            }
+        } else if let Some(hunk) = line.strip_prefix("@@ ")
+            && current
+            && let Some(new) = hunk.split(' ').find_map(|part| part.strip_prefix('+'))
+            && let Some((_, ranges)) = files.last_mut()
+        {
+            let (start, count) = match new.split_once(',') {
+                Some((start, count)) => (start.parse().unwrap_or(0), count.parse().unwrap_or(0)),
+                None => (new.parse().unwrap_or(0), 1)
+            };
+            ranges.push(if count == 0 {
+                start.max(1)..=start + 1
+            } else {
+                start..=start + count - 1
+            });
+        }
+    }
+    files.retain(|(_, ranges)| !ranges.is_empty());
+    files
+}
+
+/// Analyzes each file with its line ranges, one run per file
+///
+/// Only text output is accepted: one JSON, YAML, SARIF, or annotation
+/// document per file would not form a single parseable stream, and
+/// `--also-output` files would be overwritten by each run.
+async fn run_files(
+    params: AnalyzeParams,
+    files: Vec<(String, Option<Vec<RangeInclusive<usize>>>)>,
+    config: Config
+) -> AppResult<CommandOutput> {
+    if !matches!(params.output_format, Format::Text) || !params.also_outputs.is_empty() {
+        return Err(config_error(
+            "--changed-only and --since report each file separately and only support text \
+             output without --also-output; analyze the files one at a time with -q for other \
+             formats"
+        ));
+    }
+    let mut exit_code = 0;
+    let mut stdout = Vec::new();
+    let mut totals = ReportSummary::default();
+    let mut queries_count = 0;
+    for (file, changed_lines) in files {
+        let file_params = AnalyzeParams {
+            queries_path: file.clone(),
+            skip_llm: true,
+            changed_lines,
+            ..params.clone()
+        };
+        let result = run_analyze(file_params, config.clone()).await?;
+        exit_code = exit_code.max(result.exit_code);
+        totals.errors += result.summary.errors;
+        totals.warnings += result.summary.warnings;
+        totals.info += result.summary.info;
+        queries_count += result.queries_count;
+        stdout.push(format!("==> {} <==", file));
+        stdout.push(result.static_output);
+    }
+    let stderr = if params.quiet {
+        Vec::new()
+    } else {
+        vec![format_summary_line(&totals, queries_count, exit_code)]
+    };
+    Ok(CommandOutput {
+        exit_code,
+        stdout,
+        stderr
+    })
+}
@@ -5,9 +5,15 @@
 //! analysis engine.
 
 use crate::{
-    cli::{Dialect, Format},
-    output::OutputFormat,
-    query::SqlDialect
+    cli::{
+        CatalogFormat, Dialect, FailOn, Format, InputFormat, Profile, SchemaFormat, StatsFormat,
+        Template
+    },
+    config,
+    output::{CatalogOutputFormat, OutputFormat, SchemaOutputFormat, StatsOutputFormat},
+    preprocessor::{TemplateSyntax, WrappedFormat},
+    query::SqlDialect,
+    rules::Severity
 };
 
 /// Converts a CLI dialect enum to the internal SQL dialect type.
@@ -41,6 +47,46 @@ pub fn convert_dialect(dialect: Dialect) -> SqlDialect {
     }
 }
 
+/// Converts a CLI template language to the internal template syntax.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{app::convert_template, cli::Template, preprocessor::TemplateSyntax};
+///
+/// assert_eq!(convert_template(Template::Jinja), TemplateSyntax::Jinja);
+/// ```
+pub fn convert_template(template: Template) -> TemplateSyntax {
+    match template {
+        Template::Jinja => TemplateSyntax::Jinja,
+        Template::Handlebars => TemplateSyntax::Handlebars
+    }
+}
+
+/// Converts a CLI input format to the record layout to unwrap, or `None`
+/// for plain SQL.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     app::convert_input_format, cli::InputFormat, preprocessor::WrappedFormat
+/// };
+///
+/// assert_eq!(convert_input_format(InputFormat::Raw), None);
+/// assert_eq!(
+///     convert_input_format(InputFormat::Ndjson),
+///     Some(WrappedFormat::Ndjson)
+/// );
+/// ```
+pub fn convert_input_format(format: InputFormat) -> Option<WrappedFormat> {
+    match format {
+        InputFormat::Raw => None,
+        InputFormat::Json => Some(WrappedFormat::Json),
+        InputFormat::Ndjson => Some(WrappedFormat::Ndjson)
+    }
+}
+
 /// Converts a CLI format enum to the internal output format type.
 ///
 /// Maps the user-facing format names to the corresponding output
@@ -67,7 +113,115 @@ pub fn convert_format(format: Format) -> OutputFormat {
         Format::Text => OutputFormat::Text,
         Format::Json => OutputFormat::Json,
         Format::Yaml => OutputFormat::Yaml,
-        Format::Sarif => OutputFormat::Sarif
+        Format::Sarif => OutputFormat::Sarif,
+        Format::Github => OutputFormat::GithubAnnotations
+    }
+}
+
+/// Converts a CLI catalog format enum to the internal catalog format type.
+///
+/// # Arguments
+///
+/// * `format` - The CLI catalog format value from command-line arguments
+///
+/// # Returns
+///
+/// The corresponding internal `CatalogOutputFormat` enum variant.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     app::convert_catalog_format, cli::CatalogFormat, output::CatalogOutputFormat
+/// };
+///
+/// let format = convert_catalog_format(CatalogFormat::Markdown);
+/// assert!(matches!(format, CatalogOutputFormat::Markdown));
+/// ```
+pub fn convert_catalog_format(format: CatalogFormat) -> CatalogOutputFormat {
+    match format {
+        CatalogFormat::Json => CatalogOutputFormat::Json,
+        CatalogFormat::Markdown => CatalogOutputFormat::Markdown
+    }
+}
+
+/// Converts a CLI schema format enum to the internal schema format type.
+///
+/// # Arguments
+///
+/// * `format` - The CLI schema format value from command-line arguments
+///
+/// # Returns
+///
+/// The corresponding internal `SchemaOutputFormat` enum variant.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     app::convert_schema_format, cli::SchemaFormat, output::SchemaOutputFormat
+/// };
+///
+/// let format = convert_schema_format(SchemaFormat::Sarif);
+/// assert!(matches!(format, SchemaOutputFormat::Sarif));
+/// ```
+pub fn convert_schema_format(format: SchemaFormat) -> SchemaOutputFormat {
+    match format {
+        SchemaFormat::Json => SchemaOutputFormat::Json,
+        SchemaFormat::Sarif => SchemaOutputFormat::Sarif
+    }
+}
+
+/// Converts a CLI stats format enum to the internal stats format type.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     app::convert_stats_format, cli::StatsFormat, output::StatsOutputFormat
+/// };
+///
+/// let format = convert_stats_format(StatsFormat::Json);
+/// assert!(matches!(format, StatsOutputFormat::Json));
+/// ```
+pub fn convert_stats_format(format: StatsFormat) -> StatsOutputFormat {
+    match format {
+        StatsFormat::Text => StatsOutputFormat::Text,
+        StatsFormat::Json => StatsOutputFormat::Json
+    }
+}
+
+/// Converts a CLI `--fail-on` value to the severity threshold it names.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{app::convert_fail_on, cli::FailOn, rules::Severity};
+///
+/// assert_eq!(convert_fail_on(FailOn::Error), Severity::Error);
+/// ```
+pub fn convert_fail_on(fail_on: FailOn) -> Severity {
+    match fail_on {
+        FailOn::Error => Severity::Error,
+        FailOn::Warning => Severity::Warning,
+        FailOn::Info => Severity::Info
+    }
+}
+
+/// Converts a CLI `--profile` value to the rule preset it names.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{app::convert_profile, cli::Profile, config};
+///
+/// assert_eq!(convert_profile(Profile::Strict), config::Profile::Strict);
+/// ```
+pub fn convert_profile(profile: Profile) -> config::Profile {
+    match profile {
+        Profile::Strict => config::Profile::Strict,
+        Profile::Balanced => config::Profile::Balanced,
+        Profile::Relaxed => config::Profile::Relaxed
     }
 }
 
@@ -75,6 +229,23 @@ pub fn convert_format(format: Format) -> OutputFormat {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_convert_fail_on() {
+        assert_eq!(convert_fail_on(FailOn::Error), Severity::Error);
+        assert_eq!(convert_fail_on(FailOn::Warning), Severity::Warning);
+        assert_eq!(convert_fail_on(FailOn::Info), Severity::Info);
+    }
+
+    #[test]
+    fn test_convert_profile() {
+        assert_eq!(convert_profile(Profile::Strict), config::Profile::Strict);
+        assert_eq!(
+            convert_profile(Profile::Balanced),
+            config::Profile::Balanced
+        );
+        assert_eq!(convert_profile(Profile::Relaxed), config::Profile::Relaxed);
+    }
+
     #[test]
     fn test_convert_dialect_generic() {
         assert!(matches!(
@@ -131,4 +302,48 @@ mod tests {
     fn test_convert_format_sarif() {
         assert!(matches!(convert_format(Format::Sarif), OutputFormat::Sarif));
     }
+
+    #[test]
+    fn test_convert_format_github() {
+        assert!(matches!(
+            convert_format(Format::Github),
+            OutputFormat::GithubAnnotations
+        ));
+    }
+
+    #[test]
+    fn test_convert_catalog_format_json() {
+        assert!(matches!(
+            convert_catalog_format(CatalogFormat::Json),
+            CatalogOutputFormat::Json
+        ));
+    }
+
+    #[test]
+    fn test_convert_catalog_format_markdown() {
+        assert!(matches!(
+            convert_catalog_format(CatalogFormat::Markdown),
+            CatalogOutputFormat::Markdown
+        ));
+    }
+    #[test]
+    fn test_convert_stats_format() {
+        assert!(matches!(
+            convert_stats_format(StatsFormat::Text),
+            StatsOutputFormat::Text
+        ));
+        assert!(matches!(
+            convert_stats_format(StatsFormat::Json),
+            StatsOutputFormat::Json
+        ));
+    }
+
+    #[test]
+    fn test_convert_input_format() {
+        assert_eq!(convert_input_format(InputFormat::Raw), None);
+        assert_eq!(
+            convert_input_format(InputFormat::Json),
+            Some(WrappedFormat::Json)
+        );
+    }
 }
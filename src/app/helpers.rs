@@ -5,17 +5,19 @@
 //! providers, and managing configuration defaults.
 
 use std::{
-    fs::read_to_string,
+    fs::{read_to_string, write},
     io::{self, Read}
 };
 
+use clap::ValueEnum;
+
 use super::convert::convert_format;
 use crate::{
     cache::{cache_queries, get_cached},
     cli::{Format, Provider},
     error::{AppResult, config_error, file_read_error},
     llm::LlmProvider,
-    output::OutputOptions,
+    output::{OutputOptions, format_static_analysis},
     query::{Query, SqlDialect, parse_queries},
     rules::{AnalysisReport, Severity}
 };
@@ -44,22 +46,42 @@ use crate::{
 /// let report = AnalysisReport::new(1, 0);
 /// assert_eq!(calculate_exit_code(&report), 0);
 /// ```
+#[allow(dead_code)]
 pub fn calculate_exit_code(report: &AnalysisReport) -> i32 {
-    if report
-        .violations
-        .iter()
-        .any(|v| v.severity == Severity::Error)
-    {
-        2
-    } else if report
-        .violations
-        .iter()
-        .any(|v| v.severity == Severity::Warning)
-    {
-        1
-    } else {
-        0
-    }
+    calculate_exit_code_at(report, Severity::Warning)
+}
+
+/// Calculates the exit code counting only violations at or above
+/// `threshold`.
+///
+/// Errors still yield `2`; any other counted violation yields `1`. With a
+/// threshold of [`Severity::Warning`] this matches [`calculate_exit_code`].
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     app::calculate_exit_code_at,
+///     rules::{AnalysisReport, RuleCategory, Severity, Violation}
+/// };
+///
+/// let mut report = AnalysisReport::new(1, 1);
+/// report.add_violation(Violation {
+///     rule_id:     "PERF001",
+///     rule_name:   "SELECT * without LIMIT",
+///     message:     String::new(),
+///     severity:    Severity::Warning,
+///     category:    RuleCategory::Performance,
+///     suggestion:  None,
+///     query_index: 0,
+///     location:    None,
+///     origin:      None
+/// });
+/// assert_eq!(calculate_exit_code_at(&report, Severity::Warning), 1);
+/// assert_eq!(calculate_exit_code_at(&report, Severity::Error), 0);
+/// ```
+pub fn calculate_exit_code_at(report: &AnalysisReport, threshold: Severity) -> i32 {
+    report.exit_status(threshold).exit_code
 }
 
 /// Reads SQL queries from a file or stdin.
@@ -90,6 +112,71 @@ pub fn read_queries_input(path: &str) -> AppResult<String> {
     }
 }
 
+/// Reads the file list for `--changed-only`: one path per line from
+/// `files_from`, or from stdin when it is `None` or "-". Blank lines are
+/// skipped.
+///
+/// # Errors
+///
+/// Returns an error if the list cannot be read.
+pub fn read_file_list(files_from: Option<&str>) -> AppResult<Vec<String>> {
+    let content = read_queries_input(files_from.unwrap_or("-"))?;
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Marker line separating schema from queries when both come from stdin.
+pub const QUERIES_MARKER: &str = "-- @queries";
+
+/// Reads the schema and queries for an analysis run.
+///
+/// Either path may be "-" for stdin. Since stdin can only be consumed once,
+/// `-s - -q -` reads a single stream holding the schema, then a
+/// [`QUERIES_MARKER`] line, then the queries.
+///
+/// # Returns
+///
+/// The schema SQL and the queries SQL.
+///
+/// # Errors
+///
+/// Returns an error if an input cannot be read, or if both come from stdin
+/// and the stream has no marker line.
+pub fn read_analyze_inputs(schema_path: &str, queries_path: &str) -> AppResult<(String, String)> {
+    if schema_path != "-" {
+        let schema = read_to_string(schema_path).map_err(|e| file_read_error(schema_path, e))?;
+        return Ok((schema, read_queries_input(queries_path)?));
+    }
+    let input = read_queries_input("-")?;
+    if queries_path != "-" {
+        return Ok((input, read_queries_input(queries_path)?));
+    }
+    split_combined_input(&input)
+        .map(|(schema, queries)| (schema.to_string(), queries.to_string()))
+        .ok_or_else(|| {
+            config_error(format!(
+                "Schema and queries both read from stdin: separate them with a `{}` line",
+                QUERIES_MARKER
+            ))
+        })
+}
+
+/// Splits a combined stdin stream at the first [`QUERIES_MARKER`] line.
+fn split_combined_input(input: &str) -> Option<(&str, &str)> {
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        if line.trim().eq_ignore_ascii_case(QUERIES_MARKER) {
+            return Some((&input[..offset], &input[offset + line.len()..]));
+        }
+        offset += line.len();
+    }
+    None
+}
+
 /// Parses SQL queries with caching support.
 ///
 /// Attempts to retrieve parsed queries from the cache first. If not
@@ -136,14 +223,65 @@ pub fn create_output_options(format: Format, no_color: bool, verbose: bool) -> O
     OutputOptions {
         format: convert_format(format),
         colored: !no_color,
-        verbose
+        verbose,
+        template: None,
+        compact: false,
+        min_severity: None,
+        exit: None,
+        sources: None
     }
 }
 
+/// Parses an `--also-output` value of the form `FORMAT:PATH`.
+///
+/// `FORMAT` takes the same names as `--output-format`. The path is split at
+/// the first colon, so it may itself contain colons.
+///
+/// # Errors
+///
+/// Returns an error if the colon is missing, the format is unknown, or the
+/// path is empty.
+pub fn parse_extra_output(spec: &str) -> AppResult<(Format, String)> {
+    let invalid =
+        |reason: &str| config_error(format!("Invalid --also-output '{}': {}", spec, reason));
+    let Some((format, path)) = spec.split_once(':') else {
+        return Err(invalid("expected FORMAT:PATH"));
+    };
+    let format = Format::from_str(format.trim(), true)
+        .map_err(|_| invalid("format must be text, json, yaml, sarif, or github"))?;
+    if path.is_empty() {
+        return Err(invalid("missing path"));
+    }
+    Ok((format, path.to_string()))
+}
+
+/// Writes `report` in every `(format, path)` of `outputs`, without colors,
+/// keeping the other settings of `opts`.
+///
+/// # Errors
+///
+/// Returns an error naming the first file that cannot be written.
+pub fn write_extra_outputs(
+    report: &AnalysisReport,
+    outputs: &[(Format, String)],
+    opts: &OutputOptions
+) -> AppResult<()> {
+    for (format, path) in outputs {
+        let opts = OutputOptions {
+            format: convert_format(format.clone()),
+            colored: false,
+            ..opts.clone()
+        };
+        write(path, format_static_analysis(report, &opts))
+            .map_err(|e| config_error(format!("Failed to write report to '{}': {}", path, e)))?;
+    }
+    Ok(())
+}
+
 /// Builds an LLM provider configuration from CLI parameters.
 ///
 /// Constructs the appropriate `LlmProvider` variant based on the
-/// selected provider type. For cloud providers (OpenAI, Anthropic),
+/// selected provider type. For cloud providers (OpenAI, Anthropic, Cohere),
 /// an API key is required.
 ///
 /// # Arguments
@@ -185,6 +323,15 @@ pub fn build_llm_provider(
                 model
             })
         }
+        Provider::Cohere => {
+            let key = api_key.ok_or_else(|| {
+                config_error("API key required for Cohere (use --api-key or LLM_API_KEY)")
+            })?;
+            Ok(LlmProvider::Cohere {
+                api_key: key,
+                model
+            })
+        }
         Provider::Ollama => Ok(LlmProvider::Ollama {
             base_url: ollama_url,
             model
@@ -262,6 +409,37 @@ mod tests {
     use super::*;
     use crate::rules::{AnalysisReport, RuleCategory, Violation};
 
+    #[test]
+    fn test_parse_extra_output() {
+        let (format, path) = parse_extra_output("sarif:out/results.sarif").unwrap();
+        assert!(matches!(format, Format::Sarif));
+        assert_eq!(path, "out/results.sarif");
+        let (format, path) = parse_extra_output("JSON:C:\\report.json").unwrap();
+        assert!(matches!(format, Format::Json));
+        assert_eq!(path, "C:\\report.json");
+    }
+
+    #[test]
+    fn test_parse_extra_output_rejects_bad_specs() {
+        for spec in ["results.sarif", "xml:out.xml", "json:"] {
+            let err = parse_extra_output(spec).unwrap_err();
+            assert!(err.render_message().contains("--also-output"), "{}", spec);
+        }
+    }
+
+    #[test]
+    fn test_split_combined_input() {
+        let input = "CREATE TABLE t (id INT);\n-- @queries\nSELECT id FROM t;\n";
+        let (schema, queries) = split_combined_input(input).unwrap();
+        assert_eq!(schema, "CREATE TABLE t (id INT);\n");
+        assert_eq!(queries, "SELECT id FROM t;\n");
+    }
+
+    #[test]
+    fn test_split_combined_input_requires_marker_line() {
+        assert!(split_combined_input("CREATE TABLE t (id INT); -- @queries SELECT 1").is_none());
+    }
+
     #[test]
     fn test_calculate_exit_code_no_violations() {
         let report = AnalysisReport::new(1, 1);
@@ -278,7 +456,9 @@ mod tests {
             severity:    Severity::Info,
             category:    RuleCategory::Style,
             suggestion:  None,
-            query_index: 0
+            query_index: 0,
+            location:    None,
+            origin:      None
         });
         assert_eq!(calculate_exit_code(&report), 0);
     }
@@ -293,7 +473,9 @@ mod tests {
             severity:    Severity::Warning,
             category:    RuleCategory::Performance,
             suggestion:  None,
-            query_index: 0
+            query_index: 0,
+            location:    None,
+            origin:      None
         });
         assert_eq!(calculate_exit_code(&report), 1);
     }
@@ -308,7 +490,9 @@ mod tests {
             severity:    Severity::Error,
             category:    RuleCategory::Security,
             suggestion:  None,
-            query_index: 0
+            query_index: 0,
+            location:    None,
+            origin:      None
         });
         assert_eq!(calculate_exit_code(&report), 2);
     }
@@ -323,7 +507,9 @@ mod tests {
             severity:    Severity::Warning,
             category:    RuleCategory::Performance,
             suggestion:  None,
-            query_index: 0
+            query_index: 0,
+            location:    None,
+            origin:      None
         });
         report.add_violation(Violation {
             rule_id:     "E1",
@@ -332,7 +518,9 @@ mod tests {
             severity:    Severity::Error,
             category:    RuleCategory::Security,
             suggestion:  None,
-            query_index: 0
+            query_index: 0,
+            location:    None,
+            origin:      None
         });
         assert_eq!(calculate_exit_code(&report), 2);
     }
@@ -473,6 +661,25 @@ mod tests {
         assert!(matches!(provider, LlmProvider::Anthropic { .. }));
     }
 
+    #[test]
+    fn test_build_llm_provider_cohere() {
+        let result = build_llm_provider(
+            Provider::Cohere,
+            None,
+            "command-r".to_string(),
+            "http://localhost:11434".to_string()
+        );
+        assert!(result.is_err());
+        let provider = build_llm_provider(
+            Provider::Cohere,
+            Some("co-test".to_string()),
+            "command-r".to_string(),
+            "http://localhost:11434".to_string()
+        )
+        .unwrap();
+        assert!(matches!(provider, LlmProvider::Cohere { .. }));
+    }
+
     #[test]
     fn test_parse_queries_cached() {
         let sql = "SELECT id FROM test_cached_table_helpers";
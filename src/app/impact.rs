@@ -0,0 +1,89 @@
+//! Column impact analysis.
+//!
+//! This module lists the queries that reference a column for the `impact`
+//! subcommand, so a column can be dropped or renamed knowing what breaks.
+
+use super::{convert::convert_dialect, helpers::read_analyze_inputs, types::CommandOutput};
+use crate::{
+    cli::Dialect,
+    error::{AppResult, config_error},
+    query::parse_queries,
+    schema::Schema
+};
+
+/// Lists the queries that reference `table.column` and the clauses they
+/// use it in.
+///
+/// # Arguments
+///
+/// * `schema_path` - Path to the schema file, or "-" for stdin
+/// * `queries_path` - Path to the queries file, or "-" for stdin
+/// * `dialect` - SQL dialect for parsing
+/// * `table` - Table that owns the column
+/// * `column` - Column to look up
+///
+/// # Returns
+///
+/// A `CommandOutput` with exit code `0` and one entry per affected query.
+///
+/// # Errors
+///
+/// Returns an error if an input cannot be read or parsed, or if the schema
+/// has no such column.
+pub fn run_impact(
+    schema_path: &str,
+    queries_path: &str,
+    dialect: Dialect,
+    table: &str,
+    column: &str
+) -> AppResult<CommandOutput> {
+    let (schema_sql, queries_sql) = read_analyze_inputs(schema_path, queries_path)?;
+    let dialect = convert_dialect(dialect);
+    let schema = Schema::parse(&schema_sql, dialect)?;
+    let known = schema.tables.values().any(|t| {
+        t.name.eq_ignore_ascii_case(table)
+            && t.columns
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(column))
+    });
+    if !known {
+        return Err(config_error(format!(
+            "Column {}.{} is not defined in the schema",
+            table, column
+        )));
+    }
+    let queries = parse_queries(&queries_sql, dialect)?;
+    let affected = schema.find_column_usage(table, column, &queries);
+    let mut stdout = vec![format!(
+        "{}.{} is referenced by {} of {} queries",
+        table,
+        column,
+        affected.len(),
+        queries.len()
+    )];
+    for idx in affected {
+        let query = &queries[idx];
+        let clauses = schema
+            .column_usage(table, column, query)
+            .iter()
+            .map(|clause| clause.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let line = query
+            .span
+            .map(|span| format!(" (line {})", span.line))
+            .unwrap_or_default();
+        stdout.push(format!(
+            "  #{}{}: {}\n    {}",
+            idx + 1,
+            line,
+            clauses,
+            query.raw
+        ));
+    }
+    Ok(CommandOutput {
+        exit_code: 0,
+        stdout,
+        stderr: Vec::new()
+    })
+}
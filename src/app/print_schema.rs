@@ -0,0 +1,34 @@
+//! JSON Schema export logic.
+//!
+//! This module prints the JSON Schema of a machine-readable report format
+//! for the `print-schema` subcommand.
+
+use super::{convert::convert_schema_format, types::CommandOutput};
+use crate::{cli::SchemaFormat, output::format_json_schema};
+
+/// Renders the JSON Schema of the requested report format.
+///
+/// # Arguments
+///
+/// * `format` - Report format whose schema to render (JSON or SARIF)
+///
+/// # Returns
+///
+/// A `CommandOutput` with exit code `0` and the pretty-printed schema.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{app::run_print_schema, cli::SchemaFormat};
+///
+/// let output = run_print_schema(SchemaFormat::Json);
+/// assert_eq!(output.exit_code, 0);
+/// assert!(output.stdout[0].contains("\"violations\""));
+/// ```
+pub fn run_print_schema(format: SchemaFormat) -> CommandOutput {
+    CommandOutput {
+        exit_code: 0,
+        stdout:    vec![format_json_schema(convert_schema_format(format))],
+        stderr:    Vec::new()
+    }
+}
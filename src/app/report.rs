@@ -0,0 +1,64 @@
+//! Uploading analysis reports to a central endpoint.
+//!
+//! With `--report-url`, the JSON form of the [`AnalysisReport`] is POSTed
+//! after analysis so a dashboard can collect results from many runs. The
+//! upload is best-effort: callers log failures instead of failing the run.
+
+use std::time::Duration;
+
+use crate::{
+    error::{AppResult, config_error, http_error, report_upload_error},
+    rules::AnalysisReport
+};
+
+/// POST `report` as JSON to `url`.
+///
+/// Each entry of `headers` is a `Name: value` pair added to the request,
+/// typically for authentication.
+///
+/// # Errors
+///
+/// Returns error if a header is malformed, the request fails, or the
+/// endpoint answers with a non-success status
+///
+/// # Example
+///
+/// ```no_run
+/// use sql_query_analyzer::{app::post_report, rules::AnalysisReport};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let report = AnalysisReport::new(1, 10);
+/// post_report(
+///     "https://dashboard.example.com/api/reports",
+///     &["Authorization: Bearer token".to_string()],
+///     &report
+/// )
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn post_report(url: &str, headers: &[String], report: &AnalysisReport) -> AppResult<()> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(30))
+        .build()
+        .unwrap_or_else(|_| reqwest::Client::new());
+    let mut request = client.post(url).json(report);
+    for header in headers {
+        let Some((name, value)) = header.split_once(':') else {
+            return Err(config_error(format!(
+                "Invalid --report-header '{}': expected `Name: value`",
+                header
+            )));
+        };
+        request = request.header(name.trim(), value.trim());
+    }
+    let response = request.send().await.map_err(http_error)?;
+    if !response.status().is_success() {
+        return Err(report_upload_error(format!(
+            "{} answered {}",
+            url,
+            response.status()
+        )));
+    }
+    Ok(())
+}
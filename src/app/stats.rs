@@ -0,0 +1,57 @@
+//! Usage statistics logic.
+//!
+//! This module parses a queries file and renders table and column usage
+//! counts for the `stats` subcommand.
+
+use super::{
+    convert::{convert_dialect, convert_stats_format},
+    helpers::read_queries_input,
+    types::CommandOutput
+};
+use crate::{
+    cli::{Dialect, StatsFormat},
+    error::AppResult,
+    output::format_usage_stats,
+    query::parse_queries,
+    schema::Schema,
+    stats::usage_stats
+};
+
+/// Counts table and column references in the queries at `queries_path`.
+///
+/// # Arguments
+///
+/// * `queries_path` - Path to the queries file, or "-" for stdin
+/// * `schema_path` - Optional schema file that attributes unqualified join
+///   columns to their tables
+/// * `dialect` - SQL dialect for parsing
+/// * `format` - Output format (text or JSON)
+///
+/// # Returns
+///
+/// A `CommandOutput` with exit code `0` and the rendered statistics.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or the schema or queries fail to
+/// parse.
+pub fn run_stats(
+    queries_path: &str,
+    schema_path: Option<&str>,
+    dialect: Dialect,
+    format: StatsFormat
+) -> AppResult<CommandOutput> {
+    let dialect = convert_dialect(dialect);
+    let schema = match schema_path {
+        Some(path) => Some(Schema::parse(&read_queries_input(path)?, dialect)?),
+        None => None
+    };
+    let sql = read_queries_input(queries_path)?;
+    let queries = parse_queries(&sql, dialect)?;
+    let stats = usage_stats(&queries, schema.as_ref());
+    Ok(CommandOutput {
+        exit_code: 0,
+        stdout:    vec![format_usage_stats(&stats, convert_stats_format(format))],
+        stderr:    Vec::new()
+    })
+}
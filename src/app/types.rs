@@ -4,7 +4,12 @@
 //! application, including command parameters, analysis results, and
 //! execution outputs.
 
-use crate::cli::{Dialect, Format, Provider};
+use std::{ops::RangeInclusive, path::PathBuf};
+
+use crate::{
+    cli::{Dialect, FailOn, Format, InputFormat, Provider, Template},
+    rules::ReportSummary
+};
 
 /// Parameters for the analyze command.
 ///
@@ -21,43 +26,132 @@ use crate::cli::{Dialect, Format, Provider};
 /// };
 ///
 /// let params = AnalyzeParams {
-///     schema_path:   "schema.sql".to_string(),
-///     queries_path:  "queries.sql".to_string(),
-///     provider:      Provider::Ollama,
-///     api_key:       None,
-///     model:         None,
-///     ollama_url:    "http://localhost:11434".to_string(),
-///     dialect:       Dialect::Generic,
-///     output_format: Format::Text,
-///     verbose:       false,
-///     dry_run:       false,
-///     no_color:      false
+///     schema_path:          "schema.sql".to_string(),
+///     queries_path:         "queries.sql".to_string(),
+///     provider:             Provider::Ollama,
+///     api_key:              None,
+///     model:                None,
+///     ollama_url:           "http://localhost:11434".to_string(),
+///     dialect:              Dialect::Generic,
+///     template:             None,
+///     input_format:         sql_query_analyzer::cli::InputFormat::Raw,
+///     output_format:        Format::Text,
+///     verbose:              false,
+///     dry_run:              false,
+///     no_color:             false,
+///     compact:              false,
+///     quiet:                false,
+///     summary_only:         false,
+///     stats:                false,
+///     select:               None,
+///     fail_on_selected:     false,
+///     timings:              false,
+///     no_llm_cache:         false,
+///     llm_cache_ttl:        86400,
+///     llm_timeout:          None,
+///     llm_chunk_tokens:     8000,
+///     audit_log:            None,
+///     audit_include_prompt: false,
+///     deadline:             None,
+///     concurrency:          None,
+///     lang:                 "en".to_string(),
+///     incremental:          false,
+///     state_file:           ".sql-analyzer-state.json".to_string(),
+///     report_url:           None,
+///     report_headers:       Vec::new(),
+///     also_outputs:         Vec::new(),
+///     text_template:        None,
+///     min_severity:         None,
+///     fail_on:              sql_query_analyzer::cli::FailOn::Warning,
+///     skip_llm:             false,
+///     changed_lines:        None
 /// };
 /// ```
 #[derive(Debug, Clone)]
 pub struct AnalyzeParams {
     /// Path to the SQL schema file containing table definitions.
-    pub schema_path:   String,
+    pub schema_path:          String,
     /// Path to queries file or "-" for stdin input.
-    pub queries_path:  String,
+    pub queries_path:         String,
     /// LLM provider for AI-powered analysis.
-    pub provider:      Provider,
+    pub provider:             Provider,
     /// API key for cloud LLM providers (OpenAI, Anthropic).
-    pub api_key:       Option<String>,
+    pub api_key:              Option<String>,
     /// Model name to use for LLM analysis.
-    pub model:         Option<String>,
+    pub model:                Option<String>,
     /// Base URL for Ollama server.
-    pub ollama_url:    String,
+    pub ollama_url:           String,
     /// SQL dialect for parsing.
-    pub dialect:       Dialect,
+    pub dialect:              Dialect,
+    /// Template language to strip from the queries before parsing.
+    pub template:             Option<Template>,
+    /// Layout of the queries input.
+    pub input_format:         InputFormat,
     /// Output format for results.
-    pub output_format: Format,
+    pub output_format:        Format,
     /// Enable verbose output with additional details.
-    pub verbose:       bool,
+    pub verbose:              bool,
     /// Dry run mode - show what would be sent to LLM.
-    pub dry_run:       bool,
+    pub dry_run:              bool,
     /// Disable colored terminal output.
-    pub no_color:      bool
+    pub no_color:             bool,
+    /// Emit JSON and SARIF on a single line.
+    pub compact:              bool,
+    /// Skip the one-line result summary on stderr.
+    pub quiet:                bool,
+    /// Print only violation counts instead of every violation.
+    pub summary_only:         bool,
+    /// Print aggregate statistics instead of every violation.
+    pub stats:                bool,
+    /// Violation filter expression (see [`ViolationFilter`]).
+    ///
+    /// [`ViolationFilter`]: crate::rules::ViolationFilter
+    pub select:               Option<String>,
+    /// Compute the exit code from selected violations only.
+    pub fail_on_selected:     bool,
+    /// Measure analysis throughput and report it with the results.
+    pub timings:              bool,
+    /// Always call the LLM, bypassing the on-disk response cache.
+    pub no_llm_cache:         bool,
+    /// Seconds a cached LLM response stays valid.
+    pub llm_cache_ttl:        u64,
+    /// Per-request LLM timeout overriding `retry.timeout_secs`.
+    pub llm_timeout:          Option<u64>,
+    /// Estimated tokens of query summaries per LLM request.
+    pub llm_chunk_tokens:     usize,
+    /// File receiving a JSON line per LLM request.
+    pub audit_log:            Option<PathBuf>,
+    /// Record prompt text in the audit log.
+    pub audit_include_prompt: bool,
+    /// Seconds the whole run may take; on expiry the static results are
+    /// returned without the LLM analysis.
+    pub deadline:             Option<u64>,
+    /// Worker threads for parsing and rule evaluation; `None` uses one per
+    /// CPU.
+    pub concurrency:          Option<usize>,
+    /// `en`, or the path of a message catalog translating violations.
+    pub lang:                 String,
+    /// Reuse stored reports for unchanged inputs.
+    pub incremental:          bool,
+    /// Path of the incremental analysis state file.
+    pub state_file:           String,
+    /// Endpoint receiving the JSON report after analysis.
+    pub report_url:           Option<String>,
+    /// `Name: value` headers sent with the report upload.
+    pub report_headers:       Vec<String>,
+    /// `FORMAT:PATH` destinations that also receive the report.
+    pub also_outputs:         Vec<String>,
+    /// Template for each violation line of text output.
+    pub text_template:        Option<String>,
+    /// Lowest severity shown in the output; the exit code ignores it.
+    pub min_severity:         Option<FailOn>,
+    /// Lowest severity that makes the exit code non-zero.
+    pub fail_on:              FailOn,
+    /// Stop after static analysis even when an LLM is reachable.
+    pub skip_llm:             bool,
+    /// 1-based line ranges of the queries file to report on; violations of
+    /// statements outside them are dropped. `None` reports everything.
+    pub changed_lines:        Option<Vec<RangeInclusive<usize>>>
 }
 
 /// Result of analysis containing all outputs.
@@ -71,6 +165,8 @@ pub struct AnalyzeParams {
 /// * `static_output` - Formatted static analysis results
 /// * `llm_output` - Optional LLM analysis results
 /// * `dry_run_info` - Present when running in dry-run mode
+/// * `summary` / `queries_count` - Totals for the stderr summary line
+/// * `timed_out` - Set when the run deadline cut the LLM step short
 #[derive(Debug, Clone)]
 pub struct AnalyzeResult {
     /// Exit code based on violation severity (0, 1, or 2).
@@ -80,7 +176,14 @@ pub struct AnalyzeResult {
     /// Optional LLM analysis output.
     pub llm_output:    Option<String>,
     /// Dry run information if in dry-run mode.
-    pub dry_run_info:  Option<DryRunInfo>
+    pub dry_run_info:  Option<DryRunInfo>,
+    /// Violation counts behind `exit_code`, after `--select` filtering.
+    pub summary:       ReportSummary,
+    /// Number of queries analyzed.
+    pub queries_count: usize,
+    /// Whether `deadline` expired after static analysis, leaving the LLM
+    /// step unfinished.
+    pub timed_out:     bool
 }
 
 /// Information shown during dry run mode.
@@ -107,7 +210,8 @@ pub struct DryRunInfo {
 ///
 /// let output = CommandOutput {
 ///     exit_code: 0,
-///     stdout:    vec!["Analysis complete.".to_string()]
+///     stdout:    vec!["Analysis complete.".to_string()],
+///     stderr:    Vec::new()
 /// };
 /// ```
 #[derive(Debug, Clone)]
@@ -115,7 +219,9 @@ pub struct CommandOutput {
     /// Exit code for the process (0=success, 1=warnings, 2=errors).
     pub exit_code: i32,
     /// Lines to print to stdout.
-    pub stdout:    Vec<String>
+    pub stdout:    Vec<String>,
+    /// Lines to print to stderr after stdout.
+    pub stderr:    Vec<String>
 }
 
 #[cfg(test)]
@@ -125,17 +231,45 @@ mod tests {
     #[test]
     fn test_analyze_params_debug() {
         let params = AnalyzeParams {
-            schema_path:   "schema.sql".to_string(),
-            queries_path:  "queries.sql".to_string(),
-            provider:      Provider::Ollama,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      false
+            schema_path:          "schema.sql".to_string(),
+            queries_path:         "queries.sql".to_string(),
+            provider:             Provider::Ollama,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             false,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           ".sql-analyzer-state.json".to_string(),
+            report_url:           None,
+            report_headers:       Vec::new(),
+            also_outputs:         Vec::new(),
+            text_template:        None,
+            min_severity:         None,
+            fail_on:              FailOn::Warning,
+            skip_llm:             false,
+            changed_lines:        None
         };
         assert!(format!("{:?}", params).contains("AnalyzeParams"));
     }
@@ -143,17 +277,45 @@ mod tests {
     #[test]
     fn test_analyze_params_clone() {
         let params = AnalyzeParams {
-            schema_path:   "schema.sql".to_string(),
-            queries_path:  "queries.sql".to_string(),
-            provider:      Provider::Ollama,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      false
+            schema_path:          "schema.sql".to_string(),
+            queries_path:         "queries.sql".to_string(),
+            provider:             Provider::Ollama,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             false,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           ".sql-analyzer-state.json".to_string(),
+            report_url:           None,
+            report_headers:       Vec::new(),
+            also_outputs:         Vec::new(),
+            text_template:        None,
+            min_severity:         None,
+            fail_on:              FailOn::Warning,
+            skip_llm:             false,
+            changed_lines:        None
         };
         let cloned = params.clone();
         assert_eq!(cloned.schema_path, params.schema_path);
@@ -165,7 +327,10 @@ mod tests {
             exit_code:     0,
             static_output: "output".to_string(),
             llm_output:    None,
-            dry_run_info:  None
+            dry_run_info:  None,
+            summary:       ReportSummary::default(),
+            queries_count: 0,
+            timed_out:     false
         };
         assert!(format!("{:?}", result).contains("AnalyzeResult"));
     }
@@ -183,7 +348,8 @@ mod tests {
     fn test_command_output_debug() {
         let output = CommandOutput {
             exit_code: 0,
-            stdout:    vec!["line1".to_string()]
+            stdout:    vec!["line1".to_string()],
+            stderr:    Vec::new()
         };
         assert!(format!("{:?}", output).contains("CommandOutput"));
     }
@@ -192,7 +358,8 @@ mod tests {
     fn test_command_output_clone() {
         let output = CommandOutput {
             exit_code: 1,
-            stdout:    vec!["error".to_string()]
+            stdout:    vec!["error".to_string()],
+            stderr:    Vec::new()
         };
         let cloned = output.clone();
         assert_eq!(cloned.exit_code, 1);
@@ -0,0 +1,47 @@
+//! Configuration file validation.
+//!
+//! This module runs the `validate-config` subcommand over a
+//! `.sql-analyzer.toml`; the checks themselves live in
+//! [`validate_config`].
+
+use std::{fs::read_to_string, path::Path};
+
+use super::types::CommandOutput;
+use crate::{
+    config::validate_config,
+    error::{AppResult, file_read_error}
+};
+
+/// Validates the config file at `path`, or `.sql-analyzer.toml` when none
+/// is given.
+///
+/// Every problem is reported on its own line prefixed with the path.
+///
+/// # Returns
+///
+/// A `CommandOutput` with exit code `0` if the file is valid, `1` otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read.
+pub fn run_validate_config(path: Option<&Path>) -> AppResult<CommandOutput> {
+    let path = path.unwrap_or(Path::new(".sql-analyzer.toml"));
+    let display = path.display().to_string();
+    let content = read_to_string(path).map_err(|e| file_read_error(&display, e))?;
+    let problems = validate_config(&content);
+    if problems.is_empty() {
+        return Ok(CommandOutput {
+            exit_code: 0,
+            stdout:    vec![format!("{}: OK", display)],
+            stderr:    Vec::new()
+        });
+    }
+    Ok(CommandOutput {
+        exit_code: 1,
+        stdout:    problems
+            .iter()
+            .map(|problem| format!("{}: {}", display, problem))
+            .collect(),
+        stderr:    Vec::new()
+    })
+}
@@ -0,0 +1,177 @@
+//! Schema conformance check.
+//!
+//! This module backs the `validate-schema` subcommand: it runs only the
+//! schema-aware rules against the queries and reports which referenced
+//! tables and columns the schema defines and which filter columns no index
+//! covers.
+
+use indexmap::IndexSet;
+
+use super::{convert::convert_dialect, helpers::read_analyze_inputs, types::CommandOutput};
+use crate::{
+    cli::Dialect,
+    error::AppResult,
+    query::{Query, parse_queries},
+    rules::RuleRunner,
+    schema::Schema
+};
+
+/// Checks the queries at `queries_path` against the schema at
+/// `schema_path`.
+///
+/// # Arguments
+///
+/// * `schema_path` - Path to the schema file, or "-" for stdin
+/// * `queries_path` - Path to the queries file, or "-" for stdin
+/// * `dialect` - SQL dialect for parsing
+///
+/// # Returns
+///
+/// A `CommandOutput` with the coverage summary and the SCHEMA* findings.
+/// The exit code is `1` when a query references a table or column the
+/// schema does not define, `0` otherwise.
+///
+/// # Errors
+///
+/// Returns an error if an input cannot be read or parsed.
+pub fn run_validate_schema(
+    schema_path: &str,
+    queries_path: &str,
+    dialect: Dialect
+) -> AppResult<CommandOutput> {
+    let (schema_sql, queries_sql) = read_analyze_inputs(schema_path, queries_path)?;
+    let dialect = convert_dialect(dialect);
+    let schema = Schema::parse(&schema_sql, dialect)?;
+    let queries = parse_queries(&queries_sql, dialect)?;
+
+    let tables = referenced_tables(&queries);
+    let missing_tables: Vec<&str> = tables
+        .iter()
+        .map(|t| t.as_str())
+        .filter(|t| !has_table(&schema, t))
+        .collect();
+    let columns = referenced_columns(&queries);
+    let missing_columns: Vec<&str> = columns
+        .iter()
+        .map(|c| c.as_str())
+        .filter(|c| !has_column(&schema, c))
+        .collect();
+    let unindexed: Vec<String> = filter_columns(&queries)
+        .into_iter()
+        .filter(|c| has_column(&schema, c) && !is_indexed(&schema, c))
+        .collect();
+
+    let mut stdout = vec![format!(
+        "Tables: {} of {} found",
+        tables.len() - missing_tables.len(),
+        tables.len()
+    )];
+    if !missing_tables.is_empty() {
+        stdout.push(format!("  missing: {}", missing_tables.join(", ")));
+    }
+    stdout.push(format!(
+        "Columns: {} of {} found",
+        columns.len() - missing_columns.len(),
+        columns.len()
+    ));
+    if !missing_columns.is_empty() {
+        stdout.push(format!("  missing: {}", missing_columns.join(", ")));
+    }
+    if !unindexed.is_empty() {
+        stdout.push(format!(
+            "Filter columns without an index: {}",
+            unindexed.join(", ")
+        ));
+    }
+
+    let report = RuleRunner::schema_only(schema).analyze(&queries);
+    if !report.violations.is_empty() {
+        stdout.push("Findings:".to_string());
+    }
+    for violation in &report.violations {
+        let line = violation
+            .location
+            .map(|span| format!(" (line {})", span.line))
+            .unwrap_or_default();
+        stdout.push(format!(
+            "  #{}{}: [{}] {}",
+            violation.query_index + 1,
+            line,
+            violation.rule_id,
+            violation.message
+        ));
+    }
+
+    let exit_code = i32::from(!missing_tables.is_empty() || !missing_columns.is_empty());
+    Ok(CommandOutput {
+        exit_code,
+        stdout,
+        stderr: Vec::new()
+    })
+}
+
+/// Tables read or written by the queries, excluding CTE names
+fn referenced_tables(queries: &[Query]) -> IndexSet<String> {
+    queries
+        .iter()
+        .flat_map(|q| {
+            q.tables
+                .iter()
+                .filter(|t| !q.cte_names.iter().any(|c| c.eq_ignore_ascii_case(t)))
+                .map(|t| t.to_string())
+        })
+        .collect()
+}
+
+/// Columns used in WHERE, JOIN, ORDER BY and GROUP BY, as SCHEMA002 sees them
+fn referenced_columns(queries: &[Query]) -> IndexSet<String> {
+    queries
+        .iter()
+        .flat_map(|q| {
+            q.where_cols
+                .iter()
+                .chain(q.join_cols.iter())
+                .chain(q.order_cols.iter())
+                .chain(q.group_cols.iter())
+        })
+        .filter(|c| !c.chars().all(|ch| ch.is_numeric() || ch == '.'))
+        .map(|c| c.to_string())
+        .collect()
+}
+
+/// Columns used in WHERE and JOIN conditions
+fn filter_columns(queries: &[Query]) -> IndexSet<String> {
+    queries
+        .iter()
+        .flat_map(|q| q.where_cols.iter().chain(q.join_cols.iter()))
+        .map(|c| c.to_string())
+        .collect()
+}
+
+fn has_table(schema: &Schema, table: &str) -> bool {
+    let short = table.rsplit('.').next().unwrap_or(table);
+    schema
+        .tables
+        .values()
+        .any(|t| t.name.eq_ignore_ascii_case(table) || t.name.eq_ignore_ascii_case(short))
+}
+
+fn has_column(schema: &Schema, column: &str) -> bool {
+    schema
+        .tables
+        .values()
+        .flat_map(|t| t.columns.iter())
+        .any(|c| c.name.eq_ignore_ascii_case(column))
+}
+
+fn is_indexed(schema: &Schema, column: &str) -> bool {
+    schema.tables.values().any(|t| {
+        t.columns
+            .iter()
+            .any(|c| c.is_primary && c.name.eq_ignore_ascii_case(column))
+            || t.indexes
+                .iter()
+                .flat_map(|idx| idx.columns.iter())
+                .any(|c| c.eq_ignore_ascii_case(column))
+    })
+}
@@ -0,0 +1,181 @@
+//! Watch mode for local development.
+//!
+//! `--watch` analyzes once, then waits for the schema or queries file to
+//! change and analyzes again, clearing the terminal before each report.
+//! Changes come from the platform's file notifications (inotify, FSEvents,
+//! ReadDirectoryChangesW) through `notify`, and a burst of saves inside the
+//! debounce window triggers a single run.
+//! The LLM step is skipped, so every save gets a fast static report.
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration
+};
+
+use notify_debouncer_mini::{
+    DebounceEventResult, DebouncedEventKind, new_debouncer, notify::RecursiveMode
+};
+use tokio::sync::mpsc;
+
+use super::{
+    analyze::run_analyze,
+    types::{AnalyzeParams, CommandOutput}
+};
+use crate::{
+    config::Config,
+    error::{AppError, AppResult, watch_error},
+    output::format_summary_line
+};
+
+/// Quiet period after the last change before a run starts
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// ANSI sequence that clears the terminal and homes the cursor
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Source of file change notifications for [`run_watch`]
+pub trait ChangeSource {
+    /// Waits for the next batch of changed paths; `None` ends the watch
+    fn next_change(&mut self) -> impl Future<Output = Option<Vec<PathBuf>>>;
+}
+
+impl ChangeSource for mpsc::Receiver<Vec<PathBuf>> {
+    async fn next_change(&mut self) -> Option<Vec<PathBuf>> {
+        self.recv().await
+    }
+}
+
+/// Canonical path `path` is reported under by a watch on its directory
+fn watch_target(path: &Path) -> AppResult<PathBuf> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| watch_error(format!("'{}' is not a file", path.display())))?;
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new(".")
+    };
+    let dir = dir
+        .canonicalize()
+        .map_err(|e| watch_error(format!("'{}': {}", dir.display(), e)))?;
+    Ok(dir.join(name))
+}
+
+/// Watches `paths` on a background thread and sends the changed ones once
+/// no further change has been seen for `debounce`; interim notices for a
+/// file that keeps changing are ignored.
+///
+/// The directories holding the files are watched rather than the files
+/// themselves, so editors that save by renaming a new file over the old one
+/// keep being followed. The watch stops when the receiver is dropped.
+///
+/// # Errors
+///
+/// Returns an error if a file's directory cannot be resolved or the
+/// platform watcher cannot be started.
+pub fn watch_files(
+    paths: Vec<PathBuf>,
+    debounce: Duration
+) -> AppResult<mpsc::Receiver<Vec<PathBuf>>> {
+    let targets = paths
+        .iter()
+        .map(|path| watch_target(path))
+        .collect::<AppResult<Vec<_>>>()?;
+    let mut dirs: Vec<PathBuf> = targets
+        .iter()
+        .filter_map(|target| target.parent().map(Path::to_path_buf))
+        .collect();
+    dirs.dedup();
+    let (tx, rx) = mpsc::channel(1);
+    let keep_alive = tx.clone();
+    let mut debouncer = new_debouncer(debounce, move |result: DebounceEventResult| {
+        let Ok(events) = result else {
+            return;
+        };
+        let mut changed: Vec<PathBuf> = Vec::new();
+        for (path, target) in paths.iter().zip(&targets) {
+            let settled = events
+                .iter()
+                .any(|event| event.kind == DebouncedEventKind::Any && event.path == *target);
+            if settled && !changed.contains(path) {
+                changed.push(path.clone());
+            }
+        }
+        if !changed.is_empty() {
+            let _ = tx.blocking_send(changed);
+        }
+    })
+    .map_err(|e| watch_error(e.to_string()))?;
+    for dir in &dirs {
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+            .map_err(|e| watch_error(format!("'{}': {}", dir.display(), e)))?;
+    }
+    tokio::spawn(async move {
+        keep_alive.closed().await;
+        drop(debouncer);
+    });
+    Ok(rx)
+}
+
+/// Analyzes with `params`, then again after every change from `changes`,
+/// clearing the terminal and writing each report and, unless `quiet`, its
+/// summary line to `out`.
+///
+/// A run that fails, e.g. on a half-edited statement, prints its error and
+/// waits for the next change instead of ending the watch.
+///
+/// # Errors
+///
+/// Returns an error if `out` cannot be written.
+pub async fn run_watch(
+    params: AnalyzeParams,
+    config: Config,
+    mut changes: impl ChangeSource,
+    out: &mut impl Write
+) -> AppResult<CommandOutput> {
+    let write_error =
+        |e: std::io::Error| AppError::internal(format!("Failed to write output: {}", e));
+    let mut exit_code;
+    loop {
+        write!(out, "{}", CLEAR_SCREEN).map_err(write_error)?;
+        match run_analyze(params.clone(), config.clone()).await {
+            Ok(result) => {
+                exit_code = result.exit_code;
+                writeln!(out, "{}", result.static_output).map_err(write_error)?;
+                if !params.quiet {
+                    writeln!(
+                        out,
+                        "{}",
+                        format_summary_line(
+                            &result.summary,
+                            result.queries_count,
+                            result.exit_code
+                        )
+                    )
+                    .map_err(write_error)?;
+                }
+            }
+            Err(e) => {
+                exit_code = 1;
+                writeln!(out, "Error: {}", e.render_message()).map_err(write_error)?;
+            }
+        }
+        writeln!(
+            out,
+            "Watching {} and {} for changes (Ctrl-C to stop)",
+            params.schema_path, params.queries_path
+        )
+        .map_err(write_error)?;
+        out.flush().map_err(write_error)?;
+        if changes.next_change().await.is_none() {
+            break;
+        }
+    }
+    Ok(CommandOutput {
+        exit_code,
+        stdout: Vec::new(),
+        stderr: Vec::new()
+    })
+}
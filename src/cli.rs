@@ -12,22 +12,24 @@ pub struct Cli {
 }
 
 #[derive(Subcommand, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Commands {
     /// Analyze SQL queries against schema
     Analyze {
-        /// Path to SQL schema file
+        /// Path to SQL schema file (use - for stdin; with `-q -` too, end the
+        /// schema with a `-- @queries` line)
         #[arg(short, long)]
         schema: PathBuf,
 
         /// Path to SQL queries file (use - for stdin)
-        #[arg(short, long)]
-        queries: PathBuf,
+        #[arg(short, long, required_unless_present_any = ["changed_only", "since"])]
+        queries: Option<PathBuf>,
 
         /// LLM provider to use
         #[arg(short, long, value_enum, default_value = "ollama")]
         provider: Provider,
 
-        /// API key for OpenAI or Anthropic
+        /// API key for OpenAI, Anthropic, or Cohere
         #[arg(short, long, env = "LLM_API_KEY", hide_env_values = true)]
         api_key: Option<String>,
 
@@ -43,6 +45,17 @@ pub enum Commands {
         #[arg(long, value_enum, default_value = "generic")]
         dialect: Dialect,
 
+        /// Replace template blocks (dbt models, `.sql.j2`) with placeholders
+        /// before parsing
+        #[arg(long, value_enum)]
+        template: Option<Template>,
+
+        /// Layout of the queries file: plain SQL, or JSON/NDJSON records
+        /// with a `sql` member and an optional `name` reported with each
+        /// violation
+        #[arg(long, value_enum, default_value = "raw")]
+        input_format: InputFormat,
+
         /// Output format
         #[arg(short = 'f', long, value_enum, default_value = "text")]
         output_format: Format,
@@ -57,7 +70,218 @@ pub enum Commands {
 
         /// Disable colored output
         #[arg(long)]
-        no_color: bool
+        no_color: bool,
+
+        /// Emit JSON and SARIF output on a single line
+        #[arg(long)]
+        compact: bool,
+
+        /// Do not print the one-line result summary to stderr
+        #[arg(long)]
+        quiet: bool,
+
+        /// Print only violation counts by severity and category
+        #[arg(long)]
+        summary_only: bool,
+
+        /// Print aggregate statistics instead of the violations: counts by
+        /// severity and category, most frequent rules, query complexity
+        #[arg(long, conflicts_with = "summary_only")]
+        stats: bool,
+
+        /// Keep only violations matching all predicates, e.g.
+        /// `rule_id^=SEC,severity=error,query=3`
+        #[arg(long)]
+        select: Option<String>,
+
+        /// Compute the exit code from selected violations only
+        #[arg(long, requires = "select")]
+        fail_on_selected: bool,
+
+        /// Report analysis throughput (queries per second)
+        #[arg(long)]
+        timings: bool,
+
+        /// Always call the LLM instead of reusing a cached response
+        #[arg(long)]
+        no_llm_cache: bool,
+
+        /// Run static analysis only, even when LLM credentials are available
+        #[arg(long)]
+        no_llm: bool,
+
+        /// Seconds a cached LLM response stays valid
+        #[arg(long, default_value_t = 86400)]
+        llm_cache_ttl: u64,
+
+        /// Seconds before an LLM request times out (overrides
+        /// `retry.timeout_secs`)
+        #[arg(long)]
+        llm_timeout: Option<u64>,
+
+        /// Estimated tokens (characters / 4) of query summaries sent in one
+        /// LLM request; larger query sets are analyzed in several batches
+        #[arg(long, default_value_t = 8000)]
+        llm_chunk_tokens: usize,
+
+        /// Append a JSON line per LLM request (time, provider, model, prompt
+        /// size, status) to this file; prompts and API keys are left out
+        #[arg(long)]
+        audit_log: Option<PathBuf>,
+
+        /// Also record the prompt text in the `--audit-log` file
+        #[arg(long, requires = "audit_log")]
+        audit_include_prompt: bool,
+
+        /// Seconds the whole run may take; on expiry the static analysis
+        /// results are printed with a notice and the LLM step is abandoned
+        #[arg(long)]
+        deadline: Option<u64>,
+
+        /// Worker threads for parsing and rule evaluation (default: one per
+        /// CPU; 0 also picks the CPU count). LLM requests are sent one at
+        /// a time regardless
+        #[arg(long)]
+        concurrency: Option<usize>,
+
+        /// Language for violation messages: `en`, or the path of a TOML/JSON
+        /// message catalog
+        #[arg(long, default_value = "en")]
+        lang: String,
+
+        /// Skip the rule engine when the queries, schema, and rule
+        /// configuration are unchanged since the last run
+        #[arg(long)]
+        incremental: bool,
+
+        /// State file holding per-file hashes and reports for `--incremental`
+        #[arg(long, default_value = ".sql-analyzer-state.json")]
+        state_file: PathBuf,
+
+        /// POST the JSON report to this URL after analysis; failures are
+        /// logged and do not change the exit code
+        #[arg(long)]
+        report_url: Option<String>,
+
+        /// Header for the report upload as `Name: value` (repeatable)
+        #[arg(long, requires = "report_url")]
+        report_header: Vec<String>,
+
+        /// Also write the report as FORMAT to PATH, e.g.
+        /// `sarif:results.sarif`; repeatable
+        #[arg(long, value_name = "FORMAT:PATH", conflicts_with = "changed_only")]
+        also_output: Vec<String>,
+
+        /// Template for each violation line of text output, e.g.
+        /// `[{severity}] {rule_id}:{line} {message}`
+        #[arg(long = "format-config", value_name = "TEMPLATE")]
+        text_template: Option<String>,
+
+        /// Analyze each queries file listed one per line on stdin or in
+        /// `--files-from`, as passed by pre-commit; skips the LLM step
+        #[arg(long, conflicts_with = "queries")]
+        changed_only: bool,
+
+        /// File holding the paths for `--changed-only` (use - for stdin)
+        #[arg(long, requires = "changed_only")]
+        files_from: Option<PathBuf>,
+
+        /// Analyze the `.sql` files changed since a git ref and report only
+        /// violations in statements that overlap changed lines; skips the LLM
+        /// step
+        #[arg(long, value_name = "REF", conflicts_with_all = ["queries", "changed_only"])]
+        since: Option<String>,
+
+        /// Re-run the analysis whenever the schema or queries file changes,
+        /// clearing the terminal each time; text output only, skips the LLM
+        /// step
+        #[arg(long, requires = "queries")]
+        watch: bool,
+
+        /// Lowest severity that makes the exit code non-zero
+        #[arg(long, value_enum, default_value = "warning")]
+        fail_on: FailOn,
+
+        /// Show only violations at or above this severity in every output
+        /// format; the exit code still reflects all of them
+        #[arg(long, value_enum)]
+        min_severity: Option<FailOn>,
+
+        /// Rule preset the config file's `[rules]` section is applied on top
+        /// of
+        #[arg(long, value_enum)]
+        profile: Option<Profile>
+    },
+    /// Export the rule catalog for documentation and editor tooling
+    ExportCatalog {
+        /// Catalog format
+        #[arg(short = 'f', long, value_enum, default_value = "json")]
+        format: CatalogFormat
+    },
+    /// Print the JSON Schema of the JSON or SARIF report
+    PrintSchema {
+        /// Report format whose schema to print
+        #[arg(short = 'f', long, value_enum, default_value = "json")]
+        format: SchemaFormat
+    },
+    /// Check a config file for syntax errors, unknown keys, and unknown rule
+    /// IDs
+    ValidateConfig {
+        /// Config file to check (default: .sql-analyzer.toml)
+        path: Option<PathBuf>
+    },
+    /// Count how many queries reference each table and column
+    Stats {
+        /// Path to SQL queries file (use - for stdin)
+        #[arg(short, long)]
+        queries: PathBuf,
+
+        /// Path to SQL schema file; attributes unqualified columns of joins
+        /// to their tables
+        #[arg(short, long)]
+        schema: Option<PathBuf>,
+
+        /// SQL dialect for parsing
+        #[arg(long, value_enum, default_value = "generic")]
+        dialect: Dialect,
+
+        /// Output format
+        #[arg(short = 'f', long, value_enum, default_value = "text")]
+        format: StatsFormat
+    },
+    /// List the queries that reference a column, e.g. before dropping it
+    Impact {
+        /// Table that owns the column
+        table: String,
+
+        /// Column to look up
+        column: String,
+
+        /// Path to SQL schema file (use - for stdin)
+        #[arg(short, long)]
+        schema: PathBuf,
+
+        /// Path to SQL queries file (use - for stdin)
+        #[arg(short, long)]
+        queries: PathBuf,
+
+        /// SQL dialect for parsing
+        #[arg(long, value_enum, default_value = "generic")]
+        dialect: Dialect
+    },
+    /// Check that the tables and columns the queries use exist in the schema
+    ValidateSchema {
+        /// Path to SQL schema file (use - for stdin)
+        #[arg(short, long)]
+        schema: PathBuf,
+
+        /// Path to SQL queries file (use - for stdin)
+        #[arg(short, long)]
+        queries: PathBuf,
+
+        /// SQL dialect for parsing
+        #[arg(long, value_enum, default_value = "generic")]
+        dialect: Dialect
     }
 }
 
@@ -65,6 +289,7 @@ pub enum Commands {
 pub enum Provider {
     OpenAI,
     Anthropic,
+    Cohere,
     Ollama
 }
 
@@ -74,6 +299,7 @@ impl Provider {
         match self {
             Self::OpenAI => "gpt-4",
             Self::Anthropic => "claude-sonnet-4-20250514",
+            Self::Cohere => "command-r",
             Self::Ollama => "llama3.2"
         }
     }
@@ -89,10 +315,69 @@ pub enum Dialect {
     Clickhouse
 }
 
+/// Template language of the queries file
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Template {
+    /// `{{ ... }}`, `{% ... %}`, and `{# ... #}` (Jinja, dbt)
+    Jinja,
+    /// `{{ ... }}` with `{{#...}}`, `{{/...}}`, and `{{!...}}` blocks
+    Handlebars
+}
+
+/// Layout of the queries file
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum InputFormat {
+    /// Plain SQL statements
+    Raw,
+    /// A JSON array of `{"sql": ..., "name": ...}` records
+    Json,
+    /// One `{"sql": ..., "name": ...}` record per line
+    Ndjson
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum Format {
     Text,
     Json,
     Yaml,
+    Sarif,
+    /// GitHub Actions annotations (`::error file=...,line=...::...`)
+    Github
+}
+
+/// Severity threshold for a failing exit code or for displayed violations
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum FailOn {
+    Error,
+    Warning,
+    Info
+}
+
+/// Named rule preset
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Profile {
+    /// Promote many Info rules to Warning
+    Strict,
+    /// The built-in severities
+    Balanced,
+    /// Disable style and low-signal Info rules
+    Relaxed
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum CatalogFormat {
+    Json,
+    Markdown
+}
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum SchemaFormat {
+    Json,
     Sarif
 }
+
+#[derive(Debug, Clone, ValueEnum)]
+pub enum StatsFormat {
+    Text,
+    Json
+}
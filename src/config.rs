@@ -13,7 +13,7 @@
 //!
 //! ```toml
 //! [llm]
-//! provider = "ollama"          # openai, anthropic, ollama
+//! provider = "ollama"          # openai, anthropic, cohere, ollama
 //! model = "llama3.2"
 //! api_key = "sk-..."           # or use LLM_API_KEY env var
 //! ollama_url = "http://localhost:11434"
@@ -23,6 +23,7 @@
 //! initial_delay_ms = 1000
 //! max_delay_ms = 30000
 //! backoff_factor = 2.0
+//! timeout_secs = 120
 //!
 //! [rules]
 //! disabled = ["STYLE001", "PERF011"]
@@ -30,32 +31,56 @@
 //! [rules.severity]
 //! PERF001 = "error"
 //! SCHEMA001 = "info"
+//!
+//! [rules.category]
+//! PERF005 = "security"
+//!
+//! [[rules.custom]]
+//! id = "TEAM001"
+//! pattern = '(?i)\bTODO\b'
+//! severity = "warning"
+//! category = "style"
+//! message = "Unfinished statement marked TODO"
+//!
+//! [schema]
+//! large_tables = ["events"]
+//!
+//! [schema.deprecated]
+//! "users.legacy_email" = "Use users.email instead"
 //! ```
 //!
 //! # Environment Variables
 //!
 //! | Variable | Description |
 //! |----------|-------------|
-//! | `LLM_API_KEY` | API key for OpenAI/Anthropic |
+//! | `LLM_API_KEY` | API key for OpenAI/Anthropic/Cohere |
 //! | `LLM_PROVIDER` | Provider name |
 //! | `LLM_MODEL` | Model identifier |
 //! | `OLLAMA_URL` | Ollama base URL |
 
+mod validate;
+
 use std::{collections::HashMap, env, fs, path::PathBuf};
 
 use serde::Deserialize;
+pub use validate::validate_config;
 
-use crate::error::{AppResult, config_error};
+use crate::{
+    error::{AppResult, config_error},
+    rules::{RegexRule, rule_catalog}
+};
 
 /// Application configuration
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
-    pub llm:   LlmConfig,
+    pub llm:    LlmConfig,
+    #[serde(default)]
+    pub retry:  RetryConfig,
     #[serde(default)]
-    pub retry: RetryConfig,
+    pub rules:  RulesConfig,
     #[serde(default)]
-    pub rules: RulesConfig
+    pub schema: SchemaConfig
 }
 
 /// Rules configuration
@@ -63,10 +88,127 @@ pub struct Config {
 pub struct RulesConfig {
     /// Disabled rule IDs
     #[serde(default)]
-    pub disabled: Vec<String>,
+    pub disabled:            Vec<String>,
     /// Severity overrides (rule_id -> severity)
     #[serde(default)]
-    pub severity: HashMap<String, String>
+    pub severity:            HashMap<String, String>,
+    /// Category overrides (rule_id -> category)
+    #[serde(default)]
+    pub category:            HashMap<String, String>,
+    /// Pattern-based rules defined in `[[rules.custom]]`
+    #[serde(default)]
+    pub custom:              Vec<CustomRuleConfig>,
+    /// Variables SEC021 flags in SET statements, on top of its defaults
+    #[serde(default)]
+    pub sensitive_variables: Vec<String>,
+    /// Schemas SEC023 accepts as qualifiers, as `schema` or `database.schema`
+    #[serde(default)]
+    pub allowed_schemas:     Vec<String>
+}
+
+/// Named rule preset selected with `--profile`
+///
+/// A profile is a baseline [`RulesConfig`] that the config file is applied
+/// on top of; see [`RulesConfig::over_profile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Profile {
+    /// Promote [`STRICT_PROMOTED`] from Info to Warning
+    Strict,
+    /// The built-in severities with every rule enabled
+    #[default]
+    Balanced,
+    /// Disable [`RELAXED_DISABLED`]
+    Relaxed
+}
+
+/// Info rules the `strict` profile reports as Warning
+pub const STRICT_PROMOTED: &[&str] = &[
+    "PERF003",
+    "PERF006",
+    "PERF010",
+    "PERF014",
+    "PERF016",
+    "PERF045",
+    "PERF048",
+    "PERF056",
+    "PERF057",
+    "STYLE001",
+    "STYLE002",
+    "STYLE004",
+    "SEC018",
+    "SCHEMA017",
+    "SCHEMA020"
+];
+
+/// Style and low-signal Info rules the `relaxed` profile disables
+pub const RELAXED_DISABLED: &[&str] = &[
+    "PERF003",
+    "PERF006",
+    "PERF010",
+    "PERF011",
+    "PERF014",
+    "PERF016",
+    "PERF044",
+    "PERF047",
+    "PERF048",
+    "PERF050",
+    "PERF056",
+    "CHPERF003",
+    "STYLE001",
+    "STYLE002",
+    "STYLE004"
+];
+
+impl Profile {
+    /// Baseline rule configuration of the profile
+    pub fn rules(self) -> RulesConfig {
+        match self {
+            Self::Strict => RulesConfig {
+                severity: STRICT_PROMOTED
+                    .iter()
+                    .map(|id| (id.to_string(), "warning".to_string()))
+                    .collect(),
+                ..Default::default()
+            },
+            Self::Balanced => RulesConfig::default(),
+            Self::Relaxed => RulesConfig {
+                disabled: RELAXED_DISABLED.iter().map(|id| id.to_string()).collect(),
+                ..Default::default()
+            }
+        }
+    }
+}
+
+/// Rule that flags every statement whose text matches a regex
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomRuleConfig {
+    /// Rule ID shown in reports; must not collide with a built-in rule
+    pub id:         String,
+    /// Human-readable name; defaults to the ID
+    #[serde(default)]
+    pub name:       Option<String>,
+    /// Regex matched against the statement text and its comments
+    pub pattern:    String,
+    /// `error`, `warning`, or `info`
+    pub severity:   String,
+    /// `performance`, `style`, or `security`
+    pub category:   String,
+    /// Violation message
+    pub message:    String,
+    /// Optional fix suggestion
+    #[serde(default)]
+    pub suggestion: Option<String>
+}
+
+/// Schema annotations that the DDL does not carry
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct SchemaConfig {
+    /// Deprecated columns (`table.column` -> note shown in violations)
+    #[serde(default)]
+    pub deprecated:   HashMap<String, String>,
+    /// Tables too big to read without a filter (SEC022)
+    #[serde(default)]
+    pub large_tables: Vec<String>
 }
 
 /// LLM provider configuration
@@ -95,7 +237,14 @@ pub struct RetryConfig {
     pub max_retries:      u32,
     pub initial_delay_ms: u64,
     pub max_delay_ms:     u64,
-    pub backoff_factor:   f64
+    pub backoff_factor:   f64,
+    /// Seconds before a single LLM request is abandoned and retried
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs:     u64
+}
+
+fn default_timeout_secs() -> u64 {
+    120
 }
 
 impl Default for RetryConfig {
@@ -104,7 +253,8 @@ impl Default for RetryConfig {
             max_retries:      3,
             initial_delay_ms: 1000,
             max_delay_ms:     30000,
-            backoff_factor:   2.0
+            backoff_factor:   2.0,
+            timeout_secs:     default_timeout_secs()
         }
     }
 }
@@ -150,6 +300,64 @@ impl Config {
         if let Ok(url) = env::var("OLLAMA_URL") {
             config.llm.ollama_url = Some(url);
         }
+        config.rules.check_custom()?;
         Ok(config)
     }
 }
+
+impl RulesConfig {
+    /// Apply this configuration on top of the `profile` baseline
+    ///
+    /// Disabled lists are merged and explicit severity overrides win over
+    /// the profile's. Giving a rule a severity re-enables it when the
+    /// profile disabled it.
+    pub fn over_profile(self, profile: Profile) -> Self {
+        let base = profile.rules();
+        let mut disabled: Vec<String> = base
+            .disabled
+            .into_iter()
+            .filter(|id| !self.severity.keys().any(|key| key.eq_ignore_ascii_case(id)))
+            .collect();
+        disabled.extend(self.disabled);
+        let mut severity = base.severity;
+        for (id, level) in self.severity {
+            severity.retain(|key, _| !key.eq_ignore_ascii_case(&id));
+            severity.insert(id, level);
+        }
+        Self {
+            disabled,
+            severity,
+            ..self
+        }
+    }
+
+    /// Check every `[[rules.custom]]` definition: its regex, severity and
+    /// category must parse, and its ID must not be taken by a built-in rule
+    /// or an earlier custom rule
+    ///
+    /// # Errors
+    ///
+    /// Returns an error naming the first invalid rule.
+    pub fn check_custom(&self) -> AppResult<()> {
+        if self.custom.is_empty() {
+            return Ok(());
+        }
+        let catalog = rule_catalog();
+        for (pos, def) in self.custom.iter().enumerate() {
+            RegexRule::new(def).map_err(config_error)?;
+            let taken = catalog
+                .iter()
+                .any(|entry| entry.id.eq_ignore_ascii_case(&def.id))
+                || self.custom[..pos]
+                    .iter()
+                    .any(|other| other.id.eq_ignore_ascii_case(&def.id));
+            if taken {
+                return Err(config_error(format!(
+                    "Custom rule '{}': ID is already in use",
+                    def.id
+                )));
+            }
+        }
+        Ok(())
+    }
+}
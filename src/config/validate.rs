@@ -0,0 +1,164 @@
+//! Configuration file validation for the `validate-config` subcommand:
+//! TOML syntax, unknown keys, value types, rule IDs in `[rules]`, severity
+//! and category names, and `[[rules.custom]]` definitions.
+
+use super::Config;
+use crate::rules::{RegexRule, RuleCategory, Severity, rule_catalog};
+
+/// Keys accepted by each `[[rules.custom]]` entry.
+const CUSTOM_RULE_KEYS: &[&str] = &[
+    "id",
+    "name",
+    "pattern",
+    "severity",
+    "category",
+    "message",
+    "suggestion"
+];
+
+/// Sections of the config file and the keys each one accepts.
+const KNOWN_KEYS: [(&str, &[&str]); 4] = [
+    ("llm", &["provider", "api_key", "model", "ollama_url"]),
+    (
+        "retry",
+        &[
+            "max_retries",
+            "initial_delay_ms",
+            "max_delay_ms",
+            "backoff_factor",
+            "timeout_secs"
+        ]
+    ),
+    (
+        "rules",
+        &[
+            "disabled",
+            "severity",
+            "category",
+            "custom",
+            "sensitive_variables",
+            "allowed_schemas"
+        ]
+    ),
+    ("schema", &["deprecated", "large_tables"])
+];
+
+/// Returns every problem found in the config file contents.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::config::validate_config;
+///
+/// assert!(validate_config("[rules]\ndisabled = [\"PERF001\"]").is_empty());
+/// assert_eq!(
+///     validate_config("[rules]\ndisabled = [\"PERF999\"]"),
+///     vec!["rules.disabled: unknown rule ID 'PERF999'"]
+/// );
+/// ```
+pub fn validate_config(content: &str) -> Vec<String> {
+    let table: toml::Table = match toml::from_str(content) {
+        Ok(table) => table,
+        Err(e) => return vec![format!("invalid TOML: {}", e.to_string().trim_end())]
+    };
+    let mut problems = Vec::new();
+    for (section, value) in &table {
+        let Some((_, keys)) = KNOWN_KEYS.iter().find(|(name, _)| name == section) else {
+            problems.push(format!("unknown key '{}'", section));
+            continue;
+        };
+        if let Some(fields) = value.as_table() {
+            for field in fields.keys() {
+                if !keys.contains(&field.as_str()) {
+                    problems.push(format!("unknown key '{}.{}'", section, field));
+                }
+            }
+        }
+    }
+    let custom_entries = table
+        .get("rules")
+        .and_then(|rules| rules.get("custom"))
+        .and_then(|custom| custom.as_array());
+    for entry in custom_entries.into_iter().flatten() {
+        for field in entry
+            .as_table()
+            .into_iter()
+            .flat_map(|fields| fields.keys())
+        {
+            if !CUSTOM_RULE_KEYS.contains(&field.as_str()) {
+                problems.push(format!("unknown key 'rules.custom.{}'", field));
+            }
+        }
+    }
+    let config: Config = match toml::from_str(content) {
+        Ok(config) => config,
+        Err(e) => {
+            problems.push(format!("invalid value: {}", e.to_string().trim_end()));
+            return problems;
+        }
+    };
+    let catalog = rule_catalog();
+    let is_known = |id: &str| {
+        catalog
+            .iter()
+            .any(|entry| entry.id.eq_ignore_ascii_case(id))
+            || config
+                .rules
+                .custom
+                .iter()
+                .any(|def| def.id.eq_ignore_ascii_case(id))
+    };
+    for (pos, def) in config.rules.custom.iter().enumerate() {
+        if let Err(problem) = RegexRule::new(def) {
+            problems.push(format!("rules.custom: {}", problem));
+        }
+        let taken = catalog
+            .iter()
+            .any(|entry| entry.id.eq_ignore_ascii_case(&def.id))
+            || config.rules.custom[..pos]
+                .iter()
+                .any(|other| other.id.eq_ignore_ascii_case(&def.id));
+        if taken {
+            problems.push(format!(
+                "rules.custom: Custom rule '{}': ID is already in use",
+                def.id
+            ));
+        }
+    }
+    for id in &config.rules.disabled {
+        if !is_known(id) {
+            problems.push(format!("rules.disabled: unknown rule ID '{}'", id));
+        }
+    }
+    let mut overrides: Vec<_> = config.rules.severity.iter().collect();
+    overrides.sort();
+    for (id, severity) in overrides {
+        let is_rule = catalog.iter().any(|entry| entry.id == id)
+            || config.rules.custom.iter().any(|def| &def.id == id);
+        if !is_rule {
+            problems.push(format!("rules.severity: unknown rule ID '{}'", id));
+        }
+        if severity.parse::<Severity>().is_err() {
+            problems.push(format!(
+                "rules.severity.{}: invalid severity '{}' (expected error, warning, or info)",
+                id, severity
+            ));
+        }
+    }
+    let mut overrides: Vec<_> = config.rules.category.iter().collect();
+    overrides.sort();
+    for (id, category) in overrides {
+        let is_rule = catalog.iter().any(|entry| entry.id == id)
+            || config.rules.custom.iter().any(|def| &def.id == id);
+        if !is_rule {
+            problems.push(format!("rules.category: unknown rule ID '{}'", id));
+        }
+        if category.parse::<RuleCategory>().is_err() {
+            problems.push(format!(
+                "rules.category.{}: invalid category '{}' (expected performance, style, or security)",
+                id, category
+            ));
+        }
+    }
+    problems
+}
@@ -7,8 +7,14 @@
 //!
 //! - **File errors**: IO failures when reading schema/query files
 //! - **Parse errors**: SQL parsing failures with position information
-//! - **LLM errors**: API communication failures with retry support
+//! - **LLM errors**: API communication failures with retry support, and audit
+//!   log writes
+//! - **Deadline errors**: `--deadline` expiring before static analysis ends
+//! - **Report errors**: Failed uploads to a `--report-url` endpoint
+//! - **Git errors**: `--since` diffs that git could not produce
 //! - **Config errors**: Invalid configuration files or values
+//! - **Select errors**: Malformed `--select` violation filters
+//! - **Watch errors**: `--watch` file notifications that could not be set up
 
 pub use masterror::{AppError, AppResult};
 
@@ -22,6 +28,12 @@ pub fn file_read_error(path: &str, source: std::io::Error) -> AppError {
     AppError::internal(format!("Failed to read file '{}': {}", path, source))
 }
 
+/// Create error for SQL input that could not be read while streaming
+#[allow(dead_code)]
+pub fn input_read_error(source: std::io::Error) -> AppError {
+    AppError::internal(format!("Failed to read SQL input: {}", source))
+}
+
 /// Create schema parse error with optional position info
 pub fn schema_parse_error(message: impl Into<String>) -> AppError {
     let msg = message.into();
@@ -34,12 +46,27 @@ pub fn query_parse_error(message: impl Into<String>) -> AppError {
     AppError::bad_request(format_sql_error("Query parse error", &msg))
 }
 
+/// Create error for a run that hit `--deadline` before static analysis
+/// finished
+pub fn deadline_error(secs: u64) -> AppError {
+    AppError::timeout(format!(
+        "Analysis deadline of {}s reached before static analysis finished",
+        secs
+    ))
+}
+
 /// Create LLM API error
 pub fn llm_api_error(message: impl Into<String>) -> AppError {
     AppError::service(message.into())
 }
 
+/// Create error for a report upload the endpoint rejected
+pub fn report_upload_error(message: impl Into<String>) -> AppError {
+    AppError::service(format!("Report upload failed: {}", message.into()))
+}
+
 /// Create HTTP error
+#[cfg(feature = "llm")]
 pub fn http_error(err: reqwest::Error) -> AppError {
     let msg = if err.is_timeout() {
         format!("Request timeout: {}", err)
@@ -53,11 +80,34 @@ pub fn http_error(err: reqwest::Error) -> AppError {
     AppError::service(msg)
 }
 
+/// Create error for an LLM audit log entry that could not be written
+pub fn audit_log_error(path: &str, source: std::io::Error) -> AppError {
+    AppError::internal(format!(
+        "Failed to write LLM audit log '{}': {}",
+        path, source
+    ))
+}
+
+/// Create error for a `git diff` that could not be run or failed
+pub fn git_error(message: impl Into<String>) -> AppError {
+    AppError::bad_request(format!("git diff failed: {}", message.into()))
+}
+
 /// Create config error
 pub fn config_error(message: impl Into<String>) -> AppError {
     AppError::bad_request(message.into())
 }
 
+/// Create error for an invalid `--select` expression
+pub fn select_error(message: impl Into<String>) -> AppError {
+    AppError::bad_request(format!("Invalid --select expression: {}", message.into()))
+}
+
+/// Create error for a `--watch` file watch that could not be set up
+pub fn watch_error(message: impl Into<String>) -> AppError {
+    AppError::internal(format!("Failed to watch files: {}", message.into()))
+}
+
 /// Format SQL error with position highlighting
 ///
 /// # Notes
@@ -0,0 +1,277 @@
+//! Incremental analysis state.
+//!
+//! With `--incremental`, the report of each queries file is stored in a
+//! state file together with a hash of everything that determines it: the
+//! queries, the schema, the dialect, the effective rule configuration, and
+//! the analyzer version. A rerun whose hash matches reuses the stored report
+//! instead of running the rule engine again.
+//!
+//! # Example
+//!
+//! ```
+//! use sql_query_analyzer::{
+//!     config::Config,
+//!     incremental::{IncrementalState, analysis_key},
+//!     query::SqlDialect,
+//!     rules::AnalysisReport
+//! };
+//!
+//! let dir = tempfile::tempdir().unwrap();
+//! let mut state = IncrementalState::load(dir.path().join("state.json"));
+//! let key = analysis_key(
+//!     "CREATE TABLE t (id INT)",
+//!     "SELECT id FROM t",
+//!     SqlDialect::Generic,
+//!     &Config::default()
+//! );
+//!
+//! assert!(state.lookup("queries.sql", &key).is_none());
+//! state.record("queries.sql", key.clone(), &AnalysisReport::new(1, 10));
+//! assert!(state.lookup("queries.sql", &key).is_some());
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    hash::{Hash, Hasher},
+    path::PathBuf
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::Config,
+    error::{AppResult, config_error},
+    query::{Span, SqlDialect, StableHasher},
+    rules::{AnalysisReport, RuleCatalogEntry, RuleCategory, Severity, Violation, rule_catalog}
+};
+
+/// Stored reports of previously analyzed files, keyed by file path.
+#[derive(Debug)]
+pub struct IncrementalState {
+    path:  PathBuf,
+    state: StateFile
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StateFile {
+    #[serde(default)]
+    files: BTreeMap<String, FileState>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FileState {
+    hash:              String,
+    queries_count:     usize,
+    rules_count:       usize,
+    unanalyzed:        Vec<usize>,
+    violations:        Vec<StoredViolation>,
+    #[serde(default)]
+    schema_violations: Vec<StoredViolation>
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredViolation {
+    rule_id:     String,
+    message:     String,
+    severity:    Severity,
+    category:    RuleCategory,
+    suggestion:  Option<String>,
+    query_index: usize,
+    location:    Option<Span>
+}
+
+impl StoredViolation {
+    fn new(v: &Violation) -> Self {
+        Self {
+            rule_id:     v.rule_id.to_string(),
+            message:     v.message.clone(),
+            severity:    v.severity,
+            category:    v.category,
+            suggestion:  v.suggestion.clone(),
+            query_index: v.query_index,
+            location:    v.location
+        }
+    }
+
+    /// Rebuild the violation; `None` when `catalog` lacks its rule
+    fn restore(&self, catalog: &[RuleCatalogEntry]) -> Option<Violation> {
+        let entry = catalog.iter().find(|e| e.id == self.rule_id)?;
+        Some(Violation {
+            rule_id:     entry.id,
+            rule_name:   entry.name,
+            message:     self.message.clone(),
+            severity:    self.severity,
+            category:    self.category,
+            suggestion:  self.suggestion.clone(),
+            query_index: self.query_index,
+            location:    self.location,
+            origin:      None
+        })
+    }
+}
+
+impl IncrementalState {
+    /// Read the state file at `path`.
+    ///
+    /// A missing or unreadable state file yields an empty state, so every
+    /// file is analyzed and the file is rewritten on [`save`](Self::save).
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let state = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self {
+            path,
+            state
+        }
+    }
+
+    /// Stored report for `file` if it was recorded under the same `key`.
+    ///
+    /// Entries naming rules this build does not know are treated as stale.
+    pub fn lookup(&self, file: &str, key: &str) -> Option<AnalysisReport> {
+        let state = self
+            .state
+            .files
+            .get(file)
+            .filter(|state| state.hash == key)?;
+        let catalog = rule_catalog();
+        let mut report = AnalysisReport::new(state.queries_count, state.rules_count);
+        report.unanalyzed = state.unanalyzed.clone();
+        for stored in &state.violations {
+            report.add_violation(stored.restore(&catalog)?);
+        }
+        for stored in &state.schema_violations {
+            report.schema_violations.push(stored.restore(&catalog)?);
+        }
+        Some(report)
+    }
+
+    /// Remember `report` as the result for `file` under `key`
+    pub fn record(&mut self, file: &str, key: String, report: &AnalysisReport) {
+        let violations = report.violations.iter().map(StoredViolation::new).collect();
+        let schema_violations = report
+            .schema_violations
+            .iter()
+            .map(StoredViolation::new)
+            .collect();
+        self.state.files.insert(
+            file.to_string(),
+            FileState {
+                hash: key,
+                queries_count: report.queries_count,
+                rules_count: report.rules_count,
+                unanalyzed: report.unanalyzed.clone(),
+                violations,
+                schema_violations
+            }
+        );
+    }
+
+    /// Write the state back to its file.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the state file cannot be written
+    pub fn save(&self) -> AppResult<()> {
+        let content = serde_json::to_string_pretty(&self.state)
+            .map_err(|e| config_error(format!("Failed to encode state file: {}", e)))?;
+        fs::write(&self.path, content).map_err(|e| {
+            config_error(format!(
+                "Failed to write state file '{}': {}",
+                self.path.display(),
+                e
+            ))
+        })
+    }
+}
+
+/// Hash of every input that determines a file's report.
+///
+/// Covers the schema and queries text, the dialect, the rule configuration
+/// (disabled rules, severity and category overrides, deprecated columns,
+/// large tables, allowed schemas, sensitive variables, custom rules), and
+/// the analyzer version, so changing any of them invalidates stored reports.
+pub fn analysis_key(
+    schema_sql: &str,
+    queries_sql: &str,
+    dialect: SqlDialect,
+    config: &Config
+) -> String {
+    let mut disabled: Vec<String> = config
+        .rules
+        .disabled
+        .iter()
+        .map(|id| id.to_uppercase())
+        .collect();
+    disabled.sort();
+    let severity: BTreeMap<String, String> = config
+        .rules
+        .severity
+        .iter()
+        .map(|(id, level)| (id.to_uppercase(), level.to_lowercase()))
+        .collect();
+    let category: BTreeMap<String, String> = config
+        .rules
+        .category
+        .iter()
+        .map(|(id, name)| (id.to_uppercase(), name.to_lowercase()))
+        .collect();
+    let deprecated: BTreeMap<&String, &String> = config.schema.deprecated.iter().collect();
+    let mut large_tables: Vec<String> = config
+        .schema
+        .large_tables
+        .iter()
+        .map(|t| t.to_lowercase())
+        .collect();
+    large_tables.sort();
+    let mut allowed_schemas: Vec<String> = config
+        .rules
+        .allowed_schemas
+        .iter()
+        .map(|s| s.to_lowercase())
+        .collect();
+    allowed_schemas.sort();
+    let mut sensitive_variables: Vec<String> = config
+        .rules
+        .sensitive_variables
+        .iter()
+        .map(|v| v.to_lowercase())
+        .collect();
+    sensitive_variables.sort();
+    let custom: Vec<_> = config
+        .rules
+        .custom
+        .iter()
+        .map(|rule| {
+            (
+                &rule.id,
+                &rule.name,
+                &rule.pattern,
+                &rule.severity,
+                &rule.category,
+                &rule.message,
+                &rule.suggestion
+            )
+        })
+        .collect();
+    let mut hasher = StableHasher::default();
+    (
+        env!("CARGO_PKG_VERSION"),
+        schema_sql,
+        queries_sql,
+        format!("{:?}", dialect),
+        disabled,
+        severity,
+        category,
+        deprecated,
+        large_tables,
+        allowed_schemas,
+        sensitive_variables,
+        custom
+    )
+        .hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
@@ -1,15 +1,72 @@
 //! # SQL Query Analyzer Library
 //!
 //! Static analysis library for SQL queries.
+//!
+//! The `llm` feature (enabled by default) adds the LLM providers and the
+//! async CLI pipeline in [`app`]. Without it the crate has no network or
+//! async runtime dependencies, and [`analyze_static`] runs the rule engine
+//! synchronously, e.g. when compiled to `wasm32-unknown-unknown`.
 
+#[cfg(feature = "llm")]
 pub mod app;
 pub mod cache;
 pub mod cli;
 pub mod config;
 pub mod error;
+pub mod incremental;
+#[cfg(feature = "llm")]
 pub mod llm;
+pub mod locale;
 pub mod output;
 pub mod preprocessor;
 pub mod query;
 pub mod rules;
 pub mod schema;
+pub mod stats;
+
+use crate::{
+    config::RulesConfig,
+    error::AppResult,
+    query::{SqlDialect, parse_queries},
+    rules::{AnalysisReport, RuleRunner},
+    schema::Schema
+};
+
+/// Run static analysis synchronously.
+///
+/// Parses `queries` (and `schema` when given, enabling schema-aware rules)
+/// with `dialect` and runs every rule not disabled by `config`. Requires no
+/// async runtime.
+///
+/// # Errors
+///
+/// Returns error if the queries or the schema fail to parse
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{analyze_static, config::RulesConfig, query::SqlDialect};
+///
+/// let report = analyze_static(
+///     "DELETE FROM users",
+///     Some("CREATE TABLE users (id INT PRIMARY KEY)"),
+///     SqlDialect::Generic,
+///     RulesConfig::default()
+/// )
+/// .unwrap();
+/// assert!(report.violations.iter().any(|v| v.rule_id == "SEC002"));
+/// ```
+pub fn analyze_static(
+    queries: &str,
+    schema: Option<&str>,
+    dialect: SqlDialect,
+    config: RulesConfig
+) -> AppResult<AnalysisReport> {
+    let parsed = parse_queries(queries, dialect)?;
+    let runner = match schema {
+        Some(sql) => RuleRunner::with_schema_and_config(Schema::parse(sql, dialect)?, config),
+        None => RuleRunner::with_config(config)
+    }
+    .with_dialect(dialect);
+    Ok(runner.analyze(&parsed))
+}
@@ -10,6 +10,7 @@
 //! |----------|----------|----------------|
 //! | OpenAI | `api.openai.com` | Bearer token |
 //! | Anthropic | `api.anthropic.com` | x-api-key header |
+//! | Cohere | `api.cohere.com` | Bearer token |
 //! | Ollama | Local (configurable) | None |
 //!
 //! # Retry Behavior
@@ -21,6 +22,25 @@
 //!
 //! Retry delays use exponential backoff with configurable parameters.
 //!
+//! # Response Cache
+//!
+//! With [`LlmClient::with_cache`], responses are stored on disk keyed on
+//! the schema and query summaries plus the provider and model, so repeated
+//! runs over unchanged SQL skip the API call until the entry expires.
+//!
+//! # Batching
+//!
+//! [`LlmClient::analyze_batches`] sends one request per [`QueryBatch`] from
+//! [`split_batches`], so query sets too large for one context window are
+//! analyzed piece by piece and merged under a `=== Queries #a-#b ===` header
+//! per batch.
+//!
+//! # Audit Log
+//!
+//! With [`LlmClient::with_audit_log`], every outbound request, retries
+//! included, appends a JSON line with the provider, model, prompt size, and
+//! outcome. Prompts are recorded only on request; API keys never are.
+//!
 //! # Example
 //!
 //! ```
@@ -37,8 +57,16 @@
 //! let client = LlmClient::with_retry_config(provider, RetryConfig::default());
 //! ```
 
-use std::time::Duration;
+pub mod audit;
+mod batch;
+mod cache;
+
+use std::time::{Duration, Instant, SystemTime};
 
+pub use audit::LlmAuditLog;
+pub use batch::{QueryBatch, split_batches};
+pub use cache::LlmCache;
+use reqwest::StatusCode;
 use serde::{Deserialize, Serialize};
 use tokio::time::sleep;
 
@@ -64,6 +92,13 @@ pub enum LlmProvider {
         /// Model identifier (e.g., "claude-sonnet-4-20250514")
         model:   String
     },
+    /// Cohere API (Command models)
+    Cohere {
+        /// API key
+        api_key: String,
+        /// Model identifier (e.g., "command-r", "command-r-plus")
+        model:   String
+    },
     /// Local Ollama instance
     Ollama {
         /// Base URL (e.g., "http://localhost:11434")
@@ -73,6 +108,27 @@ pub enum LlmProvider {
     }
 }
 
+impl LlmProvider {
+    /// Provider name and model, which together with the prompt determine
+    /// the response
+    fn identity(&self) -> (&'static str, &str) {
+        match self {
+            Self::OpenAI {
+                model, ..
+            } => ("openai", model),
+            Self::Anthropic {
+                model, ..
+            } => ("anthropic", model),
+            Self::Cohere {
+                model, ..
+            } => ("cohere", model),
+            Self::Ollama {
+                model, ..
+            } => ("ollama", model)
+        }
+    }
+}
+
 /// HTTP client for LLM API communication with retry support.
 ///
 /// Handles provider-specific request formatting and response parsing.
@@ -80,7 +136,9 @@ pub enum LlmProvider {
 pub struct LlmClient {
     provider:     LlmProvider,
     client:       reqwest::Client,
-    retry_config: RetryConfig
+    retry_config: RetryConfig,
+    cache:        Option<LlmCache>,
+    audit_log:    Option<LlmAuditLog>
 }
 
 #[derive(Serialize)]
@@ -133,6 +191,66 @@ struct AnthropicContent {
     text: String
 }
 
+#[derive(Serialize)]
+struct CohereRequest {
+    model:    String,
+    messages: Vec<CohereMessage>
+}
+
+#[derive(Serialize)]
+struct CohereMessage {
+    role:    String,
+    content: String
+}
+
+#[derive(Deserialize)]
+struct CohereResponse {
+    message: CohereResponseMessage
+}
+
+#[derive(Deserialize)]
+struct CohereResponseMessage {
+    #[serde(default)]
+    content: Vec<CohereContent>
+}
+
+#[derive(Deserialize)]
+struct CohereContent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    text: String
+}
+
+impl CohereRequest {
+    fn new(model: &str, prompt: &str) -> Self {
+        Self {
+            model:    model.to_string(),
+            messages: vec![CohereMessage {
+                role:    String::from("user"),
+                content: prompt.to_string()
+            }]
+        }
+    }
+}
+
+impl CohereResponse {
+    /// Text blocks of the reply joined together
+    fn into_text(self) -> AppResult<String> {
+        let text: String = self
+            .message
+            .content
+            .into_iter()
+            .filter(|c| c.kind == "text")
+            .map(|c| c.text)
+            .collect();
+        if text.is_empty() {
+            return Err(llm_api_error("Empty response from Cohere"));
+        }
+        Ok(text)
+    }
+}
+
 #[derive(Serialize)]
 struct OllamaRequest {
     model:  String,
@@ -155,17 +273,34 @@ impl LlmClient {
     /// Create new LLM client with custom retry configuration
     pub fn with_retry_config(provider: LlmProvider, retry_config: RetryConfig) -> Self {
         let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(120))
+            .timeout(Duration::from_secs(retry_config.timeout_secs))
             .build()
             .unwrap_or_else(|_| reqwest::Client::new());
         Self {
             provider,
             client,
-            retry_config
+            retry_config,
+            cache: None,
+            audit_log: None
         }
     }
 
+    /// Reuse responses stored in `cache` for identical inputs
+    pub fn with_cache(mut self, cache: LlmCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Append an entry to `audit_log` for every request sent
+    pub fn with_audit_log(mut self, audit_log: LlmAuditLog) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
     /// Analyze SQL queries using LLM with automatic retry
+    ///
+    /// Returns the cached response instead when a cache is configured and
+    /// holds a fresh entry for the same inputs.
     pub async fn analyze(&self, schema_summary: &str, queries_summary: &str) -> AppResult<String> {
         let prompt = format!(
             "You are a database performance expert. Analyze the following SQL queries \
@@ -180,7 +315,43 @@ impl LlmClient {
             schema = schema_summary,
             queries = queries_summary
         );
-        self.call_with_retry(&prompt).await
+        let (provider, model) = self.provider.identity();
+        let key = LlmCache::key(&prompt, provider, model);
+        if let Some(cached) = self.cache.as_ref().and_then(|cache| cache.get(&key)) {
+            return Ok(cached);
+        }
+        let response = self.call_with_retry(&prompt).await?;
+        if let Some(cache) = &self.cache {
+            cache.put(&key, &response);
+        }
+        Ok(response)
+    }
+
+    /// Analyze each batch in turn and merge the responses
+    ///
+    /// A single batch is analyzed exactly like [`analyze`](Self::analyze).
+    /// With several, each response is placed under a header naming the
+    /// one-based query numbers it covers; the first failing request aborts
+    /// the whole analysis.
+    pub async fn analyze_batches(
+        &self,
+        schema_summary: &str,
+        batches: &[QueryBatch]
+    ) -> AppResult<String> {
+        if let [batch] = batches {
+            return self.analyze(schema_summary, &batch.summary).await;
+        }
+        let mut merged = Vec::with_capacity(batches.len());
+        for batch in batches {
+            let analysis = self.analyze(schema_summary, &batch.summary).await?;
+            merged.push(format!(
+                "=== Queries #{}-#{} ===\n\n{}",
+                batch.queries.start + 1,
+                batch.queries.end,
+                analysis.trim_end()
+            ));
+        }
+        Ok(merged.join("\n\n"))
     }
 
     async fn call_with_retry(&self, prompt: &str) -> AppResult<String> {
@@ -198,7 +369,7 @@ impl LlmClient {
                 delay = ((delay as f64 * self.retry_config.backoff_factor) as u64)
                     .min(self.retry_config.max_delay_ms);
             }
-            match self.call_provider(prompt).await {
+            match self.call_audited(prompt).await? {
                 Ok(result) => return Ok(result),
                 Err(e) => {
                     if self.is_retryable_error(&e) {
@@ -212,6 +383,25 @@ impl LlmClient {
         Err(last_error.unwrap_or_else(|| llm_api_error("All retry attempts failed")))
     }
 
+    /// Call the provider once, recording the request in the audit log.
+    /// The outer error is an audit log write failure, the inner one the
+    /// request's.
+    async fn call_audited(&self, prompt: &str) -> AppResult<AppResult<String>> {
+        let sent = SystemTime::now();
+        let started = Instant::now();
+        let (status, outcome) = self.call_provider(prompt).await;
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(
+                self.provider.identity(),
+                prompt,
+                (&outcome, status),
+                sent,
+                started.elapsed()
+            )?;
+        }
+        Ok(outcome)
+    }
+
     fn is_retryable_error(&self, error: &masterror::AppError) -> bool {
         let msg = error.to_string().to_lowercase();
         msg.contains("timeout")
@@ -224,112 +414,186 @@ impl LlmClient {
             || msg.contains("504")
     }
 
-    async fn call_provider(&self, prompt: &str) -> AppResult<String> {
-        match &self.provider {
+    /// Call the provider once; the status is `None` when no response
+    /// arrived
+    async fn call_provider(&self, prompt: &str) -> (Option<StatusCode>, AppResult<String>) {
+        let response = match self.send(prompt).await {
+            Ok(response) => response,
+            Err(e) => return (None, Err(e))
+        };
+        let status = response.status();
+        (Some(status), self.read_response(response).await)
+    }
+
+    /// Send the provider-specific request for `prompt`
+    async fn send(&self, prompt: &str) -> AppResult<reqwest::Response> {
+        let request = match &self.provider {
             LlmProvider::OpenAI {
                 api_key,
                 model
-            } => self.call_openai(api_key, model, prompt).await,
+            } => self
+                .client
+                .post("https://api.openai.com/v1/chat/completions")
+                .header("Authorization", format!("Bearer {}", api_key))
+                .json(&OpenAIRequest {
+                    model:    model.to_string(),
+                    messages: vec![OpenAIRequestMessage {
+                        role:    String::from("user"),
+                        content: prompt.to_string()
+                    }]
+                }),
             LlmProvider::Anthropic {
                 api_key,
                 model
-            } => self.call_anthropic(api_key, model, prompt).await,
+            } => self
+                .client
+                .post("https://api.anthropic.com/v1/messages")
+                .header("x-api-key", api_key)
+                .header("anthropic-version", "2023-06-01")
+                .json(&AnthropicRequest {
+                    model:      model.to_string(),
+                    max_tokens: 4096,
+                    messages:   vec![AnthropicMessage {
+                        role:    String::from("user"),
+                        content: prompt.to_string()
+                    }]
+                }),
+            LlmProvider::Cohere {
+                api_key,
+                model
+            } => self
+                .client
+                .post("https://api.cohere.com/v2/chat")
+                .header("Authorization", format!("Bearer {}", api_key))
+                .json(&CohereRequest::new(model, prompt)),
             LlmProvider::Ollama {
                 base_url,
                 model
-            } => self.call_ollama(base_url, model, prompt).await
-        }
+            } => self
+                .client
+                .post(format!("{}/api/generate", base_url.trim_end_matches('/')))
+                .json(&OllamaRequest {
+                    model:  model.to_string(),
+                    prompt: prompt.to_string(),
+                    stream: false
+                })
+        };
+        request.send().await.map_err(http_error)
     }
 
-    async fn call_openai(&self, api_key: &str, model: &str, prompt: &str) -> AppResult<String> {
-        let request = OpenAIRequest {
-            model:    model.to_string(),
-            messages: vec![OpenAIRequestMessage {
-                role:    String::from("user"),
-                content: prompt.to_string()
-            }]
-        };
-        let response = self
-            .client
-            .post("https://api.openai.com/v1/chat/completions")
-            .header("Authorization", format!("Bearer {}", api_key))
-            .json(&request)
-            .send()
-            .await
-            .map_err(http_error)?;
-        if !response.status().is_success() {
-            let status = response.status();
+    /// Check the status of `response` and extract the completion text
+    async fn read_response(&self, response: reqwest::Response) -> AppResult<String> {
+        let status = response.status();
+        if !status.is_success() {
+            let name = match &self.provider {
+                LlmProvider::OpenAI {
+                    ..
+                } => "OpenAI",
+                LlmProvider::Anthropic {
+                    ..
+                } => "Anthropic",
+                LlmProvider::Cohere {
+                    ..
+                } => "Cohere",
+                LlmProvider::Ollama {
+                    ..
+                } => "Ollama"
+            };
             let text = response.text().await.unwrap_or_default();
             return Err(llm_api_error(format!(
-                "OpenAI API error {}: {}",
-                status, text
+                "{} API error {}: {}",
+                name, status, text
             )));
         }
-        let result: OpenAIResponse = response.json().await.map_err(http_error)?;
-        result
-            .choices
-            .first()
-            .map(|c| c.message.content.clone())
-            .ok_or_else(|| llm_api_error("Empty response from OpenAI"))
+        match &self.provider {
+            LlmProvider::OpenAI {
+                ..
+            } => {
+                let result: OpenAIResponse = response.json().await.map_err(http_error)?;
+                result
+                    .choices
+                    .first()
+                    .map(|c| c.message.content.clone())
+                    .ok_or_else(|| llm_api_error("Empty response from OpenAI"))
+            }
+            LlmProvider::Anthropic {
+                ..
+            } => {
+                let result: AnthropicResponse = response.json().await.map_err(http_error)?;
+                result
+                    .content
+                    .first()
+                    .map(|c| c.text.clone())
+                    .ok_or_else(|| llm_api_error("Empty response from Anthropic"))
+            }
+            LlmProvider::Cohere {
+                ..
+            } => {
+                let result: CohereResponse = response.json().await.map_err(http_error)?;
+                result.into_text()
+            }
+            LlmProvider::Ollama {
+                ..
+            } => {
+                let result: OllamaResponse = response.json().await.map_err(http_error)?;
+                Ok(result.response)
+            }
+        }
     }
+}
 
-    async fn call_anthropic(&self, api_key: &str, model: &str, prompt: &str) -> AppResult<String> {
-        let request = AnthropicRequest {
-            model:      model.to_string(),
-            max_tokens: 4096,
-            messages:   vec![AnthropicMessage {
-                role:    String::from("user"),
-                content: prompt.to_string()
-            }]
-        };
-        let response = self
-            .client
-            .post("https://api.anthropic.com/v1/messages")
-            .header("x-api-key", api_key)
-            .header("anthropic-version", "2023-06-01")
-            .json(&request)
-            .send()
-            .await
-            .map_err(http_error)?;
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(llm_api_error(format!(
-                "Anthropic API error {}: {}",
-                status, text
-            )));
-        }
-        let result: AnthropicResponse = response.json().await.map_err(http_error)?;
-        result
-            .content
-            .first()
-            .map(|c| c.text.clone())
-            .ok_or_else(|| llm_api_error("Empty response from Anthropic"))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cohere_request_body() {
+        let body = serde_json::to_value(CohereRequest::new("command-r", "Analyze")).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "model": "command-r",
+                "messages": [{ "role": "user", "content": "Analyze" }]
+            })
+        );
     }
 
-    async fn call_ollama(&self, base_url: &str, model: &str, prompt: &str) -> AppResult<String> {
-        let request = OllamaRequest {
-            model:  model.to_string(),
-            prompt: prompt.to_string(),
-            stream: false
-        };
-        let url = format!("{}/api/generate", base_url.trim_end_matches('/'));
-        let response = self
-            .client
-            .post(&url)
-            .json(&request)
-            .send()
-            .await
-            .map_err(http_error)?;
-        if !response.status().is_success() {
-            let status = response.status();
-            let text = response.text().await.unwrap_or_default();
-            return Err(llm_api_error(format!(
-                "Ollama API error {}: {}",
-                status, text
-            )));
+    #[test]
+    fn test_cohere_response_text() {
+        let body = r#"{
+            "id": "c14c80c3",
+            "finish_reason": "COMPLETE",
+            "message": {
+                "role": "assistant",
+                "content": [
+                    { "type": "text", "text": "Add an index " },
+                    { "type": "text", "text": "on users.email" }
+                ]
+            },
+            "usage": { "tokens": { "input_tokens": 12, "output_tokens": 6 } }
+        }"#;
+        let response: CohereResponse = serde_json::from_str(body).unwrap();
+        assert_eq!(response.into_text().unwrap(), "Add an index on users.email");
+    }
+
+    #[test]
+    fn test_cohere_empty_response_is_error() {
+        let body = r#"{ "message": { "role": "assistant", "content": [] } }"#;
+        let response: CohereResponse = serde_json::from_str(body).unwrap();
+        assert!(response.into_text().is_err());
+    }
+
+    #[test]
+    fn test_cohere_rate_limit_and_server_errors_are_retried() {
+        let client = LlmClient::new(LlmProvider::Cohere {
+            api_key: "key".to_string(),
+            model:   "command-r".to_string()
+        });
+        for status in ["429 Too Many Requests", "503 Service Unavailable"] {
+            let error = llm_api_error(format!("Cohere API error {}: {{}}", status));
+            assert!(client.is_retryable_error(&error), "{}", status);
         }
-        let result: OllamaResponse = response.json().await.map_err(http_error)?;
-        Ok(result.response)
+        let error = llm_api_error("Cohere API error 401 Unauthorized: invalid api token");
+        assert!(!client.is_retryable_error(&error));
     }
 }
@@ -0,0 +1,99 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{AppResult, audit_log_error};
+
+/// Append-only JSON Lines record of outbound LLM requests.
+///
+/// Each request, including every retry, appends one [`AuditEntry`] with the
+/// provider, model, prompt size, and outcome. The prompt text is recorded
+/// only when enabled with [`LlmAuditLog::with_prompt`]; API keys and
+/// response bodies are never written.
+#[derive(Debug, Clone)]
+pub struct LlmAuditLog {
+    path:           PathBuf,
+    include_prompt: bool
+}
+
+/// One line of the audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Seconds since the Unix epoch when the request was sent
+    pub timestamp:    u64,
+    /// Provider name (`openai`, `anthropic`, `cohere`, `ollama`)
+    pub provider:     String,
+    /// Model requested
+    pub model:        String,
+    /// Prompt length in bytes
+    pub prompt_bytes: usize,
+    /// `ok` or `error`
+    pub status:       String,
+    /// HTTP status of the provider's response; absent when none arrived,
+    /// e.g. on a timeout
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_status:  Option<u16>,
+    /// Time until the response or error, in milliseconds
+    pub duration_ms:  u64,
+    /// Prompt text; present only with `--audit-include-prompt`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt:       Option<String>
+}
+
+impl LlmAuditLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path:           path.into(),
+            include_prompt: false
+        }
+    }
+
+    /// Also record the prompt text of each request
+    pub fn with_prompt(mut self, include_prompt: bool) -> Self {
+        self.include_prompt = include_prompt;
+        self
+    }
+
+    /// Append the entry for one request with its outcome and the HTTP
+    /// status of the response, if one arrived
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the log file cannot be opened or written
+    pub fn record(
+        &self,
+        (provider, model): (&str, &str),
+        prompt: &str,
+        (outcome, status): (&AppResult<String>, Option<StatusCode>),
+        sent: SystemTime,
+        elapsed: Duration
+    ) -> AppResult<()> {
+        let entry = AuditEntry {
+            timestamp:    sent
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            provider:     provider.to_string(),
+            model:        model.to_string(),
+            prompt_bytes: prompt.len(),
+            status:       if outcome.is_ok() { "ok" } else { "error" }.to_string(),
+            http_status:  status.map(|status| status.as_u16()),
+            duration_ms:  elapsed.as_millis() as u64,
+            prompt:       self.include_prompt.then(|| prompt.to_string())
+        };
+        let mut line = serde_json::to_string(&entry).unwrap_or_default();
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| file.write_all(line.as_bytes()))
+            .map_err(|e| audit_log_error(&self.path.display().to_string(), e))
+    }
+}
@@ -0,0 +1,53 @@
+use std::ops::Range;
+
+/// Queries summarized for one LLM request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryBatch {
+    /// Zero-based indices of the summarized queries
+    pub queries: Range<usize>,
+    /// Summary sent as the prompt's query section
+    pub summary: String
+}
+
+/// Rough token count of `text`, at four characters per token
+fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Split `count` queries into consecutive batches whose summaries fit
+/// `max_tokens`.
+///
+/// `summarize` renders the summary of a range of queries. Each query is
+/// measured on its own and batches are filled greedily; a query whose
+/// summary alone exceeds the budget gets a batch of its own.
+pub fn split_batches(
+    count: usize,
+    max_tokens: usize,
+    summarize: impl Fn(Range<usize>) -> String
+) -> Vec<QueryBatch> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut used = 0;
+    for idx in 0..count {
+        let tokens = estimate_tokens(&summarize(idx..idx + 1));
+        match ranges.last_mut() {
+            Some(range) if used + tokens <= max_tokens => {
+                range.end = idx + 1;
+                used += tokens;
+            }
+            _ => {
+                ranges.push(idx..idx + 1);
+                used = tokens;
+            }
+        }
+    }
+    if ranges.is_empty() {
+        ranges.push(0..count);
+    }
+    ranges
+        .into_iter()
+        .map(|queries| QueryBatch {
+            summary: summarize(queries.clone()),
+            queries
+        })
+        .collect()
+}
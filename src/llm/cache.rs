@@ -0,0 +1,91 @@
+use std::{
+    fs,
+    hash::{Hash, Hasher},
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::query::StableHasher;
+
+/// On-disk cache of LLM responses.
+///
+/// Each response is stored as a JSON file named after a hash of the full
+/// prompt, template included, and the provider/model pair. Entries older than
+/// the TTL are ignored and overwritten on the next call. Cache I/O is best
+/// effort: read or write failures behave like a miss.
+#[derive(Debug, Clone)]
+pub struct LlmCache {
+    dir: PathBuf,
+    ttl: Duration
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    created:  u64,
+    response: String
+}
+
+impl LlmCache {
+    pub fn new(dir: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl
+        }
+    }
+
+    /// Cache in `$XDG_CACHE_HOME/sql-query-analyzer/llm`, falling back to
+    /// `$HOME/.cache`. Returns `None` when neither variable is set.
+    pub fn in_user_cache_dir(ttl: Duration) -> Option<Self> {
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+        Some(Self::new(base.join("sql-query-analyzer").join("llm"), ttl))
+    }
+
+    /// Hash the inputs that determine an LLM response
+    pub fn key(prompt: &str, provider: &str, model: &str) -> String {
+        let mut hasher = StableHasher::default();
+        (prompt, provider, model).hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Cached response for `key`, if present and younger than the TTL
+    pub fn get(&self, key: &str) -> Option<String> {
+        let content = fs::read_to_string(self.path(key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        let age = now_secs().saturating_sub(entry.created);
+        (age < self.ttl.as_secs()).then_some(entry.response)
+    }
+
+    /// Store `response` under `key`
+    ///
+    /// # Notes
+    ///
+    /// - Written to a temporary file and renamed, so concurrent runs never read
+    ///   a partial entry
+    pub fn put(&self, key: &str, response: &str) {
+        let entry = CacheEntry {
+            created:  now_secs(),
+            response: response.to_string()
+        };
+        let Ok(content) = serde_json::to_string(&entry) else {
+            return;
+        };
+        let tmp = self.dir.join(format!("{}.{}.tmp", key, std::process::id()));
+        if fs::create_dir_all(&self.dir).is_ok() && fs::write(&tmp, content).is_ok() {
+            let _ = fs::rename(&tmp, self.path(key));
+        }
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
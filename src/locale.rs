@@ -0,0 +1,136 @@
+//! Localized violation messages.
+//!
+//! Rules produce English messages. A [`Locale`] loaded from a message
+//! catalog rewrites them after analysis, keyed by rule ID, so translations
+//! live outside the rule code. Templates may interpolate the original text:
+//!
+//! | Placeholder | Value |
+//! |-------------|-------|
+//! | `{message}` | English message |
+//! | `{suggestion}` | English suggestion (empty when the rule has none) |
+//! | `{query}` | 1-based query number |
+//!
+//! # Catalog Format
+//!
+//! TOML, or JSON when the file ends in `.json`:
+//!
+//! ```toml
+//! [messages.PERF001]
+//! message = "SELECT * sans LIMIT peut renvoyer un nombre illimité de lignes"
+//! suggestion = "Ajoutez une clause LIMIT"
+//! ```
+//!
+//! Rules missing from the catalog keep their English text.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{AppResult, config_error, file_read_error},
+    rules::{AnalysisReport, Violation}
+};
+
+/// Translated templates for one rule
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MessageTemplate {
+    /// Replacement for the violation message
+    pub message:    Option<String>,
+    /// Replacement for the fix suggestion
+    pub suggestion: Option<String>
+}
+
+/// Language used for violation messages.
+///
+/// The default is English, which leaves messages untouched.
+#[derive(Debug, Clone, Default)]
+pub struct Locale {
+    messages: HashMap<String, MessageTemplate>
+}
+
+#[derive(Deserialize)]
+struct CatalogFile {
+    #[serde(default)]
+    messages: HashMap<String, MessageTemplate>
+}
+
+impl Locale {
+    /// Resolve a `--lang` value: `en` for the built-in English messages,
+    /// otherwise the path of a message catalog.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the catalog cannot be read or parsed
+    pub fn resolve(lang: &str) -> AppResult<Self> {
+        if lang.eq_ignore_ascii_case("en") {
+            return Ok(Self::default());
+        }
+        let path = Path::new(lang);
+        if !path.exists() {
+            return Err(config_error(format!(
+                "Unknown language '{}': pass `en` or the path of a message catalog",
+                lang
+            )));
+        }
+        Self::load(path)
+    }
+
+    /// Load a message catalog from a TOML or JSON file.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the file cannot be read or is not a valid catalog
+    pub fn load(path: &Path) -> AppResult<Self> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| file_read_error(&path.display().to_string(), e))?;
+        let catalog: CatalogFile = if path.extension().is_some_and(|ext| ext == "json") {
+            serde_json::from_str(&content)
+                .map_err(|e| config_error(format!("Invalid message catalog: {}", e)))?
+        } else {
+            toml::from_str(&content)
+                .map_err(|e| config_error(format!("Invalid message catalog: {}", e)))?
+        };
+        Ok(Self {
+            messages: catalog
+                .messages
+                .into_iter()
+                .map(|(id, template)| (id.to_uppercase(), template))
+                .collect()
+        })
+    }
+
+    /// Rewrite every violation of `report` whose rule has a translation
+    pub fn translate(&self, report: &mut AnalysisReport) {
+        if self.messages.is_empty() {
+            return;
+        }
+        for violation in report
+            .violations
+            .iter_mut()
+            .chain(&mut report.schema_violations)
+        {
+            self.translate_violation(violation);
+        }
+    }
+
+    fn translate_violation(&self, violation: &mut Violation) {
+        let Some(template) = self.messages.get(violation.rule_id) else {
+            return;
+        };
+        let query = (violation.query_index + 1).to_string();
+        let original_suggestion = violation.suggestion.clone().unwrap_or_default();
+        let render = |text: &str| {
+            text.replace("{message}", &violation.message)
+                .replace("{suggestion}", &original_suggestion)
+                .replace("{query}", &query)
+        };
+        let message = template.message.as_deref().map(render);
+        let suggestion = template.suggestion.as_deref().map(render);
+        if let Some(message) = message {
+            violation.message = message;
+        }
+        if let Some(suggestion) = suggestion {
+            violation.suggestion = Some(suggestion);
+        }
+    }
+}
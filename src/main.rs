@@ -17,8 +17,8 @@
 //!    Style, or Security, each with configurable severity levels.
 //!
 //! 2. **LLM Analysis** (optional) - When API credentials are provided, queries
-//!    are sent to OpenAI, Anthropic, or a local Ollama instance for deeper
-//!    semantic analysis and optimization suggestions.
+//!    are sent to OpenAI, Anthropic, Cohere, or a local Ollama instance for
+//!    deeper semantic analysis and optimization suggestions.
 //!
 //! # Quick Start
 //!
@@ -128,7 +128,7 @@
 //! - [`rules`] - Static analysis rule engine and built-in rules
 //! - [`query`] - SQL parsing and query metadata extraction
 //! - [`schema`] - Database schema parsing and representation
-//! - [`llm`] - LLM provider integrations (OpenAI, Anthropic, Ollama)
+//! - [`llm`] - LLM provider integrations (OpenAI, Anthropic, Cohere, Ollama)
 //! - [`config`] - Configuration loading and validation
 //! - [`output`] - Result formatting for various output formats
 //! - [`cache`] - Query parsing cache for performance
@@ -140,12 +140,15 @@ mod cache;
 mod cli;
 mod config;
 mod error;
+mod incremental;
 mod llm;
+mod locale;
 mod output;
 mod preprocessor;
 mod query;
 mod rules;
 mod schema;
+mod stats;
 
 use std::process;
 
@@ -175,7 +178,12 @@ async fn main() {
 }
 
 async fn run(command: Commands) -> AppResult<CommandOutput> {
-    let config = Config::load()?;
+    let config = match command {
+        Commands::ValidateConfig {
+            ..
+        } => Config::default(),
+        _ => Config::load()?
+    };
     execute_command(command, config).await
 }
 
@@ -183,6 +191,9 @@ fn print_output(output: &CommandOutput) {
     for line in &output.stdout {
         println!("{}", line);
     }
+    for line in &output.stderr {
+        eprintln!("{}", line);
+    }
 }
 
 #[cfg(test)]
@@ -192,27 +203,59 @@ mod tests {
     use tempfile::NamedTempFile;
 
     use super::*;
-    use crate::cli::{Dialect, Format, Provider};
+    use crate::cli::{Dialect, FailOn, Format, InputFormat, Provider};
 
     #[tokio::test]
     async fn test_run_success() {
         use std::io::Write;
         let mut schema = NamedTempFile::new().unwrap();
-        writeln!(schema, "CREATE TABLE t (id INT);").unwrap();
+        writeln!(schema, "CREATE TABLE t (id INT PRIMARY KEY);").unwrap();
         let mut queries = NamedTempFile::new().unwrap();
         writeln!(queries, "SELECT id FROM t;").unwrap();
         let cmd = Commands::Analyze {
-            schema:        schema.path().to_path_buf(),
-            queries:       queries.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               schema.path().to_path_buf(),
+            queries:              Some(queries.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let result = run(cmd).await.unwrap();
         assert_eq!(result.exit_code, 0);
@@ -221,17 +264,49 @@ mod tests {
     #[tokio::test]
     async fn test_run_file_not_found() {
         let cmd = Commands::Analyze {
-            schema:        PathBuf::from("/nonexistent.sql"),
-            queries:       PathBuf::from("/nonexistent.sql"),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       false,
-            no_color:      true
+            schema:               PathBuf::from("/nonexistent.sql"),
+            queries:              Some(PathBuf::from("/nonexistent.sql")),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              false,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let result = run(cmd).await;
         assert!(result.is_err());
@@ -241,7 +316,8 @@ mod tests {
     fn test_print_output() {
         let output = CommandOutput {
             exit_code: 0,
-            stdout:    vec!["line1".to_string(), "line2".to_string()]
+            stdout:    vec!["line1".to_string(), "line2".to_string()],
+            stderr:    vec!["summary".to_string()]
         };
         print_output(&output);
     }
@@ -254,17 +330,49 @@ mod tests {
         let mut queries = NamedTempFile::new().unwrap();
         writeln!(queries, "SELECT * FROM x;").unwrap();
         let cmd = Commands::Analyze {
-            schema:        schema.path().to_path_buf(),
-            queries:       queries.path().to_path_buf(),
-            provider:      Provider::OpenAI,
-            api_key:       None,
-            model:         None,
-            ollama_url:    "http://localhost:11434".to_string(),
-            dialect:       Dialect::Generic,
-            output_format: Format::Text,
-            verbose:       false,
-            dry_run:       true,
-            no_color:      true
+            schema:               schema.path().to_path_buf(),
+            queries:              Some(queries.path().to_path_buf()),
+            provider:             Provider::OpenAI,
+            api_key:              None,
+            model:                None,
+            ollama_url:           "http://localhost:11434".to_string(),
+            dialect:              Dialect::Generic,
+            template:             None,
+            input_format:         InputFormat::Raw,
+            output_format:        Format::Text,
+            verbose:              false,
+            dry_run:              true,
+            no_color:             true,
+            compact:              false,
+            quiet:                false,
+            summary_only:         false,
+            stats:                false,
+            select:               None,
+            fail_on_selected:     false,
+            timings:              false,
+            no_llm_cache:         false,
+            llm_cache_ttl:        86400,
+            llm_timeout:          None,
+            llm_chunk_tokens:     8000,
+            audit_log:            None,
+            audit_include_prompt: false,
+            deadline:             None,
+            concurrency:          None,
+            lang:                 "en".to_string(),
+            incremental:          false,
+            state_file:           PathBuf::from(".sql-analyzer-state.json"),
+            report_url:           None,
+            report_header:        Vec::new(),
+            also_output:          Vec::new(),
+            text_template:        None,
+            changed_only:         false,
+            files_from:           None,
+            since:                None,
+            watch:                false,
+            min_severity:         None,
+            profile:              None,
+            fail_on:              FailOn::Warning,
+            no_llm:               false
         };
         let result = run(cmd).await.unwrap();
         let output = result.stdout.join("\n");
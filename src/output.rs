@@ -1,9 +1,23 @@
+use std::borrow::Cow;
+
 use colored::Colorize;
 use serde::Serialize;
 
 use crate::{
+    error::{AppResult, config_error},
     query::Query,
-    rules::{AnalysisReport, Severity}
+    rules::{
+        AnalysisReport, ExitStatus, RULE_FAMILIES, ReportSummary, RuleCatalogEntry, Severity,
+        Statistics, Violation, rule_family
+    },
+    stats::UsageStats
+};
+
+mod json_schema;
+
+#[allow(unused_imports)]
+pub use json_schema::{
+    SchemaOutputFormat, format_json_schema, report_json_schema, sarif_json_schema
 };
 
 /// Output format for results
@@ -13,25 +27,146 @@ pub enum OutputFormat {
     Text,
     Json,
     Yaml,
-    Sarif
+    Sarif,
+    /// GitHub Actions workflow commands, one annotation per violation
+    GithubAnnotations
+}
+
+/// Files the analyzed schema and queries came from, as named in
+/// annotations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourcePaths {
+    pub schema:  String,
+    pub queries: String
+}
+
+/// Output format for the rule catalog
+#[derive(Debug, Clone, Copy, Default)]
+pub enum CatalogOutputFormat {
+    #[default]
+    Json,
+    Markdown
+}
+
+/// Output format for usage statistics
+#[derive(Debug, Clone, Copy, Default)]
+pub enum StatsOutputFormat {
+    #[default]
+    Text,
+    Json
 }
 
 /// Output options
 #[derive(Debug, Clone)]
 pub struct OutputOptions {
-    pub format:  OutputFormat,
-    pub colored: bool,
-    pub verbose: bool
+    pub format:       OutputFormat,
+    pub colored:      bool,
+    pub verbose:      bool,
+    /// Layout of each violation line in text output; see
+    /// [`TEMPLATE_PLACEHOLDERS`]
+    pub template:     Option<String>,
+    /// Emit JSON and SARIF on a single line instead of pretty-printed
+    pub compact:      bool,
+    /// Hide violations below this severity in formatted reports
+    pub min_severity: Option<Severity>,
+    /// Exit code and reason JSON output reports; derived at the Warning
+    /// threshold when unset
+    pub exit:         Option<ExitStatus>,
+    /// Input files GitHub annotations point at; `schema.sql` and
+    /// `queries.sql` when unset
+    pub sources:      Option<SourcePaths>
 }
 
 impl Default for OutputOptions {
     fn default() -> Self {
         Self {
-            format:  OutputFormat::Text,
-            colored: true,
-            verbose: false
+            format:       OutputFormat::Text,
+            colored:      true,
+            verbose:      false,
+            template:     None,
+            compact:      false,
+            min_severity: None,
+            exit:         None,
+            sources:      None
+        }
+    }
+}
+
+/// Placeholders accepted in [`OutputOptions::template`]
+///
+/// `{query}` is the 1-based query number, `{line}` the source line or empty,
+/// and `{suggestion}` empty when the violation has none.
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "severity",
+    "rule_id",
+    "rule_name",
+    "message",
+    "query",
+    "line",
+    "suggestion"
+];
+
+/// Check that every `{name}` in a text template is a known placeholder
+///
+/// # Errors
+///
+/// Returns an error naming the first unknown or unclosed placeholder.
+pub fn validate_template(template: &str) -> AppResult<()> {
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            return Err(config_error(format!(
+                "Invalid --format-config: unclosed '{{' in '{}'",
+                template
+            )));
+        };
+        let name = &rest[start + 1..start + len];
+        if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+            return Err(config_error(format!(
+                "Invalid --format-config: unknown placeholder '{{{}}}' (expected one of {})",
+                name,
+                TEMPLATE_PLACEHOLDERS
+                    .iter()
+                    .map(|p| format!("{{{}}}", p))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    Ok(())
+}
+
+/// Fill the placeholders of `template` for one violation; unknown names
+/// are kept literally
+fn render_template(template: &str, violation: &Violation, severity: &str) -> String {
+    let mut output = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        match name {
+            "severity" => output.push_str(severity),
+            "rule_id" => output.push_str(violation.rule_id),
+            "rule_name" => output.push_str(violation.rule_name),
+            "message" => output.push_str(&violation.message),
+            "query" => output.push_str(&(violation.query_index + 1).to_string()),
+            "line" => {
+                if let Some(span) = violation.location {
+                    output.push_str(&span.line.to_string());
+                }
+            }
+            "suggestion" => output.push_str(violation.suggestion.as_deref().unwrap_or_default()),
+            _ => output.push_str(&rest[start..=start + len])
         }
+        rest = &rest[start + len + 1..];
     }
+    output.push_str(rest);
+    output
 }
 
 /// Analysis result for serialization
@@ -43,12 +178,22 @@ pub struct AnalysisResult {
 
 /// Format queries summary based on output options
 pub fn format_queries_summary(queries: &[Query], opts: &OutputOptions) -> String {
+    format_queries_summary_from(queries, 1, opts)
+}
+
+/// Format queries summary of a batch whose first query is number `first`
+/// of the whole input, so text headers keep the input's numbering
+pub fn format_queries_summary_from(
+    queries: &[Query],
+    first: usize,
+    opts: &OutputOptions
+) -> String {
     match opts.format {
-        OutputFormat::Json | OutputFormat::Sarif => {
-            serde_json::to_string_pretty(queries).unwrap_or_default()
-        }
+        OutputFormat::Json | OutputFormat::Sarif => to_json(queries, opts),
         OutputFormat::Yaml => serde_yaml::to_string(queries).unwrap_or_default(),
-        OutputFormat::Text => format_text_summary(queries, opts)
+        OutputFormat::Text | OutputFormat::GithubAnnotations => {
+            format_text_summary(queries, first, opts)
+        }
     }
 }
 
@@ -60,7 +205,7 @@ pub fn format_analysis_result(queries: &[Query], analysis: &str, opts: &OutputOp
                 queries:  queries.to_vec(),
                 analysis: analysis.to_string()
             };
-            serde_json::to_string_pretty(&result).unwrap_or_default()
+            to_json(&result, opts)
         }
         OutputFormat::Yaml => {
             let result = AnalysisResult {
@@ -69,7 +214,7 @@ pub fn format_analysis_result(queries: &[Query], analysis: &str, opts: &OutputOp
             };
             serde_yaml::to_string(&result).unwrap_or_default()
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::GithubAnnotations => {
             let mut output = String::new();
             if opts.colored {
                 output.push_str(&"=== SQL Query Analysis ===\n\n".bold().to_string());
@@ -82,10 +227,10 @@ pub fn format_analysis_result(queries: &[Query], analysis: &str, opts: &OutputOp
     }
 }
 
-fn format_text_summary(queries: &[Query], opts: &OutputOptions) -> String {
+fn format_text_summary(queries: &[Query], first: usize, opts: &OutputOptions) -> String {
     let mut summary = String::from("SQL Queries:\n\n");
     for (i, query) in queries.iter().enumerate() {
-        let header = format!("Query #{} ({}):", i + 1, query.query_type);
+        let header = format!("Query #{} ({}):", first + i, query.query_type);
         if opts.colored {
             summary.push_str(&header.cyan().bold().to_string());
         } else {
@@ -167,28 +312,300 @@ fn format_text_summary(queries: &[Query], opts: &OutputOptions) -> String {
     summary
 }
 
-/// Format static analysis report
+/// Renders a static analysis report into a string.
+///
+/// The built-in formats are implemented by [`TextFormatter`],
+/// [`JsonFormatter`], [`YamlFormatter`], and [`SarifFormatter`];
+/// [`OutputFormat::formatter`] selects among them. Embedders can implement
+/// the trait for their own formats.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::{
+///     output::{OutputOptions, ReportFormatter},
+///     rules::AnalysisReport
+/// };
+///
+/// struct CountFormatter;
+///
+/// impl ReportFormatter for CountFormatter {
+///     fn format(&self, report: &AnalysisReport, _: &OutputOptions) -> String {
+///         report.violations.len().to_string()
+///     }
+/// }
+///
+/// let report = AnalysisReport::new(1, 1);
+/// assert_eq!(
+///     CountFormatter.format(&report, &OutputOptions::default()),
+///     "0"
+/// );
+/// ```
+pub trait ReportFormatter: Send + Sync {
+    /// Render `report`
+    fn format(&self, report: &AnalysisReport, opts: &OutputOptions) -> String;
+}
+
+/// Human-readable report, colored when [`OutputOptions::colored`] is set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextFormatter;
+
+/// JSON serialization of the report plus `exit_code` and `exit_reason`,
+/// single-line when [`OutputOptions::compact`] is set
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter;
+
+/// Report as serialized by [`JsonFormatter`]
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    #[serde(flatten)]
+    report: &'a AnalysisReport,
+    #[serde(flatten)]
+    exit:   ExitStatus
+}
+
+/// YAML serialization of the report
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YamlFormatter;
+
+/// SARIF 2.1.0 log for code scanning integrations
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SarifFormatter;
+
+/// GitHub Actions workflow commands (`::warning file=...,line=...::...`)
+/// that the runner turns into inline pull request annotations
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GithubAnnotationsFormatter;
+
+impl ReportFormatter for TextFormatter {
+    fn format(&self, report: &AnalysisReport, opts: &OutputOptions) -> String {
+        format_text_analysis(report, opts)
+    }
+}
+
+impl ReportFormatter for JsonFormatter {
+    fn format(&self, report: &AnalysisReport, opts: &OutputOptions) -> String {
+        let exit = opts
+            .exit
+            .clone()
+            .unwrap_or_else(|| report.exit_status(Severity::Warning));
+        to_json(
+            &JsonReport {
+                report,
+                exit
+            },
+            opts
+        )
+    }
+}
+
+impl ReportFormatter for YamlFormatter {
+    fn format(&self, report: &AnalysisReport, _opts: &OutputOptions) -> String {
+        serde_yaml::to_string(report).unwrap_or_default()
+    }
+}
+
+impl ReportFormatter for SarifFormatter {
+    fn format(&self, report: &AnalysisReport, opts: &OutputOptions) -> String {
+        format_sarif(report, opts)
+    }
+}
+
+impl ReportFormatter for GithubAnnotationsFormatter {
+    fn format(&self, report: &AnalysisReport, opts: &OutputOptions) -> String {
+        format_github_annotations(report, opts)
+    }
+}
+
+impl OutputFormat {
+    /// Built-in formatter for this format
+    pub fn formatter(self) -> &'static dyn ReportFormatter {
+        match self {
+            Self::Text => &TextFormatter,
+            Self::Json => &JsonFormatter,
+            Self::Yaml => &YamlFormatter,
+            Self::Sarif => &SarifFormatter,
+            Self::GithubAnnotations => &GithubAnnotationsFormatter
+        }
+    }
+}
+
+/// `report` without the violations [`OutputOptions::min_severity`] hides
+fn displayed<'a>(report: &'a AnalysisReport, opts: &OutputOptions) -> Cow<'a, AnalysisReport> {
+    let Some(min) = opts.min_severity else {
+        return Cow::Borrowed(report);
+    };
+    let mut shown = report.clone();
+    shown.violations.retain(|v| v.severity >= min);
+    shown.schema_violations.retain(|v| v.severity >= min);
+    Cow::Owned(shown)
+}
+
+/// Format static analysis report with the formatter selected by
+/// [`OutputOptions::format`]
 pub fn format_static_analysis(report: &AnalysisReport, opts: &OutputOptions) -> String {
+    opts.format
+        .formatter()
+        .format(&displayed(report, opts), opts)
+}
+
+/// Format only the violation counts of a static analysis report
+///
+/// JSON and YAML serialize [`ReportSummary`];
+/// SARIF emits a run without results that carries the summary in its
+/// property bag.
+pub fn format_report_summary(report: &AnalysisReport, opts: &OutputOptions) -> String {
+    let report = displayed(report, opts);
+    let report = report.as_ref();
+    let summary: ReportSummary = report.summary();
     match opts.format {
-        OutputFormat::Json => serde_json::to_string_pretty(report).unwrap_or_default(),
-        OutputFormat::Yaml => serde_yaml::to_string(report).unwrap_or_default(),
-        OutputFormat::Text => format_text_analysis(report, opts),
-        OutputFormat::Sarif => format_sarif(report)
+        OutputFormat::Json => to_json(&summary, opts),
+        OutputFormat::Yaml => serde_yaml::to_string(&summary).unwrap_or_default(),
+        OutputFormat::Text | OutputFormat::GithubAnnotations => {
+            format_text_summary_counts(report, opts)
+        }
+        OutputFormat::Sarif => {
+            let mut sarif = sarif_log(Vec::new());
+            sarif["runs"][0]["properties"] = serde_json::json!(summary);
+            to_json(&sarif, opts)
+        }
+    }
+}
+
+/// Format aggregate statistics of the report and its `queries`
+pub fn format_report_statistics(
+    report: &AnalysisReport,
+    queries: &[Query],
+    opts: &OutputOptions
+) -> String {
+    let report = displayed(report, opts);
+    let stats = report.to_statistics(queries);
+    match opts.format {
+        OutputFormat::Json => to_json(&stats, opts),
+        OutputFormat::Yaml => serde_yaml::to_string(&stats).unwrap_or_default(),
+        OutputFormat::Text | OutputFormat::GithubAnnotations => {
+            format_text_statistics(&stats, opts)
+        }
+        OutputFormat::Sarif => {
+            let mut sarif = sarif_log(Vec::new());
+            sarif["runs"][0]["properties"] = serde_json::json!(stats);
+            to_json(&sarif, opts)
+        }
+    }
+}
+
+fn format_text_statistics(stats: &Statistics, opts: &OutputOptions) -> String {
+    let mut output = String::new();
+    let header = "=== Analysis Statistics ===\n";
+    if opts.colored {
+        output.push_str(&header.bold().to_string());
+    } else {
+        output.push_str(header);
+    }
+    output.push_str(&format!(
+        "Queries: {} ({} analyzed, {} unsupported)\n",
+        stats.queries, stats.analyzed, stats.unsupported
+    ));
+    output.push_str("By severity:\n");
+    for (severity, count) in stats.by_severity.iter().rev() {
+        output.push_str(&format!("  {}: {}\n", severity, count));
+    }
+    output.push_str("By category:\n");
+    for (category, count) in &stats.by_category {
+        output.push_str(&format!("  {}: {}\n", category, count));
+    }
+    if !stats.top_rules.is_empty() {
+        output.push_str("Top rules:\n");
+        for rule in &stats.top_rules {
+            output.push_str(&format!("  {}: {}\n", rule.rule_id, rule.count));
+        }
+    }
+    output.push_str(&format!(
+        "Complexity: {:.1} average, {} max\n",
+        stats.avg_complexity, stats.max_complexity
+    ));
+    output
+}
+
+/// One-line result for CI logs, printed to stderr after the report, e.g.
+/// `sql-query-analyzer: 3 errors, 5 warnings, 2 info across 12 queries
+/// (exit 2)`
+pub fn format_summary_line(
+    summary: &ReportSummary,
+    queries_count: usize,
+    exit_code: i32
+) -> String {
+    let plural = |count: usize, word: &str| {
+        format!("{} {}{}", count, word, if count == 1 { "" } else { "s" })
+    };
+    format!(
+        "sql-query-analyzer: {}, {}, {} info across {} (exit {})",
+        plural(summary.errors, "error"),
+        plural(summary.warnings, "warning"),
+        summary.info,
+        if queries_count == 1 {
+            "1 query".to_string()
+        } else {
+            format!("{} queries", queries_count)
+        },
+        exit_code
+    )
+}
+
+fn format_text_summary_counts(report: &AnalysisReport, opts: &OutputOptions) -> String {
+    let mut output = String::new();
+    let header = "=== Static Analysis Summary ===\n";
+    if opts.colored {
+        output.push_str(&header.bold().to_string());
+    } else {
+        output.push_str(header);
+    }
+    output.push_str(&format!(
+        "Errors: {errors}\nWarnings: {warnings}\nInfo: {infos}\n",
+        errors = report.error_count(),
+        warnings = report.warning_count(),
+        infos = report.info_count()
+    ));
+    output.push_str("By category:\n");
+    for (category, count) in report.category_summary() {
+        output.push_str(&format!("  {}: {}\n", category, count));
     }
+    let highest = report
+        .highest_severity()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| "none".to_string());
+    output.push_str(&format!("Highest severity: {}\n", highest));
+    output.push_str(&format!(
+        "Unanalyzed statements: {}\n",
+        report.unanalyzed.len()
+    ));
+    output
 }
 
-fn format_sarif(report: &AnalysisReport) -> String {
+fn format_sarif(report: &AnalysisReport, opts: &OutputOptions) -> String {
+    let schema_results = report.schema_violations.iter().map(|v| {
+        serde_json::json!({
+            "ruleId": v.rule_id,
+            "level": sarif_level(v.severity),
+            "message": {
+                "text": v.message
+            },
+            "locations": [{
+                "physicalLocation": {
+                    "artifactLocation": {
+                        "uri": "schema.sql"
+                    }
+                }
+            }]
+        })
+    });
     let results: Vec<serde_json::Value> = report
         .violations
         .iter()
         .map(|v| {
             serde_json::json!({
                 "ruleId": v.rule_id,
-                "level": match v.severity {
-                    Severity::Error => "error",
-                    Severity::Warning => "warning",
-                    Severity::Info => "note",
-                },
+                "level": sarif_level(v.severity),
                 "message": {
                     "text": v.message
                 },
@@ -197,15 +614,113 @@ fn format_sarif(report: &AnalysisReport) -> String {
                         "artifactLocation": {
                             "uri": "queries.sql"
                         },
-                        "region": {
-                            "startLine": v.query_index + 1
-                        }
+                        "region": sarif_region(v)
                     }
                 }]
             })
         })
+        .chain(schema_results)
         .collect();
-    let sarif = serde_json::json!({
+    to_json(&sarif_log(results), opts)
+}
+
+/// One workflow command per violation; query violations carry the line and
+/// column of their statement when it is known, schema violations only the
+/// schema file
+fn format_github_annotations(report: &AnalysisReport, opts: &OutputOptions) -> String {
+    let (schema, queries) = match &opts.sources {
+        Some(sources) => (sources.schema.as_str(), sources.queries.as_str()),
+        None => ("schema.sql", "queries.sql")
+    };
+    let query_lines = report.violations.iter().map(|v| {
+        let mut properties = format!("file={}", escape_annotation_property(queries));
+        if let Some(span) = v.location {
+            properties.push_str(&format!(",line={},col={}", span.line, span.column));
+        }
+        github_annotation(v, &properties)
+    });
+    let schema_lines = report
+        .schema_violations
+        .iter()
+        .map(|v| github_annotation(v, &format!("file={}", escape_annotation_property(schema))));
+    let mut output: String = query_lines
+        .chain(schema_lines)
+        .map(|line| line + "\n")
+        .collect();
+    output.pop();
+    output
+}
+
+/// `::level properties,title=...::RULE: message` for `v`
+fn github_annotation(v: &Violation, properties: &str) -> String {
+    let level = match v.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "notice"
+    };
+    let mut message = format!("{}: {}", v.rule_id, v.message);
+    if let Some(suggestion) = &v.suggestion {
+        message.push_str(&format!("\nSuggestion: {}", suggestion));
+    }
+    format!(
+        "::{} {},title={}::{}",
+        level,
+        properties,
+        escape_annotation_property(&format!("{} {}", v.rule_id, v.rule_name)),
+        escape_annotation_data(&message)
+    )
+}
+
+/// Escape the message of a workflow command
+fn escape_annotation_data(text: &str) -> String {
+    text.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escape a `key=value` property of a workflow command
+fn escape_annotation_property(text: &str) -> String {
+    escape_annotation_data(text)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+/// Serialize `value` as single-line JSON when [`OutputOptions::compact`] is
+/// set, pretty-printed otherwise
+fn to_json<T: Serialize + ?Sized>(value: &T, opts: &OutputOptions) -> String {
+    if opts.compact {
+        serde_json::to_string(value).unwrap_or_default()
+    } else {
+        serde_json::to_string_pretty(value).unwrap_or_default()
+    }
+}
+
+/// SARIF result level for a severity
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note"
+    }
+}
+
+/// SARIF region for a violation: the statement's line and column when the
+/// parser reported one, otherwise the 1-based query index
+fn sarif_region(v: &Violation) -> serde_json::Value {
+    match v.location {
+        Some(span) => serde_json::json!({
+            "startLine": span.line,
+            "startColumn": span.column
+        }),
+        None => serde_json::json!({
+            "startLine": v.query_index + 1
+        })
+    }
+}
+
+/// Build a SARIF 2.1.0 log with a single run holding `results`
+fn sarif_log(results: Vec<serde_json::Value>) -> serde_json::Value {
+    serde_json::json!({
         "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/master/Schemata/sarif-schema-2.1.0.json",
         "version": "2.1.0",
         "runs": [{
@@ -218,8 +733,7 @@ fn format_sarif(report: &AnalysisReport) -> String {
             },
             "results": results
         }]
-    });
-    serde_json::to_string_pretty(&sarif).unwrap_or_default()
+    })
 }
 
 fn format_text_analysis(report: &AnalysisReport, opts: &OutputOptions) -> String {
@@ -230,7 +744,52 @@ fn format_text_analysis(report: &AnalysisReport, opts: &OutputOptions) -> String
     } else {
         output.push_str(header);
     }
-    if report.violations.is_empty() {
+    if !report.unanalyzed.is_empty() {
+        let mut note = format!(
+            "Skipped {} unsupported statement(s)",
+            report.unanalyzed.len()
+        );
+        if opts.verbose {
+            let positions: Vec<String> = report
+                .unanalyzed
+                .iter()
+                .map(|idx| format!("#{}", idx + 1))
+                .collect();
+            note.push_str(&format!(": Query {}", positions.join(", ")));
+        }
+        note.push('\n');
+        if opts.colored {
+            output.push_str(&note.dimmed().to_string());
+        } else {
+            output.push_str(&note);
+        }
+    }
+    if let Some(qps) = report.queries_per_second {
+        let note = format!(
+            "Analyzed {} queries at {:.0} queries/s\n",
+            report.queries_count, qps
+        );
+        if opts.colored {
+            output.push_str(&note.dimmed().to_string());
+        } else {
+            output.push_str(&note);
+        }
+    }
+    if !report.rule_timings.is_empty() {
+        let slowest: Vec<String> = report
+            .rule_timings
+            .iter()
+            .take(5)
+            .map(|timing| format!("{} {:.2}ms", timing.rule_id, timing.nanos as f64 / 1e6))
+            .collect();
+        let note = format!("Slowest rules: {}\n", slowest.join(", "));
+        if opts.colored {
+            output.push_str(&note.dimmed().to_string());
+        } else {
+            output.push_str(&note);
+        }
+    }
+    if report.all_violations().next().is_none() {
         let msg = "✓ No issues found\n";
         if opts.colored {
             output.push_str(&msg.green().to_string());
@@ -246,55 +805,159 @@ fn format_text_analysis(report: &AnalysisReport, opts: &OutputOptions) -> String
         infos = report.info_count()
     );
     output.push_str(&summary);
+    if !report.schema_violations.is_empty() {
+        if opts.colored {
+            output.push_str(&"Schema:\n".cyan().to_string());
+        } else {
+            output.push_str("Schema:\n");
+        }
+        for violation in &report.schema_violations {
+            push_text_violation(&mut output, violation, opts);
+        }
+    }
     let mut current_query = usize::MAX;
     for violation in &report.violations {
         if violation.query_index != current_query {
             current_query = violation.query_index;
-            let query_header = format!("Query #{}:\n", current_query + 1);
+            let query_header = match &violation.origin {
+                Some(name) => format!("Query #{} ({}):\n", current_query + 1, name),
+                None => format!("Query #{}:\n", current_query + 1)
+            };
             if opts.colored {
                 output.push_str(&query_header.cyan().to_string());
             } else {
                 output.push_str(&query_header);
             }
         }
-        let severity_str = match violation.severity {
-            Severity::Error => {
-                if opts.colored {
-                    "ERROR".red().bold().to_string()
-                } else {
-                    "ERROR".to_string()
-                }
-            }
-            Severity::Warning => {
-                if opts.colored {
-                    "WARN".yellow().to_string()
-                } else {
-                    "WARN".to_string()
-                }
+        push_text_violation(&mut output, violation, opts);
+    }
+    output.push('\n');
+    output
+}
+
+/// Append the severity, rule, message, and suggestion lines of `violation`
+fn push_text_violation(output: &mut String, violation: &Violation, opts: &OutputOptions) {
+    let severity_str = match violation.severity {
+        Severity::Error => {
+            if opts.colored {
+                "ERROR".red().bold().to_string()
+            } else {
+                "ERROR".to_string()
             }
-            Severity::Info => {
-                if opts.colored {
-                    "INFO".blue().to_string()
-                } else {
-                    "INFO".to_string()
-                }
+        }
+        Severity::Warning => {
+            if opts.colored {
+                "WARN".yellow().to_string()
+            } else {
+                "WARN".to_string()
             }
-        };
-        output.push_str(&format!(
-            "  [{severity:>5}] {rule_id}: {message}\n",
-            severity = severity_str,
-            rule_id = violation.rule_id,
-            message = violation.message
-        ));
-        if let Some(suggestion) = &violation.suggestion {
-            let suggestion_line = format!("         → {}\n", suggestion);
+        }
+        Severity::Info => {
             if opts.colored {
-                output.push_str(&suggestion_line.dimmed().to_string());
+                "INFO".blue().to_string()
             } else {
-                output.push_str(&suggestion_line);
+                "INFO".to_string()
             }
         }
+    };
+    if let Some(template) = &opts.template {
+        output.push_str(&render_template(template, violation, &severity_str));
+        output.push('\n');
+        return;
+    }
+    output.push_str(&format!(
+        "  [{severity:>5}] {rule_id}: {message}\n",
+        severity = severity_str,
+        rule_id = violation.rule_id,
+        message = violation.message
+    ));
+    if let Some(suggestion) = &violation.suggestion {
+        let suggestion_line = format!("         → {}\n", suggestion);
+        if opts.colored {
+            output.push_str(&suggestion_line.dimmed().to_string());
+        } else {
+            output.push_str(&suggestion_line);
+        }
+    }
+}
+
+/// Format the rule catalog
+pub fn format_catalog(entries: &[RuleCatalogEntry], format: CatalogOutputFormat) -> String {
+    match format {
+        CatalogOutputFormat::Json => serde_json::to_string_pretty(entries).unwrap_or_default(),
+        CatalogOutputFormat::Markdown => format_catalog_markdown(entries)
+    }
+}
+
+fn format_catalog_markdown(entries: &[RuleCatalogEntry]) -> String {
+    let mut output = String::from("# Rule Catalog\n");
+    for (family, title) in RULE_FAMILIES {
+        let family_entries: Vec<&RuleCatalogEntry> = entries
+            .iter()
+            .filter(|e| rule_family(e.id) == family)
+            .collect();
+        if family_entries.is_empty() {
+            continue;
+        }
+        output.push_str(&format!("\n## {}\n\n", title));
+        output.push_str("| ID | Rule | Severity | Description |\n");
+        output.push_str("|----|------|----------|-------------|\n");
+        for entry in &family_entries {
+            output.push_str(&format!(
+                "| `{id}` | {name} | {severity:?} | {description} |\n",
+                id = entry.id,
+                name = entry.name,
+                severity = entry.severity,
+                description = entry.description
+            ));
+        }
+        for entry in &family_entries {
+            output.push_str(&format!(
+                "\n### {id} — {name} ({severity:?})\n",
+                id = entry.id,
+                name = entry.name,
+                severity = entry.severity
+            ));
+            if !entry.explanation.is_empty() {
+                output.push_str(&format!("\n{}\n", entry.explanation));
+            }
+            if !entry.examples.is_empty() {
+                output.push_str("\n```sql\n");
+                for example in &entry.examples {
+                    output.push_str(&format!("{};\n", example.trim_end_matches(';')));
+                }
+                output.push_str("```\n");
+            }
+        }
+    }
+    output
+}
+
+/// Format table and column usage statistics
+pub fn format_usage_stats(stats: &UsageStats, format: StatsOutputFormat) -> String {
+    match format {
+        StatsOutputFormat::Json => serde_json::to_string_pretty(stats).unwrap_or_default(),
+        StatsOutputFormat::Text => format_usage_stats_text(stats)
+    }
+}
+
+fn format_usage_stats_text(stats: &UsageStats) -> String {
+    let mut output = format!("Usage across {} queries\n", stats.queries);
+    for (title, counts) in [("Tables", &stats.tables), ("Columns", &stats.columns)] {
+        output.push_str(&format!("\n{}\n", title));
+        if counts.is_empty() {
+            output.push_str("  (none)\n");
+            continue;
+        }
+        let width = counts.iter().map(|c| c.name.len()).max().unwrap_or(0);
+        for usage in counts {
+            output.push_str(&format!(
+                "  {:<width$}  {}\n",
+                usage.name,
+                usage.count,
+                width = width
+            ));
+        }
     }
-    output.push('\n');
     output
 }
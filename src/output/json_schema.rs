@@ -0,0 +1,250 @@
+//! JSON Schemas of the machine-readable report formats.
+//!
+//! The schemas are written by hand to match what [`JsonFormatter`] and
+//! [`SarifFormatter`] emit, so they stay stable across serde changes and can
+//! be published for downstream validation and binding generation.
+//!
+//! [`JsonFormatter`]: super::JsonFormatter
+//! [`SarifFormatter`]: super::SarifFormatter
+
+use serde_json::{Value, json};
+
+/// Dialect every emitted schema declares
+const DRAFT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Report format a schema describes
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SchemaOutputFormat {
+    /// The `--output-format json` report
+    #[default]
+    Json,
+    /// The SARIF 2.1.0 subset of `--output-format sarif`
+    Sarif
+}
+
+/// JSON Schema of the `AnalysisReport` JSON document
+///
+/// Optional members (`schema_violations`, `queries_per_second`,
+/// `rule_timings`) are omitted from the report when empty, so they are not
+/// required here. Additional properties are allowed so that new fields do
+/// not break existing validators.
+pub fn report_json_schema() -> Value {
+    json!({
+        "$schema": DRAFT,
+        "title": "sql-query-analyzer report",
+        "description": "Static analysis report printed by `analyze --output-format json`",
+        "type": "object",
+        "required": [
+            "violations",
+            "queries_count",
+            "rules_count",
+            "unanalyzed",
+            "exit_code",
+            "exit_reason"
+        ],
+        "properties": {
+            "violations": {
+                "description": "Violations found in the queries",
+                "type": "array",
+                "items": { "$ref": "#/$defs/violation" }
+            },
+            "schema_violations": {
+                "description": "Violations found in the schema itself; omitted when empty",
+                "type": "array",
+                "items": { "$ref": "#/$defs/violation" }
+            },
+            "queries_count": {
+                "description": "Number of queries analyzed",
+                "type": "integer",
+                "minimum": 0
+            },
+            "rules_count": {
+                "description": "Number of rules executed",
+                "type": "integer",
+                "minimum": 0
+            },
+            "unanalyzed": {
+                "description": "Zero-based indices of statements no rule can analyze",
+                "type": "array",
+                "items": { "type": "integer", "minimum": 0 }
+            },
+            "exit_code": {
+                "description": "Process exit code: 0 clean, 1 warnings or info, 2 errors",
+                "enum": [0, 1, 2]
+            },
+            "exit_reason": {
+                "description": "Why the exit code was chosen, e.g. \"2 errors found\"",
+                "type": "string"
+            },
+            "queries_per_second": {
+                "description": "Analysis throughput; present with --timings",
+                "type": "number"
+            },
+            "rule_timings": {
+                "description": "Time each rule spent, slowest first; present with --timings",
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "required": ["rule_id", "nanos"],
+                    "properties": {
+                        "rule_id": { "type": "string" },
+                        "nanos": { "type": "integer", "minimum": 0 }
+                    }
+                }
+            }
+        },
+        "$defs": {
+            "violation": {
+                "type": "object",
+                "required": [
+                    "rule_id",
+                    "rule_name",
+                    "message",
+                    "severity",
+                    "category",
+                    "suggestion",
+                    "query_index",
+                    "location"
+                ],
+                "properties": {
+                    "rule_id": { "type": "string", "examples": ["PERF001"] },
+                    "rule_name": { "type": "string" },
+                    "message": { "type": "string" },
+                    "severity": { "enum": ["Info", "Warning", "Error"] },
+                    "category": { "enum": ["Performance", "Style", "Security"] },
+                    "suggestion": { "type": ["string", "null"] },
+                    "query_index": {
+                        "description": "Zero-based index of the query; 0 for schema violations",
+                        "type": "integer",
+                        "minimum": 0
+                    },
+                    "location": {
+                        "oneOf": [{ "type": "null" }, { "$ref": "#/$defs/span" }]
+                    },
+                    "origin": {
+                        "description": "Name of the query from --input-format json/ndjson; omitted when unnamed",
+                        "type": "string"
+                    }
+                }
+            },
+            "span": {
+                "type": "object",
+                "required": ["line", "column", "start", "end"],
+                "properties": {
+                    "line": { "type": "integer", "minimum": 1 },
+                    "column": { "type": "integer", "minimum": 1 },
+                    "start": { "type": "integer", "minimum": 0 },
+                    "end": { "type": "integer", "minimum": 0 }
+                }
+            }
+        }
+    })
+}
+
+/// JSON Schema of the SARIF 2.1.0 subset the SARIF formatter emits
+///
+/// Only the members this tool writes are described; the document remains
+/// valid against the full OASIS SARIF schema named in its `$schema`.
+pub fn sarif_json_schema() -> Value {
+    json!({
+        "$schema": DRAFT,
+        "title": "sql-query-analyzer SARIF log",
+        "description": "SARIF 2.1.0 subset printed by `analyze --output-format sarif`",
+        "type": "object",
+        "required": ["$schema", "version", "runs"],
+        "properties": {
+            "$schema": { "type": "string" },
+            "version": { "const": "2.1.0" },
+            "runs": {
+                "type": "array",
+                "minItems": 1,
+                "maxItems": 1,
+                "items": { "$ref": "#/$defs/run" }
+            }
+        },
+        "$defs": {
+            "run": {
+                "type": "object",
+                "required": ["tool", "results"],
+                "properties": {
+                    "tool": {
+                        "type": "object",
+                        "required": ["driver"],
+                        "properties": {
+                            "driver": {
+                                "type": "object",
+                                "required": ["name", "version"],
+                                "properties": {
+                                    "name": { "const": "sql-query-analyzer" },
+                                    "version": { "type": "string" },
+                                    "informationUri": { "type": "string" }
+                                }
+                            }
+                        }
+                    },
+                    "results": {
+                        "type": "array",
+                        "items": { "$ref": "#/$defs/result" }
+                    },
+                    "properties": {
+                        "description": "Violation counts; present with --summary-only",
+                        "type": "object"
+                    }
+                }
+            },
+            "result": {
+                "type": "object",
+                "required": ["ruleId", "level", "message", "locations"],
+                "properties": {
+                    "ruleId": { "type": "string" },
+                    "level": { "enum": ["error", "warning", "note"] },
+                    "message": {
+                        "type": "object",
+                        "required": ["text"],
+                        "properties": { "text": { "type": "string" } }
+                    },
+                    "locations": {
+                        "type": "array",
+                        "minItems": 1,
+                        "items": { "$ref": "#/$defs/location" }
+                    }
+                }
+            },
+            "location": {
+                "type": "object",
+                "required": ["physicalLocation"],
+                "properties": {
+                    "physicalLocation": {
+                        "type": "object",
+                        "required": ["artifactLocation"],
+                        "properties": {
+                            "artifactLocation": {
+                                "type": "object",
+                                "required": ["uri"],
+                                "properties": { "uri": { "type": "string" } }
+                            },
+                            "region": {
+                                "description": "Statement position; absent for schema violations",
+                                "type": "object",
+                                "required": ["startLine"],
+                                "properties": {
+                                    "startLine": { "type": "integer", "minimum": 1 },
+                                    "startColumn": { "type": "integer", "minimum": 1 }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+/// Pretty-printed JSON Schema for `format`
+pub fn format_json_schema(format: SchemaOutputFormat) -> String {
+    let schema = match format {
+        SchemaOutputFormat::Json => report_json_schema(),
+        SchemaOutputFormat::Sarif => sarif_json_schema()
+    };
+    serde_json::to_string_pretty(&schema).unwrap_or_default()
+}
@@ -8,6 +8,10 @@
 //!
 //! - **ClickHouse**: Handles `CODEC`, `TTL`, `SETTINGS` clauses
 //!
+//! Queries stored as Jinja or Handlebars templates (dbt models) are made
+//! parseable by [`strip_template`]; queries wrapped in JSON or NDJSON records
+//! by [`unwrap_queries`].
+//!
 //! # Architecture
 //!
 //! The preprocessor operates in two phases:
@@ -27,9 +31,16 @@
 //! ```
 
 pub mod clickhouse;
+pub mod template;
+pub mod wrapped;
 
 use std::collections::HashMap;
 
+#[allow(unused_imports)]
+pub use template::{StrippedTemplate, TemplateSyntax, strip_template};
+#[allow(unused_imports)]
+pub use wrapped::{UnwrappedQueries, UnwrappedRecord, WrappedFormat, unwrap_queries};
+
 use crate::query::SqlDialect;
 
 /// Preprocessor for dialect-specific SQL transformations.
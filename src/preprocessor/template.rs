@@ -0,0 +1,350 @@
+//! Template stripping for SQL kept as Jinja or Handlebars templates.
+//!
+//! dbt models and `.sql.j2` files are not SQL until rendered. Stripping
+//! replaces each template block with a neutral placeholder of at most the
+//! same length, so the SQL skeleton parses and byte offsets and line
+//! numbers still point into the original file:
+//!
+//! - `{{ ref('orders') }}` and `{{ source('shop', 'orders') }}` in table
+//!   position become the table name (`orders`, `shop.orders`); other
+//!   expressions in table position become `tpl_N`
+//! - other `{{ ... }}` expressions become a `:tplN` bind parameter, or `tplN`
+//!   when glued to an identifier
+//! - expressions at the start of a statement (`{{ config(...) }}`), control
+//!   tags, and comments are blanked
+//! - only the first branch of an `if` is kept
+
+use std::ops::Range;
+
+use crate::query::Query;
+
+/// Template language to strip
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TemplateSyntax {
+    /// `{{ ... }}` expressions, `{% ... %}` tags, `{# ... #}` comments
+    Jinja,
+    /// `{{ ... }}` expressions, `{{#...}}` / `{{/...}}` blocks, `{{! ... }}`
+    /// comments
+    Handlebars
+}
+
+/// SQL with its template blocks replaced
+#[derive(Debug, Clone)]
+pub struct StrippedTemplate {
+    /// SQL ready for parsing
+    pub sql:    String,
+    /// Byte ranges of the replaced blocks in the original text
+    pub blocks: Vec<Range<usize>>
+}
+
+impl StrippedTemplate {
+    /// Set [`Query::templated`] on every query whose span overlaps a block
+    pub fn mark(&self, queries: &mut [Query]) {
+        for query in queries {
+            if let Some(span) = query.span {
+                query.templated = self
+                    .blocks
+                    .iter()
+                    .any(|block| block.start < span.end && span.start < block.end);
+            }
+        }
+    }
+}
+
+/// Keywords after which a template expression names a table
+const TABLE_KEYWORDS: &[&str] = &["FROM", "JOIN", "INTO", "UPDATE", "TABLE", "USING"];
+
+/// Keywords that end a table list when scanning back from a comma
+const CLAUSE_KEYWORDS: &[&str] = &[
+    "SELECT",
+    "WHERE",
+    "ON",
+    "SET",
+    "VALUES",
+    "GROUP",
+    "ORDER",
+    "HAVING",
+    "LIMIT",
+    "BY",
+    "AND",
+    "OR",
+    "WITH",
+    "RETURNING"
+];
+
+/// What a template block does
+enum Block<'a> {
+    /// Expression to replace with a placeholder
+    Expression(&'a str),
+    /// Opens an `if` or `for` body
+    Open,
+    /// `else` / `elif`: skip to the end of the enclosing block
+    Else,
+    /// Closes an `if` or `for` body
+    Close,
+    /// Anything else that renders no SQL
+    Blank
+}
+
+/// Replace the template blocks of `sql` with placeholders
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::preprocessor::{TemplateSyntax, strip_template};
+///
+/// let stripped = strip_template(
+///     "SELECT id FROM {{ ref('orders') }} WHERE status = {{ var('status') }}",
+///     TemplateSyntax::Jinja
+/// );
+/// assert!(stripped.sql.starts_with("SELECT id FROM orders "));
+/// assert!(stripped.sql.contains("status = :tpl1"));
+/// assert_eq!(stripped.blocks.len(), 2);
+/// ```
+pub fn strip_template(sql: &str, syntax: TemplateSyntax) -> StrippedTemplate {
+    let mut out = String::with_capacity(sql.len());
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+    let mut placeholders = 0;
+    let mut depth = 0usize;
+    let mut skip_until: Option<usize> = None;
+    while let Some((start, open, close)) = next_block(sql, pos, syntax) {
+        let Some(end) = sql[start + open.len()..]
+            .find(close)
+            .map(|rel| start + open.len() + rel + close.len())
+        else {
+            break;
+        };
+        if skip_until.is_some() {
+            blank(&mut out, &sql[pos..start]);
+        } else {
+            out.push_str(&sql[pos..start]);
+        }
+        let text = &sql[start..end];
+        let inner = text[open.len()..text.len() - close.len()]
+            .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | '+' | '~'));
+        let block = classify(open, inner, syntax);
+        blocks.push(start..end);
+        pos = end;
+        match block {
+            Block::Open => depth += 1,
+            Block::Else if skip_until.is_none() => skip_until = Some(depth),
+            Block::Close => {
+                if skip_until == Some(depth) {
+                    skip_until = None;
+                }
+                depth = depth.saturating_sub(1);
+            }
+            Block::Expression(expr) if skip_until.is_none() => {
+                let placeholder = placeholder(&out, &sql[end..], expr, &mut placeholders);
+                if placeholder.len() <= text.len() {
+                    out.push_str(&placeholder);
+                    blank(&mut out, &text[placeholder.len()..]);
+                } else {
+                    out.push_str(&placeholder);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        blank(&mut out, text);
+    }
+    if skip_until.is_some() {
+        blank(&mut out, &sql[pos..]);
+    } else {
+        out.push_str(&sql[pos..]);
+    }
+    StrippedTemplate {
+        sql: out,
+        blocks
+    }
+}
+
+/// Start, opening, and closing delimiter of the next block at or after `pos`
+fn next_block(
+    sql: &str,
+    pos: usize,
+    syntax: TemplateSyntax
+) -> Option<(usize, &'static str, &'static str)> {
+    let delimiters: &[(&str, &str)] = match syntax {
+        TemplateSyntax::Jinja => &[("{{", "}}"), ("{%", "%}"), ("{#", "#}")],
+        TemplateSyntax::Handlebars => &[("{{!--", "--}}"), ("{{{", "}}}"), ("{{", "}}")]
+    };
+    sql[pos..].match_indices('{').find_map(|(rel, _)| {
+        let start = pos + rel;
+        delimiters
+            .iter()
+            .find(|(open, _)| sql[start..].starts_with(open))
+            .map(|(open, close)| (start, *open, *close))
+    })
+}
+
+fn classify<'a>(open: &str, inner: &'a str, syntax: TemplateSyntax) -> Block<'a> {
+    let word = |text: &str| {
+        text.split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or("")
+            .to_string()
+    };
+    match (syntax, open) {
+        (TemplateSyntax::Jinja, "{#") => Block::Blank,
+        (TemplateSyntax::Jinja, "{%") => match word(inner).as_str() {
+            "if" | "for" => Block::Open,
+            "elif" | "else" => Block::Else,
+            "endif" | "endfor" => Block::Close,
+            _ => Block::Blank
+        },
+        (TemplateSyntax::Handlebars, "{{!--") => Block::Blank,
+        (TemplateSyntax::Handlebars, _) if inner.starts_with('!') => Block::Blank,
+        (TemplateSyntax::Handlebars, _) if inner.starts_with('#') => Block::Open,
+        (TemplateSyntax::Handlebars, _) if inner.starts_with('/') => Block::Close,
+        (TemplateSyntax::Handlebars, _)
+            if inner == "else" || inner.starts_with("else ") || inner.starts_with('^') =>
+        {
+            Block::Else
+        }
+        _ => Block::Expression(inner)
+    }
+}
+
+/// Placeholder for expression `expr`, given the SQL emitted before it and
+/// the original text after it
+fn placeholder(before: &str, after: &str, expr: &str, count: &mut usize) -> String {
+    let trimmed = before.trim_end();
+    if trimmed.is_empty() || trimmed.ends_with(';') {
+        return String::new();
+    }
+    let ident = |c: char| c.is_alphanumeric() || c == '_';
+    if table_position(trimmed) {
+        return table_name(expr).unwrap_or_else(|| {
+            *count += 1;
+            format!("tpl_{}", count)
+        });
+    }
+    *count += 1;
+    if before.ends_with(ident) || after.starts_with(ident) {
+        format!("tpl{}", count)
+    } else {
+        format!(":tpl{}", count)
+    }
+}
+
+/// Whether text ending in `before` expects a table name next
+fn table_position(before: &str) -> bool {
+    let mut words = before
+        .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+        .filter(|w| !w.is_empty());
+    if !before.ends_with(',') {
+        let last = words.next().unwrap_or("");
+        return before.ends_with(|c: char| c.is_alphanumeric() || c == '_')
+            && TABLE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(last));
+    }
+    words
+        .find_map(|w| {
+            if TABLE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(w)) {
+                Some(true)
+            } else if CLAUSE_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(w)) {
+                Some(false)
+            } else {
+                None
+            }
+        })
+        .unwrap_or(false)
+}
+
+/// Table named by a dbt `ref(...)` or `source(...)` call
+fn table_name(expr: &str) -> Option<String> {
+    let (function, args) = expr.split_once('(')?;
+    let args: Vec<&str> = args
+        .strip_suffix(')')?
+        .split(',')
+        .map(|arg| arg.trim().trim_matches(|c| c == '\'' || c == '"'))
+        .filter(|arg| !arg.is_empty() && !arg.contains('='))
+        .collect();
+    let valid = |name: &str| name.chars().all(|c| c.is_alphanumeric() || c == '_');
+    match function.trim() {
+        "ref" => args
+            .last()
+            .filter(|name| valid(name))
+            .map(|name| name.to_string()),
+        "source" if args.len() == 2 && args.iter().all(|name| valid(name)) => {
+            Some(format!("{}.{}", args[0], args[1]))
+        }
+        _ => None
+    }
+}
+
+/// Append `text` with every character except line breaks turned into
+/// spaces, keeping its byte length
+fn blank(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if c == '\n' || c == '\r' {
+            out.push(c);
+        } else {
+            out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{SqlDialect, parse_queries};
+
+    #[test]
+    fn test_dbt_ref_becomes_table() {
+        let sql = "{{ config(materialized='table') }}\n\
+                   SELECT o.id FROM {{ ref('orders') }} o\n\
+                   JOIN {{ source('shop', 'users') }} u ON u.id = o.user_id";
+        let stripped = strip_template(sql, TemplateSyntax::Jinja);
+        assert_eq!(stripped.sql.len(), sql.len());
+        assert_eq!(stripped.sql.lines().count(), 3);
+        let queries = parse_queries(&stripped.sql, SqlDialect::Generic).unwrap();
+        assert_eq!(queries[0].tables, vec!["orders", "shop.users"]);
+    }
+
+    #[test]
+    fn test_expressions_become_parameters() {
+        let sql = "SELECT id FROM events_{{ var('suffix') }} \
+                   WHERE created_at > {{ var('since') }} AND kind IN ({{ kinds }})";
+        let stripped = strip_template(sql, TemplateSyntax::Jinja);
+        assert!(stripped.sql.contains("FROM events_tpl1 "));
+        assert!(stripped.sql.contains("> :tpl2"));
+        assert!(stripped.sql.contains("IN (:tpl3"));
+        assert!(parse_queries(&stripped.sql, SqlDialect::PostgreSQL).is_ok());
+    }
+
+    #[test]
+    fn test_only_first_branch_kept() {
+        let sql = "SELECT id FROM orders\n\
+                   {% if is_incremental() %}WHERE updated_at > {{ this_run }}\n\
+                   {% else %}WHERE 1 = 1 {# full refresh #}\n\
+                   {% endif %}";
+        let stripped = strip_template(sql, TemplateSyntax::Jinja);
+        assert!(stripped.sql.contains("WHERE updated_at > :tpl1"));
+        assert!(!stripped.sql.contains("1 = 1"));
+        assert!(parse_queries(&stripped.sql, SqlDialect::Generic).is_ok());
+    }
+
+    #[test]
+    fn test_handlebars_blocks() {
+        let sql = "{{!-- report --}}SELECT id FROM {{table}} \
+                   WHERE {{#if active}}active = {{flag}}{{else}}1 = 1{{/if}}";
+        let stripped = strip_template(sql, TemplateSyntax::Handlebars);
+        assert!(stripped.sql.contains("FROM tpl_1 "));
+        assert!(stripped.sql.contains("active = :tpl2"));
+        assert!(!stripped.sql.contains("1 = 1"));
+        assert!(parse_queries(&stripped.sql, SqlDialect::Generic).is_ok());
+    }
+
+    #[test]
+    fn test_mark_flags_templated_statements() {
+        let sql = "SELECT id FROM users;\nSELECT id FROM {{ ref('orders') }};";
+        let stripped = strip_template(sql, TemplateSyntax::Jinja);
+        let mut queries = parse_queries(&stripped.sql, SqlDialect::Generic).unwrap();
+        stripped.mark(&mut queries);
+        assert!(!queries[0].templated);
+        assert!(queries[1].templated);
+    }
+}
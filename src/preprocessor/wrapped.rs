@@ -0,0 +1,226 @@
+//! Queries wrapped in JSON records.
+//!
+//! Query logs and capture tools often store each statement as a JSON object
+//! with metadata, either in one array or one object per line (NDJSON):
+//!
+//! ```json
+//! [{ "sql": "SELECT * FROM users", "name": "list_users" }]
+//! ```
+//!
+//! Unwrapping joins the `sql` members into one SQL text, ending each record
+//! that lacks one with a `;` on its own line (so a trailing `--` comment
+//! cannot swallow it), and remembers which byte range each record occupies
+//! so the parsed queries can be given the record's `name` and its position
+//! in the wrapped file.
+
+use std::ops::Range;
+
+use serde::Deserialize;
+use serde_json::value::RawValue;
+
+use crate::{
+    error::{AppResult, query_parse_error},
+    query::{Query, Span}
+};
+
+/// Layout of a file of JSON-wrapped queries
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WrappedFormat {
+    /// One JSON array of records
+    Json,
+    /// One JSON record per line; blank lines are skipped
+    Ndjson
+}
+
+/// One query record of a wrapped input
+#[derive(Debug, Clone, Deserialize)]
+struct WrappedRecord {
+    sql:  String,
+    #[serde(default)]
+    name: Option<String>
+}
+
+/// One record of a wrapped input
+#[derive(Debug, Clone)]
+pub struct UnwrappedRecord {
+    /// Byte range of the record's SQL in [`UnwrappedQueries::sql`]
+    pub range:  Range<usize>,
+    /// The record's `name`, if it has one
+    pub name:   Option<String>,
+    /// Position of the whole record in the wrapped input
+    pub source: Span
+}
+
+/// SQL joined from wrapped records
+#[derive(Debug, Clone)]
+pub struct UnwrappedQueries {
+    /// SQL ready for parsing
+    pub sql:     String,
+    /// Every record, in input order
+    pub records: Vec<UnwrappedRecord>
+}
+
+impl UnwrappedQueries {
+    /// Give every query the name and the input position of the record it
+    /// starts in, so [`Query::origin`] and [`Query::span`] refer to the
+    /// wrapped file rather than the joined SQL; statements the parser
+    /// reports no position for (e.g., `BEGIN`) stay unnamed and unplaced
+    pub fn apply(&self, queries: &mut [Query]) {
+        for query in queries {
+            let Some(span) = query.span else {
+                continue;
+            };
+            let record = self
+                .records
+                .iter()
+                .find(|record| record.range.contains(&span.start));
+            query.origin = record.and_then(|record| record.name.clone());
+            query.span = record.map(|record| record.source);
+        }
+    }
+}
+
+/// Join the `sql` members of the records in `input`
+///
+/// Records are objects with a `sql` string and an optional `name` string;
+/// other members are ignored.
+///
+/// # Errors
+///
+/// Returns an error when `input` is not valid JSON in `format` or a record
+/// has no `sql` string.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::preprocessor::{WrappedFormat, unwrap_queries};
+///
+/// let input = r#"{"sql": "SELECT 1", "name": "one"}
+/// {"sql": "SELECT 2;"}"#;
+/// let unwrapped = unwrap_queries(input, WrappedFormat::Ndjson).unwrap();
+///
+/// assert_eq!(unwrapped.sql, "SELECT 1\n;\nSELECT 2;\n");
+/// assert_eq!(unwrapped.records[0].name.as_deref(), Some("one"));
+/// assert_eq!(unwrapped.records[1].name, None);
+/// assert_eq!(unwrapped.records[1].source.line, 2);
+/// ```
+pub fn unwrap_queries(input: &str, format: WrappedFormat) -> AppResult<UnwrappedQueries> {
+    let raw: Vec<&str> = match format {
+        WrappedFormat::Json => serde_json::from_str::<Vec<&RawValue>>(input)
+            .map_err(|e| query_parse_error(format!("Invalid JSON query input: {}", e)))?
+            .into_iter()
+            .map(RawValue::get)
+            .collect(),
+        WrappedFormat::Ndjson => input
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect()
+    };
+    let mut sql = String::new();
+    let mut records = Vec::with_capacity(raw.len());
+    for text in raw {
+        let source = source_span(input, text);
+        let record: WrappedRecord = serde_json::from_str(text).map_err(|e| {
+            let message = match format {
+                WrappedFormat::Json => format!("Invalid JSON query input: {}", e),
+                WrappedFormat::Ndjson => {
+                    format!("Invalid NDJSON query input on line {}: {}", source.line, e)
+                }
+            };
+            query_parse_error(message)
+        })?;
+        let start = sql.len();
+        let statement = record.sql.trim();
+        sql.push_str(statement);
+        if !statement.ends_with(';') {
+            sql.push_str("\n;");
+        }
+        sql.push('\n');
+        records.push(UnwrappedRecord {
+            range: start..sql.len(),
+            name: record.name,
+            source
+        });
+    }
+    Ok(UnwrappedQueries {
+        sql,
+        records
+    })
+}
+
+/// Position of `text`, a slice of `input`, within `input`
+fn source_span(input: &str, text: &str) -> Span {
+    let start = text.as_ptr() as usize - input.as_ptr() as usize;
+    let before = &input[..start];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    Span {
+        line: before.matches('\n').count() + 1,
+        column: before[line_start..].chars().count() + 1,
+        start,
+        end: start + text.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::{SqlDialect, parse_queries};
+
+    #[test]
+    fn test_json_array_names_queries() {
+        let input = r#"[
+            {"sql": "SELECT id FROM users -- by id", "name": "users_by_id"},
+            {"sql": "SELECT * FROM orders; DELETE FROM orders;", "name": "orders", "ms": 12},
+            {"sql": "DELETE FROM sessions"}
+        ]"#;
+        let unwrapped = unwrap_queries(input, WrappedFormat::Json).unwrap();
+        let mut queries = parse_queries(&unwrapped.sql, SqlDialect::Generic).unwrap();
+        unwrapped.apply(&mut queries);
+        let origins: Vec<Option<&str>> = queries.iter().map(|q| q.origin.as_deref()).collect();
+        assert_eq!(
+            origins,
+            [Some("users_by_id"), Some("orders"), Some("orders"), None]
+        );
+    }
+
+    #[test]
+    fn test_invalid_records_rejected() {
+        assert!(unwrap_queries("{\"sql\": 1}", WrappedFormat::Ndjson).is_err());
+        assert!(unwrap_queries("{\"name\": \"x\"}", WrappedFormat::Ndjson).is_err());
+        assert!(unwrap_queries("{\"sql\": \"SELECT 1\"}", WrappedFormat::Json).is_err());
+        let error = unwrap_queries("{\"sql\": \"SELECT 1\"}\nnot json", WrappedFormat::Ndjson)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_queries_placed_at_their_record() {
+        let input = "[\n  {\"sql\": \"SELECT 1\"},\n\n  {\"sql\": \"SELECT 2; SELECT 3\"}\n]";
+        let unwrapped = unwrap_queries(input, WrappedFormat::Json).unwrap();
+        let mut queries = parse_queries(&unwrapped.sql, SqlDialect::Generic).unwrap();
+        unwrapped.apply(&mut queries);
+        let lines: Vec<(usize, usize)> = queries
+            .iter()
+            .map(|q| q.span.map(|s| (s.line, s.column)).unwrap())
+            .collect();
+        assert_eq!(lines, [(2, 3), (4, 3), (4, 3)]);
+        let record = queries[1].span.unwrap();
+        assert_eq!(
+            &input[record.start..record.end],
+            "{\"sql\": \"SELECT 2; SELECT 3\"}"
+        );
+
+        let input = "\n{\"sql\": \"SELECT 1\"}\n  {\"sql\": \"SELECT 2\"}\n";
+        let unwrapped = unwrap_queries(input, WrappedFormat::Ndjson).unwrap();
+        let mut queries = parse_queries(&unwrapped.sql, SqlDialect::Generic).unwrap();
+        unwrapped.apply(&mut queries);
+        let lines: Vec<(usize, usize)> = queries
+            .iter()
+            .map(|q| q.span.map(|s| (s.line, s.column)).unwrap())
+            .collect();
+        assert_eq!(lines, [(2, 1), (3, 3)]);
+    }
+}
@@ -1,7 +1,27 @@
+mod export;
 mod extract;
+mod fingerprint;
+mod identifier;
+mod placeholder;
+mod stream;
+mod suppression;
 mod types;
 
-use extract::{ExtractionContext, extract_columns_from_expr, extract_from_set_expr};
+use export::strip_file_exports;
+pub(crate) use extract::literal_text;
+use extract::{
+    ExtractionContext, derived_subqueries, extract_column_refs, extract_columns_from_expr,
+    extract_from_set_expr, extract_like_patterns, extract_subqueries, has_distinct_union,
+    having_plain_columns, join_using_clauses, nested_scopes, projection_count_columns,
+    projection_function_calls, projection_has_wildcard, select_column_casts, select_column_refs,
+    select_distinct_on, select_final_tables, select_has_prewhere, ungrouped_selects,
+    unguarded_recursive_ctes, unlimited_ordered_ctes, where_equality_columns,
+    where_function_betweens, where_json_access, where_literal_comparisons,
+    where_multi_row_subqueries, where_or_branches, written_literal
+};
+pub(crate) use fingerprint::StableHasher;
+#[allow(unused_imports)]
+pub use fingerprint::{fingerprint, normalize};
 use indexmap::IndexSet;
 use rayon::prelude::*;
 use sqlparser::{
@@ -10,12 +30,20 @@ use sqlparser::{
     },
     parser::Parser
 };
-pub use types::{Query, QueryType};
+#[allow(unused_imports)]
+pub use stream::{QueryStream, RawStatement, StatementSplitter, stream_queries};
+use suppression::apply_suppressions;
+pub use types::{
+    ColumnCast, ColumnClause, ColumnComparison, ColumnRef, ColumnWrite, CompareOp, FileExport,
+    FunctionBetween, GrantInfo, JsonAccess, LiteralComparison, LockMode, MergeAction, MergeInfo,
+    MergeWhen, PlaceholderStyle, Query, QueryType, SessionSetting, SortDir, Span, UngroupedSelect,
+    UsingJoin, WrittenLiteral
+};
 
 use crate::error::{AppResult, query_parse_error};
 
 /// SQL dialect for parsing
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum SqlDialect {
     #[default]
@@ -37,6 +65,98 @@ impl SqlDialect {
             Self::ClickHouse => Box::new(ClickHouseDialect {})
         }
     }
+
+    /// Guess the dialect of schema or query text from vendor markers
+    ///
+    /// The text is tokenized, so markers inside string literals, comments,
+    /// quoted identifiers, or longer names (`serial_number`) do not count.
+    /// Each dialect scores one point per distinct marker found (ClickHouse:
+    /// `*MergeTree` engines, `CODEC(`, `toDateTime`, `LowCardinality(`;
+    /// PostgreSQL: `::` casts, `SERIAL`, `JSONB`, `ILIKE`; MySQL: backtick
+    /// quoting, `AUTO_INCREMENT`, `ENGINE = InnoDB`). The highest score wins;
+    /// no markers, a tie, or text that cannot be tokenized yield
+    /// [`SqlDialect::Generic`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sql_query_analyzer::query::SqlDialect;
+    ///
+    /// let ddl = "CREATE TABLE t (id UInt64) ENGINE = MergeTree ORDER BY id";
+    /// assert_eq!(SqlDialect::detect(ddl), SqlDialect::ClickHouse);
+    /// assert_eq!(SqlDialect::detect("SELECT 1"), SqlDialect::Generic);
+    /// ```
+    pub fn detect(sql: &str) -> Self {
+        use sqlparser::tokenizer::{Token, Tokenizer};
+        let Ok(tokens) = Tokenizer::new(&GenericDialect {}, sql).tokenize() else {
+            return Self::Generic;
+        };
+        let tokens: Vec<Token> = tokens
+            .into_iter()
+            .filter(|token| !matches!(token, Token::Whitespace(_)))
+            .collect();
+        let mut found: Vec<&str> = Vec::new();
+        for (idx, token) in tokens.iter().enumerate() {
+            let next = tokens.get(idx + 1);
+            let marker = match token {
+                Token::DoubleColon => Some("::"),
+                Token::Word(word) if word.quote_style == Some('`') => Some("`"),
+                Token::Word(word) if word.quote_style.is_none() => {
+                    let upper = word.value.to_uppercase();
+                    match upper.as_str() {
+                        "SERIAL" | "SMALLSERIAL" | "BIGSERIAL" => Some("SERIAL"),
+                        "JSONB" => Some("JSONB"),
+                        "ILIKE" => Some("ILIKE"),
+                        "AUTO_INCREMENT" => Some("AUTO_INCREMENT"),
+                        "TODATETIME" => Some("TODATETIME"),
+                        "CODEC" if next == Some(&Token::LParen) => Some("CODEC("),
+                        "LOWCARDINALITY" if next == Some(&Token::LParen) => {
+                            Some("LOWCARDINALITY(")
+                        }
+                        "ENGINE" => match (next, tokens.get(idx + 2)) {
+                            (Some(Token::Eq), Some(Token::Word(engine)))
+                                if engine.value.eq_ignore_ascii_case("InnoDB") =>
+                            {
+                                Some("ENGINE=INNODB")
+                            }
+                            _ => None
+                        },
+                        _ if upper.ends_with("MERGETREE") => Some("MERGETREE"),
+                        _ => None
+                    }
+                }
+                _ => None
+            };
+            if let Some(marker) = marker
+                && !found.contains(&marker)
+            {
+                found.push(marker);
+            }
+        }
+        let score = |markers: &[&str]| {
+            markers
+                .iter()
+                .filter(|marker| found.contains(*marker))
+                .count()
+        };
+        let candidates = [
+            (
+                Self::ClickHouse,
+                score(&["MERGETREE", "CODEC(", "TODATETIME", "LOWCARDINALITY("])
+            ),
+            (Self::PostgreSQL, score(&["::", "SERIAL", "JSONB", "ILIKE"])),
+            (
+                Self::MySQL,
+                score(&["`", "AUTO_INCREMENT", "ENGINE=INNODB"])
+            )
+        ];
+        let best = candidates.iter().map(|(_, s)| *s).max().unwrap_or(0);
+        let mut leaders = candidates.iter().filter(|(_, s)| *s == best && best > 0);
+        match (leaders.next(), leaders.next()) {
+            (Some((dialect, _)), None) => *dialect,
+            _ => Self::Generic
+        }
+    }
 }
 
 /// Parse multiple SQL queries from string (parallel)
@@ -44,12 +164,191 @@ impl SqlDialect {
 /// # Notes
 ///
 /// - Parses statements in parallel for better performance
+/// - Records each statement's position in `sql` as [`Query::span`]
+/// - Accepts `INTO OUTFILE` / `INTO DUMPFILE`, which sqlparser rejects, and
+///   records them as [`Query::file_export`]
+/// - Records `-- sqla:disable` comments as [`Query::suppressed_rules`]
+/// - Keeps every comment in or before a statement in [`Query::comments`]
 pub fn parse_queries(sql: &str, dialect: SqlDialect) -> AppResult<Vec<Query>> {
+    parse_at(
+        sql,
+        dialect,
+        Span {
+            line:   1,
+            column: 1,
+            start:  0,
+            end:    sql.len()
+        }
+    )
+}
+
+/// [`parse_queries`] for `sql` found at `origin` within a larger input;
+/// spans and error positions refer to that input
+fn parse_at(sql: &str, dialect: SqlDialect, origin: Span) -> AppResult<Vec<Query>> {
     let parser_dialect = dialect.into_parser_dialect();
+    let (blanked, exports) = strip_file_exports(sql);
+    let sql = blanked.as_str();
     let statements = Parser::parse_sql(parser_dialect.as_ref(), sql)
-        .map_err(|e| query_parse_error(e.to_string()))?;
-    let queries: Result<Vec<_>, _> = statements.into_par_iter().map(parse_statement).collect();
-    queries
+        .map_err(|e| query_parse_error(relocate_position(e.to_string(), origin)))?;
+    let line_starts: Vec<usize> = std::iter::once(0)
+        .chain(sql.match_indices('\n').map(|(pos, _)| pos + 1))
+        .collect();
+    let queries: Result<Vec<_>, _> = statements
+        .into_par_iter()
+        .map(|stmt| {
+            use sqlparser::ast::Spanned;
+            let span = source_span(sql, &line_starts, stmt.span());
+            parse_statement(stmt).map(|mut q| {
+                q.span = span;
+                q
+            })
+        })
+        .collect();
+    let mut queries = queries?;
+    for (offset, export) in exports {
+        if let Some(owner) = queries
+            .iter_mut()
+            .rev()
+            .find(|q| q.span.as_ref().is_some_and(|span| span.start <= offset))
+        {
+            owner.file_export = Some(export);
+        }
+    }
+    apply_suppressions(sql, &mut queries);
+    attach_comments(sql, &line_starts, parser_dialect.as_ref(), &mut queries);
+    for span in queries.iter_mut().filter_map(|q| q.span.as_mut()) {
+        *span = shift_span(*span, origin);
+    }
+    Ok(queries)
+}
+
+/// `span` of text that starts at `origin`, made relative to the input
+/// containing it
+fn shift_span(span: Span, origin: Span) -> Span {
+    Span {
+        line:   span.line + origin.line - 1,
+        column: if span.line == 1 {
+            span.column + origin.column - 1
+        } else {
+            span.column
+        },
+        start:  span.start + origin.start,
+        end:    span.end + origin.start
+    }
+}
+
+/// Rewrite the `Line: L, Column: C` position of a sqlparser error about text
+/// starting at `origin` so it refers to the whole input
+fn relocate_position(message: String, origin: Span) -> String {
+    let Some(at) = message.find("Line: ") else {
+        return message;
+    };
+    let number = |text: &str| {
+        let len = text
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len());
+        text[..len].parse::<usize>().ok().map(|n| (n, len))
+    };
+    let after_line = &message[at + "Line: ".len()..];
+    let Some((line, line_len)) = number(after_line) else {
+        return message;
+    };
+    let Some(after_column) = after_line[line_len..].strip_prefix(", Column: ") else {
+        return message;
+    };
+    let Some((column, column_len)) = number(after_column) else {
+        return message;
+    };
+    let shifted = shift_span(
+        Span {
+            line,
+            column,
+            start: 0,
+            end: 0
+        },
+        origin
+    );
+    format!(
+        "{}Line: {}, Column: {}{}",
+        &message[..at],
+        shifted.line,
+        shifted.column,
+        &after_column[column_len..]
+    )
+}
+
+/// Give each comment of `sql` to the statement it is in, or else to the next
+/// statement; comments after the last statement go to that statement
+fn attach_comments(
+    sql: &str,
+    line_starts: &[usize],
+    dialect: &dyn Dialect,
+    queries: &mut [Query]
+) {
+    use sqlparser::tokenizer::{Token, Tokenizer, Whitespace};
+    let Ok(tokens) = Tokenizer::new(dialect, sql).tokenize_with_location() else {
+        return;
+    };
+    for token in tokens {
+        let Token::Whitespace(
+            comment @ (Whitespace::SingleLineComment {
+                ..
+            }
+            | Whitespace::MultiLineComment(_))
+        ) = &token.token
+        else {
+            continue;
+        };
+        let Some(offset) = location_offset(sql, line_starts, token.span.start) else {
+            continue;
+        };
+        let spanned = || queries.iter().enumerate().filter(|(_, q)| q.span.is_some());
+        let owner = spanned()
+            .find(|(_, q)| q.span.as_ref().is_some_and(|span| span.end > offset))
+            .or_else(|| spanned().last())
+            .map(|(idx, _)| idx);
+        if let Some(idx) = owner {
+            queries[idx]
+                .comments
+                .push(comment.to_string().trim_end().into());
+        }
+    }
+}
+
+/// Byte offset in `sql` of a parser location (1-based line and column)
+fn location_offset(
+    sql: &str,
+    line_starts: &[usize],
+    loc: sqlparser::tokenizer::Location
+) -> Option<usize> {
+    let line_start = *line_starts.get((loc.line as usize).checked_sub(1)?)?;
+    let rest = &sql[line_start..];
+    let column = (loc.column as usize).saturating_sub(1);
+    Some(
+        line_start
+            + rest
+                .char_indices()
+                .nth(column)
+                .map_or(rest.len(), |(idx, _)| idx)
+    )
+}
+
+/// Convert a parser span (1-based line/column) into a [`Span`] with byte
+/// offsets into `sql`. Empty spans (line 0) yield `None`.
+fn source_span(
+    sql: &str,
+    line_starts: &[usize],
+    span: sqlparser::tokenizer::Span
+) -> Option<Span> {
+    let offset = |loc| location_offset(sql, line_starts, loc);
+    let start = offset(span.start)?;
+    let end = offset(span.end).unwrap_or(sql.len()).max(start);
+    Some(Span {
+        line: span.start.line as usize,
+        column: span.start.column as usize,
+        start,
+        end
+    })
 }
 
 fn parse_statement(stmt: sqlparser::ast::Statement) -> AppResult<Query> {
@@ -60,23 +359,88 @@ fn parse_statement(stmt: sqlparser::ast::Statement) -> AppResult<Query> {
         Statement::Insert(insert) => {
             let mut q = Query::new(raw, QueryType::Insert);
             q.tables.push(insert.table.to_string().into());
+            q.insert_columns = insert
+                .columns
+                .iter()
+                .map(|c| c.to_string().into())
+                .collect();
+            q.column_refs = q
+                .insert_columns
+                .iter()
+                .map(|column| ColumnRef {
+                    reference: column.clone(),
+                    clause:    ColumnClause::Insert
+                })
+                .collect();
+            if let Some(source) = insert.source {
+                if let sqlparser::ast::SetExpr::Values(values) = source.body.as_ref() {
+                    q.insert_values_arity = values.rows.first().map(|row| row.content.len());
+                    q.insert_values_rows = Some(values.rows.len());
+                    for row in &values.rows {
+                        q.column_writes.extend(
+                            q.insert_columns.iter().zip(&row.content).filter_map(
+                                |(column, value)| {
+                                    Some(ColumnWrite {
+                                        column: column.clone(),
+                                        value:  written_literal(value)?
+                                    })
+                                }
+                            )
+                        );
+                    }
+                } else {
+                    let select = parse_select_query(source.to_string(), *source)?;
+                    q.insert_select = Some(Box::new(select));
+                }
+            }
             Ok(q)
         }
         Statement::Update(update) => {
             let mut q = Query::new(raw, QueryType::Update);
+            let mut scopes = Vec::new();
             q.tables.push(update.table.relation.to_string().into());
-            if let Some(sel) = update.selection {
+            for assignment in &update.assignments {
+                let targets = match &assignment.target {
+                    sqlparser::ast::AssignmentTarget::ColumnName(name) => vec![name],
+                    sqlparser::ast::AssignmentTarget::Tuple(names) => names.iter().collect()
+                };
+                q.column_refs
+                    .extend(targets.into_iter().map(|name| ColumnRef {
+                        reference: name.to_string().into(),
+                        clause:    ColumnClause::Set
+                    }));
+                extract_column_refs(&assignment.value, ColumnClause::Set, &mut q.column_refs);
+                extract_subqueries(&assignment.value, &mut scopes);
+                if let sqlparser::ast::AssignmentTarget::ColumnName(name) = &assignment.target
+                    && let Some(value) = written_literal(&assignment.value)
+                {
+                    q.column_writes.push(ColumnWrite {
+                        column: name.to_string().into(),
+                        value
+                    });
+                }
+            }
+            if let Some(sel) = &update.selection {
+                extract_column_refs(sel, ColumnClause::Where, &mut q.column_refs);
+                extract_subqueries(sel, &mut scopes);
                 let mut cols = IndexSet::new();
-                extract_columns_from_expr(&sel, &mut cols);
+                extract_columns_from_expr(sel, &mut cols);
+                extract_like_patterns(sel, &mut q.like_patterns);
                 q.where_cols = cols.into_iter().collect();
             }
+            q.subqueries = parse_scopes(scopes)?;
             Ok(q)
         }
         Statement::Delete(delete) => {
             let mut q = Query::new(raw, QueryType::Delete);
-            if let Some(sel) = delete.selection {
+            if let Some(sel) = &delete.selection {
+                extract_column_refs(sel, ColumnClause::Where, &mut q.column_refs);
+                let mut scopes = Vec::new();
+                extract_subqueries(sel, &mut scopes);
+                q.subqueries = parse_scopes(scopes)?;
                 let mut cols = IndexSet::new();
-                extract_columns_from_expr(&sel, &mut cols);
+                extract_columns_from_expr(sel, &mut cols);
+                extract_like_patterns(sel, &mut q.like_patterns);
                 q.where_cols = cols.into_iter().collect();
             }
             if let sqlparser::ast::FromTable::WithFromKeyword(from_items) = delete.from {
@@ -106,10 +470,185 @@ fn parse_statement(stmt: sqlparser::ast::Statement) -> AppResult<Query> {
                 .push(format!("{:?}", object_type).to_lowercase().into());
             Ok(q)
         }
+        Statement::Grant(grant) => {
+            let mut q = Query::new(raw, QueryType::Grant);
+            q.grant = Some(parse_grant(&grant));
+            Ok(q)
+        }
+        Statement::Merge(merge) => {
+            let mut q = Query::new(raw, QueryType::Merge);
+            q.tables.push(merge.table.to_string().into());
+            q.merge = Some(parse_merge(&merge));
+            Ok(q)
+        }
+        Statement::Set(set) => {
+            let mut q = Query::new(raw, QueryType::Set);
+            q.session_settings = parse_set(&set);
+            Ok(q)
+        }
+        Statement::Copy {
+            source,
+            to,
+            target,
+            ..
+        } => {
+            use sqlparser::ast::{CopySource, CopyTarget};
+            let mut q = Query::new(raw, QueryType::Other);
+            if let CopySource::Table {
+                table_name, ..
+            } = &source
+            {
+                q.tables.push(table_name.to_string().into());
+            }
+            q.file_export = match target {
+                CopyTarget::File {
+                    filename
+                } if to => Some(FileExport {
+                    form:   "COPY TO".into(),
+                    target: filename.into()
+                }),
+                CopyTarget::Program {
+                    command
+                } if to => Some(FileExport {
+                    form:   "COPY TO PROGRAM".into(),
+                    target: command.into()
+                }),
+                _ => None
+            };
+            Ok(q)
+        }
         _ => Ok(Query::new(raw, QueryType::Other))
     }
 }
 
+fn parse_grant(grant: &sqlparser::ast::Grant) -> GrantInfo {
+    use sqlparser::ast::{GranteeName, GranteesType, Privileges};
+    let (privileges, all_privileges) = match &grant.privileges {
+        Privileges::All {
+            ..
+        } => (vec!["ALL".into()], true),
+        Privileges::Actions(actions) => (
+            actions.iter().map(|a| a.to_string().into()).collect(),
+            false
+        )
+    };
+    let grantees = grant
+        .grantees
+        .iter()
+        .map(|grantee| match (&grantee.grantee_type, &grantee.name) {
+            (GranteesType::Public, _) => "PUBLIC".into(),
+            (
+                _,
+                Some(GranteeName::UserHost {
+                    user,
+                    host
+                })
+            ) => format!("{}@{}", user.value, host.value).into(),
+            (_, Some(GranteeName::ObjectName(name))) => name
+                .to_string()
+                .trim_matches(|c| matches!(c, '"' | '`' | '\''))
+                .into(),
+            (_, None) => grantee.to_string().trim().into()
+        })
+        .collect();
+    GrantInfo {
+        privileges,
+        all_privileges,
+        object: grant.objects.as_ref().map(|o| o.to_string().into()),
+        grantees,
+        with_grant_option: grant.with_grant_option
+    }
+}
+
+fn parse_set(set: &sqlparser::ast::Set) -> Vec<SessionSetting> {
+    use sqlparser::ast::Set;
+    let assignments: Vec<(_, &sqlparser::ast::ObjectName, String)> = match set {
+        Set::SingleAssignment {
+            scope,
+            variable,
+            values,
+            ..
+        } => vec![(
+            scope.as_ref(),
+            variable,
+            values
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ")
+        )],
+        Set::MultipleAssignments {
+            assignments
+        } => assignments
+            .iter()
+            .map(|a| (a.scope.as_ref(), &a.name, a.value.to_string()))
+            .collect(),
+        Set::ParenthesizedAssignments {
+            variables,
+            values
+        } => variables
+            .iter()
+            .zip(values)
+            .map(|(name, value)| (None, name, value.to_string()))
+            .collect(),
+        _ => Vec::new()
+    };
+    assignments
+        .into_iter()
+        .map(|(modifier, name, value)| {
+            use sqlparser::ast::ContextModifier;
+            let mut scope = modifier.map(|m| {
+                match m {
+                    ContextModifier::Local => "LOCAL",
+                    ContextModifier::Session => "SESSION",
+                    ContextModifier::Global => "GLOBAL"
+                }
+                .into()
+            });
+            let mut name = name.to_string().replace(['"', '`'], "").to_lowercase();
+            if let Some(rest) = name.strip_prefix("@@") {
+                name = match rest.split_once('.') {
+                    Some((prefix, var)) => {
+                        scope = Some(prefix.to_uppercase().into());
+                        var.to_string()
+                    }
+                    None => rest.to_string()
+                };
+            }
+            SessionSetting {
+                name: name.into(),
+                scope,
+                value: value.into()
+            }
+        })
+        .collect()
+}
+
+fn parse_merge(merge: &sqlparser::ast::Merge) -> MergeInfo {
+    let mut on_columns = IndexSet::new();
+    extract_columns_from_expr(&merge.on, &mut on_columns);
+    let clauses = merge
+        .clauses
+        .iter()
+        .map(|clause| MergeWhen {
+            kind:      clause.clause_kind.to_string().into(),
+            predicate: clause.predicate.as_ref().map(|p| p.to_string().into()),
+            action:    match clause.action {
+                sqlparser::ast::MergeAction::Insert(_) => MergeAction::Insert,
+                sqlparser::ast::MergeAction::Update(_) => MergeAction::Update,
+                sqlparser::ast::MergeAction::Delete {
+                    ..
+                } => MergeAction::Delete
+            }
+        })
+        .collect();
+    MergeInfo {
+        source: merge.source.to_string().into(),
+        on_columns: on_columns.into_iter().collect(),
+        clauses
+    }
+}
+
 fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Query> {
     let mut q = Query::new(raw, QueryType::Select);
     for cte in &query
@@ -120,6 +659,10 @@ fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Qu
     {
         q.cte_names.push(cte.alias.name.value.as_str().into());
     }
+    if let Some(with) = &query.with {
+        q.unguarded_recursive_ctes = unguarded_recursive_ctes(with);
+        q.unlimited_ordered_ctes = unlimited_ordered_ctes(with);
+    }
     if let Some(limit_clause) = &query.limit_clause {
         match limit_clause {
             sqlparser::ast::LimitClause::LimitOffset {
@@ -138,6 +681,7 @@ fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Qu
                 {
                     q.offset = n.parse().ok();
                 }
+                q.unbounded_offset = limit.is_none() && offset.is_some() && query.fetch.is_none();
             }
             sqlparser::ast::LimitClause::OffsetCommaLimit {
                 offset,
@@ -157,6 +701,14 @@ fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Qu
             }
         }
     }
+    q.locking = query
+        .locks
+        .iter()
+        .map(|lock| match lock.lock_type {
+            sqlparser::ast::LockType::Update => LockMode::Update,
+            sqlparser::ast::LockType::Share => LockMode::Share
+        })
+        .max_by_key(|mode| *mode == LockMode::Update);
     if let Some(order_by) = &query.order_by
         && let sqlparser::ast::OrderByKind::Expressions(exprs) = &order_by.kind
     {
@@ -165,6 +717,23 @@ fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Qu
             extract_columns_from_expr(&expr.expr, &mut cols);
         }
         q.order_cols = cols.into_iter().collect();
+        q.order_directions = exprs
+            .iter()
+            .map(|expr| {
+                let column: compact_str::CompactString = match &expr.expr {
+                    sqlparser::ast::Expr::Identifier(ident) => ident.value.as_str().into(),
+                    sqlparser::ast::Expr::CompoundIdentifier(idents) => idents
+                        .iter()
+                        .map(|ident| ident.value.as_str())
+                        .collect::<Vec<_>>()
+                        .join(".")
+                        .into(),
+                    _ => return None
+                };
+                Some((column, SortDir::from_asc(expr.options.asc)))
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
     }
     let mut tables = IndexSet::new();
     let mut where_cols = IndexSet::new();
@@ -172,23 +741,74 @@ fn parse_select_query(raw: String, query: sqlparser::ast::Query) -> AppResult<Qu
     let mut group_cols = IndexSet::new();
     let mut having_cols = IndexSet::new();
     let mut window_funcs = Vec::new();
+    let mut like_patterns = Vec::new();
+    let mut table_aliases = Vec::new();
+    let mut column_comparisons = Vec::new();
+    let mut projection_cols = IndexSet::new();
+    let mut projection_aggregates = Vec::new();
     let mut ctx = ExtractionContext {
-        tables:       &mut tables,
-        where_cols:   &mut where_cols,
-        join_cols:    &mut join_cols,
-        group_cols:   &mut group_cols,
-        having_cols:  &mut having_cols,
-        window_funcs: &mut window_funcs,
-        has_union:    &mut q.has_union,
-        has_distinct: &mut q.has_distinct,
-        has_subquery: &mut q.has_subquery
+        tables:                &mut tables,
+        table_aliases:         &mut table_aliases,
+        where_cols:            &mut where_cols,
+        join_cols:             &mut join_cols,
+        group_cols:            &mut group_cols,
+        having_cols:           &mut having_cols,
+        window_funcs:          &mut window_funcs,
+        like_patterns:         &mut like_patterns,
+        column_comparisons:    &mut column_comparisons,
+        projection_cols:       &mut projection_cols,
+        projection_aggregates: &mut projection_aggregates,
+        has_union:             &mut q.has_union,
+        has_distinct:          &mut q.has_distinct,
+        has_subquery:          &mut q.has_subquery
     };
     extract_from_set_expr(&query.body, &mut ctx);
     q.tables = tables.into_iter().collect();
+    q.table_aliases = table_aliases;
     q.where_cols = where_cols.into_iter().collect();
     q.join_cols = join_cols.into_iter().collect();
     q.group_cols = group_cols.into_iter().collect();
     q.having_cols = having_cols.into_iter().collect();
     q.window_funcs = window_funcs;
+    q.like_patterns = like_patterns;
+    q.column_comparisons = column_comparisons;
+    q.projection_cols = projection_cols.into_iter().collect();
+    q.projection_aggregates = projection_aggregates;
+    q.ungrouped_selects = ungrouped_selects(&query.body);
+    q.projection_wildcard = projection_has_wildcard(&query.body);
+    q.where_or_branches = where_or_branches(&query.body);
+    q.where_equality_cols = where_equality_columns(&query.body);
+    q.where_json_access = where_json_access(&query.body);
+    q.where_function_betweens = where_function_betweens(&query.body);
+    q.column_casts = select_column_casts(&query.body);
+    q.where_literal_comparisons = where_literal_comparisons(&query.body);
+    q.where_multi_row_subqueries = parse_scopes(where_multi_row_subqueries(&query.body))?;
+    q.count_columns = projection_count_columns(&query.body);
+    q.projection_calls = projection_function_calls(&query.body);
+    q.derived_tables = derived_subqueries(&query.body);
+    q.column_refs = select_column_refs(&query.body);
+    if let Some(order_by) = &query.order_by
+        && let sqlparser::ast::OrderByKind::Expressions(exprs) = &order_by.kind
+    {
+        for expr in exprs {
+            extract_column_refs(&expr.expr, ColumnClause::OrderBy, &mut q.column_refs);
+        }
+    }
+    q.join_using = join_using_clauses(&query.body);
+    q.has_prewhere = select_has_prewhere(&query.body);
+    q.final_tables = select_final_tables(&query.body);
+    q.distinct_on = select_distinct_on(&query.body);
+    q.has_distinct_union = has_distinct_union(&query.body);
+    q.has_order_by = query.order_by.is_some();
+    q.having_plain_cols = having_plain_columns(&query.body);
+    q.subqueries = parse_scopes(nested_scopes(&query))?;
     Ok(q)
 }
+
+/// Parse each of `scopes` as a query of its own
+fn parse_scopes(scopes: Vec<&sqlparser::ast::Query>) -> AppResult<Vec<Query>> {
+    scopes
+        .into_iter()
+        .map(|scope| parse_select_query(scope.to_string(), scope.clone()))
+        .collect()
+}
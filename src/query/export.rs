@@ -0,0 +1,42 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::types::FileExport;
+
+/// MySQL/ClickHouse `INTO OUTFILE` / `INTO DUMPFILE` with its export options
+static INTO_FILE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    let literal = r"'(?:[^'\\]|\\.|'')*'";
+    Regex::new(&format!(
+        r"(?i)\bINTO\s+(OUTFILE|DUMPFILE)\s+({literal})(?:\s+(?:CHARACTER\s+SET\s+\w+|FIELDS|COLUMNS|LINES|(?:OPTIONALLY\s+)?ENCLOSED\s+BY\s+{literal}|(?:TERMINATED|ESCAPED|STARTING)\s+BY\s+{literal}|AND\s+STDOUT|APPEND|TRUNCATE|COMPRESSION\s+{literal}(?:\s+LEVEL\s+\d+)?))*"
+    ))
+    .expect("valid regex")
+});
+
+/// Blank out `INTO OUTFILE` / `INTO DUMPFILE` clauses, which sqlparser does
+/// not parse.
+///
+/// Each clause is replaced by spaces of the same byte length so statement
+/// spans still point into the original text. Returns the blanked SQL and
+/// every removed clause with its byte offset.
+pub fn strip_file_exports(sql: &str) -> (String, Vec<(usize, FileExport)>) {
+    let mut exports = Vec::new();
+    let mut blanked = String::with_capacity(sql.len());
+    let mut last = 0;
+    for caps in INTO_FILE_REGEX.captures_iter(sql) {
+        let clause = caps.get(0).expect("whole match");
+        let path = &caps[2];
+        exports.push((
+            clause.start(),
+            FileExport {
+                form:   format!("INTO {}", caps[1].to_uppercase()).into(),
+                target: path[1..path.len() - 1].into()
+            }
+        ));
+        blanked.push_str(&sql[last..clause.start()]);
+        blanked.extend(std::iter::repeat_n(' ', clause.len()));
+        last = clause.end();
+    }
+    blanked.push_str(&sql[last..]);
+    (blanked, exports)
+}
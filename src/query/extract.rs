@@ -3,21 +3,36 @@ mod set_expr;
 mod table;
 
 use compact_str::CompactString;
-pub use expr::extract_columns_from_expr;
+pub use expr::{
+    extract_column_refs, extract_columns_from_expr, extract_like_patterns, extract_subqueries,
+    literal_text, written_literal
+};
 use indexmap::IndexSet;
-pub use set_expr::extract_from_set_expr;
+pub use set_expr::{
+    derived_subqueries, extract_from_set_expr, has_distinct_union, having_plain_columns,
+    join_using_clauses, nested_scopes, projection_count_columns, projection_function_calls,
+    projection_has_wildcard, select_column_casts, select_column_refs, select_distinct_on,
+    select_final_tables, select_has_prewhere, ungrouped_selects, unguarded_recursive_ctes,
+    unlimited_ordered_ctes, where_equality_columns, where_function_betweens, where_json_access,
+    where_literal_comparisons, where_multi_row_subqueries, where_or_branches
+};
 
-use super::types::WindowFunction;
+use super::types::{ColumnComparison, WindowFunction};
 
 /// Context for extracting query metadata
 pub struct ExtractionContext<'a> {
-    pub tables:       &'a mut IndexSet<CompactString>,
-    pub where_cols:   &'a mut IndexSet<CompactString>,
-    pub join_cols:    &'a mut IndexSet<CompactString>,
-    pub group_cols:   &'a mut IndexSet<CompactString>,
-    pub having_cols:  &'a mut IndexSet<CompactString>,
-    pub window_funcs: &'a mut Vec<WindowFunction>,
-    pub has_union:    &'a mut bool,
-    pub has_distinct: &'a mut bool,
-    pub has_subquery: &'a mut bool
+    pub tables:                &'a mut IndexSet<CompactString>,
+    pub table_aliases:         &'a mut Vec<(CompactString, Option<CompactString>)>,
+    pub where_cols:            &'a mut IndexSet<CompactString>,
+    pub join_cols:             &'a mut IndexSet<CompactString>,
+    pub group_cols:            &'a mut IndexSet<CompactString>,
+    pub having_cols:           &'a mut IndexSet<CompactString>,
+    pub window_funcs:          &'a mut Vec<WindowFunction>,
+    pub like_patterns:         &'a mut Vec<CompactString>,
+    pub column_comparisons:    &'a mut Vec<ColumnComparison>,
+    pub projection_cols:       &'a mut IndexSet<CompactString>,
+    pub projection_aggregates: &'a mut Vec<CompactString>,
+    pub has_union:             &'a mut bool,
+    pub has_distinct:          &'a mut bool,
+    pub has_subquery:          &'a mut bool
 }
@@ -1,49 +1,80 @@
 use compact_str::CompactString;
 use indexmap::IndexSet;
 
-use crate::query::types::WindowFunction;
+use crate::query::types::{
+    ColumnCast, ColumnClause, ColumnComparison, ColumnRef, CompareOp, FunctionBetween, JsonAccess,
+    LiteralComparison, WindowFunction, WrittenLiteral
+};
 
 pub fn extract_columns_from_expr(
     expr: &sqlparser::ast::Expr,
     columns: &mut IndexSet<CompactString>
+) {
+    visit_column_idents(expr, &mut |idents| {
+        if let Some(col) = idents.last() {
+            columns.insert(col.value.as_str().into());
+        }
+    });
+}
+
+/// Collects column references as written (`u.email`, `email`), each tagged
+/// with the clause it appears in
+pub fn extract_column_refs(
+    expr: &sqlparser::ast::Expr,
+    clause: ColumnClause,
+    refs: &mut Vec<ColumnRef>
+) {
+    visit_column_idents(expr, &mut |idents| {
+        let reference = idents
+            .iter()
+            .map(|ident| ident.value.as_str())
+            .collect::<Vec<_>>()
+            .join(".");
+        refs.push(ColumnRef {
+            reference: reference.into(),
+            clause
+        });
+    });
+}
+
+/// Calls `visit` with the identifier parts of every column reference in
+/// `expr`, skipping `@variables` and subqueries
+fn visit_column_idents<F: FnMut(&[sqlparser::ast::Ident])>(
+    expr: &sqlparser::ast::Expr,
+    visit: &mut F
 ) {
     use sqlparser::ast::Expr;
     match expr {
-        Expr::Identifier(ident) => {
-            columns.insert(ident.value.as_str().into());
-        }
-        Expr::CompoundIdentifier(idents) => {
-            if let Some(col) = idents.last() {
-                columns.insert(col.value.as_str().into());
-            }
-        }
+        Expr::Identifier(ident) if ident.value.starts_with('@') => {}
+        Expr::Identifier(ident) => visit(std::slice::from_ref(ident)),
+        Expr::CompoundIdentifier(idents) => visit(idents),
         Expr::BinaryOp {
             left,
             right,
             ..
         } => {
-            extract_columns_from_expr(left, columns);
-            extract_columns_from_expr(right, columns);
+            visit_column_idents(left, visit);
+            visit_column_idents(right, visit);
         }
         Expr::UnaryOp {
             expr, ..
         } => {
-            extract_columns_from_expr(expr, columns);
+            visit_column_idents(expr, visit);
         }
         Expr::InList {
             expr,
             list,
             ..
         } => {
-            extract_columns_from_expr(expr, columns);
+            visit_column_idents(expr, visit);
             for item in list {
-                extract_columns_from_expr(item, columns);
+                visit_column_idents(item, visit);
             }
         }
         Expr::InSubquery {
             expr, ..
         } => {
-            extract_columns_from_expr(expr, columns);
+            visit_column_idents(expr, visit);
         }
         Expr::Subquery(_)
         | Expr::Exists {
@@ -55,15 +86,15 @@ pub fn extract_columns_from_expr(
             high,
             ..
         } => {
-            extract_columns_from_expr(expr, columns);
-            extract_columns_from_expr(low, columns);
-            extract_columns_from_expr(high, columns);
+            visit_column_idents(expr, visit);
+            visit_column_idents(low, visit);
+            visit_column_idents(high, visit);
         }
         Expr::IsNull(e) | Expr::IsNotNull(e) => {
-            extract_columns_from_expr(e, columns);
+            visit_column_idents(e, visit);
         }
         Expr::Nested(e) => {
-            extract_columns_from_expr(e, columns);
+            visit_column_idents(e, visit);
         }
         Expr::Function(func) => {
             if let sqlparser::ast::FunctionArguments::List(arg_list) = &func.args {
@@ -72,7 +103,7 @@ pub fn extract_columns_from_expr(
                         sqlparser::ast::FunctionArgExpr::Expr(e)
                     ) = arg
                     {
-                        extract_columns_from_expr(e, columns);
+                        visit_column_idents(e, visit);
                     }
                 }
             }
@@ -84,25 +115,824 @@ pub fn extract_columns_from_expr(
             ..
         } => {
             if let Some(op) = operand {
-                extract_columns_from_expr(op, columns);
+                visit_column_idents(op, visit);
             }
             for case_when in conditions {
-                extract_columns_from_expr(&case_when.condition, columns);
-                extract_columns_from_expr(&case_when.result, columns);
+                visit_column_idents(&case_when.condition, visit);
+                visit_column_idents(&case_when.result, visit);
             }
             if let Some(else_res) = else_result {
-                extract_columns_from_expr(else_res, columns);
+                visit_column_idents(else_res, visit);
             }
         }
         Expr::Cast {
             expr, ..
         } => {
-            extract_columns_from_expr(expr, columns);
+            visit_column_idents(expr, visit);
         }
         Expr::Extract {
             expr, ..
         } => {
-            extract_columns_from_expr(expr, columns);
+            visit_column_idents(expr, visit);
+        }
+        _ => {}
+    }
+}
+
+/// Collects string literals used as LIKE/ILIKE/SIMILAR TO patterns.
+pub fn extract_like_patterns(expr: &sqlparser::ast::Expr, patterns: &mut Vec<CompactString>) {
+    use sqlparser::ast::{Expr, Value};
+    match expr {
+        Expr::Like {
+            pattern, ..
+        }
+        | Expr::ILike {
+            pattern, ..
+        }
+        | Expr::SimilarTo {
+            pattern, ..
+        } => {
+            if let Expr::Value(val) = pattern.as_ref()
+                && let Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) = &val.value
+            {
+                patterns.push(s.as_str().into());
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_like_patterns(left, patterns);
+            extract_like_patterns(right, patterns);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        } => extract_like_patterns(expr, patterns),
+        Expr::Nested(e) => extract_like_patterns(e, patterns),
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(op) = operand {
+                extract_like_patterns(op, patterns);
+            }
+            for cw in conditions {
+                extract_like_patterns(&cw.condition, patterns);
+                extract_like_patterns(&cw.result, patterns);
+            }
+            if let Some(e) = else_result {
+                extract_like_patterns(e, patterns);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Columns referenced by each operand of a top-level OR chain.
+///
+/// Parentheses around the chain and around operands are looked through, so
+/// `(a = 1 OR b = 2) OR c = 3` yields three operands. Returns an empty list
+/// when `expr` is not a disjunction.
+pub fn extract_or_branches(expr: &sqlparser::ast::Expr) -> Vec<Vec<CompactString>> {
+    use sqlparser::ast::{BinaryOperator, Expr};
+    fn flatten<'a>(expr: &'a Expr, operands: &mut Vec<&'a Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::Or,
+                right
+            } => {
+                flatten(left, operands);
+                flatten(right, operands);
+            }
+            Expr::Nested(inner) => flatten(inner, operands),
+            other => operands.push(other)
+        }
+    }
+    let mut operands = Vec::new();
+    flatten(expr, &mut operands);
+    if operands.len() < 2 {
+        return Vec::new();
+    }
+    operands
+        .into_iter()
+        .map(|operand| {
+            let mut columns = IndexSet::new();
+            extract_columns_from_expr(operand, &mut columns);
+            columns.into_iter().collect()
+        })
+        .collect()
+}
+
+/// Column reference as written, without identifier quotes
+fn column_ref(expr: &sqlparser::ast::Expr) -> Option<CompactString> {
+    use sqlparser::ast::Expr;
+    match expr {
+        Expr::Identifier(ident) => Some(ident.value.as_str().into()),
+        Expr::CompoundIdentifier(idents) => Some(
+            idents
+                .iter()
+                .map(|i| i.value.as_str())
+                .collect::<Vec<_>>()
+                .join(".")
+                .into()
+        ),
+        Expr::Nested(inner) => column_ref(inner),
+        _ => None
+    }
+}
+
+/// Collects comparisons whose both operands are column references.
+///
+/// Only conjunctions, disjunctions and parentheses are descended into, so
+/// comparisons nested inside function calls or subqueries are ignored.
+pub fn extract_column_comparisons(
+    expr: &sqlparser::ast::Expr,
+    comparisons: &mut Vec<ColumnComparison>
+) {
+    use sqlparser::ast::{BinaryOperator, Expr};
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right
+        } => {
+            extract_column_comparisons(left, comparisons);
+            extract_column_comparisons(right, comparisons);
+        }
+        Expr::BinaryOp {
+            left,
+            op,
+            right
+        } => {
+            let op = match op {
+                BinaryOperator::Eq => CompareOp::Eq,
+                BinaryOperator::NotEq => CompareOp::NotEq,
+                BinaryOperator::Lt => CompareOp::Lt,
+                BinaryOperator::LtEq => CompareOp::LtEq,
+                BinaryOperator::Gt => CompareOp::Gt,
+                BinaryOperator::GtEq => CompareOp::GtEq,
+                _ => return
+            };
+            if let (Some(left), Some(right)) = (column_ref(left), column_ref(right)) {
+                comparisons.push(ColumnComparison {
+                    left,
+                    op,
+                    right
+                });
+            }
+        }
+        Expr::Nested(e) => extract_column_comparisons(e, comparisons),
+        _ => {}
+    }
+}
+
+/// Collects columns compared for equality with a non-column value
+/// (`col = 1`, `? = t.col`) in the top-level AND chain of a filter
+pub fn extract_equality_columns(expr: &sqlparser::ast::Expr, columns: &mut Vec<CompactString>) {
+    use sqlparser::ast::{BinaryOperator, Expr};
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right
+        } => {
+            extract_equality_columns(left, columns);
+            extract_equality_columns(right, columns);
+        }
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right
+        } => match (column_ref(left), column_ref(right)) {
+            (Some(col), None) | (None, Some(col)) => columns.push(col),
+            _ => {}
+        },
+        Expr::Nested(e) => extract_equality_columns(e, columns),
+        _ => {}
+    }
+}
+
+/// Collects JSON and array operators applied to a column in a filter.
+///
+/// A chain such as `data -> 'a' ->> 'b'` is recorded once, as the outermost
+/// operator applied to `data`. Subqueries are not descended into.
+pub fn extract_json_access(expr: &sqlparser::ast::Expr, accesses: &mut Vec<JsonAccess>) {
+    use sqlparser::ast::{BinaryOperator, Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    fn base_column(expr: &Expr) -> Option<CompactString> {
+        match expr {
+            Expr::BinaryOp {
+                left, ..
+            } => base_column(left),
+            other => column_ref(other)
+        }
+    }
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op:
+                op @ (BinaryOperator::Arrow
+                | BinaryOperator::LongArrow
+                | BinaryOperator::HashArrow
+                | BinaryOperator::HashLongArrow
+                | BinaryOperator::AtArrow
+                | BinaryOperator::ArrowAt
+                | BinaryOperator::Question
+                | BinaryOperator::QuestionAnd
+                | BinaryOperator::QuestionPipe
+                | BinaryOperator::AtQuestion),
+            ..
+        } => {
+            if let Some(column) = base_column(left) {
+                accesses.push(JsonAccess {
+                    column,
+                    operator: op.to_string().into(),
+                    expr: expr.to_string().into()
+                });
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_json_access(left, accesses);
+            extract_json_access(right, accesses);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Cast {
+            expr, ..
+        }
+        | Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::InList {
+            expr, ..
+        }
+        | Expr::Between {
+            expr, ..
+        } => extract_json_access(expr, accesses),
+        Expr::Function(func) => {
+            if let FunctionArguments::List(list) = &func.args {
+                for arg in &list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) = arg {
+                        extract_json_access(e, accesses);
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects `BETWEEN` predicates whose tested operand is a function call or
+/// cast applied to a column.
+///
+/// Only conjunctions, disjunctions and parentheses are descended into;
+/// `NOT BETWEEN` is skipped.
+pub fn extract_function_betweens(expr: &sqlparser::ast::Expr, found: &mut Vec<FunctionBetween>) {
+    use sqlparser::ast::{BinaryOperator, Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right
+        } => {
+            extract_function_betweens(left, found);
+            extract_function_betweens(right, found);
+        }
+        Expr::Nested(inner) => extract_function_betweens(inner, found),
+        Expr::Between {
+            expr: operand,
+            negated: false,
+            low,
+            high
+        } => {
+            let wrapped = match operand.as_ref() {
+                Expr::Function(func) => match &func.args {
+                    FunctionArguments::List(list) => list
+                        .args
+                        .iter()
+                        .find_map(|arg| match arg {
+                            FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => column_ref(e),
+                            _ => None
+                        })
+                        .map(|column| (func.name.to_string().to_uppercase(), column)),
+                    _ => None
+                },
+                Expr::Cast {
+                    expr: inner,
+                    data_type,
+                    ..
+                } => column_ref(inner).map(|column| {
+                    (
+                        format!("CAST AS {}", data_type.to_string().to_uppercase()),
+                        column
+                    )
+                }),
+                _ => None
+            };
+            if let Some((function, column)) = wrapped {
+                found.push(FunctionBetween {
+                    function: function.into(),
+                    column,
+                    expr: operand.to_string().into(),
+                    low: low.to_string().into(),
+                    high: high.to_string().into()
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects casts whose operand is a bare column, anywhere in `expr`
+/// outside subqueries
+pub fn extract_column_casts(expr: &sqlparser::ast::Expr, found: &mut Vec<ColumnCast>) {
+    use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    match expr {
+        Expr::Cast {
+            expr: inner,
+            data_type,
+            ..
+        } => match column_ref(inner) {
+            Some(column) => found.push(ColumnCast {
+                column,
+                data_type: data_type.to_string().to_uppercase().into(),
+                expr: expr.to_string().into()
+            }),
+            None => extract_column_casts(inner, found)
+        },
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_column_casts(left, found);
+            extract_column_casts(right, found);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::InSubquery {
+            expr, ..
+        } => extract_column_casts(expr, found),
+        Expr::InList {
+            expr,
+            list,
+            ..
+        } => {
+            extract_column_casts(expr, found);
+            for item in list {
+                extract_column_casts(item, found);
+            }
+        }
+        Expr::Between {
+            expr,
+            low,
+            high,
+            ..
+        } => {
+            extract_column_casts(expr, found);
+            extract_column_casts(low, found);
+            extract_column_casts(high, found);
+        }
+        Expr::Function(func) => {
+            if let FunctionArguments::List(list) = &func.args {
+                for arg in &list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) = arg {
+                        extract_column_casts(e, found);
+                    }
+                }
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(operand) = operand {
+                extract_column_casts(operand, found);
+            }
+            for case_when in conditions {
+                extract_column_casts(&case_when.condition, found);
+                extract_column_casts(&case_when.result, found);
+            }
+            if let Some(else_result) = else_result {
+                extract_column_casts(else_result, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Text of a string or numeric literal, without quotes
+pub fn literal_text(expr: &sqlparser::ast::Expr) -> Option<CompactString> {
+    use sqlparser::ast::{Expr, Value};
+    match expr {
+        Expr::Value(val) => match &val.value {
+            Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) => Some(s.as_str().into()),
+            Value::Number(n, _) => Some(n.as_str().into()),
+            _ => None
+        },
+        Expr::Nested(inner) => literal_text(inner),
+        _ => None
+    }
+}
+
+/// NULL, string, or numeric literal assigned by a write; `None` for any
+/// other expression
+pub fn written_literal(expr: &sqlparser::ast::Expr) -> Option<WrittenLiteral> {
+    use sqlparser::ast::{Expr, UnaryOperator, Value};
+    match expr {
+        Expr::Value(val) => match &val.value {
+            Value::Null => Some(WrittenLiteral::Null),
+            Value::SingleQuotedString(s) | Value::DoubleQuotedString(s) => {
+                Some(WrittenLiteral::String(s.as_str().into()))
+            }
+            Value::Number(n, _) => Some(WrittenLiteral::Number(n.as_str().into())),
+            _ => None
+        },
+        Expr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr
+        } => match written_literal(expr)? {
+            WrittenLiteral::Number(n) => Some(WrittenLiteral::Number(format!("-{}", n).into())),
+            _ => None
+        },
+        Expr::Nested(inner) => written_literal(inner),
+        _ => None
+    }
+}
+
+/// Collects columns tested with `=` or `IN` against literal values.
+///
+/// Only conjunctions, disjunctions and parentheses are descended into;
+/// `NOT IN` and lists holding anything but literals are skipped.
+pub fn extract_literal_comparisons(
+    expr: &sqlparser::ast::Expr,
+    found: &mut Vec<LiteralComparison>
+) {
+    use sqlparser::ast::{BinaryOperator, Expr};
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right
+        } => {
+            extract_literal_comparisons(left, found);
+            extract_literal_comparisons(right, found);
+        }
+        Expr::Nested(inner) => extract_literal_comparisons(inner, found),
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::Eq,
+            right
+        } => {
+            let pair = column_ref(left)
+                .zip(literal_text(right))
+                .or_else(|| column_ref(right).zip(literal_text(left)));
+            if let Some((column, value)) = pair {
+                found.push(LiteralComparison {
+                    column,
+                    values: vec![value]
+                });
+            }
+        }
+        Expr::InList {
+            expr: operand,
+            list,
+            negated: false
+        } => {
+            let values: Option<Vec<_>> = list.iter().map(literal_text).collect();
+            if let Some(column) = column_ref(operand)
+                && let Some(values) = values
+            {
+                found.push(LiteralComparison {
+                    column,
+                    values
+                });
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collects subqueries compared with `=`, `<>`, `<`, `>`, `<=` or `>=`
+/// that may return more than one row.
+///
+/// Conjunctions, disjunctions, `NOT` and parentheses are descended into.
+pub fn extract_multi_row_comparisons<'a>(
+    expr: &'a sqlparser::ast::Expr,
+    found: &mut Vec<&'a sqlparser::ast::Query>
+) {
+    use sqlparser::ast::{BinaryOperator, Expr, UnaryOperator};
+    match expr {
+        Expr::BinaryOp {
+            left,
+            op: BinaryOperator::And | BinaryOperator::Or,
+            right
+        } => {
+            extract_multi_row_comparisons(left, found);
+            extract_multi_row_comparisons(right, found);
+        }
+        Expr::BinaryOp {
+            left,
+            op:
+                BinaryOperator::Eq
+                | BinaryOperator::NotEq
+                | BinaryOperator::Lt
+                | BinaryOperator::LtEq
+                | BinaryOperator::Gt
+                | BinaryOperator::GtEq,
+            right
+        } => {
+            for side in [left, right] {
+                if let Expr::Subquery(subquery) = side.as_ref()
+                    && !returns_single_row(subquery)
+                {
+                    found.push(subquery);
+                }
+            }
+        }
+        Expr::UnaryOp {
+            op: UnaryOperator::Not,
+            expr
+        }
+        | Expr::Nested(expr) => extract_multi_row_comparisons(expr, found),
+        _ => {}
+    }
+}
+
+/// Whether `query` returns at most one row: `LIMIT 1`, `FETCH FIRST 1`,
+/// `TOP 1`, no FROM clause, or only aggregates without GROUP BY
+fn returns_single_row(query: &sqlparser::ast::Query) -> bool {
+    use sqlparser::ast::{
+        Expr, GroupByExpr, LimitClause, SelectItem, SetExpr, TopQuantity, Value
+    };
+    let is_one = |expr: &Expr| matches!(expr, Expr::Value(val) if matches!(&val.value, Value::Number(n, _) if n == "1"));
+    let limited = match &query.limit_clause {
+        Some(LimitClause::LimitOffset {
+            limit: Some(limit), ..
+        }) => is_one(limit),
+        Some(LimitClause::OffsetCommaLimit {
+            limit, ..
+        }) => is_one(limit),
+        _ => false
+    } || query
+        .fetch
+        .as_ref()
+        .and_then(|fetch| fetch.quantity.as_ref())
+        .is_some_and(is_one);
+    if limited {
+        return true;
+    }
+    let SetExpr::Select(select) = query.body.as_ref() else {
+        return false;
+    };
+    let top_one = select
+        .top
+        .as_ref()
+        .and_then(|top| top.quantity.as_ref())
+        .is_some_and(|quantity| match quantity {
+            TopQuantity::Constant(n) => *n == 1,
+            TopQuantity::Expr(expr) => is_one(expr)
+        });
+    if top_one || select.from.is_empty() {
+        return true;
+    }
+    let grouped = match &select.group_by {
+        GroupByExpr::All(_) => true,
+        GroupByExpr::Expressions(exprs, _) => !exprs.is_empty()
+    };
+    let mut aggregates = Vec::new();
+    let mut columns = IndexSet::new();
+    for item in &select.projection {
+        match item {
+            SelectItem::UnnamedExpr(expr)
+            | SelectItem::ExprWithAlias {
+                expr, ..
+            } => extract_projection(expr, &mut aggregates, &mut columns),
+            _ => return false
+        }
+    }
+    !grouped && !aggregates.is_empty() && columns.is_empty()
+}
+
+/// Aggregate functions recognized in SELECT projections.
+const AGGREGATE_FUNCTIONS: [&str; 16] = [
+    "COUNT",
+    "SUM",
+    "AVG",
+    "MIN",
+    "MAX",
+    "GROUP_CONCAT",
+    "STRING_AGG",
+    "ARRAY_AGG",
+    "LISTAGG",
+    "JSON_AGG",
+    "STDDEV",
+    "STDDEV_POP",
+    "STDDEV_SAMP",
+    "VARIANCE",
+    "VAR_POP",
+    "VAR_SAMP"
+];
+
+/// Splits a projection expression into aggregate calls and the column
+/// references found outside of them.
+///
+/// Window functions (`COUNT(*) OVER (...)`) are not aggregates here; their
+/// arguments are scanned like any other function's. Subqueries are skipped.
+pub fn extract_projection(
+    expr: &sqlparser::ast::Expr,
+    aggregates: &mut Vec<CompactString>,
+    columns: &mut IndexSet<CompactString>
+) {
+    use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    match expr {
+        Expr::Identifier(ident) => {
+            columns.insert(ident.value.as_str().into());
+        }
+        Expr::CompoundIdentifier(idents) => {
+            if let Some(col) = idents.last() {
+                columns.insert(col.value.as_str().into());
+            }
+        }
+        Expr::Function(func) => {
+            let name = func.name.to_string().to_uppercase();
+            if func.over.is_none() && AGGREGATE_FUNCTIONS.contains(&name.as_str()) {
+                aggregates.push(name.into());
+                return;
+            }
+            if let FunctionArguments::List(list) = &func.args {
+                for arg in &list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e))
+                    | FunctionArg::Named {
+                        arg: FunctionArgExpr::Expr(e),
+                        ..
+                    } = arg
+                    {
+                        extract_projection(e, aggregates, columns);
+                    }
+                }
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_projection(left, aggregates, columns);
+            extract_projection(right, aggregates, columns);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Cast {
+            expr, ..
+        }
+        | Expr::Nested(expr) => extract_projection(expr, aggregates, columns),
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(op) = operand {
+                extract_projection(op, aggregates, columns);
+            }
+            for cw in conditions {
+                extract_projection(&cw.condition, aggregates, columns);
+                extract_projection(&cw.result, aggregates, columns);
+            }
+            if let Some(e) = else_result {
+                extract_projection(e, aggregates, columns);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect the argument of every `COUNT(column)` call (as written), skipping
+/// `COUNT(*)`, `COUNT(DISTINCT ...)`, and non-column arguments
+pub fn extract_count_columns(expr: &sqlparser::ast::Expr, columns: &mut Vec<CompactString>) {
+    use sqlparser::ast::{
+        DuplicateTreatment, Expr, FunctionArg, FunctionArgExpr, FunctionArguments
+    };
+    match expr {
+        Expr::Function(func) => {
+            let FunctionArguments::List(list) = &func.args else {
+                return;
+            };
+            if func.name.to_string().eq_ignore_ascii_case("COUNT") {
+                if list.duplicate_treatment != Some(DuplicateTreatment::Distinct)
+                    && let [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] =
+                        list.args.as_slice()
+                    && matches!(arg, Expr::Identifier(_) | Expr::CompoundIdentifier(_))
+                {
+                    columns.push(arg.to_string().into());
+                }
+                return;
+            }
+            for arg in &list.args {
+                if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e))
+                | FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(e),
+                    ..
+                } = arg
+                {
+                    extract_count_columns(e, columns);
+                }
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_count_columns(left, columns);
+            extract_count_columns(right, columns);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Cast {
+            expr, ..
+        }
+        | Expr::Nested(expr) => extract_count_columns(expr, columns),
+        _ => {}
+    }
+}
+
+/// Collect every non-aggregate, non-window function call that takes
+/// arguments (as written), including calls nested inside other calls.
+/// Subqueries are skipped.
+pub fn extract_function_calls(expr: &sqlparser::ast::Expr, calls: &mut Vec<CompactString>) {
+    use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    match expr {
+        Expr::Function(func) => {
+            let FunctionArguments::List(list) = &func.args else {
+                return;
+            };
+            let name = func.name.to_string().to_uppercase();
+            if func.over.is_none()
+                && !list.args.is_empty()
+                && !AGGREGATE_FUNCTIONS.contains(&name.as_str())
+            {
+                calls.push(func.to_string().into());
+            }
+            for arg in &list.args {
+                if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e))
+                | FunctionArg::Named {
+                    arg: FunctionArgExpr::Expr(e),
+                    ..
+                } = arg
+                {
+                    extract_function_calls(e, calls);
+                }
+            }
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_function_calls(left, calls);
+            extract_function_calls(right, calls);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Cast {
+            expr, ..
+        }
+        | Expr::Nested(expr) => extract_function_calls(expr, calls),
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(op) = operand {
+                extract_function_calls(op, calls);
+            }
+            for cw in conditions {
+                extract_function_calls(&cw.condition, calls);
+                extract_function_calls(&cw.result, calls);
+            }
+            if let Some(e) = else_result {
+                extract_function_calls(e, calls);
+            }
         }
         _ => {}
     }
@@ -123,6 +953,10 @@ pub fn extract_window_functions(expr: &sqlparser::ast::Expr, windows: &mut Vec<W
                             && let Some(col) = idents.last()
                         {
                             partition_cols.push(col.value.as_str().into());
+                        } else {
+                            let mut columns = IndexSet::new();
+                            extract_columns_from_expr(part_expr, &mut columns);
+                            partition_cols.extend(columns);
                         }
                     }
                     for order_expr in &spec.order_by {
@@ -182,6 +1016,124 @@ pub fn extract_window_functions(expr: &sqlparser::ast::Expr, windows: &mut Vec<W
     }
 }
 
+/// Collects the subqueries of `expr` (`IN (SELECT ...)`, `EXISTS`, scalar
+/// subqueries); subqueries nested inside them are left to their own scope
+pub fn extract_subqueries<'a>(
+    expr: &'a sqlparser::ast::Expr,
+    found: &mut Vec<&'a sqlparser::ast::Query>
+) {
+    use sqlparser::ast::{Expr, FunctionArg, FunctionArgExpr, FunctionArguments};
+    match expr {
+        Expr::Subquery(query)
+        | Expr::Exists {
+            subquery: query, ..
+        } => found.push(query),
+        Expr::InSubquery {
+            expr,
+            subquery,
+            ..
+        } => {
+            extract_subqueries(expr, found);
+            found.push(subquery);
+        }
+        Expr::BinaryOp {
+            left,
+            right,
+            ..
+        }
+        | Expr::AnyOp {
+            left,
+            right,
+            ..
+        }
+        | Expr::AllOp {
+            left,
+            right,
+            ..
+        } => {
+            extract_subqueries(left, found);
+            extract_subqueries(right, found);
+        }
+        Expr::UnaryOp {
+            expr, ..
+        }
+        | Expr::Nested(expr)
+        | Expr::IsNull(expr)
+        | Expr::IsNotNull(expr)
+        | Expr::Cast {
+            expr, ..
+        }
+        | Expr::Extract {
+            expr, ..
+        } => extract_subqueries(expr, found),
+        Expr::InList {
+            expr,
+            list,
+            ..
+        } => {
+            extract_subqueries(expr, found);
+            for item in list {
+                extract_subqueries(item, found);
+            }
+        }
+        Expr::Between {
+            expr,
+            low,
+            high,
+            ..
+        } => {
+            extract_subqueries(expr, found);
+            extract_subqueries(low, found);
+            extract_subqueries(high, found);
+        }
+        Expr::Like {
+            expr,
+            pattern,
+            ..
+        }
+        | Expr::ILike {
+            expr,
+            pattern,
+            ..
+        } => {
+            extract_subqueries(expr, found);
+            extract_subqueries(pattern, found);
+        }
+        Expr::Function(func) => {
+            if let FunctionArguments::List(arg_list) = &func.args {
+                for arg in &arg_list.args {
+                    if let FunctionArg::Unnamed(FunctionArgExpr::Expr(e))
+                    | FunctionArg::Named {
+                        arg: FunctionArgExpr::Expr(e),
+                        ..
+                    } = arg
+                    {
+                        extract_subqueries(e, found);
+                    }
+                }
+            }
+        }
+        Expr::Case {
+            operand,
+            conditions,
+            else_result,
+            ..
+        } => {
+            if let Some(op) = operand {
+                extract_subqueries(op, found);
+            }
+            for case_when in conditions {
+                extract_subqueries(&case_when.condition, found);
+                extract_subqueries(&case_when.result, found);
+            }
+            if let Some(e) = else_result {
+                extract_subqueries(e, found);
+            }
+        }
+        _ => {}
+    }
+}
+
 pub fn contains_subquery(expr: &sqlparser::ast::Expr) -> bool {
     use sqlparser::ast::Expr;
     match expr {
@@ -1,10 +1,21 @@
+use compact_str::CompactString;
 use indexmap::IndexSet;
 
 use super::{
     ExtractionContext,
-    expr::{contains_subquery, extract_columns_from_expr, extract_window_functions},
+    expr::{
+        contains_subquery, extract_column_casts, extract_column_comparisons, extract_column_refs,
+        extract_columns_from_expr, extract_count_columns, extract_equality_columns,
+        extract_function_betweens, extract_function_calls, extract_json_access,
+        extract_like_patterns, extract_literal_comparisons, extract_multi_row_comparisons,
+        extract_or_branches, extract_projection, extract_subqueries, extract_window_functions
+    },
     table::extract_from_table_factor
 };
+use crate::query::{
+    ColumnCast, ColumnClause, ColumnRef, FunctionBetween, JsonAccess, LiteralComparison,
+    UngroupedSelect, UsingJoin
+};
 
 pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut ExtractionContext<'_>) {
     use sqlparser::ast::SetExpr;
@@ -18,15 +29,16 @@ pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut Extra
                 } = item
                 {
                     extract_window_functions(expr, ctx.window_funcs);
+                    extract_projection(expr, ctx.projection_aggregates, ctx.projection_cols);
                     if contains_subquery(expr) {
                         *ctx.has_subquery = true;
                     }
                 }
             }
             for table in &select.from {
-                extract_from_table_factor(&table.relation, ctx.tables);
+                extract_from_table_factor(&table.relation, ctx.tables, ctx.table_aliases);
                 for join in &table.joins {
-                    extract_from_table_factor(&join.relation, ctx.tables);
+                    extract_from_table_factor(&join.relation, ctx.tables, ctx.table_aliases);
                     match &join.join_operator {
                         sqlparser::ast::JoinOperator::Join(constraint)
                         | sqlparser::ast::JoinOperator::Inner(constraint)
@@ -37,6 +49,7 @@ pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut Extra
                         | sqlparser::ast::JoinOperator::FullOuter(constraint) => {
                             if let sqlparser::ast::JoinConstraint::On(expr) = constraint {
                                 extract_columns_from_expr(expr, ctx.join_cols);
+                                extract_column_comparisons(expr, ctx.column_comparisons);
                             }
                         }
                         _ => {}
@@ -45,6 +58,8 @@ pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut Extra
             }
             if let Some(selection) = &select.selection {
                 extract_columns_from_expr(selection, ctx.where_cols);
+                extract_like_patterns(selection, ctx.like_patterns);
+                extract_column_comparisons(selection, ctx.column_comparisons);
                 if contains_subquery(selection) {
                     *ctx.has_subquery = true;
                 }
@@ -56,6 +71,7 @@ pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut Extra
             }
             if let Some(having) = &select.having {
                 extract_columns_from_expr(having, ctx.having_cols);
+                extract_like_patterns(having, ctx.like_patterns);
             }
         }
         SetExpr::SetOperation {
@@ -86,3 +102,726 @@ pub fn extract_from_set_expr(set_expr: &sqlparser::ast::SetExpr, ctx: &mut Extra
         | SetExpr::Merge(_) => {}
     }
 }
+
+/// Returns true when the outermost SELECT list contains `*` or `t.*`; for
+/// set operations, when either side does.
+pub fn projection_has_wildcard(set_expr: &sqlparser::ast::SetExpr) -> bool {
+    use sqlparser::ast::{SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => select.projection.iter().any(|item| {
+            matches!(
+                item,
+                SelectItem::Wildcard(_) | SelectItem::QualifiedWildcard(..)
+            )
+        }),
+        SetExpr::Query(query) => projection_has_wildcard(&query.body),
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => projection_has_wildcard(left) || projection_has_wildcard(right),
+        _ => false
+    }
+}
+
+/// Columns of each operand when the outermost WHERE is an OR chain
+pub fn where_or_branches(set_expr: &sqlparser::ast::SetExpr) -> Vec<Vec<CompactString>> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => select
+            .selection
+            .as_ref()
+            .map(extract_or_branches)
+            .unwrap_or_default(),
+        SetExpr::Query(query) => where_or_branches(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Columns the outermost WHERE pins to a value with `=` in its top-level
+/// AND chain
+pub fn where_equality_columns(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut columns = Vec::new();
+            if let Some(selection) = &select.selection {
+                extract_equality_columns(selection, &mut columns);
+            }
+            columns
+        }
+        SetExpr::Query(query) => where_equality_columns(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// JSON and array operators applied in the outermost WHERE clause
+pub fn where_json_access(set_expr: &sqlparser::ast::SetExpr) -> Vec<JsonAccess> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut accesses = Vec::new();
+            if let Some(selection) = &select.selection {
+                extract_json_access(selection, &mut accesses);
+            }
+            accesses
+        }
+        SetExpr::Query(query) => where_json_access(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// BETWEEN predicates on wrapped columns in the outermost WHERE clause
+pub fn where_function_betweens(set_expr: &sqlparser::ast::SetExpr) -> Vec<FunctionBetween> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut found = Vec::new();
+            if let Some(selection) = &select.selection {
+                extract_function_betweens(selection, &mut found);
+            }
+            found
+        }
+        SetExpr::Query(query) => where_function_betweens(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Columns compared to literals with `=` or `IN` in the outermost WHERE
+pub fn where_literal_comparisons(set_expr: &sqlparser::ast::SetExpr) -> Vec<LiteralComparison> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut found = Vec::new();
+            if let Some(selection) = &select.selection {
+                extract_literal_comparisons(selection, &mut found);
+            }
+            found
+        }
+        SetExpr::Query(query) => where_literal_comparisons(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Subqueries the outermost WHERE compares with a scalar operator although
+/// they may return several rows
+pub fn where_multi_row_subqueries(
+    set_expr: &sqlparser::ast::SetExpr
+) -> Vec<&sqlparser::ast::Query> {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut found = Vec::new();
+            if let Some(selection) = &select.selection {
+                extract_multi_row_comparisons(selection, &mut found);
+            }
+            found
+        }
+        SetExpr::Query(query) => where_multi_row_subqueries(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// `JOIN ... USING` clauses of the outermost SELECT, with the base tables
+/// each one joins against. Joins of derived tables are skipped.
+pub fn join_using_clauses(set_expr: &sqlparser::ast::SetExpr) -> Vec<UsingJoin> {
+    use sqlparser::ast::{JoinConstraint, JoinOperator, SetExpr, TableFactor};
+    fn table_name(factor: &TableFactor) -> Option<CompactString> {
+        match factor {
+            TableFactor::Table {
+                name, ..
+            } => Some(name.to_string().into()),
+            _ => None
+        }
+    }
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut clauses = Vec::new();
+            for from in &select.from {
+                let mut left: Vec<CompactString> =
+                    table_name(&from.relation).into_iter().collect();
+                for join in &from.joins {
+                    let right = table_name(&join.relation);
+                    if let JoinOperator::Join(JoinConstraint::Using(columns))
+                    | JoinOperator::Inner(JoinConstraint::Using(columns))
+                    | JoinOperator::Left(JoinConstraint::Using(columns))
+                    | JoinOperator::LeftOuter(JoinConstraint::Using(columns))
+                    | JoinOperator::Right(JoinConstraint::Using(columns))
+                    | JoinOperator::RightOuter(JoinConstraint::Using(columns))
+                    | JoinOperator::FullOuter(JoinConstraint::Using(columns)) =
+                        &join.join_operator
+                        && let Some(right) = &right
+                    {
+                        clauses.push(UsingJoin {
+                            left:    left.clone(),
+                            right:   right.clone(),
+                            columns: columns.iter().map(|c| c.to_string().into()).collect()
+                        });
+                    }
+                    left.extend(right);
+                }
+            }
+            clauses
+        }
+        SetExpr::Query(query) => join_using_clauses(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Column references of the outermost SELECT (both sides of a set
+/// operation), tagged with their clause
+pub fn select_column_refs(set_expr: &sqlparser::ast::SetExpr) -> Vec<ColumnRef> {
+    use sqlparser::ast::{GroupByExpr, JoinConstraint, JoinOperator, SelectItem, SetExpr};
+    let mut refs = Vec::new();
+    match set_expr {
+        SetExpr::Select(select) => {
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_column_refs(expr, ColumnClause::Select, &mut refs);
+                }
+            }
+            for join in select.from.iter().flat_map(|from| &from.joins) {
+                let (JoinOperator::Join(constraint)
+                | JoinOperator::Inner(constraint)
+                | JoinOperator::Left(constraint)
+                | JoinOperator::LeftOuter(constraint)
+                | JoinOperator::Right(constraint)
+                | JoinOperator::RightOuter(constraint)
+                | JoinOperator::FullOuter(constraint)) = &join.join_operator
+                else {
+                    continue;
+                };
+                match constraint {
+                    JoinConstraint::On(expr) => {
+                        extract_column_refs(expr, ColumnClause::Join, &mut refs);
+                    }
+                    JoinConstraint::Using(columns) => {
+                        refs.extend(columns.iter().map(|column| ColumnRef {
+                            reference: column.to_string().into(),
+                            clause:    ColumnClause::Join
+                        }));
+                    }
+                    _ => {}
+                }
+            }
+            if let Some(selection) = &select.selection {
+                extract_column_refs(selection, ColumnClause::Where, &mut refs);
+            }
+            if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+                for expr in exprs {
+                    extract_column_refs(expr, ColumnClause::GroupBy, &mut refs);
+                }
+            }
+            if let Some(having) = &select.having {
+                extract_column_refs(having, ColumnClause::Having, &mut refs);
+            }
+        }
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => {
+            refs.extend(select_column_refs(left));
+            refs.extend(select_column_refs(right));
+        }
+        SetExpr::Query(query) => refs.extend(select_column_refs(&query.body)),
+        _ => {}
+    }
+    refs
+}
+
+/// Casts of bare columns in the outermost SELECT (both sides of a set
+/// operation): its list, JOIN conditions, WHERE, GROUP BY and HAVING
+pub fn select_column_casts(set_expr: &sqlparser::ast::SetExpr) -> Vec<ColumnCast> {
+    use sqlparser::ast::{GroupByExpr, JoinConstraint, JoinOperator, SelectItem, SetExpr};
+    let mut casts = Vec::new();
+    match set_expr {
+        SetExpr::Select(select) => {
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_column_casts(expr, &mut casts);
+                }
+            }
+            for join in select.from.iter().flat_map(|from| &from.joins) {
+                if let JoinOperator::Join(JoinConstraint::On(expr))
+                | JoinOperator::Inner(JoinConstraint::On(expr))
+                | JoinOperator::Left(JoinConstraint::On(expr))
+                | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                | JoinOperator::Right(JoinConstraint::On(expr))
+                | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                | JoinOperator::FullOuter(JoinConstraint::On(expr)) = &join.join_operator
+                {
+                    extract_column_casts(expr, &mut casts);
+                }
+            }
+            if let Some(selection) = &select.selection {
+                extract_column_casts(selection, &mut casts);
+            }
+            if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+                for expr in exprs {
+                    extract_column_casts(expr, &mut casts);
+                }
+            }
+            if let Some(having) = &select.having {
+                extract_column_casts(having, &mut casts);
+            }
+        }
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => {
+            casts.extend(select_column_casts(left));
+            casts.extend(select_column_casts(right));
+        }
+        SetExpr::Query(query) => casts.extend(select_column_casts(&query.body)),
+        _ => {}
+    }
+    casts
+}
+
+/// Arguments of `COUNT(column)` calls in the outermost SELECT list
+pub fn projection_count_columns(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::{SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut columns = Vec::new();
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_count_columns(expr, &mut columns);
+                }
+            }
+            columns
+        }
+        SetExpr::Query(query) => projection_count_columns(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Non-aggregate function calls with arguments in the outermost SELECT list,
+/// nested calls included
+pub fn projection_function_calls(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::{SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => {
+            let mut calls = Vec::new();
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_function_calls(expr, &mut calls);
+                }
+            }
+            calls
+        }
+        SetExpr::Query(query) => projection_function_calls(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Text of every derived table in the FROM and JOIN clauses, nested ones
+/// included, in order of appearance; CTE bodies are not visited
+pub fn derived_subqueries(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    let mut found = Vec::new();
+    collect_derived(set_expr, &mut found);
+    found
+}
+
+fn collect_derived(set_expr: &sqlparser::ast::SetExpr, found: &mut Vec<CompactString>) {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => {
+            for table in &select.from {
+                collect_derived_factor(&table.relation, found);
+                for join in &table.joins {
+                    collect_derived_factor(&join.relation, found);
+                }
+            }
+        }
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => {
+            collect_derived(left, found);
+            collect_derived(right, found);
+        }
+        SetExpr::Query(query) => collect_derived(&query.body, found),
+        _ => {}
+    }
+}
+
+fn collect_derived_factor(
+    table_factor: &sqlparser::ast::TableFactor,
+    found: &mut Vec<CompactString>
+) {
+    use sqlparser::ast::TableFactor;
+    match table_factor {
+        TableFactor::Derived {
+            subquery, ..
+        } => {
+            found.push(subquery.to_string().into());
+            collect_derived(&subquery.body, found);
+        }
+        TableFactor::NestedJoin {
+            table_with_joins, ..
+        } => {
+            collect_derived_factor(&table_with_joins.relation, found);
+            for join in &table_with_joins.joins {
+                collect_derived_factor(&join.relation, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Subqueries, derived tables, and CTE bodies directly inside `query`, each
+/// a scope of its own; queries nested deeper are reached through them
+pub fn nested_scopes(query: &sqlparser::ast::Query) -> Vec<&sqlparser::ast::Query> {
+    let mut found: Vec<&sqlparser::ast::Query> = query
+        .with
+        .iter()
+        .flat_map(|with| &with.cte_tables)
+        .map(|cte| cte.query.as_ref())
+        .collect();
+    collect_scopes(&query.body, &mut found);
+    if let Some(order_by) = &query.order_by
+        && let sqlparser::ast::OrderByKind::Expressions(exprs) = &order_by.kind
+    {
+        for expr in exprs {
+            extract_subqueries(&expr.expr, &mut found);
+        }
+    }
+    found
+}
+
+fn collect_scopes<'a>(
+    set_expr: &'a sqlparser::ast::SetExpr,
+    found: &mut Vec<&'a sqlparser::ast::Query>
+) {
+    use sqlparser::ast::{GroupByExpr, JoinConstraint, JoinOperator, SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => {
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_subqueries(expr, found);
+                }
+            }
+            for table in &select.from {
+                collect_factor_scopes(&table.relation, found);
+                for join in &table.joins {
+                    collect_factor_scopes(&join.relation, found);
+                    if let JoinOperator::Join(JoinConstraint::On(expr))
+                    | JoinOperator::Inner(JoinConstraint::On(expr))
+                    | JoinOperator::Left(JoinConstraint::On(expr))
+                    | JoinOperator::LeftOuter(JoinConstraint::On(expr))
+                    | JoinOperator::Right(JoinConstraint::On(expr))
+                    | JoinOperator::RightOuter(JoinConstraint::On(expr))
+                    | JoinOperator::FullOuter(JoinConstraint::On(expr)) = &join.join_operator
+                    {
+                        extract_subqueries(expr, found);
+                    }
+                }
+            }
+            if let Some(selection) = &select.selection {
+                extract_subqueries(selection, found);
+            }
+            if let GroupByExpr::Expressions(exprs, _) = &select.group_by {
+                for expr in exprs {
+                    extract_subqueries(expr, found);
+                }
+            }
+            if let Some(having) = &select.having {
+                extract_subqueries(having, found);
+            }
+        }
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => {
+            collect_scopes(left, found);
+            collect_scopes(right, found);
+        }
+        SetExpr::Query(query) => found.push(query),
+        _ => {}
+    }
+}
+
+fn collect_factor_scopes<'a>(
+    table_factor: &'a sqlparser::ast::TableFactor,
+    found: &mut Vec<&'a sqlparser::ast::Query>
+) {
+    use sqlparser::ast::TableFactor;
+    match table_factor {
+        TableFactor::Derived {
+            subquery, ..
+        } => found.push(subquery),
+        TableFactor::NestedJoin {
+            table_with_joins, ..
+        } => {
+            collect_factor_scopes(&table_with_joins.relation, found);
+            for join in &table_with_joins.joins {
+                collect_factor_scopes(&join.relation, found);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Names of the recursive CTEs whose recursive term references the CTE
+/// itself with neither a WHERE clause, a join condition, nor a LIMIT to stop
+/// the recursion
+pub fn unguarded_recursive_ctes(with: &sqlparser::ast::With) -> Vec<CompactString> {
+    use sqlparser::ast::SetExpr;
+    if !with.recursive {
+        return Vec::new();
+    }
+    with.cte_tables
+        .iter()
+        .filter(|cte| {
+            let SetExpr::SetOperation {
+                right, ..
+            } = cte.query.body.as_ref()
+            else {
+                return false;
+            };
+            cte.query.limit_clause.is_none()
+                && recursive_term_unguarded(right, &cte.alias.name.value)
+        })
+        .map(|cte| cte.alias.name.value.as_str().into())
+        .collect()
+}
+
+fn recursive_term_unguarded(set_expr: &sqlparser::ast::SetExpr, cte: &str) -> bool {
+    use sqlparser::ast::{SetExpr, TableFactor};
+    match set_expr {
+        SetExpr::Select(select) => {
+            select.selection.is_none()
+                && !select
+                    .from
+                    .iter()
+                    .flat_map(|t| &t.joins)
+                    .any(|join| join_has_condition(&join.join_operator))
+                && select
+                    .from
+                    .iter()
+                    .flat_map(|t| {
+                        std::iter::once(&t.relation).chain(t.joins.iter().map(|j| &j.relation))
+                    })
+                    .any(|relation| {
+                        matches!(relation, TableFactor::Table { name, .. }
+                            if name.to_string().eq_ignore_ascii_case(cte))
+                    })
+        }
+        SetExpr::Query(query) => {
+            query.limit_clause.is_none() && recursive_term_unguarded(&query.body, cte)
+        }
+        _ => false
+    }
+}
+
+/// Returns true when a join carries an ON or USING condition, which in a
+/// recursive term walks the CTE along existing rows of the joined table
+fn join_has_condition(operator: &sqlparser::ast::JoinOperator) -> bool {
+    use sqlparser::ast::{JoinConstraint, JoinOperator};
+    matches!(
+        operator,
+        JoinOperator::Join(constraint)
+            | JoinOperator::Inner(constraint)
+            | JoinOperator::Left(constraint)
+            | JoinOperator::LeftOuter(constraint)
+            | JoinOperator::Right(constraint)
+            | JoinOperator::RightOuter(constraint)
+            | JoinOperator::FullOuter(constraint)
+            if matches!(constraint, JoinConstraint::On(_) | JoinConstraint::Using(_))
+    )
+}
+
+/// CTEs of a non-recursive WITH whose body is sorted with ORDER BY but not
+/// cut with LIMIT or FETCH
+pub fn unlimited_ordered_ctes(with: &sqlparser::ast::With) -> Vec<CompactString> {
+    if with.recursive {
+        return Vec::new();
+    }
+    with.cte_tables
+        .iter()
+        .filter(|cte| {
+            cte.query.order_by.is_some()
+                && cte.query.limit_clause.is_none()
+                && cte.query.fetch.is_none()
+        })
+        .map(|cte| cte.alias.name.value.as_str().into())
+        .collect()
+}
+
+/// Expressions of the outermost SELECT's `DISTINCT ON (...)` list
+pub fn select_distinct_on(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::{Distinct, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => match &select.distinct {
+            Some(Distinct::On(exprs)) => exprs.iter().map(|e| e.to_string().into()).collect(),
+            _ => Vec::new()
+        },
+        SetExpr::Query(query) => select_distinct_on(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Base tables of the outermost SELECT followed by the ClickHouse `FINAL`
+/// modifier
+///
+/// The parser takes a trailing `FINAL` for an implicit table alias, so these
+/// are the tables with an unquoted alias of that name and no `AS`; `FROM
+/// events AS final` is an ordinary alias.
+pub fn select_final_tables(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::{SetExpr, TableFactor};
+    match set_expr {
+        SetExpr::Select(select) => select
+            .from
+            .iter()
+            .flat_map(|t| std::iter::once(&t.relation).chain(t.joins.iter().map(|j| &j.relation)))
+            .filter_map(|relation| match relation {
+                TableFactor::Table {
+                    name,
+                    alias: Some(alias),
+                    ..
+                } if !alias.explicit
+                    && alias.name.quote_style.is_none()
+                    && alias.name.value.eq_ignore_ascii_case("FINAL") =>
+                {
+                    Some(name.to_string().into())
+                }
+                _ => None
+            })
+            .collect(),
+        SetExpr::Query(query) => select_final_tables(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Returns true when the outermost SELECT has a ClickHouse PREWHERE clause
+pub fn select_has_prewhere(set_expr: &sqlparser::ast::SetExpr) -> bool {
+    use sqlparser::ast::SetExpr;
+    match set_expr {
+        SetExpr::Select(select) => select.prewhere.is_some(),
+        SetExpr::Query(query) => select_has_prewhere(&query.body),
+        _ => false
+    }
+}
+
+/// Returns true when a set operation of the statement is a UNION without
+/// `ALL`, which deduplicates the combined rows
+pub fn has_distinct_union(set_expr: &sqlparser::ast::SetExpr) -> bool {
+    use sqlparser::ast::{SetExpr, SetOperator, SetQuantifier};
+    match set_expr {
+        SetExpr::SetOperation {
+            op,
+            set_quantifier,
+            left,
+            right
+        } => {
+            (*op == SetOperator::Union
+                && !matches!(
+                    set_quantifier,
+                    SetQuantifier::All | SetQuantifier::AllByName
+                ))
+                || has_distinct_union(left)
+                || has_distinct_union(right)
+        }
+        SetExpr::Query(query) => has_distinct_union(&query.body),
+        _ => false
+    }
+}
+
+/// SELECT lists of the outermost SELECTs (each side of a set operation)
+/// that have no GROUP BY
+pub fn ungrouped_selects(set_expr: &sqlparser::ast::SetExpr) -> Vec<UngroupedSelect> {
+    use sqlparser::ast::{GroupByExpr, SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => {
+            let grouped = match &select.group_by {
+                GroupByExpr::All(_) => true,
+                GroupByExpr::Expressions(exprs, _) => !exprs.is_empty()
+            };
+            if grouped {
+                return Vec::new();
+            }
+            let mut aggregates = Vec::new();
+            let mut columns = IndexSet::new();
+            for item in &select.projection {
+                if let SelectItem::UnnamedExpr(expr)
+                | SelectItem::ExprWithAlias {
+                    expr, ..
+                } = item
+                {
+                    extract_projection(expr, &mut aggregates, &mut columns);
+                }
+            }
+            vec![UngroupedSelect {
+                aggregates,
+                columns: columns.into_iter().collect()
+            }]
+        }
+        SetExpr::SetOperation {
+            left,
+            right,
+            ..
+        } => {
+            let mut selects = ungrouped_selects(left);
+            selects.extend(ungrouped_selects(right));
+            selects
+        }
+        SetExpr::Query(query) => ungrouped_selects(&query.body),
+        _ => Vec::new()
+    }
+}
+
+/// Columns the outermost HAVING references outside aggregate calls, minus
+/// SELECT-list aliases (which HAVING may name on MySQL and SQLite)
+pub fn having_plain_columns(set_expr: &sqlparser::ast::SetExpr) -> Vec<CompactString> {
+    use sqlparser::ast::{SelectItem, SetExpr};
+    match set_expr {
+        SetExpr::Select(select) => {
+            let Some(having) = &select.having else {
+                return Vec::new();
+            };
+            let mut columns = IndexSet::new();
+            extract_projection(having, &mut Vec::new(), &mut columns);
+            let aliases: Vec<&str> = select
+                .projection
+                .iter()
+                .filter_map(|item| match item {
+                    SelectItem::ExprWithAlias {
+                        alias, ..
+                    } => Some(alias.value.as_str()),
+                    _ => None
+                })
+                .collect();
+            columns
+                .into_iter()
+                .filter(|col| !aliases.iter().any(|a| a.eq_ignore_ascii_case(col)))
+                .collect()
+        }
+        SetExpr::Query(query) => having_plain_columns(&query.body),
+        _ => Vec::new()
+    }
+}
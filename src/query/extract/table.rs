@@ -3,16 +3,26 @@ use indexmap::IndexSet;
 
 use super::{ExtractionContext, set_expr::extract_from_set_expr};
 
+/// Records the tables read by a FROM item.
+///
+/// Base tables are also pushed to `aliases` together with their alias, if
+/// any. Tables read inside a derived subquery are added to `tables` only:
+/// their aliases belong to the subquery's own scope.
 pub fn extract_from_table_factor(
     table_factor: &sqlparser::ast::TableFactor,
-    tables: &mut IndexSet<CompactString>
+    tables: &mut IndexSet<CompactString>,
+    aliases: &mut Vec<(CompactString, Option<CompactString>)>
 ) {
     use sqlparser::ast::TableFactor;
     match table_factor {
         TableFactor::Table {
-            name, ..
+            name,
+            alias,
+            ..
         } => {
-            tables.insert(name.to_string().into());
+            let name: CompactString = name.to_string().into();
+            tables.insert(name.clone());
+            aliases.push((name, alias.as_ref().map(|a| a.name.value.as_str().into())));
         }
         TableFactor::Derived {
             subquery,
@@ -27,16 +37,26 @@ pub fn extract_from_table_factor(
             let mut sub_group = IndexSet::new();
             let mut sub_having = IndexSet::new();
             let mut sub_windows = Vec::new();
+            let mut sub_like_patterns = Vec::new();
+            let mut sub_aliases = Vec::new();
+            let mut sub_comparisons = Vec::new();
+            let mut sub_projection_cols = IndexSet::new();
+            let mut sub_projection_aggregates = Vec::new();
             let mut has_union = false;
             let mut has_distinct = false;
             let mut has_subquery = false;
             let mut ctx = ExtractionContext {
                 tables,
+                table_aliases: &mut sub_aliases,
                 where_cols: &mut sub_where,
                 join_cols: &mut sub_join,
                 group_cols: &mut sub_group,
                 having_cols: &mut sub_having,
                 window_funcs: &mut sub_windows,
+                like_patterns: &mut sub_like_patterns,
+                column_comparisons: &mut sub_comparisons,
+                projection_cols: &mut sub_projection_cols,
+                projection_aggregates: &mut sub_projection_aggregates,
                 has_union: &mut has_union,
                 has_distinct: &mut has_distinct,
                 has_subquery: &mut has_subquery
@@ -49,9 +69,9 @@ pub fn extract_from_table_factor(
         TableFactor::NestedJoin {
             table_with_joins, ..
         } => {
-            extract_from_table_factor(&table_with_joins.relation, tables);
+            extract_from_table_factor(&table_with_joins.relation, tables, aliases);
             for join in &table_with_joins.joins {
-                extract_from_table_factor(&join.relation, tables);
+                extract_from_table_factor(&join.relation, tables, aliases);
             }
         }
         _ => {}
@@ -0,0 +1,255 @@
+use std::hash::Hasher;
+
+use sqlparser::{
+    dialect::GenericDialect,
+    keywords::Keyword,
+    tokenizer::{Token, Tokenizer}
+};
+
+use super::types::Query;
+
+/// Clause keywords written in uppercase whatever their case in the source.
+///
+/// Other keywords are only uppercased as function names; most of them
+/// (`name`, `status`, `id`) are ordinary column names.
+const CLAUSE_KEYWORDS: &[Keyword] = &[
+    Keyword::ALL,
+    Keyword::AND,
+    Keyword::AS,
+    Keyword::ASC,
+    Keyword::BETWEEN,
+    Keyword::BY,
+    Keyword::CASE,
+    Keyword::CROSS,
+    Keyword::DELETE,
+    Keyword::DESC,
+    Keyword::DISTINCT,
+    Keyword::ELSE,
+    Keyword::END,
+    Keyword::EXISTS,
+    Keyword::FALSE,
+    Keyword::FROM,
+    Keyword::FULL,
+    Keyword::GROUP,
+    Keyword::HAVING,
+    Keyword::ILIKE,
+    Keyword::IN,
+    Keyword::INNER,
+    Keyword::INSERT,
+    Keyword::INTO,
+    Keyword::IS,
+    Keyword::JOIN,
+    Keyword::LEFT,
+    Keyword::LIKE,
+    Keyword::LIMIT,
+    Keyword::NOT,
+    Keyword::NULL,
+    Keyword::OFFSET,
+    Keyword::ON,
+    Keyword::OR,
+    Keyword::ORDER,
+    Keyword::OUTER,
+    Keyword::OVER,
+    Keyword::PARTITION,
+    Keyword::RECURSIVE,
+    Keyword::RETURNING,
+    Keyword::RIGHT,
+    Keyword::SELECT,
+    Keyword::SET,
+    Keyword::THEN,
+    Keyword::TRUE,
+    Keyword::UNION,
+    Keyword::UPDATE,
+    Keyword::USING,
+    Keyword::VALUES,
+    Keyword::WHEN,
+    Keyword::WHERE,
+    Keyword::WITH
+];
+
+/// Keywords followed by a space before `(`; any other word followed by `(`
+/// is treated as a function or table name and glued to it
+const SPACED_KEYWORDS: &[Keyword] = &[
+    Keyword::AND,
+    Keyword::AS,
+    Keyword::ELSE,
+    Keyword::EXISTS,
+    Keyword::FROM,
+    Keyword::IN,
+    Keyword::JOIN,
+    Keyword::NOT,
+    Keyword::ON,
+    Keyword::OR,
+    Keyword::OVER,
+    Keyword::SELECT,
+    Keyword::THEN,
+    Keyword::USING,
+    Keyword::VALUES,
+    Keyword::WHEN,
+    Keyword::WHERE,
+    Keyword::WITH
+];
+
+/// Shape of a statement: literals and bind parameters become `?`, comments
+/// are dropped, keywords are uppercased and whitespace is canonical.
+///
+/// Identifiers keep their spelling and quotes, so queries against different
+/// tables or columns stay distinct. A statement that fails to tokenize is
+/// returned with its whitespace collapsed.
+#[allow(dead_code)]
+pub fn normalize(query: &Query) -> String {
+    render(&query.raw, true)
+}
+
+/// Canonical text of a statement, as returned by
+/// [`Query::to_normalized_sql`]
+pub(super) fn canonical_sql(raw: &str) -> String {
+    render(raw, false)
+}
+
+/// Token-wise rendering shared by [`normalize`] and [`canonical_sql`].
+///
+/// Clause keywords and keyword-named calls are uppercased either way. With
+/// `mask_literals`, literals and bind parameters become `?` and other words
+/// keep their spelling; without it, literals stay as written and other
+/// unquoted words are lowercased.
+fn render(raw: &str, mask_literals: bool) -> String {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect {}, raw).tokenize() else {
+        return raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    };
+    let tokens: Vec<&Token> = tokens
+        .iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .collect();
+    let mut parts: Vec<String> = Vec::with_capacity(tokens.len());
+    let mut idx = 0;
+    while idx < tokens.len() {
+        let token = tokens[idx];
+        let part = match token {
+            Token::Number(..)
+            | Token::Placeholder(_)
+            | Token::SingleQuotedString(_)
+            | Token::DoubleQuotedString(_)
+            | Token::TripleSingleQuotedString(_)
+            | Token::TripleDoubleQuotedString(_)
+            | Token::DollarQuotedString(_)
+            | Token::SingleQuotedByteStringLiteral(_)
+            | Token::DoubleQuotedByteStringLiteral(_)
+            | Token::SingleQuotedRawStringLiteral(_)
+            | Token::DoubleQuotedRawStringLiteral(_)
+            | Token::NationalStringLiteral(_)
+            | Token::EscapedStringLiteral(_)
+            | Token::UnicodeStringLiteral(_)
+            | Token::HexStringLiteral(_)
+                if mask_literals =>
+            {
+                "?".to_string()
+            }
+            Token::Colon
+                if matches!(tokens.get(idx + 1), Some(Token::Word(_)))
+                    && !matches!(
+                        idx.checked_sub(1).map(|prev| tokens[prev]),
+                        Some(Token::Word(_) | Token::Number(..) | Token::RParen | Token::RBracket)
+                    ) =>
+            {
+                idx += 1;
+                if mask_literals {
+                    "?".to_string()
+                } else {
+                    format!(":{}", tokens[idx])
+                }
+            }
+            Token::Word(word)
+                if word.quote_style.is_none()
+                    && !matches!(
+                        idx.checked_sub(1).map(|prev| tokens[prev]),
+                        Some(Token::Period)
+                    )
+                    && (CLAUSE_KEYWORDS.contains(&word.keyword)
+                        || (word.keyword != Keyword::NoKeyword
+                            && matches!(tokens.get(idx + 1), Some(Token::LParen)))) =>
+            {
+                word.value.to_uppercase()
+            }
+            Token::Word(word) if !mask_literals && word.quote_style.is_none() => {
+                word.value.to_lowercase()
+            }
+            Token::SemiColon if idx + 1 == tokens.len() => break,
+            other => other.to_string()
+        };
+        let glued = match (parts.last().map(String::as_str), token) {
+            (None, _) => true,
+            (Some("(" | "." | "::"), _) => true,
+            (_, Token::RParen | Token::Comma | Token::Period | Token::DoubleColon) => true,
+            (Some(_), Token::LParen) => matches!(
+                tokens[idx - 1],
+                Token::Word(word) if word.quote_style.is_some() || !SPACED_KEYWORDS.contains(&word.keyword)
+            ),
+            _ => false
+        };
+        if !glued {
+            parts.push(" ".to_string());
+        }
+        parts.push(part);
+        idx += 1;
+    }
+    parts.concat()
+}
+
+/// Stable 16-digit hex hash of [`normalize`]d query shape.
+///
+/// Queries that differ only in literal values, bind parameters, comments,
+/// keyword case or whitespace share a fingerprint. The hash is 64-bit
+/// FNV-1a, so it stays the same across builds and platforms and can be
+/// stored in baselines.
+#[allow(dead_code)]
+pub fn fingerprint(query: &Query) -> String {
+    let mut hasher = StableHasher::default();
+    hasher.write(normalize(query).as_bytes());
+    format!("{:016x}", hasher.finish())
+}
+
+/// 64-bit FNV-1a [`Hasher`] whose output does not depend on the build.
+///
+/// Integers are fed as little-endian bytes and `usize` as 64 bits, so a
+/// value hashed through [`Hash`](std::hash::Hash) gives the same digest on
+/// every platform and toolchain. Used for keys that are written to disk.
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u16(&mut self, i: u16) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, i: u32) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, i: u64) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, i: u128) {
+        self.write(&i.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, i: usize) {
+        self.write_u64(i as u64);
+    }
+}
@@ -0,0 +1,26 @@
+use compact_str::CompactString;
+use indexmap::IndexSet;
+use sqlparser::{
+    dialect::GenericDialect,
+    tokenizer::{Token, Tokenizer}
+};
+
+/// Double-quoted identifiers of a statement, unquoted and in their exact
+/// case (e.g., `"UserID"` yields `UserID`). Statements that fail to tokenize
+/// report none.
+pub fn detect_quoted_identifiers(raw: &str) -> Vec<CompactString> {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect {}, raw).tokenize() else {
+        return Vec::new();
+    };
+    tokens
+        .iter()
+        .filter_map(|token| match token {
+            Token::Word(word) if word.quote_style == Some('"') => {
+                Some(CompactString::from(word.value.as_str()))
+            }
+            _ => None
+        })
+        .collect::<IndexSet<_>>()
+        .into_iter()
+        .collect()
+}
@@ -0,0 +1,68 @@
+use indexmap::IndexSet;
+use sqlparser::{
+    dialect::GenericDialect,
+    tokenizer::{Token, Tokenizer}
+};
+
+use super::types::PlaceholderStyle;
+
+/// Find bind parameters in a statement.
+///
+/// Returns the placeholder style and the number of distinct parameters:
+/// every `?` is its own parameter, while a repeated `$1` or `:name` counts
+/// once. Statements that fail to tokenize report no placeholders.
+pub fn detect_placeholders(raw: &str) -> (Option<PlaceholderStyle>, usize) {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect {}, raw).tokenize() else {
+        return (None, 0);
+    };
+    let mut style: Option<PlaceholderStyle> = None;
+    let mut named = IndexSet::new();
+    let mut positional = 0usize;
+    let mut record = |found: PlaceholderStyle| {
+        style = match style {
+            None => Some(found),
+            Some(current) if current == found => Some(current),
+            Some(_) => Some(PlaceholderStyle::Mixed)
+        };
+    };
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Placeholder(p) if p.starts_with('?') => {
+                record(PlaceholderStyle::QuestionMark);
+                if p.len() == 1 {
+                    positional += 1;
+                } else {
+                    named.insert(p.clone());
+                }
+            }
+            Token::Placeholder(p)
+                if p.len() > 1 && p.starts_with('$') && !p[1..].contains('$') =>
+            {
+                record(PlaceholderStyle::Dollar);
+                named.insert(p.clone());
+            }
+            Token::Colon
+                if !matches!(
+                    idx.checked_sub(1).map(|prev| &tokens[prev]),
+                    Some(Token::Word(_) | Token::Number(..) | Token::RParen | Token::RBracket)
+                ) =>
+            {
+                if let Some(Token::Word(word)) = tokens.get(idx + 1) {
+                    record(PlaceholderStyle::Colon);
+                    named.insert(format!(":{}", word.value));
+                }
+            }
+            Token::Word(word)
+                if word.quote_style.is_none()
+                    && word.value.len() > 1
+                    && word.value.starts_with('@')
+                    && !word.value.starts_with("@@") =>
+            {
+                record(PlaceholderStyle::AtSign);
+                named.insert(word.value.clone());
+            }
+            _ => {}
+        }
+    }
+    (style, named.len() + positional)
+}
@@ -0,0 +1,318 @@
+//! Incremental statement splitting for inputs too large to hold in memory.
+//!
+//! [`StatementSplitter`] reads SQL line by line and cuts it at top-level
+//! semicolons, so only one statement is buffered at a time. [`QueryStream`]
+//! parses each piece with [`parse_queries`](super::parse_queries) and maps
+//! its spans back onto the whole input.
+
+use std::{collections::VecDeque, io::BufRead};
+
+use super::{
+    SqlDialect, parse_at,
+    suppression::file_wide_rules,
+    types::{Query, Span}
+};
+use crate::error::{AppResult, input_read_error};
+
+/// One statement cut from the input, with any comments before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawStatement {
+    /// Statement text, including its terminating semicolon
+    pub sql:  String,
+    /// Position of `sql` within the input
+    pub span: Span
+}
+
+/// What the character at the read position belongs to
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum State {
+    Code,
+    SingleQuote,
+    DoubleQuote,
+    Backtick,
+    LineComment,
+    BlockComment,
+    /// PostgreSQL `$tag$ ... $tag$` body; holds the full delimiter
+    DollarQuote(String)
+}
+
+/// Splits SQL read from `reader` into statements at top-level semicolons
+///
+/// Semicolons inside string literals, quoted identifiers, comments, and
+/// PostgreSQL dollar-quoted bodies do not end a statement. Comments and
+/// empty statements between two statements are kept in front of the next
+/// one; trailing comments after the last statement are dropped. Only the
+/// current line and the statement being collected are held in memory.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::query::{SqlDialect, StatementSplitter};
+///
+/// let sql = "SELECT ';' FROM t; SELECT 2";
+/// let statements: Vec<_> = StatementSplitter::new(sql.as_bytes(), SqlDialect::Generic)
+///     .map(|s| s.unwrap().sql)
+///     .collect();
+/// assert_eq!(statements, vec!["SELECT ';' FROM t;", " SELECT 2"]);
+/// ```
+pub struct StatementSplitter<R> {
+    reader:            R,
+    line:              String,
+    pos:               usize,
+    current:           String,
+    start:             Span,
+    here:              Span,
+    state:             State,
+    has_code:          bool,
+    backslash_escapes: bool,
+    dollar_quotes:     bool,
+    hash_comments:     bool,
+    done:              bool
+}
+
+impl<R: BufRead> StatementSplitter<R> {
+    /// Split the SQL of `reader`, quoting as `dialect` does
+    pub fn new(reader: R, dialect: SqlDialect) -> Self {
+        let origin = Span {
+            line:   1,
+            column: 1,
+            start:  0,
+            end:    0
+        };
+        Self {
+            reader,
+            line: String::new(),
+            pos: 0,
+            current: String::new(),
+            start: origin,
+            here: origin,
+            state: State::Code,
+            has_code: false,
+            backslash_escapes: dialect
+                .into_parser_dialect()
+                .supports_string_literal_backslash_escape(),
+            dollar_quotes: matches!(dialect, SqlDialect::Generic | SqlDialect::PostgreSQL),
+            hash_comments: dialect == SqlDialect::MySQL,
+            done: false
+        }
+    }
+
+    /// Move `len` bytes of the current line into the statement
+    fn take(&mut self, len: usize) {
+        let text = &self.line[self.pos..self.pos + len];
+        for c in text.chars() {
+            self.here.start += c.len_utf8();
+            if c == '\n' {
+                self.here.line += 1;
+                self.here.column = 1;
+            } else {
+                self.here.column += 1;
+            }
+        }
+        self.current.push_str(text);
+        self.pos += len;
+    }
+
+    /// Hand out the collected statement and start the next one here
+    fn finish(&mut self) -> RawStatement {
+        let mut span = self.start;
+        span.end = self.here.start;
+        self.start = self.here;
+        self.has_code = false;
+        RawStatement {
+            sql: std::mem::take(&mut self.current),
+            span
+        }
+    }
+
+    /// Consume the next token-ish piece of the line; returns true when it was
+    /// a statement-ending semicolon
+    fn step(&mut self) -> bool {
+        let rest = &self.line[self.pos..];
+        let c = rest.chars().next().expect("position inside the line");
+        match &self.state {
+            State::Code => {
+                let (len, next) = match c {
+                    ';' => {
+                        self.take(1);
+                        return self.has_code;
+                    }
+                    '\'' => (1, State::SingleQuote),
+                    '"' => (1, State::DoubleQuote),
+                    '`' => (1, State::Backtick),
+                    '-' if rest.starts_with("--") => (2, State::LineComment),
+                    '#' if self.hash_comments => (1, State::LineComment),
+                    '/' if rest.starts_with("/*") => (2, State::BlockComment),
+                    '$' if self.dollar_quotes => match dollar_tag(rest) {
+                        Some(tag) => (tag.len(), State::DollarQuote(tag.to_string())),
+                        None => (1, State::Code)
+                    },
+                    _ => (c.len_utf8(), State::Code)
+                };
+                if !c.is_whitespace() && next != State::LineComment && next != State::BlockComment
+                {
+                    self.has_code = true;
+                }
+                self.state = next;
+                self.take(len);
+            }
+            State::SingleQuote | State::DoubleQuote | State::Backtick => {
+                let quote = match self.state {
+                    State::SingleQuote => '\'',
+                    State::DoubleQuote => '"',
+                    _ => '`'
+                };
+                if c == '\\' && self.backslash_escapes && quote != '`' {
+                    let escaped = rest[1..].chars().next().map_or(0, char::len_utf8);
+                    self.take(1 + escaped);
+                } else {
+                    if c == quote {
+                        self.state = State::Code;
+                    }
+                    self.take(c.len_utf8());
+                }
+            }
+            State::LineComment => {
+                if c == '\n' {
+                    self.state = State::Code;
+                }
+                self.take(c.len_utf8());
+            }
+            State::BlockComment => {
+                if rest.starts_with("*/") {
+                    self.state = State::Code;
+                    self.take(2);
+                } else {
+                    self.take(c.len_utf8());
+                }
+            }
+            State::DollarQuote(tag) => {
+                if rest.starts_with(tag.as_str()) {
+                    let len = tag.len();
+                    self.state = State::Code;
+                    self.take(len);
+                } else {
+                    self.take(c.len_utf8());
+                }
+            }
+        }
+        false
+    }
+}
+
+/// The `$tag$` delimiter at the start of `text`, if it opens a dollar quote
+/// rather than being a `$1` placeholder
+fn dollar_tag(text: &str) -> Option<&str> {
+    let body = &text[1..];
+    let close = body.find('$')?;
+    let tag = &body[..close];
+    let valid = tag
+        .chars()
+        .next()
+        .is_none_or(|first| first.is_alphabetic() || first == '_')
+        && tag.chars().all(|c| c.is_alphanumeric() || c == '_');
+    valid.then(|| &text[..close + 2])
+}
+
+impl<R: BufRead> Iterator for StatementSplitter<R> {
+    type Item = AppResult<RawStatement>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            if self.pos >= self.line.len() {
+                self.line.clear();
+                self.pos = 0;
+                match self.reader.read_line(&mut self.line) {
+                    Ok(0) => {
+                        self.done = true;
+                        break;
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        self.done = true;
+                        return Some(Err(input_read_error(e)));
+                    }
+                }
+                continue;
+            }
+            if self.step() {
+                return Some(Ok(self.finish()));
+            }
+        }
+        self.has_code.then(|| Ok(self.finish()))
+    }
+}
+
+/// Queries parsed one statement at a time from a reader
+///
+/// Created by [`stream_queries`]. Each item is a parsed [`Query`] whose
+/// [`Query::span`] points into the whole input, or the parse error of one
+/// statement; iteration continues with the next statement after an error.
+/// `-- sqla:disable-file` directives apply to the statements that follow
+/// them.
+pub struct QueryStream<R> {
+    statements: StatementSplitter<R>,
+    dialect:    SqlDialect,
+    pending:    VecDeque<Query>,
+    file_rules: Vec<String>
+}
+
+impl<R: BufRead> Iterator for QueryStream<R> {
+    type Item = AppResult<Query>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(query) = self.pending.pop_front() {
+                return Some(Ok(query));
+            }
+            let statement = match self.statements.next()? {
+                Ok(statement) => statement,
+                Err(e) => return Some(Err(e))
+            };
+            let parsed = parse_at(&statement.sql, self.dialect, statement.span);
+            let earlier = std::mem::take(&mut self.file_rules);
+            self.file_rules = earlier
+                .iter()
+                .cloned()
+                .chain(file_wide_rules(&statement.sql))
+                .collect();
+            match parsed {
+                Ok(queries) => {
+                    self.pending.extend(queries.into_iter().map(|mut query| {
+                        query.suppressed_rules.extend(earlier.iter().cloned());
+                        query
+                    }));
+                }
+                Err(e) => return Some(Err(e))
+            }
+        }
+    }
+}
+
+/// Parse the SQL of `reader` lazily, one statement at a time
+///
+/// Unlike [`parse_queries`](super::parse_queries), the input is never held
+/// in memory as a whole, so peak memory is bounded by the largest single
+/// statement rather than the input size. Use it for multi-gigabyte dumps.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::query::{SqlDialect, stream_queries};
+///
+/// let sql = "SELECT id FROM users;\nDELETE FROM orders WHERE id = 1;";
+/// let queries: Vec<_> = stream_queries(sql.as_bytes(), SqlDialect::Generic)
+///     .collect::<Result<_, _>>()
+///     .unwrap();
+/// assert_eq!(queries.len(), 2);
+/// assert_eq!(queries[1].span.unwrap().line, 2);
+/// ```
+#[allow(dead_code)]
+pub fn stream_queries<R: BufRead>(reader: R, dialect: SqlDialect) -> QueryStream<R> {
+    QueryStream {
+        statements: StatementSplitter::new(reader, dialect),
+        dialect,
+        pending: VecDeque::new(),
+        file_rules: Vec::new()
+    }
+}
@@ -0,0 +1,82 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use super::types::Query;
+
+/// `-- sqla:<directive> [RULE, ...]` line comment
+static DIRECTIVE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"--[ \t]*sqla:(disable-next-line|disable-file|disable)\b([^\r\n]*)")
+        .expect("valid regex")
+});
+
+/// Attach inline suppression directives in `sql` to the parsed `queries`.
+///
+/// - `-- sqla:disable RULE` applies to the statement containing the comment, or
+///   else to the next statement
+/// - `-- sqla:disable-next-line RULE` applies to statements on the next line
+/// - `-- sqla:disable-file RULE` applies to every statement
+///
+/// Rule IDs are separated by commas or spaces; a directive without IDs
+/// suppresses every rule and is recorded as `*`. Statements without a
+/// [`Span`](super::Span) only receive file-wide directives.
+pub fn apply_suppressions(sql: &str, queries: &mut [Query]) {
+    for caps in DIRECTIVE_REGEX.captures_iter(sql) {
+        let offset = caps.get(0).expect("whole match").start();
+        let rules = directive_rules(&caps[2]);
+        let targets: Vec<usize> = match &caps[1] {
+            "disable-file" => (0..queries.len()).collect(),
+            "disable-next-line" => {
+                let line_start = sql[offset..]
+                    .find('\n')
+                    .map_or(sql.len(), |pos| offset + pos + 1);
+                let line_end = sql[line_start..]
+                    .find('\n')
+                    .map_or(sql.len(), |pos| line_start + pos);
+                spanned(queries)
+                    .filter(|(_, start, end)| *start <= line_end && *end > line_start)
+                    .map(|(idx, ..)| idx)
+                    .collect()
+            }
+            _ => spanned(queries)
+                .find(|(_, _, end)| *end > offset)
+                .map(|(idx, ..)| idx)
+                .into_iter()
+                .collect()
+        };
+        for idx in targets {
+            queries[idx].suppressed_rules.extend(rules.iter().cloned());
+        }
+    }
+}
+
+/// Rule IDs of every `-- sqla:disable-file` directive in `sql`
+#[allow(dead_code)]
+pub(super) fn file_wide_rules(sql: &str) -> Vec<String> {
+    DIRECTIVE_REGEX
+        .captures_iter(sql)
+        .filter(|caps| &caps[1] == "disable-file")
+        .flat_map(|caps| directive_rules(&caps[2]))
+        .collect()
+}
+
+/// Rule IDs listed after a directive, or `*` when it lists none
+fn directive_rules(list: &str) -> Vec<String> {
+    let mut rules: Vec<String> = list
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect();
+    if rules.is_empty() {
+        rules.push("*".to_string());
+    }
+    rules
+}
+
+/// Index, start, and end offset of every query with a span
+fn spanned(queries: &[Query]) -> impl Iterator<Item = (usize, usize, usize)> + '_ {
+    queries
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, q)| q.span.map(|span| (idx, span.start, span.end)))
+}
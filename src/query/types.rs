@@ -1,32 +1,154 @@
 use std::sync::OnceLock;
 
 use compact_str::CompactString;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 
+use super::{identifier::detect_quoted_identifiers, placeholder::detect_placeholders};
+
 /// Type alias for small column vectors (typically < 8 elements)
 pub type ColumnVec = SmallVec<[CompactString; 8]>;
 
 /// Parsed SQL query with metadata
 #[derive(Debug, Clone, Serialize)]
 pub struct Query {
-    pub raw:          String,
-    pub query_type:   QueryType,
-    pub tables:       Vec<CompactString>,
-    pub cte_names:    Vec<CompactString>,
-    pub where_cols:   ColumnVec,
-    pub join_cols:    ColumnVec,
-    pub order_cols:   ColumnVec,
-    pub group_cols:   ColumnVec,
-    pub having_cols:  ColumnVec,
+    pub raw: String,
+    /// Position of the statement in the input it was parsed from
+    pub span: Option<Span>,
+    pub query_type: QueryType,
+    pub tables: Vec<CompactString>,
+    /// Base tables read by the outermost FROM clauses, paired with their
+    /// alias if one was given
+    pub table_aliases: Vec<(CompactString, Option<CompactString>)>,
+    pub cte_names: Vec<CompactString>,
+    pub where_cols: ColumnVec,
+    pub join_cols: ColumnVec,
+    pub order_cols: ColumnVec,
+    /// ORDER BY columns as written (`u.email`, `email`) with their
+    /// direction; empty when any ORDER BY item is not a plain column
+    pub order_directions: Vec<(CompactString, SortDir)>,
+    pub group_cols: ColumnVec,
+    pub having_cols: ColumnVec,
+    /// HAVING column references outside aggregate calls and not naming a
+    /// SELECT alias
+    pub having_plain_cols: Vec<CompactString>,
     pub window_funcs: Vec<WindowFunction>,
-    pub limit:        Option<u64>,
-    pub offset:       Option<u64>,
-    pub has_union:    bool,
+    /// String literals used as LIKE/ILIKE patterns in filters
+    pub like_patterns: Vec<CompactString>,
+    /// Column-to-column comparisons from JOIN conditions and WHERE, as
+    /// written (e.g., `u.email = o.email`)
+    pub column_comparisons: Vec<ColumnComparison>,
+    /// Column references in the SELECT list outside aggregate calls
+    pub projection_cols: ColumnVec,
+    /// Aggregate functions called in the SELECT list (e.g., "COUNT")
+    pub projection_aggregates: Vec<CompactString>,
+    /// SELECT lists of the outermost SELECTs without GROUP BY, one per arm
+    /// of a set operation
+    pub ungrouped_selects: Vec<UngroupedSelect>,
+    /// Column references of the outermost statement with their clause;
+    /// subqueries and CTE bodies are not included
+    pub column_refs: Vec<ColumnRef>,
+    /// Columns passed alone to `COUNT` in the SELECT list, as written
+    /// (`COUNT(*)` and `COUNT(DISTINCT ...)` excluded)
+    pub count_columns: Vec<CompactString>,
+    /// Non-aggregate function calls with arguments in the SELECT list, as
+    /// written; calls nested in other calls are listed too
+    pub projection_calls: Vec<CompactString>,
+    /// Derived tables of the FROM and JOIN clauses as written, nested ones
+    /// included
+    pub derived_tables: Vec<CompactString>,
+    /// SELECT list contains `*` or `t.*`
+    pub projection_wildcard: bool,
+    /// Columns referenced by each operand when WHERE is a top-level OR
+    pub where_or_branches: Vec<Vec<CompactString>>,
+    /// Columns the WHERE clause pins to a value with `=` in its top-level
+    /// AND chain, as written
+    pub where_equality_cols: Vec<CompactString>,
+    /// JSON and array operators applied in the outermost WHERE clause
+    pub where_json_access: Vec<JsonAccess>,
+    /// BETWEEN predicates of the outermost WHERE that test a function of a
+    /// column
+    pub where_function_betweens: Vec<FunctionBetween>,
+    /// `CAST(column AS type)` and `column::type` applied to a bare column in
+    /// the outermost SELECT
+    pub column_casts: Vec<ColumnCast>,
+    /// Expressions of the outermost SELECT's `DISTINCT ON (...)`, as
+    /// written; empty without `DISTINCT ON`
+    pub distinct_on: Vec<CompactString>,
+    /// Columns the outermost WHERE compares to literals with `=` or `IN`
+    pub where_literal_comparisons: Vec<LiteralComparison>,
+    /// Subqueries the outermost WHERE compares with `=`, `<>`, `<`, `>`,
+    /// `<=` or `>=` that have no `LIMIT 1` and are not a lone aggregate
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub where_multi_row_subqueries: Vec<Query>,
+    /// Recursive CTEs whose recursive term has no WHERE or LIMIT
+    pub unguarded_recursive_ctes: Vec<CompactString>,
+    /// CTEs of a non-recursive WITH sorted with ORDER BY and no LIMIT or
+    /// FETCH
+    pub unlimited_ordered_ctes: Vec<CompactString>,
+    /// `JOIN ... USING` clauses of the outermost SELECT
+    pub join_using: Vec<UsingJoin>,
+    /// Outermost SELECT has a ClickHouse `PREWHERE` clause
+    pub has_prewhere: bool,
+    /// Base tables of the outermost SELECT read with the ClickHouse `FINAL`
+    /// modifier
+    pub final_tables: Vec<CompactString>,
+    /// A set operation is a UNION without `ALL`
+    pub has_distinct_union: bool,
+    /// The statement ends with an ORDER BY clause, positional or not
+    pub has_order_by: bool,
+    /// Explicit column list of an INSERT
+    pub insert_columns: Vec<CompactString>,
+    /// Number of values in the first VALUES tuple of an INSERT
+    pub insert_values_arity: Option<usize>,
+    /// Number of tuples in the VALUES clause of an INSERT
+    pub insert_values_rows: Option<usize>,
+    /// Source query of an `INSERT ... SELECT`
+    pub insert_select: Option<Box<Query>>,
+    /// Subqueries, derived tables, and CTE bodies directly inside the
+    /// statement, each parsed as a query with its own FROM clause; deeper
+    /// ones are in their own `subqueries`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subqueries: Vec<Query>,
+    /// Literals an INSERT's VALUES rows or an UPDATE's SET clause assign to
+    /// named columns
+    pub column_writes: Vec<ColumnWrite>,
+    /// Structured form of a GRANT statement
+    pub grant: Option<GrantInfo>,
+    /// Match condition and WHEN clauses of a MERGE statement
+    pub merge: Option<MergeInfo>,
+    /// Variables assigned by a SET statement
+    pub session_settings: Vec<SessionSetting>,
+    /// Template blocks inside the statement were replaced by placeholders
+    /// before parsing, so findings may miss or misread context
+    pub templated: bool,
+    /// Name the statement carried in a JSON or NDJSON input
+    pub origin: Option<String>,
+    /// Server-side file the statement writes (`INTO OUTFILE`, `COPY ... TO`)
+    pub file_export: Option<FileExport>,
+    /// Bind parameter syntax used by the statement, if any
+    pub placeholder_style: Option<PlaceholderStyle>,
+    /// Number of distinct bind parameters
+    pub placeholder_count: usize,
+    /// Double-quoted identifiers in their exact case, without quotes
+    pub quoted_identifiers: Vec<CompactString>,
+    /// Row locking clause of a SELECT (`FOR UPDATE`, `FOR SHARE`)
+    pub locking: Option<LockMode>,
+    /// Rule IDs silenced by `-- sqla:disable` comments; `*` silences all
+    pub suppressed_rules: Vec<String>,
+    /// Comments inside or directly before the statement, with their `--` or
+    /// `/* */` markers; [`raw`](Self::raw) drops them
+    pub comments: Vec<CompactString>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+    /// OFFSET without LIMIT or FETCH, so every row past the skipped ones is
+    /// returned
+    pub unbounded_offset: bool,
+    pub has_union: bool,
     pub has_distinct: bool,
     pub has_subquery: bool,
     #[serde(skip)]
-    complexity_cell:  OnceLock<QueryComplexity>
+    complexity_cell: OnceLock<QueryComplexity>
 }
 
 impl Query {
@@ -35,6 +157,94 @@ impl Query {
         self.complexity_cell
             .get_or_init(|| calculate_complexity(self))
     }
+
+    /// Canonical SQL text of the statement, stable across formatting
+    ///
+    /// Rendered from the parsed statement with single spaces between
+    /// tokens and no trailing semicolon. Clause keywords (`SELECT`, `WHERE`,
+    /// `JOIN`, ...) and calls to keyword-named functions (`COUNT(`) are
+    /// uppercase; every other unquoted word, including table, column, and
+    /// alias names, is lowercase. Quoted identifiers, literals, and bind
+    /// parameters are kept as written. Unlike
+    /// [`normalize`](super::normalize), literal values are preserved, so the
+    /// result still runs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sql_query_analyzer::query::{SqlDialect, parse_queries};
+    ///
+    /// let queries = parse_queries("select Id\nfrom Users where id=1", SqlDialect::Generic).unwrap();
+    /// assert_eq!(
+    ///     queries[0].to_normalized_sql(),
+    ///     "SELECT id FROM users WHERE id = 1"
+    /// );
+    /// ```
+    #[allow(dead_code)]
+    pub fn to_normalized_sql(&self) -> String {
+        super::fingerprint::canonical_sql(&self.raw)
+    }
+
+    /// Whether the statement type is understood by the rules.
+    ///
+    /// Statements classified as [`QueryType::Other`] (`BEGIN`, `COMMIT`,
+    /// `CREATE FUNCTION`, vendor DDL) pass through analysis as no-ops,
+    /// except file exports such as `COPY ... TO`, which the security rules
+    /// inspect.
+    pub fn is_analyzable(&self) -> bool {
+        self.query_type != QueryType::Other || self.file_export.is_some()
+    }
+
+    /// Whether an inline `-- sqla:disable` comment silences `rule_id` here
+    pub fn is_suppressed(&self, rule_id: &str) -> bool {
+        self.suppressed_rules
+            .iter()
+            .any(|id| id == "*" || id.eq_ignore_ascii_case(rule_id))
+    }
+
+    /// Whether the statement takes bind parameters instead of inlined values
+    pub fn is_parameterized(&self) -> bool {
+        self.placeholder_count > 0
+    }
+
+    /// Base tables referenced without an alias
+    pub fn tables_without_aliases(&self) -> impl Iterator<Item = &str> {
+        self.table_aliases
+            .iter()
+            .filter(|(_, alias)| alias.is_none())
+            .map(|(table, _)| table.as_str())
+    }
+
+    /// Base table behind a column qualifier: the table the alias names, or
+    /// the table itself when referenced by (possibly schema-qualified) name
+    pub fn table_for_qualifier(&self, qualifier: &str) -> Option<&str> {
+        self.table_aliases
+            .iter()
+            .find(|(table, alias)| match alias {
+                Some(alias) => alias.eq_ignore_ascii_case(qualifier),
+                None => {
+                    table.eq_ignore_ascii_case(qualifier)
+                        || table
+                            .rsplit('.')
+                            .next()
+                            .is_some_and(|t| t.eq_ignore_ascii_case(qualifier))
+                }
+            })
+            .map(|(table, _)| table.as_str())
+    }
+}
+
+/// Location of a statement within the analyzed input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    /// 1-based line of the first character
+    pub line:   usize,
+    /// 1-based column (in characters) of the first character
+    pub column: usize,
+    /// Byte offset of the first character
+    pub start:  usize,
+    /// Byte offset just past the last character
+    pub end:    usize
 }
 
 /// Window function information
@@ -45,6 +255,296 @@ pub struct WindowFunction {
     pub order_cols:     Vec<CompactString>
 }
 
+/// A `JOIN ... USING (...)` clause
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsingJoin {
+    /// Base tables joined before this one in the same FROM item
+    pub left:    Vec<CompactString>,
+    /// Base table being joined
+    pub right:   CompactString,
+    /// Columns named in USING
+    pub columns: Vec<CompactString>
+}
+
+/// Privileges, target, and grantees of a GRANT statement
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GrantInfo {
+    /// Granted privileges as written (e.g., "SELECT", "UPDATE (email)")
+    pub privileges:        Vec<CompactString>,
+    /// `ALL` or `ALL PRIVILEGES`
+    pub all_privileges:    bool,
+    /// Target objects as written (e.g., "users", "ALL TABLES IN SCHEMA app")
+    pub object:            Option<CompactString>,
+    /// Grantees with quoting stripped; MySQL accounts become `user@host`
+    pub grantees:          Vec<CompactString>,
+    pub with_grant_option: bool
+}
+
+/// One variable assignment of a SET statement
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSetting {
+    /// Lowercased variable name without `@@` or a `global.`/`session.` prefix
+    pub name:  CompactString,
+    /// `GLOBAL`, `SESSION`, `LOCAL` or `PERSIST` when given as a keyword or
+    /// `@@global.` style prefix
+    pub scope: Option<CompactString>,
+    /// Assigned value as written; a list of values is comma-separated
+    pub value: CompactString
+}
+
+/// Clause of a statement a column reference appears in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub enum ColumnClause {
+    Select,
+    Join,
+    Where,
+    GroupBy,
+    Having,
+    OrderBy,
+    /// `SET` of an UPDATE
+    Set,
+    /// Column list of an INSERT
+    Insert
+}
+
+impl std::fmt::Display for ColumnClause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Select => "SELECT",
+            Self::Join => "JOIN",
+            Self::Where => "WHERE",
+            Self::GroupBy => "GROUP BY",
+            Self::Having => "HAVING",
+            Self::OrderBy => "ORDER BY",
+            Self::Set => "SET",
+            Self::Insert => "INSERT"
+        })
+    }
+}
+
+/// Column reference as written, with the clause it appears in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnRef {
+    /// Reference text (e.g., "u.email", "email")
+    pub reference: CompactString,
+    pub clause:    ColumnClause
+}
+
+/// JSON or array operator applied to a column (`data ->> 'status'`,
+/// `tags @> ARRAY['a']`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonAccess {
+    /// Column the operator is applied to, as written
+    pub column:   CompactString,
+    /// Operator (e.g., "->>", "@>", "?")
+    pub operator: CompactString,
+    /// Whole access expression as written (e.g., "data ->> 'status'")
+    pub expr:     CompactString
+}
+
+impl JsonAccess {
+    /// Whether the operator extracts a value (`->`, `->>`, `#>`, `#>>`)
+    /// rather than testing containment or key existence
+    pub fn is_extraction(&self) -> bool {
+        matches!(self.operator.as_str(), "->" | "->>" | "#>" | "#>>")
+    }
+}
+
+/// BETWEEN whose tested operand wraps a column in a function
+/// (`DATE(ts) BETWEEN '2024-01-01' AND '2024-01-31'`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FunctionBetween {
+    /// Uppercased function name; casts are recorded as `CAST AS <type>`
+    pub function: CompactString,
+    /// Column passed to the function, as written
+    pub column:   CompactString,
+    /// Wrapped operand as written (e.g., "DATE(ts)")
+    pub expr:     CompactString,
+    /// Lower bound as written
+    pub low:      CompactString,
+    /// Upper bound as written
+    pub high:     CompactString
+}
+
+/// Cast applied directly to a column (`CAST(id AS INTEGER)`, `id::int`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnCast {
+    /// Column being cast, as written
+    pub column:    CompactString,
+    /// Target type, uppercased (e.g., "INTEGER", "VARCHAR(20)")
+    pub data_type: CompactString,
+    /// Cast expression as written
+    pub expr:      CompactString
+}
+
+/// Column compared to literal values with `=` or `IN`
+/// (`status IN ('new', 'paid')`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LiteralComparison {
+    /// Column as written
+    pub column: CompactString,
+    /// Literal values without quotes, in list order
+    pub values: Vec<CompactString>
+}
+
+/// SELECT list of a SELECT that has no GROUP BY
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UngroupedSelect {
+    /// Aggregate functions called in the list (e.g., "COUNT")
+    pub aggregates: Vec<CompactString>,
+    /// Column references outside aggregate calls
+    pub columns:    Vec<CompactString>
+}
+
+/// Comparison of two columns (`u.email = o.email`, `x.id < y.id`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnComparison {
+    /// Left operand as written
+    pub left:  CompactString,
+    /// Comparison operator
+    pub op:    CompareOp,
+    /// Right operand as written
+    pub right: CompactString
+}
+
+/// Operator of a [`ColumnComparison`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum CompareOp {
+    /// `=`
+    Eq,
+    /// `<>` or `!=`
+    NotEq,
+    /// `<`
+    Lt,
+    /// `<=`
+    LtEq,
+    /// `>`
+    Gt,
+    /// `>=`
+    GtEq
+}
+
+/// Literal assigned to a column by `INSERT ... VALUES` or `UPDATE ... SET`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ColumnWrite {
+    /// Target column as written
+    pub column: CompactString,
+    /// Value assigned to it
+    pub value:  WrittenLiteral
+}
+
+/// Kind of literal a write assigns
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub enum WrittenLiteral {
+    /// `NULL`
+    Null,
+    /// Quoted string, without quotes
+    String(CompactString),
+    /// Numeric literal, with its sign
+    Number(CompactString)
+}
+
+/// Match condition and WHEN clauses of a MERGE statement
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MergeInfo {
+    /// Source table or subquery as written
+    pub source:     CompactString,
+    /// Columns referenced by the ON condition
+    pub on_columns: Vec<CompactString>,
+    /// `WHEN` clauses in declaration order
+    pub clauses:    Vec<MergeWhen>
+}
+
+/// One `WHEN [NOT] MATCHED [AND ...] THEN ...` clause of a MERGE
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeWhen {
+    /// Clause kind as written (e.g., "MATCHED", "NOT MATCHED BY SOURCE")
+    pub kind:      CompactString,
+    /// Extra `AND` condition restricting the clause, as written
+    pub predicate: Option<CompactString>,
+    /// Action taken for the rows the clause selects
+    pub action:    MergeAction
+}
+
+/// Action of a MERGE `WHEN` clause
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum MergeAction {
+    Insert,
+    Update,
+    Delete
+}
+
+/// Statement that writes to a file on the database server
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FileExport {
+    /// Export form (e.g., "INTO OUTFILE", "COPY TO")
+    pub form:   CompactString,
+    /// Target path, or the command of `COPY ... TO PROGRAM`
+    pub target: CompactString
+}
+
+/// Sort direction of an ORDER BY item or an index column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub enum SortDir {
+    /// Ascending, the default when no direction is written
+    #[default]
+    Asc,
+    /// `DESC`
+    Desc
+}
+
+impl SortDir {
+    /// Direction declared by `ASC`/`DESC` options (`Some(false)` is DESC)
+    pub fn from_asc(asc: Option<bool>) -> Self {
+        if asc == Some(false) {
+            Self::Desc
+        } else {
+            Self::Asc
+        }
+    }
+
+    /// The opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Asc => Self::Desc,
+            Self::Desc => Self::Asc
+        }
+    }
+
+    /// SQL keyword of the direction
+    pub fn keyword(self) -> &'static str {
+        match self {
+            Self::Asc => "ASC",
+            Self::Desc => "DESC"
+        }
+    }
+}
+
+/// Row lock requested by a SELECT
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum LockMode {
+    /// `FOR UPDATE`: exclusive row locks
+    Update,
+    /// `FOR SHARE`: shared row locks
+    Share
+}
+
+/// Bind parameter syntax
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+pub enum PlaceholderStyle {
+    /// PostgreSQL numbered parameters (`$1`)
+    Dollar,
+    /// JDBC/ODBC positional parameters (`?`, SQLite `?1`)
+    QuestionMark,
+    /// Named parameters (`:name`)
+    Colon,
+    /// SQL Server named parameters (`@p0`)
+    AtSign,
+    /// More than one style in the same statement
+    Mixed
+}
+
 /// Query complexity metrics
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct QueryComplexity {
@@ -67,27 +567,78 @@ pub enum QueryType {
     Delete = 3,
     Truncate = 4,
     Other = 5,
-    Drop = 6
+    Drop = 6,
+    Grant = 7,
+    Merge = 8,
+    Set = 9
 }
 
 impl Default for Query {
     fn default() -> Self {
         Self {
-            raw:             String::new(),
-            query_type:      QueryType::Other,
-            tables:          Vec::new(),
-            cte_names:       Vec::new(),
-            where_cols:      ColumnVec::new(),
-            join_cols:       ColumnVec::new(),
-            order_cols:      ColumnVec::new(),
-            group_cols:      ColumnVec::new(),
-            having_cols:     ColumnVec::new(),
-            window_funcs:    Vec::new(),
-            limit:           None,
-            offset:          None,
-            has_union:       false,
-            has_distinct:    false,
-            has_subquery:    false,
+            raw: String::new(),
+            span: None,
+            query_type: QueryType::Other,
+            tables: Vec::new(),
+            table_aliases: Vec::new(),
+            cte_names: Vec::new(),
+            where_cols: ColumnVec::new(),
+            join_cols: ColumnVec::new(),
+            order_cols: ColumnVec::new(),
+            order_directions: Vec::new(),
+            group_cols: ColumnVec::new(),
+            having_cols: ColumnVec::new(),
+            having_plain_cols: Vec::new(),
+            window_funcs: Vec::new(),
+            like_patterns: Vec::new(),
+            column_comparisons: Vec::new(),
+            projection_cols: ColumnVec::new(),
+            projection_aggregates: Vec::new(),
+            ungrouped_selects: Vec::new(),
+            column_refs: Vec::new(),
+            count_columns: Vec::new(),
+            projection_calls: Vec::new(),
+            derived_tables: Vec::new(),
+            projection_wildcard: false,
+            where_or_branches: Vec::new(),
+            where_equality_cols: Vec::new(),
+            join_using: Vec::new(),
+            where_json_access: Vec::new(),
+            where_function_betweens: Vec::new(),
+            column_casts: Vec::new(),
+            distinct_on: Vec::new(),
+            where_literal_comparisons: Vec::new(),
+            where_multi_row_subqueries: Vec::new(),
+            unguarded_recursive_ctes: Vec::new(),
+            unlimited_ordered_ctes: Vec::new(),
+            has_prewhere: false,
+            final_tables: Vec::new(),
+            has_distinct_union: false,
+            has_order_by: false,
+            insert_columns: Vec::new(),
+            insert_values_arity: None,
+            insert_values_rows: None,
+            insert_select: None,
+            subqueries: Vec::new(),
+            column_writes: Vec::new(),
+            grant: None,
+            merge: None,
+            session_settings: Vec::new(),
+            templated: false,
+            origin: None,
+            file_export: None,
+            placeholder_style: None,
+            placeholder_count: 0,
+            quoted_identifiers: Vec::new(),
+            locking: None,
+            suppressed_rules: Vec::new(),
+            comments: Vec::new(),
+            limit: None,
+            offset: None,
+            unbounded_offset: false,
+            has_union: false,
+            has_distinct: false,
+            has_subquery: false,
             complexity_cell: OnceLock::new()
         }
     }
@@ -95,9 +646,14 @@ impl Default for Query {
 
 impl Query {
     pub fn new(raw: String, query_type: QueryType) -> Self {
+        let (placeholder_style, placeholder_count) = detect_placeholders(&raw);
+        let quoted_identifiers = detect_quoted_identifiers(&raw);
         Self {
             raw,
             query_type,
+            placeholder_style,
+            placeholder_count,
+            quoted_identifiers,
             ..Default::default()
         }
     }
@@ -112,6 +668,9 @@ impl std::fmt::Display for QueryType {
             Self::Delete => write!(f, "DELETE"),
             Self::Truncate => write!(f, "TRUNCATE"),
             Self::Drop => write!(f, "DROP"),
+            Self::Grant => write!(f, "GRANT"),
+            Self::Merge => write!(f, "MERGE"),
+            Self::Set => write!(f, "SET"),
             Self::Other => write!(f, "OTHER")
         }
     }
@@ -23,10 +23,12 @@
 //!
 //! # Rule Categories
 //!
-//! - **Performance** (`PERF001`-`PERF020`) - Query optimization issues
+//! - **Performance** (`PERF001`-`PERF063`) - Query optimization issues
 //! - **Style** (`STYLE001`-`STYLE004`) - Best practice violations
-//! - **Security** (`SEC001`-`SEC008`) - Dangerous operations
-//! - **Schema** (`SCHEMA001`-`SCHEMA004`) - Schema validation (requires schema)
+//! - **Security** (`SEC001`-`SEC023`) - Dangerous operations
+//! - **Schema** (`SCHEMA001`-`SCHEMA027`) - Schema validation (requires schema)
+//! - **ClickHouse** (`CHPERF001`-`CHPERF003`) - Engine-specific performance
+//!   issues (only with [`SqlDialect::ClickHouse`])
 //!
 //! # Configuration
 //!
@@ -66,16 +68,36 @@
 //! }
 //! ```
 
+mod batch;
+mod catalog;
+mod clickhouse;
+mod custom;
+mod filter;
 mod performance;
 pub mod schema_aware;
+mod schema_lint;
 mod security;
 mod style;
 mod types;
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
+pub use catalog::{RULE_FAMILIES, RuleCatalogEntry, rule_catalog, rule_family};
+pub use custom::RegexRule;
+pub use filter::ViolationFilter;
 use rayon::prelude::*;
-pub use types::{AnalysisReport, RuleCategory, RuleInfo, Severity, Violation};
+#[allow(unused_imports)]
+pub use types::{
+    AnalysisReport, ExitStatus, ReportSummary, RuleCategory, RuleCount, RuleInfo, RuleTiming,
+    Severity, Statistics, Violation
+};
 
-use crate::{config::RulesConfig, query::Query, schema::Schema};
+use crate::{
+    config::RulesConfig,
+    error::AppResult,
+    query::{Query, SqlDialect},
+    schema::Schema
+};
 
 /// Trait for implementing SQL analysis rules.
 ///
@@ -111,7 +133,9 @@ use crate::{config::RulesConfig, query::Query, schema::Schema};
 ///                 severity: Severity::Warning,
 ///                 category: RuleCategory::Performance,
 ///                 suggestion: Some("Use keyset pagination instead".into()),
-///                 query_index
+///                 query_index,
+///                 location: None,
+///                 origin: None
 ///             }]
 ///         } else {
 ///             vec![]
@@ -134,6 +158,83 @@ pub trait Rule: Send + Sync {
     ///
     /// A vector of violations, empty if the query passes this rule.
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation>;
+
+    /// One-line summary of what the rule detects.
+    ///
+    /// Defaults to the rule name.
+    fn description(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Longer explanation of why the detected pattern is a problem.
+    fn explanation(&self) -> &'static str {
+        ""
+    }
+
+    /// Example SQL statements that trigger this rule.
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Trait for rules that check the schema itself rather than a query.
+///
+/// Schema lints catch DDL authoring mistakes and run once per analysis,
+/// through [`Schema::lint`]. Their violations land in
+/// [`AnalysisReport::schema_violations`] with `query_index` 0.
+pub trait SchemaLint: Send + Sync {
+    /// Returns metadata about this lint.
+    fn info(&self) -> RuleInfo;
+
+    /// Checks the schema and returns any violations found.
+    fn check(&self, schema: &Schema) -> Vec<Violation>;
+
+    /// One-line summary of what the lint detects.
+    ///
+    /// Defaults to the lint name.
+    fn description(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Longer explanation of why the detected pattern is a problem.
+    fn explanation(&self) -> &'static str {
+        ""
+    }
+
+    /// Example DDL that triggers this lint.
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
+}
+
+/// Trait for rules that look across the queries of a run.
+///
+/// Batch rules find patterns no single statement shows, such as a run of
+/// inserts that could be one. They see every query in input order, once per
+/// analysis, and report against the query where the pattern starts.
+pub trait BatchRule: Send + Sync {
+    /// Returns metadata about this rule.
+    fn info(&self) -> RuleInfo;
+
+    /// Checks all queries of the run and returns any violations found.
+    fn check(&self, queries: &[Query]) -> Vec<Violation>;
+
+    /// One-line summary of what the rule detects.
+    ///
+    /// Defaults to the rule name.
+    fn description(&self) -> &'static str {
+        self.info().name
+    }
+
+    /// Longer explanation of why the detected pattern is a problem.
+    fn explanation(&self) -> &'static str {
+        ""
+    }
+
+    /// Example SQL statements that trigger this rule.
+    fn examples(&self) -> &'static [&'static str] {
+        &[]
+    }
 }
 
 /// Parallel rule execution engine.
@@ -164,7 +265,14 @@ pub trait Rule: Send + Sync {
 /// ```
 pub struct RuleRunner {
     rules:          Vec<Box<dyn Rule>>,
-    severity_cache: std::collections::HashMap<&'static str, Severity>
+    batch_rules:    Vec<Box<dyn BatchRule>>,
+    lint_count:     usize,
+    /// Schema lint violations, found once when the runner is built
+    lint_findings:  Vec<Violation>,
+    severity_cache: std::collections::HashMap<&'static str, Severity>,
+    category_cache: std::collections::HashMap<&'static str, RuleCategory>,
+    config:         RulesConfig,
+    timings:        bool
 }
 
 impl Default for RuleRunner {
@@ -183,135 +291,458 @@ impl RuleRunner {
     ///
     /// # Notes
     ///
-    /// - Performance rules (PERF001-PERF020) detect query optimization issues
+    /// - Performance rules (PERF001-PERF062) detect query optimization issues;
+    ///   PERF052 looks across all queries of the run
     /// - Style rules (STYLE001-STYLE004) enforce best practices
-    /// - Security rules (SEC001-SEC008) detect dangerous operations
+    /// - Security rules (SEC001-SEC021, SEC023) detect dangerous operations
+    /// - `[[rules.custom]]` definitions run as [`RegexRule`]s; invalid ones,
+    ///   which [`Config::load`](crate::config::Config::load) rejects, are
+    ///   skipped
     pub fn with_config(config: RulesConfig) -> Self {
-        let all_rules: Vec<Box<dyn Rule>> = vec![
-            Box::new(performance::SelectStarWithoutLimit),
-            Box::new(performance::LeadingWildcard),
-            Box::new(performance::OrInsteadOfIn),
-            Box::new(performance::LargeOffset),
-            Box::new(performance::MissingJoinCondition),
-            Box::new(performance::DistinctWithOrderBy),
-            Box::new(performance::ScalarSubquery),
-            Box::new(performance::FunctionOnColumn),
-            Box::new(performance::NotInWithSubquery),
-            Box::new(performance::UnionWithoutAll),
-            Box::new(performance::SelectWithoutWhere),
-            Box::new(performance::OrderByRandom),
-            Box::new(performance::CountWithoutWhere),
-            Box::new(performance::LargeInClause),
-            Box::new(performance::HavingWithoutAggregate),
-            Box::new(performance::UnnecessaryDistinct),
-            Box::new(performance::DeeplyNestedSubqueries),
-            Box::new(performance::RepeatedTableScan),
-            Box::new(performance::CorrelatedSubquery),
-            Box::new(style::SelectStar),
-            Box::new(style::MissingTableAlias),
-            Box::new(style::OrdinalInOrderOrGroupBy),
-            Box::new(security::MissingWhereInUpdate),
-            Box::new(security::MissingWhereInDelete),
-            Box::new(security::TruncateDetected),
-            Box::new(security::DropDetected),
-            Box::new(security::InjectionTautology),
-            Box::new(security::HardcodedCredential),
-            Box::new(security::PrivilegeChange),
-            Box::new(security::DynamicSqlExecution),
-        ];
-        let rules: Vec<Box<dyn Rule>> = all_rules
-            .into_iter()
-            .filter(|r| {
-                !config
-                    .disabled
-                    .iter()
-                    .any(|d| d.eq_ignore_ascii_case(r.info().id))
-            })
-            .collect();
-        let mut severity_cache = std::collections::HashMap::new();
-        for rule in &rules {
-            let rule_id = rule.info().id;
-            if let Some(sev_str) = config.severity.get(rule_id)
-                && let Some(sev) = parse_severity(sev_str)
-            {
-                severity_cache.insert(rule_id, sev);
+        Self::build(config, None)
+    }
+
+    /// Runner with the built-in, custom and batch rules; `schema` lets
+    /// built-in rules that can use one (PERF058) see it
+    fn build(config: RulesConfig, schema: Option<&Schema>) -> Self {
+        let builtin = builtin_rules(&config, schema);
+        let custom = custom_rules(&config);
+        let mut runner = Self {
+            rules: Vec::new(),
+            batch_rules: Vec::new(),
+            lint_count: 0,
+            lint_findings: Vec::new(),
+            severity_cache: std::collections::HashMap::new(),
+            category_cache: std::collections::HashMap::new(),
+            config,
+            timings: false
+        };
+        runner.add_rules(builtin);
+        runner.add_rules(custom);
+        for rule in batch_rules() {
+            if runner.register(rule.info().id) {
+                runner.batch_rules.push(rule);
             }
         }
-        Self {
-            rules,
-            severity_cache
-        }
+        runner
     }
 
     /// Create runner with schema-aware rules and configuration
     ///
     /// # Notes
     ///
-    /// - Adds schema-aware rules (SCHEMA001-SCHEMA004) if not disabled
-    /// - Updates severity cache for schema rules
+    /// - Adds schema-aware rules (SCHEMA001-SCHEMA017, SCHEMA021-SCHEMA024,
+    ///   SCHEMA026-SCHEMA027, PERF054, PERF060-PERF061, PERF063, SEC022) if not
+    ///   disabled
+    /// - Adds schema lints (SCHEMA019-SCHEMA020, SCHEMA025), which check the
+    ///   DDL once per run
+    /// - Updates severity and category caches for schema rules
+    /// - PERF058 also skips subqueries that look up a row by a unique key
     pub fn with_schema_and_config(schema: Schema, config: RulesConfig) -> Self {
-        let mut runner = Self::with_config(config.clone());
-        let schema_rules: Vec<Box<dyn Rule>> = vec![
-            Box::new(schema_aware::MissingIndexOnFilterColumn::new(
-                schema.clone()
-            )),
-            Box::new(schema_aware::ColumnNotInSchema::new(schema.clone())),
-            Box::new(schema_aware::SuggestIndex::new(schema.clone())),
-            Box::new(schema_aware::JoinOnNonIndexedColumn::new(schema.clone())),
-            Box::new(schema_aware::ImplicitTypeConversion::new(schema)),
-        ];
-        for rule in schema_rules {
-            if !config
-                .disabled
-                .iter()
-                .any(|d| d.eq_ignore_ascii_case(rule.info().id))
-            {
-                let rule_id = rule.info().id;
-                if let Some(sev_str) = config.severity.get(rule_id)
-                    && let Some(sev) = parse_severity(sev_str)
-                {
-                    runner.severity_cache.insert(rule_id, sev);
-                }
-                runner.rules.push(rule);
+        let mut runner = Self::build(config, Some(&schema));
+        let enabled: Vec<&str> = schema_lints()
+            .iter()
+            .map(|lint| lint.info().id)
+            .filter(|id| runner.register(id))
+            .collect();
+        runner.lint_count = enabled.len();
+        runner.lint_findings = schema
+            .lint()
+            .into_iter()
+            .filter(|v| enabled.contains(&v.rule_id))
+            .collect();
+        runner.add_rules(schema_rules(schema));
+        runner
+    }
+
+    /// Create a runner with only the schema-aware rules (SCHEMA001-SCHEMA017,
+    /// SCHEMA021-SCHEMA024, SCHEMA026-SCHEMA027, PERF054, PERF060-PERF061,
+    /// PERF063, SEC022)
+    ///
+    /// # Notes
+    ///
+    /// - Used by `validate-schema`; built-in rules and schema lints are skipped
+    pub fn schema_only(schema: Schema) -> Self {
+        let mut runner = Self {
+            rules:          Vec::new(),
+            batch_rules:    Vec::new(),
+            lint_count:     0,
+            lint_findings:  Vec::new(),
+            severity_cache: std::collections::HashMap::new(),
+            category_cache: std::collections::HashMap::new(),
+            config:         RulesConfig::default(),
+            timings:        false
+        };
+        runner.add_rules(schema_rules(schema));
+        runner
+    }
+
+    /// Add the rules specific to `dialect`
+    ///
+    /// # Notes
+    ///
+    /// - ClickHouse adds CHPERF001-CHPERF003; other dialects add nothing
+    pub fn with_dialect(mut self, dialect: SqlDialect) -> Self {
+        if dialect == SqlDialect::ClickHouse {
+            self.add_rules(clickhouse_rules());
+        }
+        self
+    }
+
+    /// Register `rules` that are not disabled, caching severity and category
+    /// overrides
+    fn add_rules(&mut self, rules: Vec<Box<dyn Rule>>) {
+        for rule in rules {
+            if self.register(rule.info().id) {
+                self.rules.push(rule);
             }
         }
-        runner
+    }
+
+    /// Cache the severity and category overrides of `rule_id`; false when it
+    /// is disabled
+    fn register(&mut self, rule_id: &'static str) -> bool {
+        if self
+            .config
+            .disabled
+            .iter()
+            .any(|d| d.eq_ignore_ascii_case(rule_id))
+        {
+            return false;
+        }
+        if let Some(sev_str) = self.config.severity.get(rule_id)
+            && let Ok(sev) = sev_str.parse::<Severity>()
+        {
+            self.severity_cache.insert(rule_id, sev);
+        }
+        if let Some(category) = self.config.category.get(rule_id)
+            && let Ok(category) = category.parse::<RuleCategory>()
+        {
+            self.category_cache.insert(rule_id, category);
+        }
+        true
+    }
+
+    /// Measure throughput and per-rule time during
+    /// [`analyze`](Self::analyze) and store them in
+    /// [`AnalysisReport::queries_per_second`] and
+    /// [`AnalysisReport::rule_timings`]
+    pub fn with_timings(mut self, enabled: bool) -> Self {
+        self.timings = enabled;
+        self
+    }
+
+    /// Run the per-query rules on one query
+    ///
+    /// Applies inline suppressions and severity overrides like
+    /// [`analyze`](Self::analyze). Rules that look across queries (PERF052)
+    /// and schema lints are not run.
+    #[allow(dead_code)]
+    pub fn check_query(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let violations: Vec<Violation> = self
+            .rules
+            .par_iter()
+            .flat_map_iter(|rule| rule.check(query, query_index))
+            .collect();
+        let mut violations: Vec<Violation> = violations
+            .into_iter()
+            .filter_map(|violation| self.finish(violation, query))
+            .collect();
+        violations.sort_by(report_order);
+        violations
+    }
+
+    /// Check queries as they arrive, yielding the violations of each one
+    ///
+    /// Pair with [`stream_queries`](crate::query::stream_queries) to analyze
+    /// inputs too large to parse up front: one item is produced per query,
+    /// before the next is read. Parse errors are passed through and do not
+    /// stop the stream. Works like [`check_query`](Self::check_query), so
+    /// cross-query rules and schema lints are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sql_query_analyzer::{
+    ///     query::{SqlDialect, stream_queries},
+    ///     rules::RuleRunner
+    /// };
+    ///
+    /// let sql = "SELECT * FROM users;\nDELETE FROM orders;";
+    /// let runner = RuleRunner::new();
+    /// let mut results = runner.analyze_stream(stream_queries(sql.as_bytes(), SqlDialect::Generic));
+    /// let first = results.next().unwrap().unwrap();
+    /// assert!(first.iter().any(|v| v.rule_id == "PERF001"));
+    /// ```
+    #[allow(dead_code)]
+    pub fn analyze_stream<'a, I>(
+        &'a self,
+        queries: I
+    ) -> impl Iterator<Item = AppResult<Vec<Violation>>> + 'a
+    where
+        I: IntoIterator<Item = AppResult<Query>>,
+        I::IntoIter: 'a
+    {
+        queries
+            .into_iter()
+            .enumerate()
+            .map(|(idx, query)| query.map(|query| self.check_query(&query, idx)))
+    }
+
+    /// Drop `violation` if `query` suppresses it; otherwise apply severity
+    /// and category overrides and default its location and origin to the
+    /// query's
+    fn finish(&self, mut violation: Violation, query: &Query) -> Option<Violation> {
+        if query.is_suppressed(violation.rule_id) {
+            return None;
+        }
+        self.apply_overrides(&mut violation);
+        if violation.location.is_none() {
+            violation.location = query.span;
+        }
+        if violation.origin.is_none() {
+            violation.origin = query.origin.clone();
+        }
+        Some(violation)
+    }
+
+    /// Replace the severity and category of `violation` with configured
+    /// overrides
+    fn apply_overrides(&self, violation: &mut Violation) {
+        if let Some(&severity) = self.severity_cache.get(violation.rule_id) {
+            violation.severity = severity;
+        }
+        if let Some(&category) = self.category_cache.get(violation.rule_id) {
+            violation.category = category;
+        }
     }
 
     /// Run all rules on the provided queries (parallel execution)
     pub fn analyze(&self, queries: &[Query]) -> AnalysisReport {
-        let mut report = AnalysisReport::new(queries.len(), self.rules.len());
+        let started = self.timings.then(std::time::Instant::now);
+        let mut report = AnalysisReport::new(
+            queries.len(),
+            self.rules.len() + self.batch_rules.len() + self.lint_count
+        );
+        report.unanalyzed = queries
+            .iter()
+            .enumerate()
+            .filter(|(_, query)| !query.is_analyzable())
+            .map(|(idx, _)| idx)
+            .collect();
+        let elapsed: Vec<AtomicU64> = if self.timings {
+            (0..self.rules.len() + self.batch_rules.len())
+                .map(|_| AtomicU64::new(0))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let elapsed = &elapsed;
         let violations: Vec<Violation> = queries
             .par_iter()
             .enumerate()
             .flat_map(|(idx, query)| {
                 self.rules
                     .par_iter()
-                    .flat_map(|rule| rule.check(query, idx))
-                    .collect::<Vec<_>>()
+                    .enumerate()
+                    .flat_map_iter(move |(pos, rule)| {
+                        timed(elapsed.get(pos), || rule.check(query, idx))
+                    })
             })
+            .chain(
+                self.batch_rules
+                    .par_iter()
+                    .enumerate()
+                    .flat_map_iter(|(pos, rule)| {
+                        timed(elapsed.get(self.rules.len() + pos), || rule.check(queries))
+                    })
+            )
             .collect();
-        for mut violation in violations {
-            if let Some(&severity) = self.severity_cache.get(violation.rule_id) {
-                violation.severity = severity;
+        for violation in violations {
+            let query = &queries[violation.query_index];
+            if let Some(violation) = self.finish(violation, query) {
+                report.add_violation(violation);
             }
-            report.add_violation(violation);
         }
-        report.violations.sort_by(|a, b| {
-            b.severity
-                .cmp(&a.severity)
-                .then_with(|| a.query_index.cmp(&b.query_index))
+        report.violations.sort_by(report_order);
+        for violation in &self.lint_findings {
+            let mut violation = violation.clone();
+            self.apply_overrides(&mut violation);
+            report.schema_violations.push(violation);
+        }
+        report.schema_violations.sort_by(report_order);
+        report.queries_per_second = started.map(|started| {
+            queries.len() as f64 / started.elapsed().as_secs_f64().max(f64::EPSILON)
         });
+        report.rule_timings = self
+            .rules
+            .iter()
+            .map(|rule| rule.info().id)
+            .chain(self.batch_rules.iter().map(|rule| rule.info().id))
+            .zip(elapsed)
+            .map(|(rule_id, nanos)| RuleTiming {
+                rule_id,
+                nanos: nanos.load(Ordering::Relaxed)
+            })
+            .collect();
+        report
+            .rule_timings
+            .sort_by_key(|timing| std::cmp::Reverse(timing.nanos));
         report
     }
 }
 
-/// Parse severity string to enum
-fn parse_severity(s: &str) -> Option<Severity> {
-    match s.to_lowercase().as_str() {
-        "error" => Some(Severity::Error),
-        "warning" | "warn" => Some(Severity::Warning),
-        "info" => Some(Severity::Info),
-        _ => None
-    }
+/// Order of violations in a report: most severe first, then by query, rule
+/// ID and message, so the output does not depend on which rule finished
+/// first
+fn report_order(a: &Violation, b: &Violation) -> std::cmp::Ordering {
+    b.severity
+        .cmp(&a.severity)
+        .then_with(|| a.query_index.cmp(&b.query_index))
+        .then_with(|| a.rule_id.cmp(b.rule_id))
+        .then_with(|| a.message.cmp(&b.message))
+}
+
+/// Run `check`, adding its duration in nanoseconds to `slot` if there is one
+fn timed(slot: Option<&AtomicU64>, check: impl FnOnce() -> Vec<Violation>) -> Vec<Violation> {
+    let Some(slot) = slot else {
+        return check();
+    };
+    let started = std::time::Instant::now();
+    let violations = check();
+    slot.fetch_add(started.elapsed().as_nanos() as u64, Ordering::Relaxed);
+    violations
+}
+
+/// Instantiate every built-in rule; they work without a schema, and those
+/// that can use one get `schema`
+fn builtin_rules(config: &RulesConfig, schema: Option<&Schema>) -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(performance::SelectStarWithoutLimit),
+        Box::new(performance::LeadingWildcard),
+        Box::new(performance::OrInsteadOfIn),
+        Box::new(performance::LargeOffset),
+        Box::new(performance::MissingJoinCondition),
+        Box::new(performance::DistinctWithOrderBy),
+        Box::new(performance::ScalarSubquery),
+        Box::new(performance::FunctionOnColumn),
+        Box::new(performance::NotInWithSubquery),
+        Box::new(performance::UnionWithoutAll),
+        Box::new(performance::SelectWithoutWhere),
+        Box::new(performance::OrderByRandom),
+        Box::new(performance::CountWithoutWhere),
+        Box::new(performance::LargeInClause),
+        Box::new(performance::HavingWithoutAggregate),
+        Box::new(performance::UnnecessaryDistinct),
+        Box::new(performance::DeeplyNestedSubqueries),
+        Box::new(performance::RepeatedTableScan),
+        Box::new(performance::CorrelatedSubquery),
+        Box::new(performance::AggregateWithoutGroupBy),
+        Box::new(performance::BroadRowLock),
+        Box::new(performance::RedundantSelfJoin),
+        Box::new(performance::OrAcrossColumns),
+        Box::new(performance::HavingUngroupedColumn),
+        Box::new(performance::UnpartitionedWindow),
+        Box::new(performance::CountColumnVsStar),
+        Box::new(performance::RepeatedExpressionInProjection),
+        Box::new(performance::UnboundedRecursiveCte),
+        Box::new(performance::DerivedTableToCte),
+        Box::new(performance::NonSargableBetween),
+        Box::new(performance::UnionDoubleSort),
+        Box::new(performance::DistinctLimitNoOrder),
+        Box::new(performance::ScalarSubqueryMultiRow::new(schema)),
+        Box::new(performance::OffsetWithoutLimit),
+        Box::new(performance::OrderByInCte),
+        Box::new(style::SelectStar),
+        Box::new(style::MissingTableAlias),
+        Box::new(style::OrdinalInOrderOrGroupBy),
+        Box::new(security::MissingWhereInUpdate),
+        Box::new(security::MissingWhereInDelete),
+        Box::new(security::TruncateDetected),
+        Box::new(security::DropDetected),
+        Box::new(security::InjectionTautology),
+        Box::new(security::HardcodedCredential),
+        Box::new(security::PrivilegeChange),
+        Box::new(security::DynamicSqlExecution),
+        Box::new(security::PathologicalLikePattern),
+        Box::new(security::OverlyBroadGrant),
+        Box::new(security::InsertSelectStar),
+        Box::new(security::LimitWithoutOrderBy),
+        Box::new(security::FileExport),
+        Box::new(security::UnsafeMerge),
+        Box::new(security::DangerousSessionVariable::new(
+            &config.sensitive_variables
+        )),
+        Box::new(security::CrossSchemaReference::new(&config.allowed_schemas)),
+    ]
+}
+
+/// Instantiate every rule that only runs on ClickHouse
+fn clickhouse_rules() -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(clickhouse::ScanWithoutPrewhere),
+        Box::new(clickhouse::FinalModifier),
+        Box::new(clickhouse::CountStar),
+    ]
+}
+
+/// Instantiate every schema-aware rule against the given schema
+fn schema_rules(schema: Schema) -> Vec<Box<dyn Rule>> {
+    vec![
+        Box::new(schema_aware::MissingIndexOnFilterColumn::new(
+            schema.clone()
+        )),
+        Box::new(schema_aware::ColumnNotInSchema::new(schema.clone())),
+        Box::new(schema_aware::SuggestIndex::new(schema.clone())),
+        Box::new(schema_aware::JoinOnNonIndexedColumn::new(schema.clone())),
+        Box::new(schema_aware::ImplicitTypeConversion::new(schema.clone())),
+        Box::new(schema_aware::CollationMismatch::new(schema.clone())),
+        Box::new(schema_aware::MissingIndexOnGroupBy::new(schema.clone())),
+        Box::new(schema_aware::InsertArityMismatch::new(schema.clone())),
+        Box::new(schema_aware::CaseSensitiveColumnMismatch::new(
+            schema.clone()
+        )),
+        Box::new(schema_aware::OrderByCrossTable::new(schema.clone())),
+        Box::new(schema_aware::InvalidUsingColumn::new(schema.clone())),
+        Box::new(schema_aware::NullableJoinColumn::new(schema.clone())),
+        Box::new(schema_aware::IndexPrefixMismatch::new(schema.clone())),
+        Box::new(schema_aware::DeprecatedColumnUsage::new(schema.clone())),
+        Box::new(schema_aware::SuggestCoveringIndex::new(schema.clone())),
+        Box::new(schema_aware::JsonAccessNoIndex::new(schema.clone())),
+        Box::new(schema_aware::OutOfDomainComparison::new(schema.clone())),
+        Box::new(schema_aware::UnfilteredLargeTableRead::new(schema.clone())),
+        Box::new(schema_aware::WriteConstraintViolation::new(schema.clone())),
+        Box::new(schema_aware::OrderDirectionIndexMismatch::new(
+            schema.clone()
+        )),
+        Box::new(schema_aware::RedundantCast::new(schema.clone())),
+        Box::new(schema_aware::DistinctOnNoIndex::new(schema.clone())),
+        Box::new(schema_aware::InsertIntoGeneratedColumn::new(schema.clone())),
+        Box::new(schema_aware::ViewAssumedIndexed::new(schema.clone())),
+        Box::new(schema_aware::GroupByPrimaryKeyRedundant::new(schema)),
+    ]
+}
+
+/// Instantiate the valid `[[rules.custom]]` definitions of `config`
+fn custom_rules(config: &RulesConfig) -> Vec<Box<dyn Rule>> {
+    config
+        .custom
+        .iter()
+        .filter_map(|def| RegexRule::new(def).ok())
+        .map(|rule| Box::new(rule) as Box<dyn Rule>)
+        .collect()
+}
+
+/// Instantiate every rule that checks the queries of a run together
+fn batch_rules() -> Vec<Box<dyn BatchRule>> {
+    vec![Box::new(batch::BatchableInserts)]
+}
+
+/// Instantiate every schema lint
+pub(crate) fn schema_lints() -> Vec<Box<dyn SchemaLint>> {
+    vec![
+        Box::new(schema_lint::DuplicateColumnName),
+        Box::new(schema_lint::VarcharWithoutLength),
+        Box::new(schema_lint::MissingPrimaryKey),
+    ]
 }
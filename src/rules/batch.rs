@@ -0,0 +1,96 @@
+//! Rules that look across the queries of a run.
+//!
+//! Each [`BatchRule`](super::BatchRule) sees every query in input order and
+//! reports patterns that span statements, against the query where the
+//! pattern starts.
+
+use super::{BatchRule, RuleCategory, RuleInfo, Severity, Violation};
+use crate::query::{Query, QueryType};
+
+/// Consecutive single-row INSERTs that form a batch
+const MIN_BATCHABLE_INSERTS: usize = 3;
+
+/// Run of single-row INSERTs into the same table
+///
+/// Each statement is a round trip and, outside a transaction, a commit of
+/// its own; one multi-row `VALUES` list inserts the same rows at once.
+/// Only inserts with the same column list count toward a run, since only
+/// those can be merged.
+pub struct BatchableInserts;
+
+impl BatchableInserts {
+    fn single_row_target(query: &Query) -> Option<&str> {
+        if query.query_type != QueryType::Insert || query.insert_values_rows != Some(1) {
+            return None;
+        }
+        query.tables.first().map(|table| table.as_str())
+    }
+}
+
+impl BatchRule for BatchableInserts {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF052",
+            name:     "Batchable single-row INSERTs",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Consecutive single-row INSERTs into one table could be one multi-row INSERT"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Every INSERT statement costs a round trip, a parse, and, in autocommit mode, a commit. A run of single-row INSERTs into the same table and columns does that work once per row, while a single INSERT with a multi-row VALUES list pays it once."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "INSERT INTO orders (user_id, status) VALUES (1, 'new'); INSERT INTO orders (user_id, status) VALUES (2, 'new'); INSERT INTO orders (user_id, status) VALUES (3, 'new')"
+        ]
+    }
+
+    fn check(&self, queries: &[Query]) -> Vec<Violation> {
+        let info = self.info();
+        let mut violations = Vec::new();
+        let mut start = 0;
+        while start < queries.len() {
+            let Some(table) = Self::single_row_target(&queries[start]) else {
+                start += 1;
+                continue;
+            };
+            let columns = &queries[start].insert_columns;
+            let len = queries[start..]
+                .iter()
+                .take_while(|query| {
+                    Self::single_row_target(query).is_some_and(|t| t.eq_ignore_ascii_case(table))
+                        && query.insert_columns == *columns
+                })
+                .count();
+            if len >= MIN_BATCHABLE_INSERTS {
+                violations.push(Violation {
+                    rule_id:     info.id,
+                    rule_name:   info.name,
+                    message:     format!(
+                        "{} consecutive single-row INSERTs into {} (queries #{}-#{})",
+                        len,
+                        table,
+                        start + 1,
+                        start + len
+                    ),
+                    severity:    info.severity,
+                    category:    info.category,
+                    suggestion:  Some(
+                        "Combine them into one INSERT with a multi-row VALUES list".to_string()
+                    ),
+                    query_index: start,
+                    location:    None,
+                    origin:      None
+                });
+            }
+            start += len;
+        }
+        violations
+    }
+}
@@ -0,0 +1,135 @@
+//! Machine-readable catalog of every built-in rule.
+//!
+//! The catalog describes each rule independently of any query or schema:
+//! its identifier, category, default severity, a short description, a longer
+//! explanation, and example SQL that triggers it. Documentation sites and
+//! editor plugins consume it through the `export-catalog` subcommand.
+
+use serde::Serialize;
+
+use super::{
+    BatchRule, Rule, RuleCategory, SchemaLint, Severity, batch_rules, builtin_rules,
+    clickhouse_rules, schema_lints, schema_rules
+};
+use crate::{config::RulesConfig, schema::Schema};
+
+/// Catalog entry describing a single rule.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleCatalogEntry {
+    /// Unique rule identifier (e.g., "PERF001")
+    pub id:          &'static str,
+    /// Human-readable rule name
+    pub name:        &'static str,
+    /// Rule category
+    pub category:    RuleCategory,
+    /// Default severity before configuration overrides
+    pub severity:    Severity,
+    /// One-line summary of what the rule detects
+    pub description: &'static str,
+    /// Why the detected pattern is a problem
+    pub explanation: &'static str,
+    /// Example SQL statements that trigger the rule (DDL for schema lints)
+    pub examples:    Vec<&'static str>
+}
+
+impl RuleCatalogEntry {
+    fn from_rule(rule: &dyn Rule) -> Self {
+        let info = rule.info();
+        Self {
+            id:          info.id,
+            name:        info.name,
+            category:    info.category,
+            severity:    info.severity,
+            description: rule.description(),
+            explanation: rule.explanation(),
+            examples:    rule.examples().to_vec()
+        }
+    }
+
+    fn from_batch(rule: &dyn BatchRule) -> Self {
+        let info = rule.info();
+        Self {
+            id:          info.id,
+            name:        info.name,
+            category:    info.category,
+            severity:    info.severity,
+            description: rule.description(),
+            explanation: rule.explanation(),
+            examples:    rule.examples().to_vec()
+        }
+    }
+
+    fn from_lint(lint: &dyn SchemaLint) -> Self {
+        let info = lint.info();
+        Self {
+            id:          info.id,
+            name:        info.name,
+            category:    info.category,
+            severity:    info.severity,
+            description: lint.description(),
+            explanation: lint.explanation(),
+            examples:    lint.examples().to_vec()
+        }
+    }
+}
+
+/// Build the catalog of all built-in, batch, schema-aware, and
+/// dialect-specific rules, and of the schema lints.
+///
+/// Entries are grouped by ID family (PERF, STYLE, SEC, SCHEMA, CHPERF) and
+/// ordered numerically within each family.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::rules::rule_catalog;
+///
+/// let catalog = rule_catalog();
+/// assert!(catalog.iter().any(|entry| entry.id == "PERF001"));
+/// ```
+pub fn rule_catalog() -> Vec<RuleCatalogEntry> {
+    let mut entries: Vec<RuleCatalogEntry> = builtin_rules(&RulesConfig::default(), None)
+        .iter()
+        .chain(schema_rules(Schema::default()).iter())
+        .chain(clickhouse_rules().iter())
+        .map(|rule| RuleCatalogEntry::from_rule(rule.as_ref()))
+        .chain(
+            batch_rules()
+                .iter()
+                .map(|rule| RuleCatalogEntry::from_batch(rule.as_ref()))
+        )
+        .chain(
+            schema_lints()
+                .iter()
+                .map(|lint| RuleCatalogEntry::from_lint(lint.as_ref()))
+        )
+        .collect();
+    entries.sort_by_key(|entry| id_sort_key(entry.id));
+    entries
+}
+
+/// ID families in catalog order, paired with their section titles.
+pub const RULE_FAMILIES: [(&str, &str); 5] = [
+    ("PERF", "Performance Rules"),
+    ("STYLE", "Style Rules"),
+    ("SEC", "Security Rules"),
+    ("SCHEMA", "Schema-Aware Rules"),
+    ("CHPERF", "ClickHouse Rules")
+];
+
+/// Returns the ID family prefix of a rule ID ("SCHEMA004" -> "SCHEMA").
+pub fn rule_family(id: &str) -> &str {
+    let split = id.find(|c: char| c.is_ascii_digit()).unwrap_or(id.len());
+    &id[..split]
+}
+
+/// Sort key placing families in [`RULE_FAMILIES`] order and IDs within a
+/// family numerically.
+fn id_sort_key(id: &str) -> (usize, u32) {
+    let family = rule_family(id);
+    let family_pos = RULE_FAMILIES
+        .iter()
+        .position(|(prefix, _)| *prefix == family)
+        .unwrap_or(RULE_FAMILIES.len());
+    (family_pos, id[family.len()..].parse().unwrap_or(0))
+}
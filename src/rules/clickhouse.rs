@@ -0,0 +1,168 @@
+//! Rules specific to ClickHouse.
+//!
+//! These run only when the runner is built with
+//! [`SqlDialect::ClickHouse`](crate::query::SqlDialect::ClickHouse), since
+//! the constructs they look for are either ClickHouse syntax (`PREWHERE`,
+//! `FINAL`) or idioms that do not carry over to other engines.
+
+use super::{Rule, RuleCategory, RuleInfo, Severity, Violation};
+use crate::query::{Query, QueryType};
+
+/// Filtered SELECT without PREWHERE or LIMIT
+///
+/// PREWHERE reads the filter columns first and loads the remaining columns
+/// only for granules that pass, which on wide MergeTree tables skips most of
+/// the I/O. ClickHouse moves simple conditions automatically only when
+/// `optimize_move_to_prewhere` is on and the heuristics agree. A LIMIT
+/// already stops the scan early, so those reads are left alone.
+pub struct ScanWithoutPrewhere;
+
+impl Rule for ScanWithoutPrewhere {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "CHPERF001",
+            name:     "SELECT without PREWHERE",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Filtered MergeTree scan reads every selected column before filtering"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "WHERE on a MergeTree table loads all selected columns for every granule before filtering. PREWHERE reads only the filter columns first and skips granules that do not match, which saves most of the I/O on wide tables with selective filters. Reads bounded by LIMIT are not reported, since they stop early anyway."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id, status FROM orders WHERE status = 'new'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select
+            || query.has_prewhere
+            || query.limit.is_some()
+            || query.where_cols.is_empty()
+            || query.tables.is_empty()
+        {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "Filter on {} uses WHERE without PREWHERE",
+                query.tables.join(", ")
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Move the most selective condition on a small column into PREWHERE".to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// `FINAL` modifier on a table read
+///
+/// FINAL merges ReplacingMergeTree/CollapsingMergeTree parts at query time,
+/// which is single-threaded per partition on older releases and always
+/// reads the sorting key columns of every part.
+pub struct FinalModifier;
+
+impl Rule for FinalModifier {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "CHPERF002",
+            name:     "FINAL modifier",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "FINAL merges data parts at query time"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "SELECT ... FINAL deduplicates or collapses rows by merging data parts while the query runs. The merge reads the sorting key of every part and scales with the number of unmerged parts, so latency grows with insert rate."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders FINAL WHERE user_id = 1"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.final_tables.is_empty() {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "FINAL merges parts of {} at query time",
+                query.final_tables.join(", ")
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Deduplicate with argMax/GROUP BY, or restrict FINAL to a narrow partition range"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// `count(*)` instead of ClickHouse's `count()`
+pub struct CountStar;
+
+impl Rule for CountStar {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "CHPERF003",
+            name:     "count(*) instead of count()",
+            severity: Severity::Info,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "count() is the idiomatic row count in ClickHouse"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "ClickHouse treats count(*) as count(), but count() is the documented form and makes it obvious the query can be answered from part metadata without reading any column."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT count(*) FROM orders WHERE status = 'new'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let compact: String = query.raw.chars().filter(|c| !c.is_whitespace()).collect();
+        if !compact.to_uppercase().contains("COUNT(*)") {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: "count(*) used where ClickHouse idiom is count()".to_string(),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some("Write count() instead of count(*)".to_string()),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
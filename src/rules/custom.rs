@@ -0,0 +1,119 @@
+//! Rules defined in the config file.
+//!
+//! Each `[[rules.custom]]` entry becomes a [`RegexRule`] that flags
+//! statements whose raw text matches a regex, so teams can add simple
+//! checks without recompiling.
+
+use std::{
+    collections::HashSet,
+    sync::{LazyLock, Mutex}
+};
+
+use regex::Regex;
+
+use super::{Rule, RuleCategory, RuleInfo, Severity, Violation};
+use crate::{config::CustomRuleConfig, query::Query};
+
+/// IDs and names of custom rules; [`RuleInfo`] holds `&'static str`, and
+/// interning leaks each distinct string once however many runners are built
+static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+fn intern(value: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(existing) = interned.get(value) {
+        return existing;
+    }
+    let leaked: &'static str = Box::leak(value.to_string().into_boxed_str());
+    interned.insert(leaked);
+    leaked
+}
+
+/// Flags statements whose raw text matches a configured regex
+///
+/// The pattern is matched against [`Query::raw`] and each of
+/// [`Query::comments`], and reports one violation per matching statement.
+pub struct RegexRule {
+    id:         &'static str,
+    name:       &'static str,
+    regex:      Regex,
+    severity:   Severity,
+    category:   RuleCategory,
+    message:    String,
+    suggestion: Option<String>
+}
+
+impl RegexRule {
+    /// Build a rule from its config definition
+    ///
+    /// # Errors
+    ///
+    /// Returns a description naming the rule when its ID is empty or its
+    /// pattern, severity or category is invalid.
+    pub fn new(def: &CustomRuleConfig) -> Result<Self, String> {
+        let invalid = |reason: String| format!("Custom rule '{}': {}", def.id, reason);
+        if def.id.trim().is_empty() {
+            return Err(invalid("ID must not be empty".to_string()));
+        }
+        let regex =
+            Regex::new(&def.pattern).map_err(|e| invalid(format!("invalid pattern: {}", e)))?;
+        let severity = def.severity.parse::<Severity>().map_err(|_| {
+            invalid(format!(
+                "invalid severity '{}' (expected error, warning, or info)",
+                def.severity
+            ))
+        })?;
+        let category = def.category.parse::<RuleCategory>().map_err(|_| {
+            invalid(format!(
+                "invalid category '{}' (expected performance, style, or security)",
+                def.category
+            ))
+        })?;
+        Ok(Self {
+            id: intern(&def.id),
+            name: intern(def.name.as_deref().unwrap_or(&def.id)),
+            regex,
+            severity,
+            category,
+            message: def.message.clone(),
+            suggestion: def.suggestion.clone()
+        })
+    }
+}
+
+impl Rule for RegexRule {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       self.id,
+            name:     self.name,
+            severity: self.severity,
+            category: self.category
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Statement matches a pattern from [[rules.custom]]"
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let matched = self.regex.is_match(&query.raw)
+            || query
+                .comments
+                .iter()
+                .any(|comment| self.regex.is_match(comment));
+        if !matched {
+            return vec![];
+        }
+        vec![Violation {
+            rule_id: self.id,
+            rule_name: self.name,
+            message: self.message.clone(),
+            severity: self.severity,
+            category: self.category,
+            suggestion: self.suggestion.clone(),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
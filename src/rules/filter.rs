@@ -0,0 +1,155 @@
+//! Violation filtering for the `--select` flag.
+//!
+//! A selection is a comma-separated list of predicates, all of which must
+//! hold for a violation to be kept:
+//!
+//! | Predicate | Matches |
+//! |-----------|---------|
+//! | `rule_id=SEC001` | Exact rule ID |
+//! | `rule_id^=SEC` | Rule ID prefix |
+//! | `severity=error` | Severity (`error`, `warning`, `info`) |
+//! | `category=security` | Category (`performance`, `style`, `security`) |
+//! | `query=3` | Query position, 1-based as in text output |
+//!
+//! Every field also accepts `!=`. Comparisons ignore ASCII case.
+
+use super::{RuleCategory, Severity, Violation};
+use crate::error::{AppResult, select_error};
+
+/// Violation field a predicate tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    RuleId,
+    Severity,
+    Category,
+    Query
+}
+
+/// Comparison applied between field and value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    NotEq,
+    Prefix
+}
+
+#[derive(Debug, Clone)]
+struct Predicate {
+    field: Field,
+    op:    Op,
+    value: String
+}
+
+/// Parsed `--select` expression.
+///
+/// # Example
+///
+/// ```
+/// use sql_query_analyzer::rules::ViolationFilter;
+///
+/// assert!(ViolationFilter::parse("rule_id^=SEC,severity=error").is_ok());
+/// assert!(ViolationFilter::parse("color=red").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ViolationFilter {
+    predicates: Vec<Predicate>
+}
+
+impl ViolationFilter {
+    /// Parse a selection expression
+    ///
+    /// # Errors
+    ///
+    /// Returns error for unknown fields, unsupported operators, empty
+    /// predicates, and values that do not fit the field
+    pub fn parse(expr: &str) -> AppResult<Self> {
+        let predicates = expr
+            .split(',')
+            .map(str::trim)
+            .map(parse_predicate)
+            .collect::<AppResult<Vec<_>>>()?;
+        Ok(Self {
+            predicates
+        })
+    }
+
+    /// Returns true when the violation satisfies every predicate
+    pub fn matches(&self, violation: &Violation) -> bool {
+        self.predicates.iter().all(|p| p.matches(violation))
+    }
+}
+
+fn parse_predicate(text: &str) -> AppResult<Predicate> {
+    if text.is_empty() {
+        return Err(select_error("empty predicate"));
+    }
+    let (field, op, value) = if let Some((field, value)) = text.split_once("^=") {
+        (field, Op::Prefix, value)
+    } else if let Some((field, value)) = text.split_once("!=") {
+        (field, Op::NotEq, value)
+    } else if let Some((field, value)) = text.split_once('=') {
+        (field, Op::Eq, value)
+    } else {
+        return Err(select_error(format!(
+            "'{}' has no operator, expected '=', '!=' or '^='",
+            text
+        )));
+    };
+    let field = match field.trim().to_lowercase().as_str() {
+        "rule_id" | "rule" => Field::RuleId,
+        "severity" => Field::Severity,
+        "category" => Field::Category,
+        "query" => Field::Query,
+        other => return Err(select_error(format!("unknown field '{}'", other)))
+    };
+    let value = value.trim().to_string();
+    if value.is_empty() {
+        return Err(select_error(format!("'{}' has no value", text)));
+    }
+    match field {
+        Field::Severity if value.parse::<Severity>().is_err() => {
+            return Err(select_error(format!("unknown severity '{}'", value)));
+        }
+        Field::Category if value.parse::<RuleCategory>().is_err() => {
+            return Err(select_error(format!("unknown category '{}'", value)));
+        }
+        Field::Query if value.parse::<usize>().is_err() => {
+            return Err(select_error(format!("query '{}' is not a number", value)));
+        }
+        Field::Severity | Field::Category | Field::Query if op == Op::Prefix => {
+            return Err(select_error(format!(
+                "'^=' is only supported for rule_id, got '{}'",
+                text
+            )));
+        }
+        _ => {}
+    }
+    Ok(Predicate {
+        field,
+        op,
+        value
+    })
+}
+
+impl Predicate {
+    fn matches(&self, violation: &Violation) -> bool {
+        let equal = match self.field {
+            Field::RuleId => match self.op {
+                Op::Prefix => {
+                    return violation
+                        .rule_id
+                        .get(..self.value.len())
+                        .is_some_and(|head| head.eq_ignore_ascii_case(&self.value));
+                }
+                _ => violation.rule_id.eq_ignore_ascii_case(&self.value)
+            },
+            Field::Severity => self.value.parse::<Severity>().ok() == Some(violation.severity),
+            Field::Category => self.value.parse::<RuleCategory>().ok() == Some(violation.category),
+            Field::Query => self.value.parse() == Ok(violation.query_index + 1)
+        };
+        match self.op {
+            Op::NotEq => !equal,
+            _ => equal
+        }
+    }
+}
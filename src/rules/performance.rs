@@ -1,5 +1,8 @@
 use super::{Rule, RuleCategory, RuleInfo, Severity, Violation};
-use crate::query::{Query, QueryType};
+use crate::{
+    query::{ColumnComparison, CompareOp, LockMode, Query, QueryType},
+    schema::Schema
+};
 
 /// Scalar subquery in SELECT (N+1 pattern)
 pub struct ScalarSubquery;
@@ -14,6 +17,18 @@ impl Rule for ScalarSubquery {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "N+1 query pattern detected"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A subquery in the SELECT list executes once per returned row, the classic N+1 pattern."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u.id, (SELECT COUNT(*) FROM orders o WHERE o.user_id = u.id) FROM users u"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -33,7 +48,9 @@ impl Rule for ScalarSubquery {
                     severity: info.severity,
                     category: info.category,
                     suggestion: Some("Use JOIN or window function instead".to_string()),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 }];
             }
         }
@@ -54,6 +71,18 @@ impl Rule for FunctionOnColumn {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Function calls prevent index usage"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Wrapping a filtered column in a function forces the engine to evaluate it for every row and disables any index on the column."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE LOWER(email) = 'a@b.c'"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let patterns = [
@@ -81,7 +110,9 @@ impl Rule for FunctionOnColumn {
                     suggestion: Some(
                         "Use computed column, functional index, or rewrite condition".to_string()
                     ),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 }];
             }
         }
@@ -102,6 +133,18 @@ impl Rule for NotInWithSubquery {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Can cause unexpected NULL behavior"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A single NULL in the subquery result makes NOT IN return no rows; NOT EXISTS has the intended semantics."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE id NOT IN (SELECT user_id FROM orders)"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         if upper.contains("NOT IN") && upper.contains("SELECT") {
@@ -114,7 +157,9 @@ impl Rule for NotInWithSubquery {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Use NOT EXISTS or LEFT JOIN with IS NULL instead".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -134,6 +179,18 @@ impl Rule for UnionWithoutAll {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Unnecessary deduplication overhead"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "UNION deduplicates the combined result with a sort or hash; UNION ALL skips that work when duplicates are impossible or acceptable."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM a UNION SELECT id FROM b"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if !query.has_union {
             return vec![];
@@ -148,7 +205,9 @@ impl Rule for UnionWithoutAll {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Use UNION ALL if duplicates are acceptable".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -168,6 +227,18 @@ impl Rule for SelectWithoutWhere {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Full table scan on large tables"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A SELECT without WHERE or LIMIT reads the entire table, which is intentional only for small reference tables."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id, name FROM users"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -181,7 +252,9 @@ impl Rule for SelectWithoutWhere {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Add WHERE clause or LIMIT to restrict results".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -201,6 +274,18 @@ impl Rule for SelectStarWithoutLimit {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`SELECT *` without `LIMIT` can return unbounded rows"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Unbounded result sets of every column consume memory and bandwidth on both the server and the client."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM orders"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -216,7 +301,9 @@ impl Rule for SelectStarWithoutLimit {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Add LIMIT clause or specify explicit columns".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -236,6 +323,18 @@ impl Rule for LeadingWildcard {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`LIKE '%value'` prevents index usage"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A pattern that starts with a wildcard cannot use a B-tree index, so every row is compared."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE email LIKE '%@example.com'"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         if upper.contains("LIKE '%") || upper.contains("LIKE \"%") {
@@ -247,7 +346,9 @@ impl Rule for LeadingWildcard {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Consider full-text search or restructure query".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -267,6 +368,18 @@ impl Rule for OrInsteadOfIn {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Multiple `OR` conditions can be simplified to `IN`"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Long OR chains over the same column are harder to read and optimize than an equivalent IN list."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE id = 1 OR id = 2 OR id = 3 OR id = 4"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let or_count = upper.matches(" OR ").count();
@@ -284,7 +397,9 @@ impl Rule for OrInsteadOfIn {
                 suggestion: Some(
                     "Replace multiple OR conditions with IN (val1, val2, ...)".to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -304,6 +419,18 @@ impl Rule for LargeOffset {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`OFFSET > 1000` causes performance degradation"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "OFFSET n reads and discards n rows, so pagination cost grows linearly with the page number."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders ORDER BY id LIMIT 20 OFFSET 100000"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if let Some(offset) = query.offset
             && offset > 1000
@@ -319,7 +446,9 @@ impl Rule for LargeOffset {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Use keyset pagination (WHERE id > last_id) instead".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -339,6 +468,18 @@ impl Rule for MissingJoinCondition {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Cartesian product detected"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Multiple tables without join or filter conditions multiply row counts into a Cartesian product."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM users, orders"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -359,7 +500,9 @@ impl Rule for MissingJoinCondition {
                 suggestion: Some(
                     "Add JOIN conditions or WHERE clause to prevent Cartesian product".to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -384,6 +527,18 @@ impl Rule for OrderByRandom {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Full scan and sort regardless of `LIMIT`"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "The database must generate a random value per row and sort the whole result set before applying LIMIT, so cost stays O(n log n) regardless of how few rows are returned."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users ORDER BY RAND() LIMIT 1"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -407,7 +562,9 @@ impl Rule for OrderByRandom {
                     "For random selection use a random id range (WHERE id >= FLOOR(RAND() * max_id)) or a pre-generated indexed random column"
                         .to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -431,6 +588,18 @@ impl Rule for CountWithoutWhere {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Counting every row scans the entire table"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Counting every row cannot use an index shortcut on most engines; the query time grows linearly with table size and can block writes on busy tables."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT COUNT(*) FROM users"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -453,7 +622,9 @@ impl Rule for CountWithoutWhere {
                 "Add a WHERE clause, use EXISTS for existence checks, or cache/estimate counts for large tables"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -503,6 +674,15 @@ fn max_in_list_size(upper: &str) -> usize {
     max_items
 }
 
+/// Catalog example: an IN list just past the Info threshold.
+const LARGE_IN_CLAUSE_EXAMPLE: &str = concat!(
+    "SELECT id FROM users WHERE id IN (",
+    "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, ",
+    "18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, ",
+    "35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51",
+    ")"
+);
+
 impl Rule for LargeInClause {
     fn info(&self) -> RuleInfo {
         RuleInfo {
@@ -513,6 +693,18 @@ impl Rule for LargeInClause {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "50+ values degrade planning; severity scales with size"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Very long IN lists blow up parse and plan time, defeat plan caching, and on some engines hit hard parameter limits. More than 50 items is Info, more than 200 Warning, more than 1000 Error."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[LARGE_IN_CLAUSE_EXAMPLE]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let items = max_in_list_size(&upper);
@@ -537,7 +729,9 @@ impl Rule for LargeInClause {
                 "Load the values into a temporary table and JOIN against it, or split the query into batches"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -546,7 +740,8 @@ impl Rule for LargeInClause {
 ///
 /// HAVING filters after grouping, so a condition on plain columns forces the
 /// engine to group rows it could have discarded up front. Moving the
-/// condition into WHERE prunes rows before the GROUP BY.
+/// condition into WHERE prunes rows before the GROUP BY. A HAVING that
+/// names a column outside the GROUP BY is left to [`HavingUngroupedColumn`].
 pub struct HavingWithoutAggregate;
 
 /// Aggregate function openers that justify a HAVING clause.
@@ -572,6 +767,18 @@ impl Rule for HavingWithoutAggregate {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Non-aggregate conditions belong in `WHERE`"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "HAVING filters after grouping, so a condition on plain columns forces the engine to group rows it could have discarded up front."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT status, COUNT(*) FROM orders GROUP BY status HAVING status = 'paid'"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select || query.having_cols.is_empty() {
             return vec![];
@@ -587,7 +794,9 @@ impl Rule for HavingWithoutAggregate {
             .min()
             .unwrap_or(having_part.len());
         let clause = &having_part[..clause_end];
-        if AGGREGATE_OPENERS.iter().any(|agg| clause.contains(agg)) {
+        if AGGREGATE_OPENERS.iter().any(|agg| clause.contains(agg))
+            || !ungrouped_having_columns(query).is_empty()
+        {
             return vec![];
         }
         let info = self.info();
@@ -601,7 +810,9 @@ impl Rule for HavingWithoutAggregate {
                 "Move non-aggregate conditions into WHERE so rows are pruned before GROUP BY"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -624,6 +835,18 @@ impl Rule for UnnecessaryDistinct {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`DISTINCT` with `JOIN` often hides join fan-out; `DISTINCT *` escalates to Warning"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "DISTINCT combined with JOIN usually hides duplicate rows produced by a missing or too-loose join condition; deduplication then costs a sort or hash over the whole result."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT DISTINCT u.id FROM users u JOIN orders o ON o.user_id = u.id"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select || !query.has_distinct {
             return vec![];
@@ -656,7 +879,9 @@ impl Rule for UnnecessaryDistinct {
                 "Check the join conditions for fan-out before deduplicating; select explicit columns instead of DISTINCT *"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -706,6 +931,18 @@ impl Rule for DeeplyNestedSubqueries {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "3+ SELECT levels; severity scales with depth"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Each nesting level multiplies planning complexity and usually hides a JOIN or CTE that would express the same logic flatter and faster."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM a WHERE id IN (SELECT id FROM b WHERE id IN (SELECT id FROM c))"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -733,7 +970,9 @@ impl Rule for DeeplyNestedSubqueries {
                 "Flatten nested subqueries into JOINs or name the steps with CTEs (WITH ...)"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -775,6 +1014,18 @@ impl Rule for RepeatedTableScan {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Self-joins and repeated subqueries multiply I/O"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Repeated FROM/JOIN references to one table multiply I/O; a CTE, window function, or conditional aggregation usually reads the table once instead."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT a.id FROM users a JOIN users b ON a.manager_id = b.id"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -794,7 +1045,9 @@ impl Rule for RepeatedTableScan {
                         "Read the table once via a CTE, window function, or conditional aggregation"
                             .to_string()
                     ),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 }];
             }
         }
@@ -924,6 +1177,18 @@ impl Rule for CorrelatedSubquery {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Subquery referencing the outer query re-executes per row"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A subquery that references a table or alias of the outer query cannot be evaluated once; the engine re-runs it for every candidate row."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u.id FROM users u WHERE EXISTS (SELECT 1 FROM orders o WHERE o.user_id = u.id)"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select || !query.has_subquery {
             return vec![];
@@ -945,7 +1210,9 @@ impl Rule for CorrelatedSubquery {
             suggestion: Some(
                 "Rewrite as a JOIN or window function so the inner data is read once".to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -963,6 +1230,18 @@ impl Rule for DistinctWithOrderBy {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Potentially redundant operations"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "DISTINCT and ORDER BY both sort or hash the result; combined they are often redundant."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT DISTINCT name FROM users ORDER BY name"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.has_distinct && !query.order_cols.is_empty() {
             let info = self.info();
@@ -975,9 +1254,1070 @@ impl Rule for DistinctWithOrderBy {
                 suggestion: Some(
                     "Consider if both are necessary, or use GROUP BY instead".to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
     }
 }
+
+/// Aggregate mixed with bare columns and no GROUP BY
+///
+/// `SELECT name, COUNT(*) FROM users` asks for one row per table but also a
+/// per-row column. Standard SQL rejects it (as does MySQL with
+/// `ONLY_FULL_GROUP_BY`); engines that accept it return the column from an
+/// arbitrary row.
+pub struct AggregateWithoutGroupBy;
+
+impl Rule for AggregateWithoutGroupBy {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF040",
+            name:     "Aggregate without GROUP BY",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "SELECT list mixes aggregates with bare columns but has no GROUP BY"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Without GROUP BY an aggregate collapses the result to one row, so a bare column in the same SELECT list is ambiguous; standard SQL rejects the query and permissive engines return a value from an arbitrary row."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT name, COUNT(*) FROM users"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select {
+            return vec![];
+        }
+        let info = self.info();
+        query
+            .ungrouped_selects
+            .iter()
+            .filter(|select| !select.aggregates.is_empty() && !select.columns.is_empty())
+            .map(|select| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "SELECT list mixes {} with ungrouped column(s): {}",
+                    select.aggregates.join(", "),
+                    select.columns.join(", ")
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Add GROUP BY {} or wrap the column(s) in an aggregate",
+                    select.columns.join(", ")
+                )),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// `SELECT ... FOR UPDATE` that can lock an unbounded set of rows
+///
+/// Without a WHERE clause or LIMIT the lock covers every row the scan
+/// touches, so concurrent writers (and other `FOR UPDATE` readers) queue
+/// behind the transaction until it commits.
+pub struct BroadRowLock;
+
+impl Rule for BroadRowLock {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF042",
+            name:     "Broad row lock",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "`SELECT ... FOR UPDATE` without WHERE or LIMIT locks every scanned row"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A locking read with no filter and no LIMIT holds exclusive locks on every row it scans until the transaction ends, serializing concurrent writers behind it."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM orders FOR UPDATE"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select
+            || query.locking != Some(LockMode::Update)
+            || query.limit.is_some()
+            || !query.where_cols.is_empty()
+        {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: "SELECT ... FOR UPDATE has no WHERE or LIMIT and locks every scanned row"
+                .to_string(),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Narrow the rows with a WHERE predicate or LIMIT, or add SKIP LOCKED for queue-style consumers"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Table joined to itself on its own key
+///
+/// `FROM users u1 JOIN users u2 ON u1.id = u2.id` pairs every row with
+/// itself, so the second reference adds a join and nothing else: it is
+/// either a mistake (the intended column was something like `manager_id`)
+/// or can be dropped. Key columns are `id` and `<table>_id`.
+pub struct RedundantSelfJoin;
+
+/// Returns true when `column` looks like the key of `table`: `id`, or
+/// `<table>_id` with the table name taken as written or singularized.
+fn is_key_column(table: &str, column: &str) -> bool {
+    let table = table.rsplit('.').next().unwrap_or(table).to_lowercase();
+    let column = column.to_lowercase();
+    column == "id"
+        || column == format!("{}_id", table)
+        || table
+            .strip_suffix('s')
+            .is_some_and(|singular| column == format!("{}_id", singular))
+}
+
+impl Rule for RedundantSelfJoin {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF043",
+            name:     "Redundant self-join",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Table joined to itself on its key column"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Joining a table to itself on its key pairs every row with itself, so the second reference only adds work; usually the join was meant to use another column."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u1.name FROM users u1 JOIN users u2 ON u1.id = u2.id"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select {
+            return vec![];
+        }
+        let info = self.info();
+        query
+            .column_comparisons
+            .iter()
+            .filter(|comparison| comparison.op == CompareOp::Eq)
+            .filter_map(|ColumnComparison { left, right, .. }| {
+                let (left_qualifier, left_col) = left.rsplit_once('.')?;
+                let (right_qualifier, right_col) = right.rsplit_once('.')?;
+                let table = query.table_for_qualifier(left_qualifier)?;
+                let same_table = query
+                    .table_for_qualifier(right_qualifier)
+                    .is_some_and(|t| t.eq_ignore_ascii_case(table));
+                (same_table
+                    && !left_qualifier.eq_ignore_ascii_case(right_qualifier)
+                    && left_col.eq_ignore_ascii_case(right_col)
+                    && is_key_column(table, left_col))
+                .then(|| Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "Table '{}' is joined to itself on its key '{}' ({} = {})",
+                        table, left_col, left, right
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(
+                        "Drop the second reference or join on the intended column (e.g., a parent or manager key)"
+                            .to_string()
+                    ),
+                    query_index,
+                    location: None,
+                    origin: None
+                })
+            })
+            .collect()
+    }
+}
+
+/// Top-level OR whose operands filter different columns
+///
+/// `WHERE email = ? OR phone = ?` cannot be served by one index: a
+/// composite `(email, phone)` only helps the first branch, and most
+/// planners fall back to a full scan rather than merging two index
+/// lookups. Unlike PERF003, which targets ORs over the *same* column, this
+/// rule fires only when the branches reference different column sets.
+pub struct OrAcrossColumns;
+
+impl Rule for OrAcrossColumns {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF044",
+            name:     "OR across different columns",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Top-level `OR` filters different columns, so no single index serves it"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A WHERE clause that ORs conditions on different columns cannot be answered from one index and often degrades to a full scan; a UNION of one indexed query per branch lets each branch use its own index."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE email = 'a@example.com' OR phone = '555-0100'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select {
+            return vec![];
+        }
+        let mut branches: Vec<Vec<String>> = query
+            .where_or_branches
+            .iter()
+            .filter(|columns| !columns.is_empty())
+            .map(|columns| {
+                let mut lowered: Vec<String> = columns
+                    .iter()
+                    .map(|c| c.to_lowercase().to_string())
+                    .collect();
+                lowered.sort();
+                lowered
+            })
+            .collect();
+        branches.dedup();
+        if branches.len() < 2 {
+            return vec![];
+        }
+        let mut columns: Vec<&str> = branches.iter().flatten().map(String::as_str).collect();
+        columns.sort();
+        columns.dedup();
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "WHERE ORs conditions on different columns ({}); no single index covers every branch",
+                columns.join(", ")
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Split the branches into separate queries that each use their own index and combine them with UNION"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// HAVING predicate on a column that is neither grouped nor aggregated
+///
+/// `GROUP BY a HAVING b = 1` filters after grouping on a column that is not
+/// part of the groups, so every row is grouped before the condition can
+/// discard any. The predicate belongs in WHERE. Columns inside aggregate
+/// calls, grouping columns, and SELECT aliases are not reported.
+pub struct HavingUngroupedColumn;
+
+/// HAVING columns outside aggregate calls that are not grouping columns
+fn ungrouped_having_columns(query: &Query) -> Vec<&str> {
+    query
+        .having_plain_cols
+        .iter()
+        .map(|c| c.as_str())
+        .filter(|col| !query.group_cols.iter().any(|g| g.eq_ignore_ascii_case(col)))
+        .collect()
+}
+
+impl Rule for HavingUngroupedColumn {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF045",
+            name:     "HAVING on ungrouped column",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "HAVING references a column that is neither aggregated nor grouped"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A HAVING predicate on a plain column that is not a grouping expression runs after every row has been grouped; as a WHERE predicate it would discard rows before grouping."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT status, COUNT(*) FROM orders GROUP BY status HAVING user_id = 1"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select {
+            return vec![];
+        }
+        let columns = ungrouped_having_columns(query);
+        if columns.is_empty() {
+            return vec![];
+        }
+        let listed = columns
+            .iter()
+            .map(|c| format!("'{}'", c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "HAVING filters on {} outside any aggregate or GROUP BY expression",
+                listed
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(format!(
+                "Move the predicate on {} into WHERE so rows are discarded before grouping",
+                listed
+            )),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Window function without PARTITION BY in an unbounded result
+///
+/// `ROW_NUMBER() OVER (ORDER BY x)` computes over the whole result set as a
+/// single partition, so the engine sorts and buffers every row before the
+/// first one is returned. A LIMIT on the query is taken as a sign the result
+/// is bounded and the rule stays quiet.
+pub struct UnpartitionedWindow;
+
+impl Rule for UnpartitionedWindow {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF047",
+            name:     "Window function without PARTITION BY",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Window function computes over the entire result set"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A window function without PARTITION BY treats the whole result as one partition. The engine has to sort and buffer every row before producing output, which on large tables means a full sort in memory or on disk."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id, ROW_NUMBER() OVER (ORDER BY created_at) FROM orders"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.limit.is_some() {
+            return vec![];
+        }
+        let names: Vec<&str> = query
+            .window_funcs
+            .iter()
+            .filter(|w| w.partition_cols.is_empty())
+            .map(|w| w.name.as_str())
+            .collect();
+        if names.is_empty() {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "Window function {} has no PARTITION BY and the query has no LIMIT",
+                names.join(", ")
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Add PARTITION BY to split the window, or LIMIT the result".to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// `COUNT(column)` where `COUNT(*)` may be meant
+///
+/// `COUNT(col)` counts only rows where `col` is not NULL, and has to read the
+/// column to find out. When the intent is a row count this is slower than
+/// `COUNT(*)` and silently undercounts once the column gains NULLs.
+pub struct CountColumnVsStar;
+
+impl Rule for CountColumnVsStar {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF048",
+            name:     "COUNT(column) instead of COUNT(*)",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "COUNT(column) skips NULLs and reads the column"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "COUNT(column) counts non-NULL values, not rows. It reads the column for every row, where COUNT(*) can use any index or row-count metadata, and returns a smaller number than expected as soon as the column contains NULLs."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT COUNT(user_id) FROM orders WHERE status = 'new'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.count_columns.is_empty() {
+            return vec![];
+        }
+        let info = self.info();
+        query
+            .count_columns
+            .iter()
+            .map(|col| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("COUNT({}) counts only rows where {} is not NULL", col, col),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Use COUNT(*) to count rows; keep COUNT({}) only if excluding NULLs is intended",
+                    col
+                )),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Same function call computed more than once in the SELECT list
+///
+/// Engines rarely deduplicate non-aggregate calls, so `f(x), f(x) + 1`
+/// evaluates `f` twice per row. Aggregates are excluded since the planner
+/// computes each distinct aggregate once.
+pub struct RepeatedExpressionInProjection;
+
+impl Rule for RepeatedExpressionInProjection {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF050",
+            name:     "Repeated function call in SELECT",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Identical function call appears more than once in the SELECT list"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Each occurrence of a function call in the SELECT list is evaluated separately for every row. When the function is expensive (JSON parsing, regular expressions, user-defined functions), computing it once in a CTE, derived table, or LATERAL join and referencing the result avoids the duplicate work."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT LOWER(TRIM(email)), LENGTH(LOWER(TRIM(email))) FROM users"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let calls = &query.projection_calls;
+        let mut repeated: Vec<&str> = Vec::new();
+        for (pos, call) in calls.iter().enumerate() {
+            if repeated.iter().any(|r| r.eq_ignore_ascii_case(call)) {
+                continue;
+            }
+            if calls[pos + 1..]
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(call))
+            {
+                repeated.push(call);
+            }
+        }
+        let outermost: Vec<&str> = repeated
+            .iter()
+            .filter(|call| {
+                !repeated
+                    .iter()
+                    .any(|other| other.len() > call.len() && other.contains(**call))
+            })
+            .copied()
+            .collect();
+        let info = self.info();
+        outermost
+            .into_iter()
+            .map(|call| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("{} is computed more than once per row", call),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Compute {} once in a CTE, derived table, or LATERAL join and reference the result",
+                    call
+                )),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Recursive CTE without a termination guard
+///
+/// The recursive term of a `WITH RECURSIVE` CTE runs until it produces no
+/// new rows. Without a WHERE clause or LIMIT, only the data itself stops
+/// it, and a counter (`n + 1`) or a cycle in the data never does.
+pub struct UnboundedRecursiveCte;
+
+impl Rule for UnboundedRecursiveCte {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF051",
+            name:     "Unbounded recursive CTE",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Recursive CTE has no WHERE or LIMIT to stop the recursion"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A recursive CTE repeats its recursive term until an iteration returns no rows. When that term neither filters with WHERE nor is capped with LIMIT, a generated sequence or a cycle in the data keeps it running until the engine's recursion limit, memory, or statement timeout ends the query."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq) SELECT n FROM seq"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .unguarded_recursive_ctes
+            .iter()
+            .map(|cte| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("Recursive CTE '{}' has no termination guard", cte),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Add a WHERE condition such as a depth counter (depth < 10) to the recursive term"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Same derived table written more than once in a query
+///
+/// Each copy of a subquery in FROM is planned and usually evaluated on its
+/// own. A CTE names it once; engines that materialize CTEs then compute it
+/// a single time, and the query is shorter either way.
+pub struct DerivedTableToCte;
+
+impl Rule for DerivedTableToCte {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF053",
+            name:     "Repeated derived table",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Identical subquery appears more than once in FROM or JOIN"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Derived tables are evaluated where they appear, so the same subquery written twice in FROM or JOIN is typically computed twice. Moving it into a WITH clause names it once, lets engines that materialize CTEs run it a single time, and keeps the copies from drifting apart."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "SELECT a.user_id FROM (SELECT user_id, COUNT(*) AS n FROM orders GROUP BY user_id) a JOIN (SELECT user_id, COUNT(*) AS n FROM orders GROUP BY user_id) b ON a.n = b.n AND a.user_id <> b.user_id"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let derived = &query.derived_tables;
+        let info = self.info();
+        let mut violations = Vec::new();
+        for (pos, subquery) in derived.iter().enumerate() {
+            if derived[..pos].contains(subquery) {
+                continue;
+            }
+            let count = derived[pos..].iter().filter(|d| *d == subquery).count();
+            if count < 2 {
+                continue;
+            }
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("Derived table ({}) appears {} times", subquery, count),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Define the subquery once in a WITH clause and reference it by name"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// BETWEEN on a column wrapped in a function
+///
+/// `DATE(ts) BETWEEN '2024-01-01' AND '2024-01-31'` evaluates the function
+/// for every row, so an index on `ts` is never used for the range. Unlike
+/// the text-based PERF008, this rule inspects the parsed BETWEEN operand.
+pub struct NonSargableBetween;
+
+/// `'YYYY-MM-DD'` literal of the day after `literal`, if it is a date literal
+fn next_day(literal: &str) -> Option<String> {
+    let date = literal.strip_prefix('\'')?.strip_suffix('\'')?;
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let (year, month, day): (u32, u32, u32) =
+        (year.parse().ok()?, month.parse().ok()?, day.parse().ok()?);
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None
+    };
+    if day == 0 || day > days {
+        return None;
+    }
+    let (year, month, day) = match (day == days, month == 12) {
+        (false, _) => (year, month, day + 1),
+        (true, false) => (year, month + 1, 1),
+        (true, true) => (year + 1, 1, 1)
+    };
+    Some(format!("'{:04}-{:02}-{:02}'", year, month, day))
+}
+
+impl Rule for NonSargableBetween {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF055",
+            name:     "BETWEEN on wrapped column",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "BETWEEN tests a function of a column, so its index is not used"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A range on DATE(ts), LOWER(name), or a cast of a column has to compute the expression for every row, which turns an index range scan into a full scan. Comparing the bare column against a half-open range keeps the predicate index-friendly."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE DATE(created_at) BETWEEN '2024-01-01' AND '2024-01-31'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .where_function_betweens
+            .iter()
+            .map(|between| {
+                let is_date = matches!(between.function.as_str(), "DATE" | "CAST AS DATE");
+                let suggestion = match next_day(&between.high).filter(|_| is_date) {
+                    Some(next) => format!(
+                        "Use a half-open range on the bare column: {col} >= {low} AND {col} < {next}",
+                        col = between.column,
+                        low = between.low,
+                        next = next
+                    ),
+                    None => format!(
+                        "Use a half-open range on the bare column ({col} >= ... AND {col} < ...), or index the expression {expr}",
+                        col = between.column,
+                        expr = between.expr
+                    )
+                };
+                Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "{} BETWEEN {} AND {} wraps '{}' in a function and cannot use its index",
+                        between.expr, between.low, between.high, between.column
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(suggestion),
+                    query_index,
+                    location: None,
+                    origin: None
+                }
+            })
+            .collect()
+    }
+}
+
+/// UNION (without ALL) whose result is sorted again by ORDER BY
+///
+/// Deduplicating the union already sorts or hashes every combined row; the
+/// ORDER BY on top is a second pass over the same rows.
+pub struct UnionDoubleSort;
+
+impl Rule for UnionDoubleSort {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF056",
+            name:     "UNION with ORDER BY",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "UNION deduplication and ORDER BY sort the same rows twice"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "UNION removes duplicates by sorting or hashing the combined rows, and the ORDER BY applied to the result sorts them again. Use UNION ALL, deduplicating explicitly only where the branches can overlap, when the ORDER BY is needed; when the order the deduplication leaves behind is good enough, drop the ORDER BY."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users UNION SELECT user_id FROM orders ORDER BY id"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if !query.has_distinct_union || !query.has_order_by {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: "UNION sorts to remove duplicates and ORDER BY sorts the result again"
+                .to_string(),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Use UNION ALL with explicit deduplication if the ORDER BY is needed, or drop \
+                 the ORDER BY if the union's own ordering is compatible"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// SELECT DISTINCT with LIMIT but no ORDER BY
+///
+/// Which distinct rows survive the LIMIT is up to the execution plan, and
+/// every distinct value is still computed before the cut.
+pub struct DistinctLimitNoOrder;
+
+impl Rule for DistinctLimitNoOrder {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF057",
+            name:     "DISTINCT with LIMIT and no ORDER BY",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "DISTINCT with LIMIT but no ORDER BY returns an arbitrary subset"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Without ORDER BY, the rows LIMIT keeps from a DISTINCT result depend on the plan and can change between runs, which is rarely intended. The engine also has to deduplicate the whole input before it can stop, so the LIMIT saves little work."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT DISTINCT status FROM orders LIMIT 10"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select
+            || !query.has_distinct
+            || query.limit.is_none()
+            || query.has_order_by
+        {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: "DISTINCT with LIMIT but no ORDER BY returns an arbitrary set of values"
+                .to_string(),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Add an ORDER BY so the same distinct values are returned every time".to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Scalar comparison against a subquery that may return several rows
+///
+/// `WHERE x = (SELECT y FROM t)` only works while the subquery yields at
+/// most one row; once the data grows a second match, the statement fails at
+/// runtime. `LIMIT 1`, `TOP 1`, or a lone aggregate such as `MAX(y)` without
+/// GROUP BY guarantee a single row. With a schema, a subquery over one table
+/// whose WHERE pins its primary key or a unique index with `=` counts as
+/// single-row too.
+pub struct ScalarSubqueryMultiRow {
+    schema: Option<Schema>
+}
+
+impl ScalarSubqueryMultiRow {
+    pub fn new(schema: Option<&Schema>) -> Self {
+        Self {
+            schema: schema.cloned()
+        }
+    }
+
+    /// Whether the schema shows `subquery` looks up a single row by key
+    fn pins_unique_key(&self, subquery: &Query) -> bool {
+        let Some(schema) = &self.schema else {
+            return false;
+        };
+        if subquery.has_union || subquery.tables.len() != 1 || subquery.table_aliases.len() != 1 {
+            return false;
+        }
+        let mut table = None;
+        let mut pinned = Vec::new();
+        for reference in &subquery.where_equality_cols {
+            if let Some((info, column)) = schema.resolve_column(subquery, reference) {
+                table = Some(info);
+                pinned.push(column.name.as_str());
+            }
+        }
+        table.is_some_and(|table| table.is_unique_key(&pinned))
+    }
+}
+
+impl Rule for ScalarSubqueryMultiRow {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF058",
+            name:     "Scalar subquery may return several rows",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "WHERE compares a value to a subquery that is not limited to one row"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A subquery used as an operand of `=`, `<>`, `<`, `>`, `<=` or `>=` must return at most one row. Without `LIMIT 1` or an aggregate that collapses the result, the statement works until the data holds a second match and then fails with a \"more than one row returned by a subquery\" error."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders WHERE user_id = (SELECT id FROM users WHERE name = 'alice')"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .where_multi_row_subqueries
+            .iter()
+            .filter(|subquery| !self.pins_unique_key(subquery))
+            .map(|subquery| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Subquery ({}) is compared as a scalar but may return several rows",
+                    subquery.raw
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Use IN or EXISTS for several rows, or add LIMIT 1 or an aggregate if one \
+                     row is meant"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Detects OFFSET without LIMIT
+///
+/// `OFFSET 100` on its own still reads and discards the first 100 rows and
+/// then returns every remaining one. Unlike PERF004 the offset size does not
+/// matter: the missing LIMIT is what makes the result unbounded.
+pub struct OffsetWithoutLimit;
+
+impl Rule for OffsetWithoutLimit {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF059",
+            name:     "OFFSET without LIMIT",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "`OFFSET` without `LIMIT` returns every row after the skipped ones"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "OFFSET skips rows but does not bound the result, so the query still scans the skipped rows and then returns the rest of the table. Paging code almost always meant to pair it with LIMIT or FETCH."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders ORDER BY id OFFSET 50"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if !query.unbounded_offset {
+            return vec![];
+        }
+        let info = self.info();
+        let offset = query
+            .offset
+            .map(|n| format!("OFFSET {}", n))
+            .unwrap_or_else(|| "OFFSET".to_string());
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "{} without LIMIT returns every row after the skipped ones",
+                offset
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Add a LIMIT (or FETCH FIRST n ROWS ONLY) for the page size".to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// ORDER BY inside a CTE that has no LIMIT
+///
+/// SQL gives a CTE no row order of its own: the outer query may join,
+/// aggregate, or scan it in any order, so the sort is wasted work. Engines
+/// that materialize the CTE still perform it first.
+pub struct OrderByInCte;
+
+impl Rule for OrderByInCte {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF062",
+            name:     "ORDER BY in CTE",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "CTE body is sorted with ORDER BY but has no LIMIT"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Rows of a CTE have no guaranteed order once the outer query reads them, so an ORDER BY inside it is discarded by the planner or, where the CTE is materialized, sorts every row for nothing. The sort only matters together with a LIMIT that picks the top rows."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "WITH recent AS (SELECT id, total FROM orders ORDER BY created_at DESC) SELECT id FROM recent WHERE total > 100"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .unlimited_ordered_ctes
+            .iter()
+            .map(|cte| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("CTE '{}' is sorted with ORDER BY but has no LIMIT", cte),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Move the ORDER BY to the outer query, or drop it if the result order does not matter"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
@@ -1,7 +1,10 @@
 use super::{Rule, RuleCategory, RuleInfo, Severity, Violation};
 use crate::{
-    query::{Query, QueryType},
-    schema::Schema
+    query::{
+        ColumnClause, ColumnComparison, JsonAccess, Query, QueryType, SortDir, SqlDialect,
+        WrittenLiteral
+    },
+    schema::{IndexInfo, Schema, TableInfo}
 };
 
 /// Check if WHERE/JOIN columns have indexes
@@ -35,13 +38,29 @@ impl Rule for MissingIndexOnFilterColumn {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "WHERE/JOIN column lacks index"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Filtering or joining on a column that no index covers forces a scan of the table."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE email = 'a@b.c'"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
         }
         let indexed_cols = self.get_indexed_columns();
         let mut violations = Vec::new();
-        for col in &query.where_cols {
+        for col in query
+            .where_cols
+            .iter()
+            .filter(|col| !is_view_column(&self.schema, query, col))
+        {
             let col_lower = col.to_lowercase();
             if !indexed_cols.iter().any(|c| c.to_lowercase() == col_lower) {
                 let info = self.info();
@@ -52,11 +71,17 @@ impl Rule for MissingIndexOnFilterColumn {
                     severity: info.severity,
                     category: info.category,
                     suggestion: Some(format!("Consider adding index on '{}'", col)),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 });
             }
         }
-        for col in &query.join_cols {
+        for col in query
+            .join_cols
+            .iter()
+            .filter(|col| !is_view_column(&self.schema, query, col))
+        {
             let col_lower = col.to_lowercase();
             if !indexed_cols.iter().any(|c| c.to_lowercase() == col_lower) {
                 let info = self.info();
@@ -67,7 +92,9 @@ impl Rule for MissingIndexOnFilterColumn {
                     severity: info.severity,
                     category: info.category,
                     suggestion: Some(format!("Consider adding index on '{}'", col)),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 });
             }
         }
@@ -106,6 +133,18 @@ impl Rule for ColumnNotInSchema {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Referenced column doesn't exist"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A filter, join, sort, or grouping column that is not defined in the schema is usually a typo or a stale reference."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE emial = 'a@b.c'"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let all_cols = self.get_all_columns();
         let mut violations = Vec::new();
@@ -131,7 +170,9 @@ impl Rule for ColumnNotInSchema {
                     severity: info.severity,
                     category: info.category,
                     suggestion: Some("Check column name spelling or table reference".to_string()),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 });
             }
         }
@@ -168,6 +209,18 @@ impl Rule for JoinOnNonIndexedColumn {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "JOIN column must lead an index of its own table"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A join degrades to a per-row scan when the joined table lacks an index whose leading column is the join column."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u.id FROM users u JOIN orders o ON o.user_id = u.id"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select || query.join_cols.is_empty() {
             return vec![];
@@ -190,13 +243,7 @@ impl Rule for JoinOnNonIndexedColumn {
                 else {
                     continue;
                 };
-                let leads_index = column.is_primary
-                    || table.indexes.iter().any(|idx| {
-                        idx.columns
-                            .first()
-                            .is_some_and(|first| first.eq_ignore_ascii_case(col))
-                    });
-                if !leads_index {
+                if !column.is_primary && !has_leading_index(table, &[col.as_str()]) {
                     let info = self.info();
                     violations.push(Violation {
                         rule_id: info.id,
@@ -214,7 +261,9 @@ impl Rule for JoinOnNonIndexedColumn {
                             table = table.name,
                             col = col
                         )),
-                        query_index
+                        query_index,
+                        location: None,
+                        origin: None
                     });
                 }
             }
@@ -223,6 +272,38 @@ impl Rule for JoinOnNonIndexedColumn {
     }
 }
 
+/// Returns true when some index of `table` starts with exactly `columns`,
+/// in any order. A single column also qualifies as the primary key. Views
+/// always qualify: whether an index applies depends on their base tables.
+fn has_leading_index(table: &TableInfo, columns: &[&str]) -> bool {
+    if table.is_view {
+        return true;
+    }
+    if let [col] = columns
+        && table
+            .columns
+            .iter()
+            .any(|c| c.is_primary && c.name.eq_ignore_ascii_case(col))
+    {
+        return true;
+    }
+    table.indexes.iter().any(|idx| {
+        idx.columns.len() >= columns.len()
+            && columns.iter().all(|col| {
+                idx.columns[..columns.len()]
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case(col))
+            })
+    })
+}
+
+/// Returns true when `reference` resolves to a column of a view
+fn is_view_column(schema: &Schema, query: &Query, reference: &str) -> bool {
+    schema
+        .resolve_column(query, reference)
+        .is_some_and(|(table, _)| table.is_view)
+}
+
 /// String column compared with a bare numeric literal
 ///
 /// Comparing a text column to a number forces the engine to cast one side
@@ -284,6 +365,18 @@ impl Rule for ImplicitTypeConversion {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Text column compared with numeric literal disables its index (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Comparing a text column to a number forces the engine to cast one side on every row; usually the column side is cast, which disables its index."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE phone = 5551234"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.where_cols.is_empty() {
             return vec![];
@@ -311,7 +404,9 @@ impl Rule for ImplicitTypeConversion {
                         "Quote the literal to match the column type; implicit casts disable indexes"
                             .to_string()
                     ),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 });
             }
         }
@@ -342,6 +437,18 @@ impl Rule for SuggestIndex {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "ORDER BY column could benefit from index"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Sorting on a column without an index requires a full sort of the result set."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users ORDER BY created_at"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -352,7 +459,11 @@ impl Rule for SuggestIndex {
             .values()
             .flat_map(|t| t.indexes.iter().flat_map(|idx| idx.columns.clone()))
             .collect();
-        for col in &query.order_cols {
+        for col in query
+            .order_cols
+            .iter()
+            .filter(|col| !is_view_column(&self.schema, query, col))
+        {
             let col_lower = col.to_lowercase();
             if !indexed_cols.iter().any(|c| c.to_lowercase() == col_lower) {
                 let info = self.info();
@@ -367,10 +478,2100 @@ impl Rule for SuggestIndex {
                         col_lower = col.to_lowercase(),
                         col = col
                     )),
-                    query_index
+                    query_index,
+                    location: None,
+                    origin: None
                 }];
             }
         }
         vec![]
     }
 }
+
+/// Columns with different collations or CHAR/NCHAR types compared directly
+///
+/// When a join or filter compares two columns whose collations differ, or
+/// where one side is a national (NCHAR/NVARCHAR) type and the other is not,
+/// the engine converts one side on every row. The converted side can no
+/// longer use its index; SQL Server in particular falls back to a scan.
+pub struct CollationMismatch {
+    schema: Schema
+}
+
+impl CollationMismatch {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+/// Returns true for national character types (NCHAR, NVARCHAR, NTEXT).
+fn is_national_text(data_type: &str) -> bool {
+    let ty = data_type.to_uppercase();
+    ty.starts_with("NCHAR")
+        || ty.starts_with("NVARCHAR")
+        || ty.starts_with("NTEXT")
+        || ty.starts_with("NATIONAL")
+}
+
+/// Returns true for any character type, national or not.
+fn is_text(data_type: &str) -> bool {
+    let ty = data_type.to_uppercase();
+    ty.contains("CHAR") || ty.contains("TEXT")
+}
+
+impl Rule for CollationMismatch {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF039",
+            name:     "Collation mismatch",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Columns with different collations or CHAR/NCHAR types compared (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Comparing columns whose collations or character types differ forces an implicit conversion of one side on every row, which disables that column's index."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u.id FROM users u JOIN contacts c ON u.email = c.email"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for ColumnComparison {
+            left,
+            right,
+            ..
+        } in &query.column_comparisons
+        {
+            let (Some((_, l)), Some((_, r))) = (
+                self.schema.resolve_column(query, left),
+                self.schema.resolve_column(query, right)
+            ) else {
+                continue;
+            };
+            let reason = match (&l.collation, &r.collation) {
+                (Some(lc), Some(rc)) if !lc.eq_ignore_ascii_case(rc) => {
+                    Some(format!("collations {} and {} differ", lc, rc))
+                }
+                _ if is_text(&l.data_type)
+                    && is_text(&r.data_type)
+                    && is_national_text(&l.data_type) != is_national_text(&r.data_type) =>
+                {
+                    Some(format!("types {} and {} differ", l.data_type, r.data_type))
+                }
+                _ => None
+            };
+            if let Some(reason) = reason {
+                let info = self.info();
+                violations.push(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "Comparison of '{}' with '{}' needs an implicit conversion: {}",
+                        left, right, reason
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(
+                        "Align the column definitions, or COLLATE/CAST the side whose index is not needed"
+                            .to_string()
+                    ),
+                    query_index,
+                    location: None,
+                    origin: None
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// INSERT whose VALUES tuple does not match the target column count
+///
+/// `INSERT INTO t (a, b) VALUES (1, 2, 3)` fails at runtime. With an explicit
+/// column list the first VALUES tuple is compared against it; without one,
+/// against the number of columns the schema declares for the table.
+pub struct InsertArityMismatch {
+    schema: Schema
+}
+
+impl InsertArityMismatch {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for InsertArityMismatch {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA013",
+            name:     "INSERT column count mismatch",
+            severity: Severity::Error,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "VALUES tuple size differs from the INSERT column list"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An INSERT whose VALUES tuple has more or fewer values than target columns is rejected by the database at runtime."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "INSERT INTO orders (id, user_id) VALUES (1, 2, 'new')",
+            "INSERT INTO orders VALUES (1, 2)"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Insert {
+            return vec![];
+        }
+        let Some(values) = query.insert_values_arity else {
+            return vec![];
+        };
+        let (expected, source) = if !query.insert_columns.is_empty() {
+            (query.insert_columns.len(), "the column list".to_string())
+        } else {
+            let Some(table) = query.tables.first().and_then(|name| {
+                self.schema
+                    .tables
+                    .values()
+                    .find(|t| t.name.eq_ignore_ascii_case(name))
+            }) else {
+                return vec![];
+            };
+            (table.columns.len(), format!("table '{}'", table.name))
+        };
+        if values == expected {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "INSERT supplies {} value(s) but {} has {} column(s)",
+                values, source, expected
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "List the target columns explicitly and supply one value per column".to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// GROUP BY columns without an index that leads with them
+///
+/// Without such an index the engine must hash or sort every row before it
+/// can aggregate. An index whose leading columns are the grouping columns
+/// lets it read rows already grouped and aggregate in a single stream.
+pub struct MissingIndexOnGroupBy {
+    schema: Schema
+}
+
+impl MissingIndexOnGroupBy {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for MissingIndexOnGroupBy {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF041",
+            name:     "Missing index on GROUP BY columns",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "GROUP BY columns do not lead any index (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Grouping on columns that no index leads with forces a hash or sort aggregate over the whole table; an index on the grouping columns allows a streaming aggregate."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT status, COUNT(*) FROM orders GROUP BY status"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.group_cols.is_empty() {
+            return vec![];
+        }
+        let mut violations = Vec::new();
+        for table_name in &query.tables {
+            let Some(table) = self
+                .schema
+                .tables
+                .values()
+                .find(|t| t.name.eq_ignore_ascii_case(table_name))
+            else {
+                continue;
+            };
+            let columns: Vec<&str> = query
+                .group_cols
+                .iter()
+                .map(|c| c.as_str())
+                .filter(|col| {
+                    table
+                        .columns
+                        .iter()
+                        .any(|c| c.name.eq_ignore_ascii_case(col))
+                })
+                .collect();
+            if columns.is_empty() || has_leading_index(table, &columns) {
+                continue;
+            }
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "GROUP BY on {} of table '{}' has no index leading with these columns",
+                    columns
+                        .iter()
+                        .map(|c| format!("'{}'", c))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    table.name
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "CREATE INDEX idx_{table_lower}_{cols_lower} ON {table}({cols}) to enable a streaming group aggregate",
+                    table_lower = table.name.to_lowercase(),
+                    cols_lower = columns.join("_").to_lowercase(),
+                    table = table.name,
+                    cols = columns.join(", ")
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Quoted column reference whose case differs from the schema (PostgreSQL)
+///
+/// PostgreSQL folds unquoted identifiers to lowercase but matches quoted
+/// ones exactly, so `"UserID"` does not find a column declared as `userid`.
+/// The case-insensitive SCHEMA002 cannot see this; the rule applies only
+/// when the schema was parsed with the PostgreSQL dialect.
+pub struct CaseSensitiveColumnMismatch {
+    schema: Schema
+}
+
+impl CaseSensitiveColumnMismatch {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+/// Name PostgreSQL stores for a declared identifier: the exact text inside
+/// double quotes, otherwise the lowercase fold.
+fn folded_identifier(name: &str) -> String {
+    match name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+        Some(quoted) => quoted.to_string(),
+        None => name.to_lowercase()
+    }
+}
+
+impl Rule for CaseSensitiveColumnMismatch {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA014",
+            name:     "Case-sensitive column mismatch",
+            severity: Severity::Error,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Quoted column differs in case from the schema (PostgreSQL)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "PostgreSQL matches quoted identifiers exactly and folds unquoted ones to lowercase, so a quoted column whose case differs from the declared name fails with \"column does not exist\"."
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if !matches!(self.schema.dialect, SqlDialect::PostgreSQL)
+            || query.quoted_identifiers.is_empty()
+        {
+            return vec![];
+        }
+        let tables: Vec<_> = self
+            .schema
+            .tables
+            .values()
+            .filter(|t| {
+                let folded = folded_identifier(&t.name);
+                query.tables.iter().any(|q| q.eq_ignore_ascii_case(&folded))
+            })
+            .collect();
+        let columns: Vec<String> = tables
+            .iter()
+            .flat_map(|t| t.columns.iter().map(|c| folded_identifier(&c.name)))
+            .collect();
+        let mut violations = Vec::new();
+        for ident in &query.quoted_identifiers {
+            if columns.iter().any(|c| c == ident)
+                || tables.iter().any(|t| folded_identifier(&t.name) == *ident)
+            {
+                continue;
+            }
+            let Some(declared) = columns.iter().find(|c| c.eq_ignore_ascii_case(ident)) else {
+                continue;
+            };
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Quoted column \"{}\" does not match declared column '{}' in case",
+                    ident, declared
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Write \"{}\" or leave the identifier unquoted",
+                    declared
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// ORDER BY on a different table than the one the WHERE clause filters
+///
+/// The planner drives a join from the filtered table, so rows reach the
+/// sort in that table's order and the whole joined result must be sorted
+/// before the first row is returned. An index on the ordering table that
+/// leads with the ORDER BY columns lets the planner drive the join from
+/// that side instead and skip the sort.
+pub struct OrderByCrossTable {
+    schema: Schema
+}
+
+impl OrderByCrossTable {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for OrderByCrossTable {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA015",
+            name:     "ORDER BY on a different table than the filter",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "ORDER BY columns belong to a different table than the WHERE filter (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "When a join filters one table but orders by another, the planner drives the join from the filtered table and must sort the entire joined result; an index on the ordering table that leads with the ORDER BY columns avoids the sort."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "SELECT o.id FROM orders o JOIN users u ON u.id = o.user_id WHERE o.status = 'open' ORDER BY u.email"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select
+            || query.tables.len() < 2
+            || query.where_cols.is_empty()
+            || query.order_cols.is_empty()
+        {
+            return vec![];
+        }
+        // Qualified references, so a column name shared by both tables
+        // still resolves
+        let resolved = |clause: ColumnClause| {
+            query
+                .column_refs
+                .iter()
+                .filter(move |r| r.clause == clause)
+                .filter_map(|r| self.schema.resolve_column(query, &r.reference))
+        };
+        let filtered: Vec<&str> = resolved(ColumnClause::Where)
+            .map(|(table, _)| table.name.as_str())
+            .collect();
+        if filtered.is_empty() {
+            return vec![];
+        }
+        let mut ordered: Vec<(&TableInfo, Vec<&str>)> = Vec::new();
+        for (table, column) in resolved(ColumnClause::OrderBy) {
+            match ordered.iter_mut().find(|(t, _)| t.name == table.name) {
+                Some((_, columns)) => {
+                    if !columns.contains(&column.name.as_str()) {
+                        columns.push(column.name.as_str());
+                    }
+                }
+                None => ordered.push((table, vec![column.name.as_str()]))
+            }
+        }
+        let mut violations = Vec::new();
+        for (table, columns) in ordered {
+            if filtered.contains(&table.name.as_str()) || has_leading_index(table, &columns) {
+                continue;
+            }
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "ORDER BY {} of table '{}' while WHERE filters '{}'; the joined result must be sorted",
+                    columns
+                        .iter()
+                        .map(|c| format!("'{}'", c))
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                    table.name,
+                    filtered[0]
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Order by a column of '{}', or CREATE INDEX idx_{}_{} ON {}({})",
+                    filtered[0],
+                    table.name.to_lowercase(),
+                    columns.join("_").to_lowercase(),
+                    table.name,
+                    columns.join(", ")
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Query references a column marked deprecated
+///
+/// Columns are deprecated with a `-- @deprecated <note>` comment in the
+/// schema or a `[schema.deprecated]` entry in the config file. The note
+/// becomes the suggestion, so each deprecation can point at its
+/// replacement.
+pub struct DeprecatedColumnUsage {
+    schema: Schema
+}
+
+impl DeprecatedColumnUsage {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for DeprecatedColumnUsage {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA016",
+            name:     "Deprecated column usage",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Query references a column marked deprecated (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Columns being phased out are annotated with `-- @deprecated` in the schema or listed under [schema.deprecated]; every query still reading or writing them blocks the removal."
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let comparisons = query
+            .column_comparisons
+            .iter()
+            .flat_map(|comparison| [&comparison.left, &comparison.right]);
+        let references = query
+            .projection_cols
+            .iter()
+            .chain(&query.where_cols)
+            .chain(&query.join_cols)
+            .chain(&query.order_cols)
+            .chain(&query.group_cols)
+            .chain(&query.having_cols)
+            .chain(&query.insert_columns)
+            .chain(comparisons);
+        let mut seen: Vec<(&str, &str)> = Vec::new();
+        let mut violations = Vec::new();
+        for reference in references {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            let Some(note) = &column.deprecated else {
+                continue;
+            };
+            if seen.contains(&(table.name.as_str(), column.name.as_str())) {
+                continue;
+            }
+            seen.push((table.name.as_str(), column.name.as_str()));
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("Column '{}.{}' is deprecated", table.name, column.name),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(if note.is_empty() {
+                    format!(
+                        "Stop using '{}.{}' before it is dropped",
+                        table.name, column.name
+                    )
+                } else {
+                    note.clone()
+                }),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects `JOIN ... USING` columns missing from one side of the join
+///
+/// USING requires the column on both sides. Depending on the engine a
+/// missing one is a runtime error or, through a same-named column of
+/// another joined table, a join on the wrong key that multiplies rows.
+pub struct InvalidUsingColumn {
+    schema: Schema
+}
+
+impl InvalidUsingColumn {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+
+    fn table(&self, name: &str) -> Option<&TableInfo> {
+        self.schema
+            .tables
+            .values()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Rule for InvalidUsingColumn {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF046",
+            name:     "USING column missing from joined table",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "JOIN ... USING names a column one side does not have (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "USING (col) joins on a column both sides must share. When one table lacks it, the query fails at runtime or binds to a same-named column of another table, joining on the wrong key and multiplying rows."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM users JOIN orders USING (status)"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let has_column = |table: &TableInfo, column: &str| {
+            table
+                .columns
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(column))
+        };
+        let mut violations = Vec::new();
+        for join in &query.join_using {
+            let Some(right) = self.table(&join.right) else {
+                continue;
+            };
+            let left: Option<Vec<&TableInfo>> =
+                join.left.iter().map(|name| self.table(name)).collect();
+            for column in &join.columns {
+                let mut missing = Vec::new();
+                if let Some(left) = &left
+                    && !left.iter().any(|t| has_column(t, column))
+                {
+                    missing.push(join.left.join(", "));
+                }
+                if !has_column(right, column) {
+                    missing.push(right.name.clone());
+                }
+                if missing.is_empty() {
+                    continue;
+                }
+                let info = self.info();
+                violations.push(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "USING column '{}' does not exist in {}",
+                        column,
+                        missing.join(" or ")
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(format!(
+                        "Join {} with an explicit ON condition on the matching columns",
+                        join.right
+                    )),
+                    query_index,
+                    location: None,
+                    origin: None
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Detects nullable columns compared in JOIN conditions
+///
+/// `NULL = NULL` is never true, so rows whose join key is NULL silently
+/// drop out of inner joins. A nullable join key is usually a missing
+/// constraint rather than intended behavior.
+pub struct NullableJoinColumn {
+    schema: Schema
+}
+
+impl NullableJoinColumn {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for NullableJoinColumn {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA017",
+            name:     "Nullable join column",
+            severity: Severity::Info,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "JOIN compares a column that allows NULL (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "NULL never equals anything, including another NULL, so rows with a NULL join key never match and vanish from inner joins. A join key that may be NULL usually lacks a NOT NULL constraint."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT u.name FROM users u JOIN orders o ON o.user_id = u.id"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let is_join_col = |reference: &str| {
+            let column = reference.rsplit('.').next().unwrap_or(reference);
+            query.join_cols.iter().any(|c| c == column)
+        };
+        let references = query
+            .column_comparisons
+            .iter()
+            .filter(|comparison| is_join_col(&comparison.left) && is_join_col(&comparison.right))
+            .flat_map(|comparison| [&comparison.left, &comparison.right]);
+        let mut seen: Vec<(&str, &str)> = Vec::new();
+        let mut violations = Vec::new();
+        for reference in references {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            if !column.is_nullable || column.is_primary {
+                continue;
+            }
+            if seen.contains(&(table.name.as_str(), column.name.as_str())) {
+                continue;
+            }
+            seen.push((table.name.as_str(), column.name.as_str()));
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Join column '{}.{}' is nullable; rows with NULL never match",
+                    table.name, column.name
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Declare '{}.{}' NOT NULL, or handle NULL keys explicitly",
+                    table.name, column.name
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects equality filters that match no index's leading column
+///
+/// A composite index `(a, b)` serves `WHERE a = ?` and `WHERE a = ? AND
+/// b = ?`, but not `WHERE b = ?` alone: B-tree lookups need a prefix of the
+/// key. The filtered column looks indexed, yet the engine scans.
+pub struct IndexPrefixMismatch {
+    schema: Schema
+}
+
+impl IndexPrefixMismatch {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for IndexPrefixMismatch {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF049",
+            name:     "Index prefix not filtered",
+            severity: Severity::Warning,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Equality filter skips the leading column of every index (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A composite index can only be searched from its leading column. When a query filters a table on columns that appear in an index but never on the leading column of any index, the index cannot narrow the search and the table is scanned."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE name = 'Ann'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let mut filtered: Vec<(&TableInfo, Vec<&str>)> = Vec::new();
+        for reference in &query.where_equality_cols {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            match filtered.iter_mut().find(|(t, _)| t.name == table.name) {
+                Some((_, columns)) => columns.push(&column.name),
+                None => filtered.push((table, vec![&column.name]))
+            }
+        }
+        let mut violations = Vec::new();
+        for (table, columns) in filtered {
+            let is_filtered = |name: &str| columns.iter().any(|c| c.eq_ignore_ascii_case(name));
+            let prefix_used = table
+                .indexes
+                .iter()
+                .filter_map(|idx| idx.columns.first())
+                .any(|leading| is_filtered(leading))
+                || table
+                    .columns
+                    .iter()
+                    .any(|c| c.is_primary && is_filtered(&c.name));
+            if prefix_used {
+                continue;
+            }
+            let Some(index) = table
+                .indexes
+                .iter()
+                .find(|idx| idx.columns.iter().any(|c| is_filtered(c)))
+            else {
+                continue;
+            };
+            let leading = &index.columns[0];
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Filter on {}.{} cannot use index {} ({}): its leading column '{}' is not filtered",
+                    table.name,
+                    columns.join(", "),
+                    index.name,
+                    index.columns.join(", "),
+                    leading
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Also filter on '{}', or add an index whose leading column is '{}'",
+                    leading, columns[0]
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Suggests a covering index when an indexed filter still needs table
+/// lookups
+///
+/// An index that serves the WHERE clause only locates rows; every projected
+/// column missing from it is fetched from the heap or clustered index, one
+/// random read per matching row. Primary key columns count as covered since
+/// clustered engines store them in every secondary index.
+pub struct SuggestCoveringIndex {
+    schema: Schema
+}
+
+impl SuggestCoveringIndex {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for SuggestCoveringIndex {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA021",
+            name:     "Covering index suggestion",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Indexed filter projects columns outside the index (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "When the index used by the WHERE clause does not contain every selected column, the engine looks up each matching row in the table to read the rest. An index that also includes the projected columns answers the query from the index alone."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT email FROM users WHERE phone = '555'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.projection_wildcard {
+            return vec![];
+        }
+        let mut tables: Vec<(&TableInfo, Vec<&str>, Vec<&str>)> = Vec::new();
+        let references = query
+            .where_cols
+            .iter()
+            .map(|c| (c, true))
+            .chain(query.projection_cols.iter().map(|c| (c, false)));
+        for (reference, is_filter) in references {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            let entry = match tables.iter_mut().position(|(t, ..)| t.name == table.name) {
+                Some(pos) => &mut tables[pos],
+                None => {
+                    tables.push((table, Vec::new(), Vec::new()));
+                    tables.last_mut().unwrap()
+                }
+            };
+            let list = if is_filter {
+                &mut entry.1
+            } else {
+                &mut entry.2
+            };
+            if !list.iter().any(|c| c.eq_ignore_ascii_case(&column.name)) {
+                list.push(&column.name);
+            }
+        }
+        let mut violations = Vec::new();
+        for (table, filtered, projected) in tables {
+            let is_filtered = |name: &str| filtered.iter().any(|c| c.eq_ignore_ascii_case(name));
+            let usable: Vec<_> = table
+                .indexes
+                .iter()
+                .filter(|idx| idx.columns.first().is_some_and(|c| is_filtered(c)))
+                .collect();
+            let Some(index) = usable.first() else {
+                continue;
+            };
+            let needed: Vec<&str> = filtered
+                .iter()
+                .chain(projected.iter().filter(|c| !is_filtered(c)))
+                .copied()
+                .filter(|name| {
+                    !table
+                        .columns
+                        .iter()
+                        .any(|c| c.is_primary && c.name.eq_ignore_ascii_case(name))
+                })
+                .collect();
+            let covered = usable.iter().any(|idx| {
+                needed
+                    .iter()
+                    .all(|name| idx.columns.iter().any(|c| c.eq_ignore_ascii_case(name)))
+            });
+            if covered {
+                continue;
+            }
+            let missing: Vec<&str> = projected
+                .iter()
+                .copied()
+                .filter(|name| needed.contains(name))
+                .filter(|name| !index.columns.iter().any(|c| c.eq_ignore_ascii_case(name)))
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Index {} ({}) serves the filter on {} but not projected column(s) {}; each match needs a table lookup",
+                    index.name,
+                    index.columns.join(", "),
+                    table.name,
+                    missing.join(", ")
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "CREATE INDEX idx_{}_covering ON {}({})",
+                    table.name.to_lowercase(),
+                    table.name,
+                    needed.join(", ")
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects JSON and array operators in WHERE without an index to serve them
+///
+/// A B-tree on a `jsonb` or array column cannot answer `data ->> 'k' = ?`
+/// or `tags @> ARRAY[...]`: path extraction needs an expression index on
+/// that exact path, and containment or key existence needs a GIN index.
+/// Without one, every row is decoded and tested.
+pub struct JsonAccessNoIndex {
+    schema: Schema
+}
+
+impl JsonAccessNoIndex {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+
+    /// Expression text without whitespace, parentheses, or case, so
+    /// `((data ->> 'k'))` and `data->>'k'` compare equal
+    fn expression_key(expr: &str) -> String {
+        expr.chars()
+            .filter(|c| !c.is_whitespace() && *c != '(' && *c != ')')
+            .flat_map(char::to_lowercase)
+            .collect()
+    }
+
+    fn has_supporting_index(table: &TableInfo, column: &str, access: &JsonAccess) -> bool {
+        if access.is_extraction() {
+            let rest = access
+                .expr
+                .strip_prefix(access.column.as_str())
+                .unwrap_or(&access.expr);
+            let key = Self::expression_key(&format!("{}{}", column, rest));
+            return table
+                .indexes
+                .iter()
+                .flat_map(|idx| &idx.columns)
+                .any(|c| Self::expression_key(c) == key);
+        }
+        table.indexes.iter().any(|idx| {
+            idx.method
+                .as_deref()
+                .is_some_and(|m| m.eq_ignore_ascii_case("GIN"))
+                && idx
+                    .columns
+                    .first()
+                    .is_some_and(|c| Self::expression_key(c) == column.to_lowercase())
+        })
+    }
+}
+
+impl Rule for JsonAccessNoIndex {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF054",
+            name:     "JSON access without index",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "WHERE filters on a JSON path or array operator no index supports (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Filtering on a JSON path (`data ->> 'status' = 'x'`) or on containment and key existence (`@>`, `?`) cannot use an ordinary B-tree index on the column. Unless an expression index on the path or a GIN index on the column exists, the engine decodes and tests every row."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders WHERE status ->> 'code' = 'new'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for access in &query.where_json_access {
+            let Some((table, column)) = self.schema.resolve_column(query, &access.column) else {
+                continue;
+            };
+            if table.is_view || Self::has_supporting_index(table, &column.name, access) {
+                continue;
+            }
+            let suggestion = if access.is_extraction() {
+                let rest = access
+                    .expr
+                    .strip_prefix(access.column.as_str())
+                    .unwrap_or(&access.expr);
+                format!(
+                    "CREATE INDEX idx_{}_{}_path ON {}(({}{}))",
+                    table.name.to_lowercase(),
+                    column.name.to_lowercase(),
+                    table.name,
+                    column.name,
+                    rest
+                )
+            } else {
+                format!(
+                    "CREATE INDEX idx_{}_{}_gin ON {} USING GIN ({})",
+                    table.name.to_lowercase(),
+                    column.name.to_lowercase(),
+                    table.name,
+                    column.name
+                )
+            };
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "JSON access {} on {}.{} has no supporting index; every row is scanned",
+                    access.expr, table.name, column.name
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(suggestion),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects comparisons against values an ENUM or CHECK constraint rules out
+///
+/// When a column only admits the labels of an `ENUM` type or the list of a
+/// `CHECK (col IN (...))` constraint, `WHERE col = 'other'` can never match
+/// a row. The literal is usually a typo or a value the domain no longer
+/// has.
+pub struct OutOfDomainComparison {
+    schema: Schema
+}
+
+impl OutOfDomainComparison {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+
+    /// MySQL compares ENUM members through the column collation, which is
+    /// case-insensitive by default; PostgreSQL labels match exactly
+    fn allows(&self, allowed: &[String], value: &str) -> bool {
+        if self.schema.dialect == SqlDialect::MySQL {
+            allowed.iter().any(|a| a.eq_ignore_ascii_case(value))
+        } else {
+            allowed.iter().any(|a| a == value)
+        }
+    }
+}
+
+impl Rule for OutOfDomainComparison {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA022",
+            name:     "Out-of-domain comparison",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "WHERE compares an ENUM or CHECK-constrained column to a value it cannot hold (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A column typed as an ENUM or restricted by `CHECK (col IN (...))` only ever holds the listed values. Comparing it to anything else with `=` or `IN` never matches a row, so the predicate is dead; the literal is usually a typo or a value removed from the domain."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders WHERE status = 'dispatched'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for comparison in &query.where_literal_comparisons {
+            let Some((table, column)) = self.schema.resolve_column(query, &comparison.column)
+            else {
+                continue;
+            };
+            let Some(allowed) = &column.allowed_values else {
+                continue;
+            };
+            let outside: Vec<String> = comparison
+                .values
+                .iter()
+                .filter(|value| !self.allows(allowed, value))
+                .map(|value| format!("'{}'", value))
+                .collect();
+            if outside.is_empty() {
+                continue;
+            }
+            let domain = allowed
+                .iter()
+                .map(|value| format!("'{}'", value))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "{}.{} never equals {}; it only allows {}",
+                    table.name,
+                    column.name,
+                    outside.join(", "),
+                    domain
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!("Compare {} to one of {}", column.name, domain)),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects SELECTs that read a large table without filtering it
+///
+/// Tables listed under `large_tables` in the `[schema]` config section are
+/// too big to ship whole to a client. A SELECT whose WHERE clause never
+/// touches such a table, and that is not bounded by LIMIT, pulls every row
+/// out of it, usually to filter in application code. A large table joined
+/// to a filtered one counts as bounded by the join.
+pub struct UnfilteredLargeTableRead {
+    schema: Schema
+}
+
+impl UnfilteredLargeTableRead {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for UnfilteredLargeTableRead {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC022",
+            name:     "Unfiltered large table read",
+            severity: Severity::Warning,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "SELECT reads a table marked large without a WHERE predicate on it (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Tables listed under `large_tables` in the [schema] config section hold more rows than a client should ever receive. Reading one without a WHERE predicate on it or a LIMIT transfers the whole table, typically to be filtered in application code; besides the load, every row is exposed to the caller. Filter in SQL instead."
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.limit.is_some() {
+            return vec![];
+        }
+        let mut filtered: Vec<&str> = Vec::new();
+        for reference in &query.where_cols {
+            let Some((table, _)) = self.schema.resolve_column(query, reference) else {
+                return vec![];
+            };
+            filtered.push(&table.name);
+        }
+        let joined: Vec<&str> = query
+            .join_cols
+            .iter()
+            .filter_map(|reference| self.schema.resolve_column(query, reference))
+            .map(|(table, _)| table.name.as_str())
+            .collect();
+        let mut reported: Vec<&str> = Vec::new();
+        let mut violations = Vec::new();
+        for (name, _) in &query.table_aliases {
+            let Some(table) = self
+                .schema
+                .tables
+                .values()
+                .find(|t| t.is_large && t.name.eq_ignore_ascii_case(name))
+            else {
+                continue;
+            };
+            let table_name = table.name.as_str();
+            if filtered.contains(&table_name)
+                || (!filtered.is_empty() && joined.contains(&table_name))
+                || reported.contains(&table_name)
+            {
+                continue;
+            }
+            reported.push(table_name);
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "Large table '{}' is read without a WHERE predicate on it",
+                    table.name
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Filter {} in the WHERE clause or bound the read with LIMIT",
+                    table.name
+                )),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// Detects INSERT and UPDATE literals a column's declaration rejects
+///
+/// An explicit `NULL` written to a `NOT NULL` column fails the statement,
+/// and a string that is not a number written to an integer column either
+/// fails or, in lenient MySQL modes, silently stores `0`. Only literals in
+/// `VALUES` rows with a column list and in `SET` assignments are checked.
+/// Primary keys are skipped for `NULL`, since MySQL `AUTO_INCREMENT` and
+/// SQLite rowid columns replace it with the next ID.
+pub struct WriteConstraintViolation {
+    schema: Schema
+}
+
+impl WriteConstraintViolation {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+/// Returns true for whole-number types (`INT`, `BIGINT UNSIGNED`, `UInt32`).
+fn is_integer(data_type: &str) -> bool {
+    let ty = data_type.to_uppercase();
+    let base = ty.split(['(', ' ']).next().unwrap_or_default();
+    let bits = base.trim_start_matches('U').strip_prefix("INT");
+    matches!(
+        base,
+        "INTEGER"
+            | "BIGINT"
+            | "SMALLINT"
+            | "TINYINT"
+            | "MEDIUMINT"
+            | "SERIAL"
+            | "BIGSERIAL"
+            | "SMALLSERIAL"
+    ) || bits.is_some_and(|bits| bits.is_empty() || bits.parse::<u16>().is_ok())
+}
+
+impl Rule for WriteConstraintViolation {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA023",
+            name:     "Write violates column constraint",
+            severity: Severity::Error,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "INSERT or UPDATE writes NULL to a NOT NULL column or text to an integer column (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Writing an explicit NULL into a NOT NULL column is rejected at runtime (Error). Writing a non-numeric string into an integer column is rejected by strict engines and silently stored as 0 by lenient MySQL modes (Warning)."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "INSERT INTO orders (id, user_id) VALUES (1, 'alice')",
+            "UPDATE orders SET user_id = 'n/a' WHERE id = 7"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let mut reported = Vec::new();
+        for write in &query.column_writes {
+            let Some((table, column)) = self.schema.resolve_column(query, &write.column) else {
+                continue;
+            };
+            let (severity, message, suggestion) = match &write.value {
+                WrittenLiteral::Null if !column.is_nullable && !column.is_primary => (
+                    Severity::Error,
+                    format!(
+                        "{}.{} is NOT NULL but is assigned NULL",
+                        table.name, column.name
+                    ),
+                    format!("Supply a value for {} or drop NOT NULL", column.name)
+                ),
+                WrittenLiteral::String(value)
+                    if is_integer(&column.data_type) && value.trim().parse::<i128>().is_err() =>
+                {
+                    (
+                        Severity::Warning,
+                        format!(
+                            "{}.{} is {} but is assigned the string '{}'",
+                            table.name, column.name, column.data_type, value
+                        ),
+                        format!("Assign an integer to {}", column.name)
+                    )
+                }
+                _ => continue
+            };
+            if reported.contains(&message) {
+                continue;
+            }
+            reported.push(message.clone());
+            let info = self.info();
+            violations.push(Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message,
+                severity,
+                category: info.category,
+                suggestion: Some(suggestion),
+                query_index,
+                location: None,
+                origin: None
+            });
+        }
+        violations
+    }
+}
+
+/// ORDER BY directions that the only matching index was declared against
+///
+/// An index declared with `DESC` columns stores rows in that order. When
+/// every index leading with the ORDER BY columns disagrees with the
+/// requested directions, the engine either reads the index backward, which
+/// some engines cannot do for descending indexes, or sorts the rows itself.
+/// Indexes without `DESC` columns are left to the other index rules, since
+/// reading them backward is routine.
+pub struct OrderDirectionIndexMismatch {
+    schema: Schema
+}
+
+impl OrderDirectionIndexMismatch {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+/// `col DIR, ...` listing of columns with their directions
+fn directed_columns<'a>(columns: impl Iterator<Item = (&'a str, SortDir)>) -> String {
+    columns
+        .map(|(column, dir)| format!("{} {}", column, dir.keyword()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Rule for OrderDirectionIndexMismatch {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA024",
+            name:     "ORDER BY against index direction",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "ORDER BY directions conflict with the DESC index on those columns (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An index declared with DESC columns returns rows in that order. Ordering the other way needs a backward scan, which some engines do not support for descending indexes, and a mix of directions the index does not have cannot use it at all, so the rows are sorted after they are read."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM events ORDER BY occurred_at LIMIT 50"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.order_directions.is_empty() {
+            return vec![];
+        }
+        let mut table: Option<&TableInfo> = None;
+        let mut order: Vec<(&str, SortDir)> = Vec::new();
+        for (reference, dir) in &query.order_directions {
+            let Some((resolved, column)) = self.schema.resolve_column(query, reference) else {
+                return vec![];
+            };
+            if table.is_some_and(|t| t.name != resolved.name) {
+                return vec![];
+            }
+            table = Some(resolved);
+            order.push((column.name.as_str(), *dir));
+        }
+        let Some(table) = table else {
+            return vec![];
+        };
+        if let [(column, _)] = order.as_slice()
+            && table
+                .columns
+                .iter()
+                .any(|c| c.is_primary && c.name.eq_ignore_ascii_case(column))
+        {
+            return vec![];
+        }
+        let candidates: Vec<_> = table
+            .indexes
+            .iter()
+            .filter(|idx| {
+                idx.directions.len() >= order.len()
+                    && order
+                        .iter()
+                        .zip(&idx.directions)
+                        .all(|((column, _), (indexed, _))| indexed.eq_ignore_ascii_case(column))
+            })
+            .collect();
+        let serves = |idx: &&IndexInfo| {
+            let same = order
+                .iter()
+                .zip(&idx.directions)
+                .all(|((_, dir), (_, indexed))| dir == indexed);
+            let reversed = order
+                .iter()
+                .zip(&idx.directions)
+                .all(|((_, dir), (_, indexed))| dir.reversed() == *indexed);
+            same || (reversed && !idx.has_desc())
+        };
+        if candidates.iter().any(serves) {
+            return vec![];
+        }
+        let Some(index) = candidates.into_iter().find(|idx| idx.has_desc()) else {
+            return vec![];
+        };
+        let reversed = order
+            .iter()
+            .zip(&index.directions)
+            .all(|((_, dir), (_, indexed))| dir.reversed() == *indexed);
+        let indexed = directed_columns(
+            index.directions[..order.len()]
+                .iter()
+                .map(|(column, dir)| (column.as_str(), *dir))
+        );
+        let requested = directed_columns(order.iter().copied());
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: if reversed {
+                format!(
+                    "ORDER BY {} is the reverse of index {} ({}); it can only be read backward",
+                    requested, index.name, indexed
+                )
+            } else {
+                format!(
+                    "ORDER BY {} mixes directions unlike index {} ({}); the rows are sorted after reading",
+                    requested, index.name, indexed
+                )
+            },
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(format!(
+                "ORDER BY {} to follow the index, or declare an index on {}({})",
+                indexed, table.name, requested
+            )),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Cast of a column to the type the schema already declares for it
+///
+/// `CAST(id AS INTEGER)` on an `INT` column converts nothing, but the
+/// engine still evaluates it on every row, and in a filter it hides the
+/// column from its index. Type synonyms (`INT`/`INTEGER`/`INT4`,
+/// `VARCHAR`/`CHARACTER VARYING`, `SERIAL`/`INTEGER`) are compared by their
+/// canonical name; length and precision arguments must match exactly, since
+/// a narrower cast truncates.
+pub struct RedundantCast {
+    schema: Schema
+}
+
+impl RedundantCast {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+/// Canonical spelling of `data_type` for comparing type synonyms:
+/// uppercased, with the base name mapped to one synonym and whitespace
+/// removed from its arguments.
+fn canonical_type(data_type: &str) -> String {
+    let upper = data_type.to_uppercase();
+    let (base, args) = match upper.find('(') {
+        Some(pos) => (&upper[..pos], &upper[pos..]),
+        None => (upper.as_str(), "")
+    };
+    let base = base.split_whitespace().collect::<Vec<_>>().join(" ");
+    let canonical = match base.as_str() {
+        "INT" | "INT4" | "SERIAL" | "SERIAL4" => "INTEGER",
+        "INT8" | "BIGSERIAL" | "SERIAL8" => "BIGINT",
+        "INT2" | "SMALLSERIAL" | "SERIAL2" => "SMALLINT",
+        "BOOL" => "BOOLEAN",
+        "CHARACTER VARYING" | "CHAR VARYING" => "VARCHAR",
+        "CHARACTER" => "CHAR",
+        "DOUBLE PRECISION" | "FLOAT8" => "DOUBLE",
+        "FLOAT4" => "REAL",
+        "DECIMAL" => "NUMERIC",
+        "TIMESTAMP WITHOUT TIME ZONE" => "TIMESTAMP",
+        "TIMESTAMP WITH TIME ZONE" => "TIMESTAMPTZ",
+        other => other
+    };
+    let args: String = args.chars().filter(|c| !c.is_whitespace()).collect();
+    format!("{}{}", canonical, args)
+}
+
+impl Rule for RedundantCast {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF060",
+            name:     "Redundant cast",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "CAST of a column to the type it already has (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Casting a column to its declared type converts nothing, yet the cast is still evaluated on every row, and in a WHERE or JOIN condition it wraps the column in an expression its index cannot serve."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM orders WHERE CAST(user_id AS INTEGER) = 42"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .column_casts
+            .iter()
+            .filter_map(|cast| {
+                let (table, column) = self.schema.resolve_column(query, &cast.column)?;
+                if canonical_type(&column.data_type) != canonical_type(&cast.data_type) {
+                    return None;
+                }
+                Some(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "{} casts {}.{} to {}, the type it is already declared as",
+                        cast.expr, table.name, column.name, column.data_type
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(format!("Remove the cast and use {} directly", cast.column)),
+                    query_index,
+                    location: None,
+                    origin: None
+                })
+            })
+            .collect()
+    }
+}
+
+/// `DISTINCT ON` columns without an index that leads with them (PostgreSQL)
+///
+/// PostgreSQL answers `DISTINCT ON (a, b)` by sorting the rows on `a, b`
+/// and keeping the first of each group. An index leading with those
+/// columns supplies the rows already in that order, so the sort, and with
+/// `LIMIT` most of the scan, is skipped.
+pub struct DistinctOnNoIndex {
+    schema: Schema
+}
+
+impl DistinctOnNoIndex {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for DistinctOnNoIndex {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF061",
+            name:     "DISTINCT ON without index",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "DISTINCT ON columns do not lead any index (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "DISTINCT ON keeps the first row of each group in ORDER BY order, so PostgreSQL sorts the rows on the DISTINCT ON columns first. Without an index leading with those columns that is a full sort of every matching row; with one, the rows are read in order."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT DISTINCT ON (user_id) id, user_id FROM orders ORDER BY user_id, id DESC"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.distinct_on.is_empty() {
+            return vec![];
+        }
+        let mut table: Option<&TableInfo> = None;
+        let mut columns: Vec<&str> = Vec::new();
+        for reference in &query.distinct_on {
+            let Some((resolved, column)) = self.schema.resolve_column(query, reference) else {
+                return vec![];
+            };
+            if table.is_some_and(|t| t.name != resolved.name) {
+                return vec![];
+            }
+            table = Some(resolved);
+            columns.push(column.name.as_str());
+        }
+        let Some(table) = table else {
+            return vec![];
+        };
+        if has_leading_index(table, &columns) {
+            return vec![];
+        }
+        let mut index_columns: Vec<&str> = columns.clone();
+        for (reference, _) in &query.order_directions {
+            match self.schema.resolve_column(query, reference) {
+                Some((resolved, column))
+                    if resolved.name == table.name
+                        && !index_columns
+                            .iter()
+                            .any(|c| c.eq_ignore_ascii_case(&column.name)) =>
+                {
+                    index_columns.push(column.name.as_str());
+                }
+                _ => {}
+            }
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "DISTINCT ON ({}) of table '{}' has no index leading with these columns",
+                columns.join(", "),
+                table.name
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(format!(
+                "CREATE INDEX idx_{table_lower}_{cols_lower} ON {table}({cols}) to read the rows in DISTINCT ON order",
+                table_lower = table.name.to_lowercase(),
+                cols_lower = columns.join("_").to_lowercase(),
+                table = table.name,
+                cols = index_columns.join(", ")
+            )),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// INSERT that lists a column the database generates itself
+///
+/// Serial, auto-increment, identity and generated columns get their value
+/// from the database. Supplying one explicitly is rejected for `GENERATED
+/// ALWAYS` columns and, for sequences, leaves the sequence behind the stored
+/// keys so a later INSERT collides with them.
+pub struct InsertIntoGeneratedColumn {
+    schema: Schema
+}
+
+impl InsertIntoGeneratedColumn {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for InsertIntoGeneratedColumn {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA026",
+            name:     "INSERT into generated column",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "INSERT column list names a serial, identity or generated column"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Serial, auto-increment, identity and generated columns are filled by the database. Writing one explicitly fails for GENERATED ALWAYS columns and, for sequence-backed keys, leaves the sequence behind the stored values so later inserts hit duplicate keys."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["INSERT INTO events (id, occurred_at) VALUES (1, NOW())"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Insert || query.insert_columns.is_empty() {
+            return vec![];
+        }
+        let Some(table) = query.tables.first().and_then(|name| {
+            self.schema
+                .tables
+                .values()
+                .find(|t| t.name.eq_ignore_ascii_case(name))
+        }) else {
+            return vec![];
+        };
+        let info = self.info();
+        query
+            .insert_columns
+            .iter()
+            .filter_map(|name| {
+                table
+                    .columns
+                    .iter()
+                    .find(|c| c.is_generated && c.name.eq_ignore_ascii_case(name))
+            })
+            .map(|column| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "INSERT writes column '{}.{}', which the database generates",
+                    table.name, column.name
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Omit '{}' from the column list and let the database assign it",
+                    column.name
+                )),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Filter, join or sort on the columns of a plain view
+///
+/// A view stores no rows and has no indexes of its own; the planner inlines
+/// its query, so whether a predicate on a view column can use an index
+/// depends on the base table column it maps to and on whether the view's
+/// own joins, aggregates or expressions let the predicate be pushed down.
+pub struct ViewAssumedIndexed {
+    schema: Schema
+}
+
+impl ViewAssumedIndexed {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for ViewAssumedIndexed {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA027",
+            name:     "View assumed indexed",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "WHERE, JOIN or ORDER BY uses view columns, which rely on base table indexes"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A view has no indexes of its own. A filter or sort on a view column can only use an index of the base table column behind it, and only when the view's joins, aggregates and expressions let the planner push the predicate down to that table."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM active_users WHERE name = 'a'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select {
+            return vec![];
+        }
+        let mut views: Vec<(&TableInfo, Vec<&str>)> = Vec::new();
+        let references = query
+            .where_cols
+            .iter()
+            .chain(&query.join_cols)
+            .chain(&query.order_cols);
+        for reference in references {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            if !table.is_view {
+                continue;
+            }
+            match views.iter_mut().find(|(t, _)| t.name == table.name) {
+                Some((_, columns)) => {
+                    if !columns.iter().any(|c| c.eq_ignore_ascii_case(&column.name)) {
+                        columns.push(column.name.as_str());
+                    }
+                }
+                None => views.push((table, vec![column.name.as_str()]))
+            }
+        }
+        let info = self.info();
+        views
+            .into_iter()
+            .map(|(view, columns)| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!(
+                    "'{}' is a view without indexes of its own; using {} relies on its base tables",
+                    view.name,
+                    columns.join(", ")
+                ),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(format!(
+                    "Check the plan, or index the base table columns behind {} of '{}'",
+                    columns.join(", "),
+                    view.name
+                )),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// GROUP BY a full primary key plus further columns or DISTINCT
+///
+/// Every group of a grouping that contains a table's primary key holds one
+/// row of that table, so other columns of the same table are functionally
+/// dependent on the key and a DISTINCT over the groups removes nothing.
+pub struct GroupByPrimaryKeyRedundant {
+    schema: Schema
+}
+
+impl GroupByPrimaryKeyRedundant {
+    pub fn new(schema: Schema) -> Self {
+        Self {
+            schema
+        }
+    }
+}
+
+impl Rule for GroupByPrimaryKeyRedundant {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "PERF063",
+            name:     "Redundant grouping on primary key",
+            severity: Severity::Info,
+            category: RuleCategory::Performance
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "GROUP BY a primary key also lists columns of its table or adds DISTINCT (needs schema)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A primary key identifies one row, so grouping by it already makes every group a single row of that table. Further GROUP BY columns of the same table are determined by the key and only widen the grouping key the engine hashes or sorts, and DISTINCT over such groups has nothing left to remove. PostgreSQL and MySQL accept the table's other columns in SELECT when only the key is grouped."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id, name, COUNT(*) FROM users GROUP BY id, name"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        if query.query_type != QueryType::Select || query.group_cols.is_empty() {
+            return vec![];
+        }
+        let mut grouped: Vec<(&TableInfo, Vec<&str>)> = Vec::new();
+        for reference in &query.group_cols {
+            let Some((table, column)) = self.schema.resolve_column(query, reference) else {
+                continue;
+            };
+            match grouped.iter_mut().find(|(t, _)| t.name == table.name) {
+                Some((_, columns)) => {
+                    if !columns.iter().any(|c| c.eq_ignore_ascii_case(&column.name)) {
+                        columns.push(column.name.as_str());
+                    }
+                }
+                None => grouped.push((table, vec![column.name.as_str()]))
+            }
+        }
+        let distinct = query.has_distinct && query.distinct_on.is_empty();
+        let info = self.info();
+        let mut violations = Vec::new();
+        for (table, columns) in grouped {
+            let key = primary_key_columns(table);
+            let is_key = |name: &str| key.iter().any(|k| k.eq_ignore_ascii_case(name));
+            if key.is_empty()
+                || !key
+                    .iter()
+                    .all(|k| columns.iter().any(|c| c.eq_ignore_ascii_case(k)))
+            {
+                continue;
+            }
+            let extra: Vec<&str> = columns.iter().copied().filter(|c| !is_key(c)).collect();
+            if !extra.is_empty() {
+                violations.push(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "GROUP BY contains the primary key ({}) of '{}'; the other columns ({}) add nothing to the grouping",
+                        key.join(", "),
+                        table.name,
+                        extra.join(", ")
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(format!(
+                        "GROUP BY {} alone; on engines without functional dependency support, wrap {} in MAX() instead",
+                        key.join(", "),
+                        extra.join(", ")
+                    )),
+                    query_index,
+                    location: None,
+                    origin: None
+                });
+            }
+            if distinct {
+                violations.push(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "DISTINCT over a GROUP BY on the primary key ({}) of '{}' removes no rows",
+                        key.join(", "),
+                        table.name
+                    ),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some("Drop DISTINCT; the groups are already unique".to_string()),
+                    query_index,
+                    location: None,
+                    origin: None
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Columns of the primary key of `table`, from a table-level constraint or
+/// a column marked `PRIMARY KEY`
+fn primary_key_columns(table: &TableInfo) -> Vec<&str> {
+    match &table.primary_key {
+        Some(columns) => columns.iter().map(String::as_str).collect(),
+        None => table
+            .columns
+            .iter()
+            .filter(|c| c.is_primary)
+            .map(|c| c.name.as_str())
+            .collect()
+    }
+}
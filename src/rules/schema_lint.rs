@@ -0,0 +1,223 @@
+//! Lints that check the schema DDL itself.
+//!
+//! Unlike [`Rule`](super::Rule)s these do not look at queries: each runs
+//! once per analysis against the parsed [`Schema`] and reports authoring
+//! mistakes in the `CREATE TABLE` statements.
+
+use super::{RuleCategory, RuleInfo, SchemaLint, Severity, Violation};
+use crate::{query::SqlDialect, schema::Schema};
+
+/// Detects a table that declares the same column twice
+///
+/// Most engines reject the DDL, but the parser accepts it, so a bad merge
+/// of two migrations goes unnoticed until the schema is applied.
+pub struct DuplicateColumnName;
+
+impl SchemaLint for DuplicateColumnName {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA019",
+            name:     "Duplicate column name",
+            severity: Severity::Error,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "CREATE TABLE declares a column name more than once"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Column names are unique within a table, compared case-insensitively by most engines. A repeated name, usually left behind by a bad merge, makes the CREATE TABLE fail when it is applied, and until then every analysis of the table sees only one of the definitions."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["CREATE TABLE users (id INT PRIMARY KEY, email TEXT, Email VARCHAR(255))"]
+    }
+
+    fn check(&self, schema: &Schema) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for table in schema.tables.values().filter(|t| !t.is_view) {
+            let mut reported: Vec<&str> = Vec::new();
+            for (pos, column) in table.columns.iter().enumerate() {
+                let name = column.name.as_str();
+                if reported.iter().any(|r| r.eq_ignore_ascii_case(name)) {
+                    continue;
+                }
+                let count = table.columns[pos..]
+                    .iter()
+                    .filter(|c| c.name.eq_ignore_ascii_case(name))
+                    .count();
+                if count < 2 {
+                    continue;
+                }
+                reported.push(name);
+                let info = self.info();
+                violations.push(Violation {
+                    rule_id:     info.id,
+                    rule_name:   info.name,
+                    message:     format!(
+                        "Table '{}' declares column '{}' {} times",
+                        table.name, name, count
+                    ),
+                    severity:    info.severity,
+                    category:    info.category,
+                    suggestion:  Some(format!(
+                        "Remove or rename the extra definitions of '{}'",
+                        name
+                    )),
+                    query_index: 0,
+                    location:    None,
+                    origin:      None
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Detects character columns declared without a length
+///
+/// The standard gives bare `CHAR` a length of 1, MySQL rejects a bare
+/// `VARCHAR`, and other engines pick their own limits, so the same DDL
+/// means different things per engine. PostgreSQL reads a bare `VARCHAR` as
+/// unlimited on purpose, so only `CHAR` is checked there.
+pub struct VarcharWithoutLength;
+
+impl SchemaLint for VarcharWithoutLength {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA020",
+            name:     "VARCHAR without length",
+            severity: Severity::Info,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Character column type has no length"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A VARCHAR, CHAR, or NVARCHAR column without a length depends on the engine: standard SQL makes CHAR one character long, MySQL rejects a bare VARCHAR, and others apply an implementation limit. Declaring the length states the intended size and keeps the DDL portable."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR)"]
+    }
+
+    fn check(&self, schema: &Schema) -> Vec<Violation> {
+        let info = self.info();
+        let severity = if schema.dialect == SqlDialect::MySQL {
+            Severity::Warning
+        } else {
+            info.severity
+        };
+        let mut violations = Vec::new();
+        for table in schema.tables.values().filter(|t| !t.is_view) {
+            for column in &table.columns {
+                let data_type = column.data_type.trim().to_uppercase();
+                let bare = match data_type.as_str() {
+                    "VARCHAR" | "NVARCHAR" | "CHARACTER VARYING" => {
+                        schema.dialect != SqlDialect::PostgreSQL
+                    }
+                    "CHAR" | "NCHAR" | "CHARACTER" => true,
+                    _ => false
+                };
+                if !bare {
+                    continue;
+                }
+                violations.push(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "Column '{}.{}' is declared as {} without a length",
+                        table.name, column.name, data_type
+                    ),
+                    severity,
+                    category: info.category,
+                    suggestion: Some(format!(
+                        "Declare the maximum length, e.g. {}(255)",
+                        data_type
+                    )),
+                    query_index: 0,
+                    location: None,
+                    origin: None
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// Detects a table with no primary key
+///
+/// Rows without a key cannot be told apart, so duplicates go unnoticed,
+/// row-based replication has to scan the table for every change, and
+/// tools that sync or deduplicate by key skip the table. ClickHouse
+/// MergeTree tables are keyed by their `ORDER BY` or `PRIMARY KEY` clause;
+/// ClickHouse engines outside the MergeTree family have no key at all and
+/// are not checked.
+pub struct MissingPrimaryKey;
+
+impl SchemaLint for MissingPrimaryKey {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SCHEMA025",
+            name:     "Missing primary key",
+            severity: Severity::Warning,
+            category: RuleCategory::Style
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "CREATE TABLE declares no primary key"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Without a primary key, rows cannot be identified: duplicate rows go unnoticed, row-based replication must search the table for each changed row, and tools that sync or deduplicate by key cannot handle the table. Declare a PRIMARY KEY, on a surrogate ID if no natural key exists."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["CREATE TABLE audit_entries (action VARCHAR(50), created_at TIMESTAMP)"]
+    }
+
+    fn check(&self, schema: &Schema) -> Vec<Violation> {
+        let info = self.info();
+        schema
+            .tables
+            .values()
+            .filter(|table| {
+                !table.is_view
+                    && table.primary_key.is_none()
+                    && !table.columns.iter().any(|c| c.is_primary)
+            })
+            .filter(|table| {
+                let sorted = table
+                    .order_by
+                    .as_ref()
+                    .is_some_and(|cols| !cols.is_empty() && cols[0] != "tuple()");
+                match table.engine.as_deref() {
+                    Some(engine) if schema.dialect == SqlDialect::ClickHouse => {
+                        engine.ends_with("MergeTree") && !sorted
+                    }
+                    _ => !sorted
+                }
+            })
+            .map(|table| Violation {
+                rule_id:     info.id,
+                rule_name:   info.name,
+                message:     format!("Table '{}' has no primary key", table.name),
+                severity:    info.severity,
+                category:    info.category,
+                suggestion:  Some(format!(
+                    "Declare a PRIMARY KEY on '{}', e.g. a surrogate id column",
+                    table.name
+                )),
+                query_index: 0,
+                location:    None,
+                origin:      None
+            })
+            .collect()
+    }
+}
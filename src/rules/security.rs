@@ -1,5 +1,5 @@
 use super::{Rule, RuleCategory, RuleInfo, Severity, Violation};
-use crate::query::{Query, QueryType};
+use crate::query::{MergeAction, PlaceholderStyle, Query, QueryType};
 
 /// Detects TRUNCATE statements which can instantly delete all data
 ///
@@ -20,6 +20,18 @@ impl Rule for TruncateDetected {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Instant data deletion without logging"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "TRUNCATE removes every row without firing DELETE triggers and with minimal logging, so the data loss is hard or impossible to roll back."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["TRUNCATE TABLE users"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Truncate {
             return vec![];
@@ -39,7 +51,9 @@ impl Rule for TruncateDetected {
                 "Use DELETE with WHERE for safer data removal, or ensure backups exist"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -57,6 +71,18 @@ impl Rule for MissingWhereInUpdate {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Potentially dangerous bulk update"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An UPDATE without WHERE rewrites every row of the table."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["UPDATE users SET active = false"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Update {
             return vec![];
@@ -70,7 +96,9 @@ impl Rule for MissingWhereInUpdate {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Add WHERE clause to limit affected rows".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -95,6 +123,18 @@ impl Rule for DropDetected {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Permanent data/schema destruction"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "DROP permanently removes the object and its data, with cascading effects on foreign keys, views, and stored procedures."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["DROP TABLE users"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Drop {
             return vec![];
@@ -118,7 +158,9 @@ impl Rule for DropDetected {
             suggestion: Some(
                 "Ensure this is intentional and backups exist before dropping".to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -128,7 +170,8 @@ impl Rule for DropDetected {
 /// EXEC/EXECUTE/PREPARE run SQL assembled at runtime; when any part of that
 /// string comes from user input, the construct is an injection vector that
 /// static analysis of the outer statement cannot see through. Flags the
-/// SQL Server, Oracle/PostgreSQL, and MySQL spellings.
+/// SQL Server, Oracle/PostgreSQL, and MySQL spellings, except a PREPARE of a
+/// statement that takes bind parameters.
 pub struct DynamicSqlExecution;
 
 /// Statement openers that hand a runtime string to the SQL engine.
@@ -144,6 +187,18 @@ impl Rule for DynamicSqlExecution {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`EXEC`/`EXECUTE`/`PREPARE` runs a string assembled at runtime"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Dynamic SQL runs a string assembled at runtime; when any part of it comes from user input the construct is an injection vector that static analysis cannot see through."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["EXECUTE stmt"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let trimmed = upper.trim_start();
@@ -153,6 +208,14 @@ impl Rule for DynamicSqlExecution {
         {
             return vec![];
         }
+        // PREPARE ... AS <statement with $1/?/:name> is the parameterized
+        // form; MySQL `PREPARE stmt FROM @sql` runs a user variable instead
+        if trimmed.starts_with("PREPARE ")
+            && query.is_parameterized()
+            && query.placeholder_style != Some(PlaceholderStyle::AtSign)
+        {
+            return vec![];
+        }
         let info = self.info();
         vec![Violation {
             rule_id: info.id,
@@ -164,7 +227,9 @@ impl Rule for DynamicSqlExecution {
                 "Validate every input that reaches the executed string and prefer parameterized execution (sp_executesql, prepared statements with bound parameters)"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -190,6 +255,18 @@ impl Rule for PrivilegeChange {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Privilege changes belong in reviewed migrations; broad grants escalate to Error"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "An unnoticed GRANT widens the attack surface permanently. Broad grants (ALL PRIVILEGES, ON *.*, TO PUBLIC, SUPERUSER) escalate to Error."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["GRANT SELECT ON users TO reporting"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let trimmed = upper.trim_start();
@@ -223,7 +300,9 @@ impl Rule for PrivilegeChange {
                 "Keep GRANT/REVOKE in reviewed migrations and grant the narrowest privileges needed"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -315,6 +394,18 @@ impl Rule for HardcodedCredential {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Plaintext secret in `IDENTIFIED BY`, `SET PASSWORD`, or a sensitive column"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Secrets committed inside query files leak through source control, slow query logs, and error logs."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["UPDATE users SET password = 'hunter2' WHERE id = 1"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         let ddl_credential = upper.contains("IDENTIFIED BY '")
@@ -337,7 +428,9 @@ impl Rule for HardcodedCredential {
                 "Use environment variables, a secret manager, or parameterized values instead of plaintext secrets"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -389,6 +482,18 @@ impl Rule for InjectionTautology {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Always-true `OR` tautology (`OR 1 = 1`)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A comparison of two identical literals joined by OR is always true and is the classic fingerprint of injected input widening a WHERE clause to every row."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE name = 'x' OR 1 = 1"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         let upper = query.raw.to_uppercase();
         if !has_or_tautology(&upper) {
@@ -406,7 +511,9 @@ impl Rule for InjectionTautology {
                 "If this query is built in application code, replace string concatenation with parameterized queries"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -424,6 +531,18 @@ impl Rule for MissingWhereInDelete {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Potentially dangerous bulk delete"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A DELETE without WHERE removes every row of the table."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["DELETE FROM users"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Delete {
             return vec![];
@@ -437,9 +556,714 @@ impl Rule for MissingWhereInDelete {
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some("Add WHERE clause to limit deleted rows".to_string()),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
     }
 }
+
+/// LIKE patterns dominated by wildcards
+///
+/// Patterns such as `'%%%'` or `'%_%_%'` match nearly everything, cannot use
+/// an index, and force the engine to backtrack across every row. When the
+/// pattern comes from user input, a crafted wildcard string is a cheap way to
+/// make the database burn CPU (a ReDoS-style attack on LIKE matching).
+pub struct PathologicalLikePattern;
+
+/// Returns true when a LIKE pattern is dominated by `%` and `_` wildcards.
+///
+/// A pattern is pathological when it starts with a run of two or more
+/// wildcards, contains four or more `%`, or has at least three wildcards
+/// making up half of its characters. Characters escaped with a backslash are
+/// treated as literals.
+fn is_pathological_like(pattern: &str) -> bool {
+    let mut wildcards = 0usize;
+    let mut percents = 0usize;
+    let mut total = 0usize;
+    let mut leading_run = 0usize;
+    let mut in_leading_run = true;
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        total += 1;
+        let is_wildcard = match c {
+            '\\' => {
+                chars.next();
+                false
+            }
+            '%' | '_' => true,
+            _ => false
+        };
+        if is_wildcard {
+            wildcards += 1;
+            if c == '%' {
+                percents += 1;
+            }
+            if in_leading_run {
+                leading_run += 1;
+            }
+        } else {
+            in_leading_run = false;
+        }
+    }
+    leading_run >= 2 || percents >= 4 || (wildcards >= 3 && wildcards * 2 >= total)
+}
+
+impl Rule for PathologicalLikePattern {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC015",
+            name:     "Pathological LIKE pattern",
+            severity: Severity::Warning,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Wildcard-heavy LIKE pattern (`'%%%'`)"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A LIKE pattern made mostly of `%` and `_` wildcards cannot use an index and makes the matcher backtrack over every row; user-supplied patterns of this shape are a cheap denial-of-service vector."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT id FROM users WHERE name LIKE '%%%'"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .like_patterns
+            .iter()
+            .filter(|pattern| is_pathological_like(pattern))
+            .map(|pattern| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: format!("LIKE pattern '{}' is dominated by wildcards", pattern),
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Anchor the pattern with literal characters, validate user-supplied patterns, or use full-text search"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Detects GRANT statements that hand out overly broad access
+///
+/// Unlike the text-based SEC005, this rule inspects the parsed GRANT: it
+/// flags `ALL [PRIVILEGES]`, grants to `PUBLIC`, `everyone`, or a MySQL
+/// account reachable from any host (`'user'@'%'`), and `WITH GRANT OPTION`,
+/// which lets the grantee pass the privileges on without review.
+pub struct OverlyBroadGrant;
+
+/// Returns true for grantees that stand for every user or every host.
+fn is_public_grantee(grantee: &str) -> bool {
+    grantee.eq_ignore_ascii_case("PUBLIC")
+        || grantee.eq_ignore_ascii_case("everyone")
+        || grantee == "%"
+        || grantee.ends_with("@%")
+}
+
+impl Rule for OverlyBroadGrant {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC016",
+            name:     "Overly broad GRANT",
+            severity: Severity::Error,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "GRANT ALL, grants to PUBLIC or any host, or WITH GRANT OPTION"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Granting every privilege, granting to every user or host, or allowing grantees to re-grant removes least privilege from the database and is rarely reverted once shipped."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["GRANT ALL PRIVILEGES ON users TO PUBLIC WITH GRANT OPTION"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let Some(grant) = &query.grant else {
+            return vec![];
+        };
+        let mut reasons = Vec::new();
+        if grant.all_privileges {
+            reasons.push("grants ALL privileges".to_string());
+        }
+        let public: Vec<&str> = grant
+            .grantees
+            .iter()
+            .map(|g| g.as_str())
+            .filter(|g| is_public_grantee(g))
+            .collect();
+        if !public.is_empty() {
+            reasons.push(format!("grants to {}", public.join(", ")));
+        }
+        if grant.with_grant_option {
+            reasons.push("allows re-granting WITH GRANT OPTION".to_string());
+        }
+        if reasons.is_empty() {
+            return vec![];
+        }
+        let target = grant.object.as_deref().unwrap_or("the database");
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!("GRANT on {} {}", target, reasons.join("; ")),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Grant only the privileges needed to named roles and drop WITH GRANT OPTION"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Detects `INSERT ... SELECT *` copying whole rows between tables
+///
+/// The values land in the target by position, so adding, dropping, or
+/// reordering a column in the source silently shifts data into the wrong
+/// target columns, or copies sensitive fields nobody meant to move.
+pub struct InsertSelectStar;
+
+impl Rule for InsertSelectStar {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC017",
+            name:     "INSERT ... SELECT *",
+            severity: Severity::Warning,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "`INSERT ... SELECT *` maps columns by position and breaks on schema drift"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "INSERT ... SELECT assigns values by position, so a column added to or reordered in the source table silently shifts data into the wrong target columns or copies fields that were never meant to move."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["INSERT INTO users SELECT * FROM contacts"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let Some(source) = &query.insert_select else {
+            return vec![];
+        };
+        if !source.projection_wildcard {
+            return vec![];
+        }
+        let target = query.tables.first().map(|t| t.as_str()).unwrap_or("table");
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!(
+                "INSERT into '{}' copies SELECT * from {}",
+                target,
+                source.tables.join(", ")
+            ),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "List the target columns and select the matching source columns explicitly"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Whether the SELECT list holds only aggregates and there is no GROUP BY,
+/// so the query returns exactly one row whatever the ordering
+fn returns_single_aggregate_row(query: &Query) -> bool {
+    !query.has_union
+        && !query.projection_wildcard
+        && matches!(
+            query.ungrouped_selects.as_slice(),
+            [select] if !select.aggregates.is_empty() && select.columns.is_empty()
+        )
+}
+
+/// A `SELECT DISTINCT ... LIMIT` without `ORDER BY` is already reported by
+/// PERF057, which covers the same unordered cut
+fn distinct_limit_reported(query: &Query) -> bool {
+    query.query_type == QueryType::Select && query.has_distinct && !query.has_order_by
+}
+
+/// Detects `LIMIT` without `ORDER BY`
+///
+/// Without an ordering the database returns whichever rows it reaches first,
+/// so `SELECT role FROM users WHERE ... LIMIT 1` can pick a different row
+/// after a plan change, which matters when the result drives an
+/// authorization decision.
+pub struct LimitWithoutOrderBy;
+
+impl Rule for LimitWithoutOrderBy {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC018",
+            name:     "LIMIT without ORDER BY",
+            severity: Severity::Info,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "LIMIT without ORDER BY returns an arbitrary subset of rows"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Without ORDER BY the database may return any matching rows, and the choice can change with the plan, so a LIMIT 1 read used for an authorization check may see a different row from one run to the next."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT name FROM users WHERE manager_id = 1 LIMIT 1"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let Some(limit) = query.limit else {
+            return vec![];
+        };
+        if !query.order_cols.is_empty()
+            || returns_single_aggregate_row(query)
+            || distinct_limit_reported(query)
+        {
+            return vec![];
+        }
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!("LIMIT {} without ORDER BY returns arbitrary rows", limit),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Add an ORDER BY on a unique key so the same rows are returned every time"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Detects statements that write query results to the server's filesystem
+///
+/// `SELECT ... INTO OUTFILE` / `INTO DUMPFILE` (MySQL, ClickHouse) and
+/// `COPY ... TO 'file'` / `TO PROGRAM` (PostgreSQL) copy data out of the
+/// database onto the host, where it escapes access control and auditing.
+/// With `INTO DUMPFILE` or `TO PROGRAM` the same statement can also drop
+/// files or run commands on the server.
+pub struct FileExport;
+
+impl Rule for FileExport {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC019",
+            name:     "Export to server file",
+            severity: Severity::Error,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Statement writes data to a file on the database server"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "INTO OUTFILE, INTO DUMPFILE, and COPY ... TO write query results to the database host, outside the access control and auditing of the database. In application code they are a common exfiltration step of SQL injection, and DUMPFILE or COPY TO PROGRAM can plant files or execute commands."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &[
+            "SELECT email FROM users INTO OUTFILE '/tmp/users.csv'",
+            "COPY users TO '/tmp/users.csv'"
+        ]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let Some(export) = &query.file_export else {
+            return vec![];
+        };
+        let info = self.info();
+        vec![Violation {
+            rule_id: info.id,
+            rule_name: info.name,
+            message: format!("{} writes to '{}' on the server", export.form, export.target),
+            severity: info.severity,
+            category: info.category,
+            suggestion: Some(
+                "Return rows to the client and write files there; revoke the FILE privilege or pg_write_server_files from application roles"
+                    .to_string()
+            ),
+            query_index,
+            location: None,
+            origin: None
+        }]
+    }
+}
+
+/// Detects MERGE statements that can delete rows without a narrow condition
+///
+/// A `WHEN MATCHED THEN DELETE` with no `AND` condition removes every target
+/// row the ON condition pairs with a source row, and `WHEN NOT MATCHED BY
+/// SOURCE THEN DELETE` removes every target row missing from the source. An
+/// empty or partial source then deletes most of the table.
+pub struct UnsafeMerge;
+
+impl Rule for UnsafeMerge {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC020",
+            name:     "Unrestricted MERGE delete",
+            severity: Severity::Error,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "MERGE deletes rows without a restrictive condition"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "A DELETE branch of a MERGE removes every target row its WHEN clause selects. Without an AND condition on the branch, or with an ON condition that compares no columns, the set of deleted rows depends only on what the source happens to contain, and a stale or empty source wipes the table."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["MERGE INTO users u USING contacts c ON u.email = c.email WHEN MATCHED THEN DELETE"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let Some(merge) = &query.merge else {
+            return vec![];
+        };
+        let broad_on = merge.on_columns.is_empty();
+        let info = self.info();
+        merge
+            .clauses
+            .iter()
+            .filter(|clause| {
+                clause.action == MergeAction::Delete && (broad_on || clause.predicate.is_none())
+            })
+            .map(|clause| Violation {
+                rule_id: info.id,
+                rule_name: info.name,
+                message: if broad_on {
+                    format!(
+                        "WHEN {} THEN DELETE with an ON condition that compares no columns",
+                        clause.kind
+                    )
+                } else {
+                    format!("WHEN {} THEN DELETE has no AND condition", clause.kind)
+                },
+                severity: info.severity,
+                category: info.category,
+                suggestion: Some(
+                    "Join on key columns and restrict the DELETE branch, e.g. WHEN MATCHED AND t.status = 'archived' THEN DELETE"
+                        .to_string()
+                ),
+                query_index,
+                location: None,
+                origin: None
+            })
+            .collect()
+    }
+}
+
+/// Variables whose changes [`DangerousSessionVariable`] always flags
+pub const DEFAULT_SENSITIVE_VARIABLES: &[&str] = &[
+    // MySQL
+    "autocommit",
+    "foreign_key_checks",
+    "general_log",
+    "local_infile",
+    "require_secure_transport",
+    "secure_file_priv",
+    "sql_log_bin",
+    "sql_mode",
+    "sql_safe_updates",
+    "unique_checks",
+    // PostgreSQL
+    "row_security",
+    "search_path",
+    "session_replication_role"
+];
+
+/// Sensitive on/off variables whose protection is switched off by a false
+/// value; setting them on is not reported
+const SAFE_WHEN_ON: &[&str] = &[
+    "autocommit",
+    "foreign_key_checks",
+    "general_log",
+    "require_secure_transport",
+    "row_security",
+    "sql_log_bin",
+    "sql_safe_updates",
+    "unique_checks"
+];
+
+/// Sensitive on/off variables that are only dangerous when switched on
+const SAFE_WHEN_OFF: &[&str] = &["local_infile"];
+
+/// Detects SET statements that weaken security-relevant server variables
+///
+/// Relaxing `sql_mode`, turning off `foreign_key_checks` or `sql_log_bin`,
+/// or pointing `search_path` at another schema silently changes how every
+/// later statement on the connection is validated, logged, or resolved.
+/// `SET GLOBAL local_infile = 1` is reported as an error: it lets any client
+/// of the server read files from its host with `LOAD DATA LOCAL`. Setting
+/// an on/off variable to its protective value, or any variable to
+/// `DEFAULT`, is not reported.
+pub struct DangerousSessionVariable {
+    variables: Vec<String>
+}
+
+impl DangerousSessionVariable {
+    /// Flag the defaults plus the `extra` variable names
+    pub fn new(extra: &[String]) -> Self {
+        let mut variables: Vec<String> = DEFAULT_SENSITIVE_VARIABLES
+            .iter()
+            .map(|v| v.to_string())
+            .collect();
+        for name in extra {
+            let name = name.trim().to_lowercase();
+            if !name.is_empty() && !variables.contains(&name) {
+                variables.push(name);
+            }
+        }
+        Self {
+            variables
+        }
+    }
+}
+
+/// Returns true for the values MySQL accepts as "enabled".
+fn is_enabled_value(value: &str) -> bool {
+    let value = value.trim_matches(|c| matches!(c, '\'' | '"'));
+    value == "1" || value.eq_ignore_ascii_case("ON") || value.eq_ignore_ascii_case("TRUE")
+}
+
+/// Returns true for the values MySQL accepts as "disabled".
+fn is_disabled_value(value: &str) -> bool {
+    let value = value.trim_matches(|c| matches!(c, '\'' | '"'));
+    value == "0" || value.eq_ignore_ascii_case("OFF") || value.eq_ignore_ascii_case("FALSE")
+}
+
+/// Whether assigning `value` to the sensitive variable `name` weakens it
+fn weakens(name: &str, value: &str) -> bool {
+    let unquoted = value.trim_matches(|c| matches!(c, '\'' | '"'));
+    if unquoted.eq_ignore_ascii_case("DEFAULT") {
+        return false;
+    }
+    if SAFE_WHEN_ON.contains(&name) {
+        return !is_enabled_value(value);
+    }
+    if SAFE_WHEN_OFF.contains(&name) {
+        return !is_disabled_value(value);
+    }
+    !(name == "session_replication_role" && unquoted.eq_ignore_ascii_case("origin"))
+}
+
+impl Rule for DangerousSessionVariable {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC021",
+            name:     "Dangerous session variable",
+            severity: Severity::Warning,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "SET weakens a security-relevant session or global variable"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Variables such as sql_mode, foreign_key_checks, sql_log_bin, local_infile, and search_path control validation, auditing, file access, and name resolution. Changing them from application SQL weakens guarantees the rest of the system relies on, and GLOBAL changes affect every connection until the server restarts."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SET GLOBAL local_infile = 1", "SET foreign_key_checks = 0"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .session_settings
+            .iter()
+            .filter(|setting| {
+                self.variables.iter().any(|v| v == setting.name.as_str())
+                    && weakens(&setting.name, &setting.value)
+            })
+            .map(|setting| {
+                let global = setting
+                    .scope
+                    .as_deref()
+                    .is_some_and(|s| s.eq_ignore_ascii_case("GLOBAL"));
+                let scope = match &setting.scope {
+                    Some(scope) => format!("{} ", scope),
+                    None => String::new()
+                };
+                let infile = global
+                    && setting.name == "local_infile"
+                    && is_enabled_value(&setting.value);
+                Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!(
+                        "SET {}{} = {} weakens a security-relevant variable",
+                        scope, setting.name, setting.value
+                    ),
+                    severity: if infile {
+                        Severity::Error
+                    } else {
+                        info.severity
+                    },
+                    category: info.category,
+                    suggestion: Some(if infile {
+                        "Keep local_infile disabled on the server; load files from the client with a dedicated tool instead".to_string()
+                    } else {
+                        "Configure the variable on the server or per role and keep it out of application SQL".to_string()
+                    }),
+                    query_index,
+                    location: None,
+                    origin: None
+                }
+            })
+            .collect()
+    }
+}
+
+/// Detects tables qualified with another database or a schema outside the
+/// allowlist
+///
+/// A three-part `database.schema.table` name reaches into another database,
+/// which usually means a query written for one environment or tenant reads
+/// the data of another. Two-part `schema.table` names are checked only when
+/// `rules.allowed_schemas` is configured.
+pub struct CrossSchemaReference {
+    allowed: Vec<String>
+}
+
+impl CrossSchemaReference {
+    /// Allow the `allowed` schemas; entries may also be `database.schema`
+    pub fn new(allowed: &[String]) -> Self {
+        Self {
+            allowed: allowed
+                .iter()
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect()
+        }
+    }
+
+    fn is_allowed(&self, qualifier: &str) -> bool {
+        self.allowed.iter().any(|a| a == qualifier)
+    }
+}
+
+/// Splits a table name into its lowercased, unquoted dotted components.
+///
+/// Dots inside a quoted identifier such as `"my.schema"` belong to the
+/// identifier and do not split it.
+fn name_parts(table: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut part = String::new();
+    let mut closing = None;
+    for c in table.chars() {
+        match (closing, c) {
+            (Some(end), c) if c == end => closing = None,
+            (Some(_), c) => part.push(c),
+            (None, '"' | '`') => closing = Some(c),
+            (None, '[') => closing = Some(']'),
+            (None, '.') => parts.push(std::mem::take(&mut part).to_lowercase()),
+            (None, c) => part.push(c)
+        }
+    }
+    parts.push(part.to_lowercase());
+    parts
+}
+
+impl Rule for CrossSchemaReference {
+    fn info(&self) -> RuleInfo {
+        RuleInfo {
+            id:       "SEC023",
+            name:     "Cross-schema reference",
+            severity: Severity::Warning,
+            category: RuleCategory::Security
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        "Table is qualified with another database or a schema outside the allowlist"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Fully qualified names such as prod.public.users bypass the connection's default database and search path, so the same query can read another tenant's or environment's data. Application SQL should stay within the schemas it is deployed against."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM prod.public.users"]
+    }
+
+    fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
+        let info = self.info();
+        query
+            .tables
+            .iter()
+            .filter_map(|table| {
+                let parts = name_parts(table);
+                let (qualifier, what) = match parts.as_slice() {
+                    [database, schema, _] => (
+                        format!("{}.{}", database, schema),
+                        format!("database '{}'", database)
+                    ),
+                    [schema, _] if !self.allowed.is_empty() => {
+                        (schema.clone(), format!("schema '{}'", schema))
+                    }
+                    _ => return None
+                };
+                if self.is_allowed(&qualifier) {
+                    return None;
+                }
+                Some(Violation {
+                    rule_id: info.id,
+                    rule_name: info.name,
+                    message: format!("Table '{}' is referenced through {}", table, what),
+                    severity: info.severity,
+                    category: info.category,
+                    suggestion: Some(
+                        "Use the connection's default database and search path, or add the schema to rules.allowed_schemas".to_string()
+                    ),
+                    query_index,
+                    location: None,
+                    origin: None
+                })
+            })
+            .collect()
+    }
+}
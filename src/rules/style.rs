@@ -14,6 +14,18 @@ impl Rule for SelectStar {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Explicit column list preferred"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "SELECT * couples the query to the table layout and fetches columns the caller never reads."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT * FROM users LIMIT 10"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -31,7 +43,9 @@ impl Rule for SelectStar {
                 suggestion: Some(
                     "Specify explicit columns to improve clarity and performance".to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
@@ -101,6 +115,18 @@ impl Rule for OrdinalInOrderOrGroupBy {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "`ORDER BY 1` breaks silently when the SELECT list changes"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "ORDER BY 1, 2 sorts by SELECT-list position, so adding or reordering selected columns silently changes the sort with no error."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT name, email FROM users ORDER BY 1"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
@@ -127,7 +153,9 @@ impl Rule for OrdinalInOrderOrGroupBy {
                 "Ordinals silently break when the SELECT list changes; use explicit column names"
                     .to_string()
             ),
-            query_index
+            query_index,
+            location: None,
+            origin: None
         }]
     }
 }
@@ -145,27 +173,44 @@ impl Rule for MissingTableAlias {
         }
     }
 
+    fn description(&self) -> &'static str {
+        "Multi-table queries should use aliases"
+    }
+
+    fn explanation(&self) -> &'static str {
+        "Short table aliases make column ownership explicit and keep multi-table queries readable."
+    }
+
+    fn examples(&self) -> &'static [&'static str] {
+        &["SELECT users.id FROM users JOIN orders ON users.id = orders.user_id"]
+    }
+
     fn check(&self, query: &Query, query_index: usize) -> Vec<Violation> {
         if query.query_type != QueryType::Select {
             return vec![];
         }
-        if query.tables.len() <= 1 {
-            return vec![];
-        }
-        let upper = query.raw.to_uppercase();
-        let has_aliases = upper.contains(" AS ") || query.tables.iter().any(|t| t.contains(' '));
-        if !has_aliases && !query.join_cols.is_empty() {
+        // Set operations combine independent single-table SELECTs; aliases
+        // only matter when one SELECT reads several tables.
+        let multi_table =
+            query.table_aliases.len() > 1 && (!query.has_union || !query.join_cols.is_empty());
+        let unaliased: Vec<&str> = query.tables_without_aliases().collect();
+        if multi_table && !unaliased.is_empty() {
             let info = self.info();
             return vec![Violation {
                 rule_id: info.id,
                 rule_name: info.name,
-                message: "Multi-table query without table aliases".to_string(),
+                message: format!(
+                    "Multi-table query without table aliases: {}",
+                    unaliased.join(", ")
+                ),
                 severity: info.severity,
                 category: info.category,
                 suggestion: Some(
                     "Add short aliases (e.g., users u, orders o) for readability".to_string()
                 ),
-                query_index
+                query_index,
+                location: None,
+                origin: None
             }];
         }
         vec![]
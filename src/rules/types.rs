@@ -5,14 +5,27 @@
 //! - [`RuleCategory`] - Rule categories (Performance, Style, Security)
 //! - [`Violation`] - Individual rule violations with context
 //! - [`AnalysisReport`] - Complete analysis results
+//! - [`ReportSummary`] - Violation counts without the violations
+//! - [`Statistics`] - Aggregate figures of a run, including query complexity
+//! - [`ExitStatus`] - Exit code of a run and the reason for it
 
-use serde::Serialize;
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::{AppError, config_error},
+    query::{Query, Span}
+};
+
+/// Number of rules [`Statistics::top_rules`] keeps
+const TOP_RULES: usize = 10;
 
 /// Severity level of a rule violation.
 ///
 /// Ordered from lowest to highest severity for sorting purposes.
 /// Exit codes are determined by the highest severity violation found.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Severity {
     /// Informational suggestion, does not affect exit code
     Info,
@@ -32,8 +45,25 @@ impl std::fmt::Display for Severity {
     }
 }
 
+impl std::str::FromStr for Severity {
+    type Err = AppError;
+
+    /// Parses `error`, `warning` (or `warn`), and `info`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "error" => Ok(Self::Error),
+            "warning" | "warn" => Ok(Self::Warning),
+            "info" => Ok(Self::Info),
+            _ => Err(config_error(format!(
+                "Invalid severity '{}' (expected error, warning, or info)",
+                s
+            )))
+        }
+    }
+}
+
 /// Category of a rule for grouping and filtering.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum RuleCategory {
     /// Rules that detect potential performance issues
     Performance,
@@ -53,6 +83,23 @@ impl std::fmt::Display for RuleCategory {
     }
 }
 
+impl std::str::FromStr for RuleCategory {
+    type Err = AppError;
+
+    /// Parses `performance`, `style`, and `security`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "performance" => Ok(Self::Performance),
+            "style" => Ok(Self::Style),
+            "security" => Ok(Self::Security),
+            _ => Err(config_error(format!(
+                "Invalid category '{}' (expected performance, style, or security)",
+                s
+            )))
+        }
+    }
+}
+
 /// A single rule violation found in a query.
 ///
 /// Contains all context needed to display and filter the violation,
@@ -71,8 +118,14 @@ pub struct Violation {
     pub category:    RuleCategory,
     /// Optional suggestion for fixing the issue
     pub suggestion:  Option<String>,
-    /// Zero-based index of the query in the input
-    pub query_index: usize
+    /// Zero-based index of the query in the input; always 0 for schema
+    /// lints, which are not tied to a query
+    pub query_index: usize,
+    /// Position of the offending statement in the input, when known
+    pub location:    Option<Span>,
+    /// Name of the query from a JSON or NDJSON input, when it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin:      Option<String>
 }
 
 /// Metadata about a rule for identification and configuration.
@@ -96,19 +149,44 @@ pub struct RuleInfo {
 #[derive(Debug, Clone, Serialize)]
 pub struct AnalysisReport {
     /// All violations found during analysis
-    pub violations:    Vec<Violation>,
+    pub violations:         Vec<Violation>,
+    /// Violations found in the schema itself, independent of any query
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub schema_violations:  Vec<Violation>,
     /// Number of queries analyzed
-    pub queries_count: usize,
+    pub queries_count:      usize,
     /// Number of rules executed
-    pub rules_count:   usize
+    pub rules_count:        usize,
+    /// Zero-based indices of statements no rule can analyze (e.g., `BEGIN`)
+    pub unanalyzed:         Vec<usize>,
+    /// Queries analyzed per second, measured when timings are enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queries_per_second: Option<f64>,
+    /// Time each rule spent, slowest first, measured when timings are
+    /// enabled
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub rule_timings:       Vec<RuleTiming>
+}
+
+/// Time one rule spent checking the queries of a run
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleTiming {
+    /// Rule identifier
+    pub rule_id: &'static str,
+    /// Total time in nanoseconds, summed over all worker threads
+    pub nanos:   u64
 }
 
 impl AnalysisReport {
     pub fn new(queries_count: usize, rules_count: usize) -> Self {
         Self {
             violations: Vec::new(),
+            schema_violations: Vec::new(),
             queries_count,
-            rules_count
+            rules_count,
+            unanalyzed: Vec::new(),
+            queries_per_second: None,
+            rule_timings: Vec::new()
         }
     }
 
@@ -116,24 +194,218 @@ impl AnalysisReport {
         self.violations.push(violation);
     }
 
+    /// Query violations followed by schema violations
+    pub fn all_violations(&self) -> impl Iterator<Item = &Violation> {
+        self.violations.iter().chain(&self.schema_violations)
+    }
+
     pub fn error_count(&self) -> usize {
-        self.violations
-            .iter()
+        self.all_violations()
             .filter(|v| v.severity == Severity::Error)
             .count()
     }
 
     pub fn warning_count(&self) -> usize {
-        self.violations
-            .iter()
+        self.all_violations()
             .filter(|v| v.severity == Severity::Warning)
             .count()
     }
 
     pub fn info_count(&self) -> usize {
-        self.violations
-            .iter()
+        self.all_violations()
             .filter(|v| v.severity == Severity::Info)
             .count()
     }
+    /// Count violations per category.
+    ///
+    /// Every category is present in the result, with zero when it has no
+    /// violations.
+    pub fn category_summary(&self) -> BTreeMap<RuleCategory, usize> {
+        let mut summary = BTreeMap::from([
+            (RuleCategory::Performance, 0),
+            (RuleCategory::Style, 0),
+            (RuleCategory::Security, 0)
+        ]);
+        for violation in self.all_violations() {
+            *summary.entry(violation.category).or_insert(0) += 1;
+        }
+        summary
+    }
+
+    /// Highest severity among all violations, which determines the exit code
+    pub fn highest_severity(&self) -> Option<Severity> {
+        self.all_violations().map(|v| v.severity).max()
+    }
+
+    /// Exit code counting only violations at or above `threshold`, with a
+    /// short reason such as `2 errors found`.
+    ///
+    /// Errors yield `2`, any other counted violation `1`, and none `0`.
+    pub fn exit_status(&self, threshold: Severity) -> ExitStatus {
+        let counted: Vec<(Severity, usize)> = [Severity::Error, Severity::Warning, Severity::Info]
+            .into_iter()
+            .filter(|severity| *severity >= threshold)
+            .map(|severity| {
+                let count = self
+                    .all_violations()
+                    .filter(|v| v.severity == severity)
+                    .count();
+                (severity, count)
+            })
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        let Some(&(highest, _)) = counted.first() else {
+            let threshold = match threshold {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+                Severity::Info => "info"
+            };
+            return ExitStatus {
+                exit_code:   0,
+                exit_reason: if self.all_violations().next().is_none() {
+                    "no violations found".to_string()
+                } else {
+                    format!("no violations at or above {}", threshold)
+                }
+            };
+        };
+        let found: Vec<String> = counted
+            .iter()
+            .filter(|(severity, _)| highest != Severity::Error || *severity == Severity::Error)
+            .map(|&(severity, count)| {
+                let noun = match (severity, count) {
+                    (Severity::Error, 1) => "error",
+                    (Severity::Error, _) => "errors",
+                    (Severity::Warning, 1) => "warning",
+                    (Severity::Warning, _) => "warnings",
+                    (Severity::Info, 1) => "info violation",
+                    (Severity::Info, _) => "info violations"
+                };
+                format!("{} {}", count, noun)
+            })
+            .collect();
+        ExitStatus {
+            exit_code:   if highest == Severity::Error { 2 } else { 1 },
+            exit_reason: format!("{} found", found.join(" and "))
+        }
+    }
+
+    /// Condense the report into counts by severity and category.
+    pub fn summary(&self) -> ReportSummary {
+        ReportSummary {
+            errors:           self.error_count(),
+            warnings:         self.warning_count(),
+            info:             self.info_count(),
+            by_category:      self.category_summary(),
+            highest_severity: self.highest_severity(),
+            unanalyzed:       self.unanalyzed.len()
+        }
+    }
+
+    /// Aggregate the report and the `queries` it was produced from.
+    ///
+    /// Complexity figures cover the analyzed queries only; statements listed
+    /// in [`unanalyzed`](Self::unanalyzed) are skipped.
+    pub fn to_statistics(&self, queries: &[Query]) -> Statistics {
+        let mut by_severity = BTreeMap::from([
+            (Severity::Info, 0),
+            (Severity::Warning, 0),
+            (Severity::Error, 0)
+        ]);
+        let mut by_rule: BTreeMap<&'static str, usize> = BTreeMap::new();
+        for violation in self.all_violations() {
+            *by_severity.entry(violation.severity).or_insert(0) += 1;
+            *by_rule.entry(violation.rule_id).or_insert(0) += 1;
+        }
+        let mut top_rules: Vec<RuleCount> = by_rule
+            .into_iter()
+            .map(|(rule_id, count)| RuleCount {
+                rule_id,
+                count
+            })
+            .collect();
+        top_rules.sort_by_key(|rule| Reverse(rule.count));
+        top_rules.truncate(TOP_RULES);
+        let scores: Vec<u32> = queries
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !self.unanalyzed.contains(index))
+            .map(|(_, query)| query.complexity().score)
+            .collect();
+        let avg_complexity = if scores.is_empty() {
+            0.0
+        } else {
+            f64::from(scores.iter().sum::<u32>()) / scores.len() as f64
+        };
+        Statistics {
+            queries: self.queries_count,
+            analyzed: self.queries_count.saturating_sub(self.unanalyzed.len()),
+            unsupported: self.unanalyzed.len(),
+            by_severity,
+            by_category: self.category_summary(),
+            top_rules,
+            avg_complexity,
+            max_complexity: scores.into_iter().max().unwrap_or(0)
+        }
+    }
+}
+
+/// Violation counts of an [`AnalysisReport`], without the violations.
+///
+/// Used by summary-only output, where large batches only need the totals.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReportSummary {
+    /// Number of Error violations
+    pub errors:           usize,
+    /// Number of Warning violations
+    pub warnings:         usize,
+    /// Number of Info violations
+    pub info:             usize,
+    /// Number of violations per category
+    pub by_category:      BTreeMap<RuleCategory, usize>,
+    /// Highest severity found; `None` when the report is clean
+    pub highest_severity: Option<Severity>,
+    /// Number of statements of unsupported types that were not analyzed
+    pub unanalyzed:       usize
+}
+
+/// Aggregate figures of an [`AnalysisReport`] and its queries.
+///
+/// Printed by `--stats`; built with [`AnalysisReport::to_statistics`].
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct Statistics {
+    /// Number of statements in the input
+    pub queries:        usize,
+    /// Number of statements the rules analyzed
+    pub analyzed:       usize,
+    /// Number of statements of unsupported types (e.g., `BEGIN`)
+    pub unsupported:    usize,
+    /// Number of violations per severity
+    pub by_severity:    BTreeMap<Severity, usize>,
+    /// Number of violations per category
+    pub by_category:    BTreeMap<RuleCategory, usize>,
+    /// Most frequent rules, by violation count then rule ID
+    pub top_rules:      Vec<RuleCount>,
+    /// Mean complexity score of the analyzed queries
+    pub avg_complexity: f64,
+    /// Highest complexity score of the analyzed queries
+    pub max_complexity: u32
+}
+
+/// Number of violations one rule produced
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleCount {
+    /// Rule identifier
+    pub rule_id: &'static str,
+    /// Number of violations
+    pub count:   usize
+}
+
+/// Exit code of a run and why it was chosen, as carried by JSON output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExitStatus {
+    /// `0` clean, `1` warnings or info, `2` errors
+    pub exit_code:   i32,
+    /// Human-readable cause, e.g. `2 errors found`
+    pub exit_reason: String
 }
@@ -7,8 +7,14 @@
 //!
 //! - `CREATE TABLE` with columns, types, constraints
 //! - `CREATE INDEX` with column lists and uniqueness
+//! - `CREATE VIEW`, with columns typed after the base table columns they select
+//!   (materialized views are skipped)
 //! - Primary key constraints (inline and table-level)
 //! - NOT NULL constraints
+//! - Column `COLLATE` clauses
+//! - `ENUM` column types, PostgreSQL `CREATE TYPE ... AS ENUM`, and `CHECK (col
+//!   IN (...))` constraints
+//! - `-- @deprecated <note>` comments on a column's line or the line above
 //!
 //! # Example
 //!
@@ -40,7 +46,8 @@ use sqlparser::parser::Parser;
 use crate::{
     error::{AppResult, schema_parse_error},
     preprocessor::{Preprocessor, PreprocessorMetadata},
-    query::SqlDialect
+    query::{ColumnClause, Query, SortDir, SqlDialect, literal_text},
+    rules::{Violation, schema_lints}
 };
 
 /// Complete information about a database table.
@@ -56,38 +63,104 @@ pub struct TableInfo {
     pub engine:       Option<String>,
     /// Physical sort order columns (ClickHouse ORDER BY)
     pub order_by:     Option<Vec<String>>,
-    /// Sparse index columns (ClickHouse PRIMARY KEY)
+    /// Primary key declared apart from the columns: a table-level
+    /// `PRIMARY KEY (...)` constraint or ClickHouse `PRIMARY KEY` clause
     pub primary_key:  Option<Vec<String>>,
     /// Partitioning expression (ClickHouse PARTITION BY)
     pub partition_by: Option<String>,
     /// Cluster name (ClickHouse ON CLUSTER)
-    pub cluster:      Option<String>
+    pub cluster:      Option<String>,
+    /// Listed under `large_tables` in the `[schema]` config section
+    pub is_large:     bool,
+    /// Defined by `CREATE VIEW`; a view has no indexes of its own
+    pub is_view:      bool
 }
 
 /// Column metadata extracted from CREATE TABLE.
 #[derive(Debug, Clone)]
 pub struct ColumnInfo {
     /// Column name
-    pub name:        String,
+    pub name:           String,
     /// SQL data type (e.g., "INT", "VARCHAR(255)")
-    pub data_type:   String,
+    pub data_type:      String,
     /// Whether NULL values are allowed
-    pub is_nullable: bool,
+    pub is_nullable:    bool,
     /// Whether this is a primary key column
-    pub is_primary:  bool,
+    pub is_primary:     bool,
     /// Compression codec (ClickHouse: ZSTD, LZ4, Delta, etc.)
-    pub codec:       Option<String>
+    pub codec:          Option<String>,
+    /// Collation from a `COLLATE` clause (e.g., "utf8mb4_bin")
+    pub collation:      Option<String>,
+    /// Deprecation note; empty when the column is deprecated without one
+    pub deprecated:     Option<String>,
+    /// Values an `ENUM` type or a `CHECK (col IN (...))` constraint allows;
+    /// `None` when the column is unrestricted
+    pub allowed_values: Option<Vec<String>>,
+    /// Whether the database fills the column itself (`SERIAL`,
+    /// `AUTO_INCREMENT`, `IDENTITY`, `GENERATED`, `MATERIALIZED`, `ALIAS`)
+    pub is_generated:   bool
 }
 
 /// Index metadata extracted from CREATE INDEX or table constraints.
 #[derive(Debug, Clone)]
 pub struct IndexInfo {
     /// Index name (may be empty for anonymous indexes)
-    pub name:      String,
+    pub name:       String,
     /// Ordered list of indexed columns
-    pub columns:   Vec<String>,
+    pub columns:    Vec<String>,
+    /// Indexed columns with their declared sort direction, in index order
+    pub directions: Vec<(String, SortDir)>,
     /// Whether this is a unique index
-    pub is_unique: bool
+    pub is_unique:  bool,
+    /// Access method from `USING` (e.g., "GIN"), when declared
+    pub method:     Option<String>
+}
+
+impl TableInfo {
+    /// Whether `columns` cover the primary key or every column of a unique
+    /// index, so that pinning them with `=` selects at most one row
+    pub fn is_unique_key(&self, columns: &[&str]) -> bool {
+        let covered = |key: &[String]| {
+            !key.is_empty()
+                && key
+                    .iter()
+                    .all(|k| columns.iter().any(|c| c.eq_ignore_ascii_case(k)))
+        };
+        let primary_key = match &self.primary_key {
+            Some(key) => key.clone(),
+            None => self
+                .columns
+                .iter()
+                .filter(|c| c.is_primary)
+                .map(|c| c.name.clone())
+                .collect()
+        };
+        covered(&primary_key)
+            || self
+                .indexes
+                .iter()
+                .any(|idx| idx.is_unique && covered(&idx.columns))
+    }
+}
+
+impl IndexInfo {
+    /// Whether any column is declared `DESC`
+    pub fn has_desc(&self) -> bool {
+        self.directions.iter().any(|(_, dir)| *dir == SortDir::Desc)
+    }
+}
+
+/// Column name (or expression) and direction of an index column
+fn index_columns(columns: &[sqlparser::ast::IndexColumn]) -> Vec<(String, SortDir)> {
+    columns
+        .iter()
+        .map(|c| {
+            (
+                c.column.expr.to_string(),
+                SortDir::from_asc(c.column.options.asc)
+            )
+        })
+        .collect()
 }
 
 /// Parsed database schema containing all tables and their metadata.
@@ -96,7 +169,11 @@ pub struct IndexInfo {
 #[derive(Debug, Default, Clone)]
 pub struct Schema {
     /// Map of table name to table information
-    pub tables: BTreeMap<String, TableInfo>
+    pub tables:     BTreeMap<String, TableInfo>,
+    /// Labels of enum types from `CREATE TYPE ... AS ENUM`, by type name
+    pub enum_types: BTreeMap<String, Vec<String>>,
+    /// Dialect the DDL was parsed with; decides identifier case rules
+    pub dialect:    SqlDialect
 }
 
 impl Schema {
@@ -120,19 +197,59 @@ impl Schema {
         let parser_dialect = dialect.into_parser_dialect();
         let statements = Parser::parse_sql(parser_dialect.as_ref(), &preprocessed.sql)
             .map_err(|e| schema_parse_error(e.to_string()))?;
-        let mut schema = Self::default();
+        let mut schema = Self {
+            dialect,
+            ..Self::default()
+        };
+        let lines: Vec<&str> = preprocessed.sql.lines().collect();
         for stmt in statements {
-            schema.process_statement(stmt, &preprocessed.metadata)?;
+            schema.process_statement(stmt, &preprocessed.metadata, &lines)?;
         }
         Ok(schema)
     }
 
+    /// Mark columns deprecated from `table.column` keys, each mapped to a
+    /// note shown in violations. Entries naming unknown columns are ignored.
+    pub fn deprecate_columns<'a>(
+        &mut self,
+        entries: impl IntoIterator<Item = (&'a String, &'a String)>
+    ) {
+        for (key, note) in entries {
+            let Some((table, column)) = key.rsplit_once('.') else {
+                continue;
+            };
+            let column = self
+                .tables
+                .values_mut()
+                .filter(|t| t.name.eq_ignore_ascii_case(table))
+                .flat_map(|t| t.columns.iter_mut())
+                .find(|c| c.name.eq_ignore_ascii_case(column));
+            if let Some(column) = column {
+                column.deprecated = Some(note.clone());
+            }
+        }
+    }
+
+    /// Mark the named tables as large; names of unknown tables are ignored
+    pub fn mark_large_tables<'a>(&mut self, names: impl IntoIterator<Item = &'a String>) {
+        for name in names {
+            for table in self
+                .tables
+                .values_mut()
+                .filter(|t| t.name.eq_ignore_ascii_case(name))
+            {
+                table.is_large = true;
+            }
+        }
+    }
+
     fn process_statement(
         &mut self,
         stmt: sqlparser::ast::Statement,
-        metadata: &PreprocessorMetadata
+        metadata: &PreprocessorMetadata,
+        lines: &[&str]
     ) -> AppResult<()> {
-        use sqlparser::ast::Statement;
+        use sqlparser::ast::{Statement, UserDefinedTypeRepresentation};
         match stmt {
             Statement::CreateTable(create) => {
                 let table_name = create.name.to_string();
@@ -144,6 +261,22 @@ impl Schema {
                         matches!(opt.option, sqlparser::ast::ColumnOption::PrimaryKey(_))
                     });
                     let codec = metadata.codecs.get(&col_name).cloned();
+                    let collation = column.options.iter().find_map(|opt| match &opt.option {
+                        sqlparser::ast::ColumnOption::Collation(name) => Some(name.to_string()),
+                        _ => None
+                    });
+                    let deprecated = deprecation_note(lines, column.name.span.start.line);
+                    let allowed_values = self.enum_labels(&column.data_type).or_else(|| {
+                        column.options.iter().find_map(|opt| match &opt.option {
+                            sqlparser::ast::ColumnOption::Check(check) => {
+                                check_domain(&check.expr)
+                                    .filter(|(name, _)| name.eq_ignore_ascii_case(&col_name))
+                                    .map(|(_, values)| values)
+                            }
+                            _ => None
+                        })
+                    });
+                    let is_generated = is_generated_column(&column);
                     columns.push(ColumnInfo {
                         name: col_name,
                         data_type: column.data_type.to_string(),
@@ -151,21 +284,54 @@ impl Schema {
                             matches!(opt.option, sqlparser::ast::ColumnOption::NotNull)
                         }),
                         is_primary,
-                        codec
+                        codec,
+                        collation,
+                        deprecated,
+                        allowed_values,
+                        is_generated
                     });
                 }
+                let mut primary_key = create.primary_key.as_ref().map(|pk| vec![pk.to_string()]);
                 for constraint in create.constraints {
-                    if let sqlparser::ast::TableConstraint::Index(idx) = constraint {
-                        indexes.push(IndexInfo {
-                            name:      idx.name.map(|n| n.to_string()).unwrap_or_default(),
-                            columns:   idx.columns.iter().map(|c| c.to_string()).collect(),
-                            is_unique: false
-                        });
+                    match constraint {
+                        sqlparser::ast::TableConstraint::PrimaryKey(pk) => {
+                            let names: Vec<String> = index_columns(&pk.columns)
+                                .into_iter()
+                                .map(|(column, _)| column)
+                                .collect();
+                            if let [name] = names.as_slice()
+                                && let Some(column) = columns
+                                    .iter_mut()
+                                    .find(|c| c.name.eq_ignore_ascii_case(name))
+                            {
+                                column.is_primary = true;
+                            }
+                            primary_key.get_or_insert(names);
+                        }
+                        sqlparser::ast::TableConstraint::Index(idx) => {
+                            let directions = index_columns(&idx.columns);
+                            indexes.push(IndexInfo {
+                                name: idx.name.map(|n| n.to_string()).unwrap_or_default(),
+                                columns: directions.iter().map(|(c, _)| c.clone()).collect(),
+                                directions,
+                                is_unique: false,
+                                method: idx.index_type.map(|t| t.to_string())
+                            });
+                        }
+                        sqlparser::ast::TableConstraint::Check(check) => {
+                            if let Some((name, values)) = check_domain(&check.expr)
+                                && let Some(column) = columns
+                                    .iter_mut()
+                                    .find(|c| c.name.eq_ignore_ascii_case(&name))
+                            {
+                                column.allowed_values.get_or_insert(values);
+                            }
+                        }
+                        _ => {}
                     }
                 }
                 let engine = Self::extract_engine(&create.table_options);
                 let order_by = create.order_by.as_ref().map(Self::extract_exprs);
-                let primary_key = create.primary_key.as_ref().map(|pk| vec![pk.to_string()]);
                 let cluster = create.on_cluster.map(|c| c.value);
                 self.tables.insert(
                     table_name.clone(),
@@ -177,17 +343,59 @@ impl Schema {
                         order_by,
                         primary_key,
                         partition_by: None,
-                        cluster
+                        cluster,
+                        is_large: false,
+                        is_view: false
                     }
                 );
             }
+            Statement::CreateView(view) if !view.materialized => {
+                let name = view.name.to_string();
+                let mut columns = self.view_columns(&view.query);
+                for (idx, declared) in view.columns.iter().enumerate() {
+                    match columns.get_mut(idx) {
+                        Some(column) => column.name = declared.name.value.clone(),
+                        None => columns.push(untyped_column(declared.name.value.clone()))
+                    }
+                }
+                self.tables.insert(
+                    name.clone(),
+                    TableInfo {
+                        name,
+                        columns,
+                        indexes: Vec::new(),
+                        engine: None,
+                        order_by: None,
+                        primary_key: None,
+                        partition_by: None,
+                        cluster: None,
+                        is_large: false,
+                        is_view: true
+                    }
+                );
+            }
+            Statement::CreateType {
+                name,
+                representation:
+                    Some(UserDefinedTypeRepresentation::Enum {
+                        labels
+                    })
+            } => {
+                self.enum_types.insert(
+                    name.to_string(),
+                    labels.into_iter().map(|label| label.value).collect()
+                );
+            }
             Statement::CreateIndex(create_index) => {
                 let table_name = create_index.table_name.to_string();
                 if let Some(table) = self.tables.get_mut(&table_name) {
+                    let directions = index_columns(&create_index.columns);
                     table.indexes.push(IndexInfo {
-                        name:      create_index.name.map(|n| n.to_string()).unwrap_or_default(),
-                        columns:   create_index.columns.iter().map(|c| c.to_string()).collect(),
-                        is_unique: create_index.unique
+                        name: create_index.name.map(|n| n.to_string()).unwrap_or_default(),
+                        columns: directions.iter().map(|(c, _)| c.clone()).collect(),
+                        directions,
+                        is_unique: create_index.unique,
+                        method: create_index.using.map(|t| t.to_string())
                     });
                 }
             }
@@ -196,6 +404,115 @@ impl Schema {
         Ok(())
     }
 
+    /// Columns a view's SELECT list produces, typed after the base table
+    /// columns they name; expressions without an alias and `t.*` are skipped
+    fn view_columns(&self, query: &sqlparser::ast::Query) -> Vec<ColumnInfo> {
+        use sqlparser::ast::{Expr, SelectItem, SetExpr, TableFactor};
+        let SetExpr::Select(select) = query.body.as_ref() else {
+            return Vec::new();
+        };
+        let sources: Vec<(&TableInfo, Option<String>)> = select
+            .from
+            .iter()
+            .flat_map(|t| std::iter::once(&t.relation).chain(t.joins.iter().map(|j| &j.relation)))
+            .filter_map(|relation| match relation {
+                TableFactor::Table {
+                    name,
+                    alias,
+                    ..
+                } => self
+                    .tables
+                    .get(&name.to_string())
+                    .map(|table| (table, alias.as_ref().map(|a| a.name.value.clone()))),
+                _ => None
+            })
+            .collect();
+        let lookup = |expr: &Expr| -> Option<ColumnInfo> {
+            let (qualifier, column) = match expr {
+                Expr::Identifier(ident) => (None, &ident.value),
+                Expr::CompoundIdentifier(parts) => {
+                    let (column, qualifier) = parts.split_last()?;
+                    (qualifier.last().map(|q| &q.value), &column.value)
+                }
+                _ => return None
+            };
+            sources
+                .iter()
+                .filter(|(table, alias)| {
+                    qualifier.is_none_or(|q| {
+                        alias
+                            .as_ref()
+                            .unwrap_or(&table.name)
+                            .eq_ignore_ascii_case(q)
+                    })
+                })
+                .find_map(|(table, _)| {
+                    table
+                        .columns
+                        .iter()
+                        .find(|c| c.name.eq_ignore_ascii_case(column))
+                })
+                .map(|c| ColumnInfo {
+                    is_primary: false,
+                    is_generated: false,
+                    ..c.clone()
+                })
+        };
+        let mut columns = Vec::new();
+        for item in &select.projection {
+            match item {
+                SelectItem::UnnamedExpr(expr) => {
+                    if let Some(column) = lookup(expr) {
+                        columns.push(column);
+                    }
+                }
+                SelectItem::ExprWithAlias {
+                    expr,
+                    alias
+                } => columns.push(ColumnInfo {
+                    name: alias.value.clone(),
+                    ..lookup(expr).unwrap_or_else(|| untyped_column(String::new()))
+                }),
+                SelectItem::Wildcard(_) => {
+                    for (table, _) in &sources {
+                        columns.extend(table.columns.iter().map(|c| ColumnInfo {
+                            is_primary: false,
+                            is_generated: false,
+                            ..c.clone()
+                        }));
+                    }
+                }
+                SelectItem::QualifiedWildcard(..)
+                | SelectItem::ExprWithAliases {
+                    ..
+                } => {}
+            }
+        }
+        columns
+    }
+
+    /// Members of an inline `ENUM(...)` type, or labels of a named enum type
+    /// declared earlier with `CREATE TYPE`
+    fn enum_labels(&self, data_type: &sqlparser::ast::DataType) -> Option<Vec<String>> {
+        use sqlparser::ast::{DataType, EnumMember};
+        match data_type {
+            DataType::Enum(members, _) => Some(
+                members
+                    .iter()
+                    .map(|member| match member {
+                        EnumMember::Name(name) | EnumMember::NamedValue(name, _) => name.clone()
+                    })
+                    .collect()
+            ),
+            DataType::Custom(name, _) => self
+                .enum_types
+                .iter()
+                .find(|(type_name, _)| type_name.eq_ignore_ascii_case(&name.to_string()))
+                .map(|(_, labels)| labels.clone()),
+            _ => None
+        }
+    }
+
     fn extract_engine(options: &sqlparser::ast::CreateTableOptions) -> Option<String> {
         use sqlparser::ast::{CreateTableOptions, SqlOption};
         let opts = match options {
@@ -225,11 +542,179 @@ impl Schema {
         }
     }
 
+    /// Resolve a column reference as written in the query (`u.email` or
+    /// `email`) to its table and column. Unqualified names resolve only when
+    /// exactly one table of the query has such a column. Statements without
+    /// FROM-clause aliases (UPDATE, DELETE, INSERT) resolve against their
+    /// target tables.
+    pub fn resolve_column(
+        &self,
+        query: &Query,
+        reference: &str
+    ) -> Option<(&TableInfo, &ColumnInfo)> {
+        let (qualifier, column) = match reference.rsplit_once('.') {
+            Some((qualifier, column)) => (Some(qualifier), column),
+            None => (None, reference)
+        };
+        let pairs: Vec<(&str, Option<&str>)> = if query.table_aliases.is_empty() {
+            query.tables.iter().map(|t| (t.as_str(), None)).collect()
+        } else {
+            query
+                .table_aliases
+                .iter()
+                .map(|(t, a)| (t.as_str(), a.as_deref()))
+                .collect()
+        };
+        let tables = pairs.into_iter().filter(|(table, alias)| {
+            qualifier.is_none_or(|q| {
+                alias.is_some_and(|a| a.eq_ignore_ascii_case(q))
+                    || table.eq_ignore_ascii_case(q)
+                    || table
+                        .rsplit('.')
+                        .next()
+                        .is_some_and(|t| t.eq_ignore_ascii_case(q))
+            })
+        });
+        let mut found = None;
+        for (table, _) in tables {
+            let Some(info) = self
+                .tables
+                .values()
+                .find(|t| t.name.eq_ignore_ascii_case(table))
+            else {
+                continue;
+            };
+            if let Some(col) = info
+                .columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(column))
+            {
+                if found.is_some() {
+                    return None;
+                }
+                found = Some((info, col));
+            }
+        }
+        found
+    }
+
+    /// Clauses in which `query` references `table.column`, in order of first
+    /// appearance.
+    ///
+    /// Subqueries, derived tables, CTE bodies, and the source query of an
+    /// `INSERT ... SELECT` are included. A reference resolves against the
+    /// FROM clause of the query it appears in first, then against the
+    /// enclosing ones, so correlated subqueries are attributed too.
+    pub fn column_usage(&self, table: &str, column: &str, query: &Query) -> Vec<ColumnClause> {
+        let mut clauses = Vec::new();
+        self.collect_column_usage(table, column, &mut vec![query], &mut clauses);
+        clauses
+    }
+
+    /// Adds the clauses of the innermost of `scopes` and its nested queries
+    /// that reference `table.column`
+    fn collect_column_usage(
+        &self,
+        table: &str,
+        column: &str,
+        scopes: &mut Vec<&Query>,
+        clauses: &mut Vec<ColumnClause>
+    ) {
+        let Some(&query) = scopes.last() else {
+            return;
+        };
+        for reference in &query.column_refs {
+            let Some((table_info, column_info)) = scopes
+                .iter()
+                .rev()
+                .find_map(|scope| self.resolve_column(scope, &reference.reference))
+            else {
+                continue;
+            };
+            if table_info.name.eq_ignore_ascii_case(table)
+                && column_info.name.eq_ignore_ascii_case(column)
+                && !clauses.contains(&reference.clause)
+            {
+                clauses.push(reference.clause);
+            }
+        }
+        for nested in &query.subqueries {
+            scopes.push(nested);
+            self.collect_column_usage(table, column, scopes, clauses);
+            scopes.pop();
+        }
+        if let Some(source) = &query.insert_select {
+            self.collect_column_usage(table, column, &mut vec![source.as_ref()], clauses);
+        }
+    }
+
+    /// Indices of the queries that reference `table.column`, for impact
+    /// analysis before a column is dropped or renamed
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sql_query_analyzer::{
+    ///     query::{SqlDialect, parse_queries},
+    ///     schema::Schema
+    /// };
+    ///
+    /// let schema = Schema::parse(
+    ///     "CREATE TABLE users (id INT, email TEXT); CREATE TABLE orders (id INT, user_id INT);",
+    ///     SqlDialect::Generic
+    /// )
+    /// .unwrap();
+    /// let queries = parse_queries(
+    ///     "SELECT id FROM users WHERE email = 'a'; SELECT id FROM orders",
+    ///     SqlDialect::Generic
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     schema.find_column_usage("users", "email", &queries),
+    ///     vec![0]
+    /// );
+    /// ```
+    pub fn find_column_usage(&self, table: &str, column: &str, queries: &[Query]) -> Vec<usize> {
+        queries
+            .iter()
+            .enumerate()
+            .filter(|(_, query)| !self.column_usage(table, column, query).is_empty())
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    /// Run every schema lint (SCHEMA019, SCHEMA020, SCHEMA025) against this
+    /// schema.
+    ///
+    /// Lints check the DDL itself, so they need no queries. Rule
+    /// configuration is not applied here; [`RuleRunner`] does that when it
+    /// reports them.
+    ///
+    /// [`RuleRunner`]: crate::rules::RuleRunner
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use sql_query_analyzer::{query::SqlDialect, schema::Schema};
+    ///
+    /// let schema =
+    ///     Schema::parse("CREATE TABLE users (id INT, id INT);", SqlDialect::Generic).unwrap();
+    /// assert!(schema.lint().iter().any(|v| v.rule_id == "SCHEMA019"));
+    /// ```
+    pub fn lint(&self) -> Vec<Violation> {
+        schema_lints()
+            .iter()
+            .flat_map(|lint| lint.check(self))
+            .collect()
+    }
+
     /// Get summary of schema for LLM analysis
     pub fn to_summary(&self) -> String {
         let mut summary = String::from("Database Schema:\n\n");
         for table in self.tables.values() {
-            summary.push_str(&format!("Table: {}\n", table.name));
+            let kind = if table.is_view { "View" } else { "Table" };
+            summary.push_str(&format!("{}: {}\n", kind, table.name));
             if let Some(engine) = &table.engine {
                 summary.push_str(&format!("Engine: {}\n", engine));
             }
@@ -280,3 +765,98 @@ impl Schema {
         summary
     }
 }
+
+/// Nullable column of unknown type, for view columns computed by an
+/// expression
+fn untyped_column(name: String) -> ColumnInfo {
+    ColumnInfo {
+        name,
+        data_type: String::new(),
+        is_nullable: true,
+        is_primary: false,
+        codec: None,
+        collation: None,
+        deprecated: None,
+        allowed_values: None,
+        is_generated: false
+    }
+}
+
+/// Column and literal values of a `CHECK (col IN (...))` expression
+fn check_domain(expr: &sqlparser::ast::Expr) -> Option<(String, Vec<String>)> {
+    use sqlparser::ast::Expr;
+    match expr {
+        Expr::Nested(inner) => check_domain(inner),
+        Expr::InList {
+            expr: column,
+            list,
+            negated: false
+        } => {
+            let Expr::Identifier(column) = column.as_ref() else {
+                return None;
+            };
+            let values = list
+                .iter()
+                .map(|item| literal_text(item).map(String::from))
+                .collect::<Option<Vec<_>>>()?;
+            Some((column.value.clone(), values))
+        }
+        _ => None
+    }
+}
+
+/// Whether the database fills the column itself: a serial type, an
+/// auto-increment or identity option, or a generated expression
+fn is_generated_column(column: &sqlparser::ast::ColumnDef) -> bool {
+    use sqlparser::ast::ColumnOption;
+    const SERIAL_TYPES: &[&str] = &[
+        "SERIAL",
+        "SERIAL2",
+        "SERIAL4",
+        "SERIAL8",
+        "SMALLSERIAL",
+        "BIGSERIAL"
+    ];
+    let data_type = column.data_type.to_string();
+    if SERIAL_TYPES
+        .iter()
+        .any(|t| t.eq_ignore_ascii_case(&data_type))
+    {
+        return true;
+    }
+    column.options.iter().any(|opt| match &opt.option {
+        ColumnOption::Identity(_)
+        | ColumnOption::Generated {
+            ..
+        }
+        | ColumnOption::Materialized(_)
+        | ColumnOption::Alias(_) => true,
+        ColumnOption::DialectSpecific(tokens) => tokens.iter().any(|token| {
+            let token = token.to_string();
+            token.eq_ignore_ascii_case("AUTO_INCREMENT")
+                || token.eq_ignore_ascii_case("AUTOINCREMENT")
+        }),
+        _ => false
+    })
+}
+
+/// Note of a `-- @deprecated` comment trailing the given 1-based line, or
+/// alone on the line above it.
+fn deprecation_note(lines: &[&str], line: u64) -> Option<String> {
+    const MARKER: &str = "@deprecated";
+    let idx = usize::try_from(line).ok()?.checked_sub(1)?;
+    let trailing = lines
+        .get(idx)
+        .and_then(|l| l.split_once("--"))
+        .map(|(_, comment)| comment);
+    let above = idx
+        .checked_sub(1)
+        .and_then(|prev| lines.get(prev))
+        .and_then(|l| l.trim_start().strip_prefix("--"));
+    [trailing, above].into_iter().flatten().find_map(|comment| {
+        let comment = comment.trim_start();
+        comment
+            .strip_prefix(MARKER)
+            .map(|note| note.trim().to_string())
+    })
+}
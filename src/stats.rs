@@ -0,0 +1,141 @@
+//! Table and column usage statistics.
+//!
+//! Counts how many statements reference each table and column, for the
+//! `stats` subcommand. This is reporting, not linting: no rules run.
+//!
+//! A column is attributed to a table (`users.email`) through its qualifier
+//! (`u.email` with `users u`), through the schema when one is given, or
+//! because its statement reads exactly one table. Only an unqualified column
+//! of a join that no schema resolves is counted by its bare name.
+//!
+//! # Example
+//!
+//! ```
+//! use sql_query_analyzer::{
+//!     query::{SqlDialect, parse_queries},
+//!     stats::usage_stats
+//! };
+//!
+//! let queries = parse_queries(
+//!     "SELECT email FROM users WHERE id = 1; SELECT id FROM users",
+//!     SqlDialect::Generic
+//! )
+//! .unwrap();
+//! let stats = usage_stats(&queries, None);
+//!
+//! assert_eq!(stats.tables[0].name, "users");
+//! assert_eq!(stats.tables[0].count, 2);
+//! assert_eq!(stats.columns[0].name, "users.id");
+//! ```
+
+use std::collections::HashMap;
+
+use indexmap::IndexSet;
+use serde::Serialize;
+
+use crate::{
+    query::{ColumnClause, Query},
+    schema::Schema
+};
+
+/// Usage counts across a set of statements
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageStats {
+    /// Number of statements counted
+    pub queries: usize,
+    /// Tables ranked by the number of statements referencing them
+    pub tables:  Vec<UsageCount>,
+    /// Columns ranked by the number of statements referencing them
+    pub columns: Vec<UsageCount>
+}
+
+/// Number of statements referencing a table or column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UsageCount {
+    pub name:  String,
+    pub count: usize
+}
+
+/// Count table and column references in `queries`.
+///
+/// Each statement counts at most once per table or column. Columns come from
+/// the SELECT list, WHERE, JOIN conditions, GROUP BY, HAVING, and ORDER BY
+/// of the outermost statement. `schema`, when given, attributes unqualified
+/// columns of joins to their table. Ties are ranked by name.
+pub fn usage_stats(queries: &[Query], schema: Option<&Schema>) -> UsageStats {
+    let mut tables: HashMap<String, usize> = HashMap::new();
+    let mut columns: HashMap<String, usize> = HashMap::new();
+    for query in queries {
+        let query_tables: IndexSet<&str> = query.tables.iter().map(|t| t.as_str()).collect();
+        for table in &query_tables {
+            *tables.entry(table.to_string()).or_default() += 1;
+        }
+        let query_columns: IndexSet<String> = query
+            .column_refs
+            .iter()
+            .filter(|r| !matches!(r.clause, ColumnClause::Set | ColumnClause::Insert))
+            .map(|r| column_name(query, &query_tables, &r.reference, schema))
+            .collect();
+        for name in query_columns {
+            *columns.entry(name).or_default() += 1;
+        }
+    }
+    UsageStats {
+        queries: queries.len(),
+        tables:  ranked(tables),
+        columns: ranked(columns)
+    }
+}
+
+/// `table.column` name of `reference`, or the bare column name when its
+/// table cannot be told
+fn column_name(
+    query: &Query,
+    query_tables: &IndexSet<&str>,
+    reference: &str,
+    schema: Option<&Schema>
+) -> String {
+    if let Some((table, column)) = schema.and_then(|s| s.resolve_column(query, reference)) {
+        return format!("{}.{}", table.name, column.name);
+    }
+    let (qualifier, column) = match reference.rsplit_once('.') {
+        Some((qualifier, column)) => (Some(qualifier), column),
+        None => (None, reference)
+    };
+    let owner = match qualifier {
+        Some(qualifier) => query
+            .table_aliases
+            .iter()
+            .find(|(table, alias)| {
+                alias
+                    .as_ref()
+                    .is_some_and(|a| a.eq_ignore_ascii_case(qualifier))
+                    || table.eq_ignore_ascii_case(qualifier)
+            })
+            .map(|(table, _)| table.as_str())
+            .or_else(|| {
+                query_tables
+                    .iter()
+                    .find(|t| t.eq_ignore_ascii_case(qualifier))
+                    .copied()
+            }),
+        None if query_tables.len() == 1 => query_tables.first().copied(),
+        None => None
+    };
+    match owner {
+        Some(table) => format!("{}.{}", table, column),
+        None => column.to_string()
+    }
+}
+
+fn ranked(counts: HashMap<String, usize>) -> Vec<UsageCount> {
+    let mut ranked: Vec<UsageCount> = counts
+        .into_iter()
+        .map(|(name, count)| UsageCount {
+            name,
+            count
+        })
+        .collect();
+    ranked.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    ranked
+}
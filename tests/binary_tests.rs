@@ -1,5 +1,7 @@
 //! Integration tests for the sql-query-analyzer binary.
 
+#![cfg(feature = "llm")]
+
 use std::io::Write;
 
 use assert_cmd::{Command, cargo::cargo_bin_cmd};
@@ -73,7 +75,7 @@ fn test_analyze_file_not_found() {
 #[test]
 fn test_analyze_dry_run() {
     let mut schema = NamedTempFile::new().unwrap();
-    writeln!(schema, "CREATE TABLE t (id INT);").unwrap();
+    writeln!(schema, "CREATE TABLE t (id INT PRIMARY KEY);").unwrap();
     let mut queries = NamedTempFile::new().unwrap();
     writeln!(queries, "SELECT id FROM t;").unwrap();
     cmd()
@@ -96,7 +98,7 @@ fn test_analyze_dry_run() {
 #[test]
 fn test_analyze_json_format() {
     let mut schema = NamedTempFile::new().unwrap();
-    writeln!(schema, "CREATE TABLE items (id INT);").unwrap();
+    writeln!(schema, "CREATE TABLE items (id INT PRIMARY KEY);").unwrap();
     let mut queries = NamedTempFile::new().unwrap();
     writeln!(queries, "SELECT id FROM items;").unwrap();
     cmd()
@@ -120,7 +122,7 @@ fn test_analyze_json_format() {
 #[test]
 fn test_analyze_yaml_format() {
     let mut schema = NamedTempFile::new().unwrap();
-    writeln!(schema, "CREATE TABLE events (id INT);").unwrap();
+    writeln!(schema, "CREATE TABLE events (id INT PRIMARY KEY);").unwrap();
     let mut queries = NamedTempFile::new().unwrap();
     writeln!(queries, "SELECT id FROM events;").unwrap();
     cmd()
@@ -143,7 +145,7 @@ fn test_analyze_yaml_format() {
 #[test]
 fn test_analyze_sarif_format() {
     let mut schema = NamedTempFile::new().unwrap();
-    writeln!(schema, "CREATE TABLE metrics (id INT);").unwrap();
+    writeln!(schema, "CREATE TABLE metrics (id INT PRIMARY KEY);").unwrap();
     let mut queries = NamedTempFile::new().unwrap();
     writeln!(queries, "SELECT id FROM metrics;").unwrap();
     cmd()
@@ -177,7 +179,7 @@ fn test_version() {
 #[test]
 fn test_analyze_verbose() {
     let mut schema = NamedTempFile::new().unwrap();
-    writeln!(schema, "CREATE TABLE logs (id INT);").unwrap();
+    writeln!(schema, "CREATE TABLE logs (id INT PRIMARY KEY);").unwrap();
     let mut queries = NamedTempFile::new().unwrap();
     writeln!(queries, "SELECT id FROM logs;").unwrap();
     cmd()
@@ -245,3 +247,698 @@ fn test_analyze_clickhouse_dialect() {
         .assert()
         .success();
 }
+
+#[test]
+fn test_analyze_schema_and_queries_from_stdin() {
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            "-",
+            "-q",
+            "-",
+            "--provider",
+            "open-ai",
+            "--no-color"
+        ])
+        .write_stdin(
+            "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255));\n\
+             -- @queries\n\
+             SELECT id FROM users WHERE nickname = 'ann';\n"
+        )
+        .assert()
+        .stdout(contains("SCHEMA002"));
+}
+
+#[test]
+fn test_analyze_schema_from_stdin_with_queries_file() {
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT id FROM users WHERE nickname = 'ann';").unwrap();
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            "-",
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--provider",
+            "open-ai",
+            "--no-color"
+        ])
+        .write_stdin("CREATE TABLE users (id INT PRIMARY KEY);\n")
+        .assert()
+        .stdout(contains("SCHEMA002"));
+}
+
+#[test]
+fn test_analyze_stdin_without_queries_marker_fails() {
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            "-",
+            "-q",
+            "-",
+            "--provider",
+            "open-ai",
+            "--no-color"
+        ])
+        .write_stdin("CREATE TABLE users (id INT);\nSELECT id FROM users;\n")
+        .assert()
+        .failure()
+        .stderr(contains("-- @queries"));
+}
+
+#[test]
+fn test_analyze_incremental_reuses_unchanged_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = dir.path().join("schema.sql");
+    let queries = dir.path().join("queries.sql");
+    let state = dir.path().join("state.json");
+    std::fs::write(&schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    std::fs::write(&queries, "SELECT * FROM users;").unwrap();
+    let run = || {
+        cmd()
+            .args([
+                "analyze",
+                "-s",
+                schema.to_str().unwrap(),
+                "-q",
+                queries.to_str().unwrap(),
+                "--provider",
+                "open-ai",
+                "--no-color",
+                "--incremental",
+                "--state-file",
+                state.to_str().unwrap()
+            ])
+            .assert()
+    };
+    run().stdout(contains("PERF001"));
+
+    let stored = std::fs::read_to_string(&state).unwrap();
+    let tampered = stored.replace("\"message\": \"", "\"message\": \"STORED ");
+    assert_ne!(stored, tampered);
+    std::fs::write(&state, tampered).unwrap();
+    run().stdout(contains("STORED "));
+
+    std::fs::write(&queries, "SELECT * FROM users WHERE id = 1;").unwrap();
+    run().stdout(contains("STORED ").not());
+}
+
+#[test]
+fn test_analyze_report_upload_failure_keeps_exit_code() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE users (id INT PRIMARY KEY); CREATE INDEX idx_users_id ON users (id);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT id FROM users WHERE id = 1;").unwrap();
+    let unused_port = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--provider",
+            "open-ai",
+            "--no-color",
+            "--report-url",
+            &format!("http://127.0.0.1:{}/reports", unused_port),
+            "--report-header",
+            "Authorization: Bearer token"
+        ])
+        .assert()
+        .success()
+        .stderr(contains("Warning:"));
+}
+
+#[test]
+fn test_analyze_stats_json() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE orders (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT * FROM orders;\nBEGIN;").unwrap();
+    let output = cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "-f",
+            "json",
+            "--stats",
+            "--quiet"
+        ])
+        .output()
+        .unwrap();
+    let stats: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(stats["queries"], 2);
+    assert_eq!(stats["unsupported"], 1);
+    assert_eq!(stats["top_rules"][0]["rule_id"], "PERF001");
+    assert!(stats.get("violations").is_none());
+}
+
+#[test]
+fn test_analyze_prints_summary_line_to_stderr() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE orders (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "SELECT * FROM orders;\nSELECT id FROM orders WHERE id = 1;"
+    )
+    .unwrap();
+    let args = [
+        "analyze",
+        "-s",
+        schema.path().to_str().unwrap(),
+        "-q",
+        queries.path().to_str().unwrap(),
+        "-f",
+        "json",
+        "--no-llm"
+    ];
+    let output = cmd().args(args).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(serde_json::from_str::<serde_json::Value>(&stdout).is_ok());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let pattern = regex::Regex::new(
+        r"^sql-query-analyzer: \d+ errors?, \d+ warnings?, \d+ info across 2 queries \(exit (\d)\)\n$"
+    )
+    .unwrap();
+    let caps = pattern
+        .captures(&stderr)
+        .unwrap_or_else(|| panic!("{:?}", stderr));
+    assert_eq!(
+        caps[1].parse::<i32>().unwrap(),
+        output.status.code().unwrap()
+    );
+    cmd()
+        .args(args)
+        .arg("--quiet")
+        .assert()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_analyze_quiet_hides_detected_dialect_notice() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE users (id SERIAL PRIMARY KEY, data JSONB);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT id FROM users WHERE id = 1;").unwrap();
+    let args = [
+        "analyze",
+        "-s",
+        schema.path().to_str().unwrap(),
+        "-q",
+        queries.path().to_str().unwrap(),
+        "--no-llm"
+    ];
+    cmd()
+        .args(args)
+        .assert()
+        .stderr(contains("Detected PostgreSQL dialect"));
+    cmd()
+        .args(args)
+        .arg("--quiet")
+        .assert()
+        .stderr(predicate::str::is_empty());
+}
+
+#[test]
+fn test_analyze_changed_only_files_from() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = dir.path().join("schema.sql");
+    std::fs::write(
+        &schema,
+        "CREATE TABLE users (id INT PRIMARY KEY, name TEXT);"
+    )
+    .unwrap();
+    let clean = dir.path().join("clean.sql");
+    std::fs::write(&clean, "SELECT id FROM users WHERE id = 1;").unwrap();
+    let dirty = dir.path().join("dirty.sql");
+    std::fs::write(&dirty, "SELECT * FROM users;").unwrap();
+    let list = dir.path().join("changed.txt");
+    std::fs::write(
+        &list,
+        format!("{}\n\n{}\n", clean.display(), dirty.display())
+    )
+    .unwrap();
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.to_str().unwrap(),
+            "--changed-only",
+            "--files-from",
+            list.to_str().unwrap(),
+            "--no-color"
+        ])
+        .assert()
+        .code(1)
+        .stdout(contains(format!("==> {} <==", clean.display())))
+        .stdout(contains(format!("==> {} <==", dirty.display())))
+        .stdout(contains("PERF001"));
+}
+
+#[test]
+fn test_analyze_changed_only_rejects_structured_output() {
+    let dir = tempfile::tempdir().unwrap();
+    let schema = dir.path().join("schema.sql");
+    std::fs::write(&schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let queries = dir.path().join("a.sql");
+    std::fs::write(&queries, "SELECT * FROM users;").unwrap();
+    let list = dir.path().join("changed.txt");
+    std::fs::write(&list, format!("{}\n", queries.display())).unwrap();
+    for format in ["json", "sarif", "github"] {
+        cmd()
+            .args([
+                "analyze",
+                "-s",
+                schema.to_str().unwrap(),
+                "--changed-only",
+                "--files-from",
+                list.to_str().unwrap(),
+                "-f",
+                format
+            ])
+            .assert()
+            .failure()
+            .stdout(predicate::str::is_empty())
+            .stderr(contains("only support text output"));
+    }
+}
+
+#[test]
+fn test_analyze_fail_on_error_ignores_warnings() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT * FROM users;").unwrap();
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--provider",
+            "open-ai",
+            "--no-color",
+            "--fail-on",
+            "error"
+        ])
+        .assert()
+        .success()
+        .stdout(contains("PERF001"));
+}
+
+#[test]
+fn test_analyze_min_severity_hides_warnings_but_keeps_exit_code() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT * FROM users;").unwrap();
+    for format in ["text", "json"] {
+        cmd()
+            .args([
+                "analyze",
+                "-s",
+                schema.path().to_str().unwrap(),
+                "-q",
+                queries.path().to_str().unwrap(),
+                "--no-llm",
+                "--no-color",
+                "-f",
+                format,
+                "--min-severity",
+                "error"
+            ])
+            .assert()
+            .code(1)
+            .stdout(contains("PERF001").not());
+    }
+}
+
+#[test]
+fn test_analyze_min_severity_shows_errors() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT * FROM users; DELETE FROM users;").unwrap();
+    let output = cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--no-llm",
+            "-f",
+            "json",
+            "--min-severity",
+            "error"
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violations = report["violations"].as_array().unwrap();
+    assert!(!violations.is_empty());
+    assert!(violations.iter().all(|v| v["severity"] == "Error"));
+}
+
+#[test]
+fn test_analyze_also_output_writes_json_file() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT * FROM users;").unwrap();
+    let dir = tempfile::tempdir().unwrap();
+    let report = dir.path().join("report.json");
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--no-llm",
+            "--no-color",
+            "--also-output",
+            &format!("json:{}", report.display())
+        ])
+        .assert()
+        .code(1)
+        .stdout(contains("=== Static Analysis ==="))
+        .stdout(contains("PERF001"));
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&report).unwrap()).unwrap();
+    assert!(
+        json["violations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|v| v["rule_id"] == "PERF001")
+    );
+}
+
+#[test]
+fn test_analyze_also_output_rejects_unknown_format() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE users (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT id FROM users;").unwrap();
+    cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "--also-output",
+            "xml:report.xml"
+        ])
+        .assert()
+        .failure()
+        .stderr(contains("Invalid --also-output 'xml:report.xml'"));
+}
+
+#[test]
+fn test_validate_config_unknown_rule_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    std::fs::write(
+        &config,
+        "[rules]\ndisabled = [\"PERF999\"]\n\n[rules.severity]\nPERF001 = \"fatal\"\n"
+    )
+    .unwrap();
+    cmd()
+        .args(["validate-config", config.to_str().unwrap()])
+        .assert()
+        .code(1)
+        .stdout(contains("rules.disabled: unknown rule ID 'PERF999'"))
+        .stdout(contains("rules.severity.PERF001: invalid severity 'fatal'"));
+}
+
+#[test]
+fn test_validate_config_valid_succeeds() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("config.toml");
+    std::fs::write(
+        &config,
+        "[llm]\nprovider = \"ollama\"\n\n[rules]\ndisabled = [\"STYLE001\"]\n\n\
+         [rules.severity]\nPERF001 = \"error\"\n"
+    )
+    .unwrap();
+    cmd()
+        .args(["validate-config", config.to_str().unwrap()])
+        .assert()
+        .success()
+        .stdout(contains("OK"));
+}
+
+#[test]
+fn test_stats_json() {
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "SELECT id FROM users WHERE id = 1; SELECT name FROM users; SELECT id FROM orders;"
+    )
+    .unwrap();
+    cmd()
+        .args([
+            "stats",
+            "-q",
+            queries.path().to_str().unwrap(),
+            "-f",
+            "json"
+        ])
+        .assert()
+        .success()
+        .stdout(contains("\"name\": \"users\",\n      \"count\": 2"));
+}
+
+#[test]
+fn test_impact_lists_affected_queries() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT); CREATE TABLE orders (id INT);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "SELECT id FROM orders;\nSELECT id FROM users WHERE email = 'x';"
+    )
+    .unwrap();
+    cmd()
+        .args([
+            "impact",
+            "users",
+            "email",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap()
+        ])
+        .assert()
+        .success()
+        .stdout(contains("users.email is referenced by 1 of 2 queries"))
+        .stdout(contains("#2 (line 2): WHERE"));
+}
+
+#[test]
+fn test_validate_schema_fails_on_unknown_column() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT, status TEXT);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "SELECT id FROM users WHERE status = 'a';\nSELECT id FROM users WHERE emial = 'x';"
+    )
+    .unwrap();
+    cmd()
+        .args([
+            "validate-schema",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap()
+        ])
+        .assert()
+        .code(1)
+        .stdout(contains("Columns: 1 of 2 found"))
+        .stdout(contains("missing: emial"))
+        .stdout(contains("Filter columns without an index: status"))
+        .stdout(contains(
+            "#2 (line 2): [SCHEMA002] Column 'emial' not found in schema"
+        ));
+}
+
+#[test]
+fn test_validate_schema_passes_when_all_objects_exist() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(queries, "SELECT email FROM users WHERE id = 1;").unwrap();
+    cmd()
+        .args([
+            "validate-schema",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap()
+        ])
+        .assert()
+        .success()
+        .stdout(contains("Tables: 1 of 1 found"))
+        .stdout(contains("Columns: 1 of 1 found"));
+}
+
+#[test]
+fn test_analyze_ndjson_input_names_violations() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE orders (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "{{\"sql\": \"SELECT * FROM orders\", \"name\": \"list_orders\"}}\n\
+         {{\"sql\": \"DELETE FROM orders\", \"name\": \"purge_orders\"}}"
+    )
+    .unwrap();
+    let args = [
+        "analyze",
+        "-s",
+        schema.path().to_str().unwrap(),
+        "-q",
+        queries.path().to_str().unwrap(),
+        "-f",
+        "json",
+        "--no-llm",
+        "--input-format",
+        "ndjson"
+    ];
+    let output = cmd().args(args).output().unwrap();
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let violations = report["violations"].as_array().unwrap();
+    let origin_of = |rule_id: &str| {
+        violations
+            .iter()
+            .find(|v| v["rule_id"] == rule_id)
+            .map(|v| v["origin"].clone())
+    };
+    assert_eq!(origin_of("STYLE001"), Some("list_orders".into()));
+    assert_eq!(origin_of("SEC002"), Some("purge_orders".into()));
+    assert!(
+        violations
+            .iter()
+            .all(|v| v["query_index"] == 0 || v["origin"] == "purge_orders")
+    );
+
+    let output = cmd().args(&args[..8]).output().unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_analyze_ndjson_input_reports_record_lines() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(schema, "CREATE TABLE orders (id INT PRIMARY KEY);").unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "{{\"sql\": \"SELECT id FROM orders WHERE id = 1\"}}\n\
+         {{\"sql\": \"DELETE FROM orders\", \"name\": \"purge_orders\"}}"
+    )
+    .unwrap();
+    let output = cmd()
+        .args([
+            "analyze",
+            "-s",
+            schema.path().to_str().unwrap(),
+            "-q",
+            queries.path().to_str().unwrap(),
+            "-f",
+            "github",
+            "--no-llm",
+            "--input-format",
+            "ndjson"
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let annotation = stdout
+        .lines()
+        .find(|line| line.contains("SEC002"))
+        .expect("SEC002 annotation");
+    assert!(annotation.contains(",line=2,col=1,"), "{}", annotation);
+}
+
+#[test]
+fn test_analyze_jinja_template() {
+    let mut schema = NamedTempFile::new().unwrap();
+    writeln!(
+        schema,
+        "CREATE TABLE orders (id INT PRIMARY KEY, status TEXT);"
+    )
+    .unwrap();
+    let mut queries = NamedTempFile::new().unwrap();
+    writeln!(
+        queries,
+        "{{{{ config(materialized='view') }}}}\nSELECT * FROM {{{{ ref('orders') }}}} WHERE status = {{{{ var('status') }}}};"
+    )
+    .unwrap();
+    let args = [
+        "analyze",
+        "-s",
+        schema.path().to_str().unwrap(),
+        "-q",
+        queries.path().to_str().unwrap(),
+        "-f",
+        "json",
+        "--no-llm",
+        "--template",
+        "jinja"
+    ];
+    let output = cmd().args(args).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let violations = report["violations"].as_array().unwrap();
+    let select_star = violations
+        .iter()
+        .find(|v| v["rule_id"] == "STYLE001")
+        .expect("STYLE001");
+    assert!(
+        select_star["message"]
+            .as_str()
+            .unwrap()
+            .ends_with("(templated statement; reduced confidence)")
+    );
+    assert_eq!(select_star["location"]["line"], 2);
+
+    let output = cmd().args(&args[..8]).output().unwrap();
+    assert!(!output.status.success());
+}
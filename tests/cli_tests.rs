@@ -15,6 +15,12 @@ fn test_provider_default_model_anthropic() {
     assert_eq!(provider.default_model(), "claude-sonnet-4-20250514");
 }
 
+#[test]
+fn test_provider_default_model_cohere() {
+    let provider = Provider::Cohere;
+    assert_eq!(provider.default_model(), "command-r");
+}
+
 #[test]
 fn test_provider_default_model_ollama() {
     let provider = Provider::Ollama;
@@ -3,7 +3,11 @@
 
 use std::env::{remove_var, set_var};
 
-use sql_query_analyzer::config::{Config, RulesConfig};
+use sql_query_analyzer::{
+    config::{Config, Profile, RELAXED_DISABLED, RulesConfig, STRICT_PROMOTED},
+    query::{SqlDialect, parse_queries},
+    rules::{RuleRunner, Severity, rule_catalog}
+};
 
 #[test]
 fn test_default_config() {
@@ -44,7 +48,8 @@ fn test_rules_config_with_severity() {
     severity.insert("PERF001".to_string(), "error".to_string());
     let config = RulesConfig {
         disabled: vec![],
-        severity
+        severity,
+        ..Default::default()
     };
     assert_eq!(config.severity.get("PERF001").unwrap(), "error");
 }
@@ -70,6 +75,7 @@ fn test_retry_config_default() {
     assert_eq!(config.initial_delay_ms, 1000);
     assert_eq!(config.max_delay_ms, 30000);
     assert_eq!(config.backoff_factor, 2.0);
+    assert_eq!(config.timeout_secs, 120);
 }
 
 #[test]
@@ -161,3 +167,168 @@ fn test_config_load_with_env_vars() {
         remove_var("OLLAMA_URL");
     }
 }
+
+#[test]
+fn test_validate_config_reports_unknown_keys() {
+    let problems = sql_query_analyzer::config::validate_config(
+        "[rules]\ndisabled = []\nignored = []\n\n[output]\ncolor = false\n"
+    );
+    assert_eq!(
+        problems,
+        vec!["unknown key 'output'", "unknown key 'rules.ignored'"]
+    );
+}
+
+#[test]
+fn test_validate_config_reports_wrong_types() {
+    let problems =
+        sql_query_analyzer::config::validate_config("[retry]\nmax_retries = \"three\"\n");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("invalid value:"));
+}
+
+#[test]
+fn test_validate_config_reports_unknown_severity_rule() {
+    let problems =
+        sql_query_analyzer::config::validate_config("[rules.severity]\nNOPE001 = \"info\"\n");
+    assert_eq!(problems, vec!["rules.severity: unknown rule ID 'NOPE001'"]);
+}
+
+#[test]
+fn test_validate_config_reports_bad_category_override() {
+    let problems = sql_query_analyzer::config::validate_config(
+        "[rules.category]
+NOPE001 = \"security\"
+PERF001 = \"speed\"
+"
+    );
+    assert_eq!(
+        problems,
+        vec![
+            "rules.category: unknown rule ID 'NOPE001'",
+            "rules.category.PERF001: invalid category 'speed' (expected performance, style, or security)"
+        ]
+    );
+}
+
+#[test]
+fn test_validate_config_accepts_category_override() {
+    let problems = sql_query_analyzer::config::validate_config(
+        "[rules.category]
+PERF005 = \"security\"
+"
+    );
+    assert!(problems.is_empty(), "{:?}", problems);
+}
+
+fn custom_rule(id: &str, pattern: &str) -> String {
+    format!(
+        "[[rules.custom]]\nid = \"{}\"\npattern = '{}'\nseverity = \"info\"\ncategory = \"style\"\nmessage = \"m\"\n",
+        id, pattern
+    )
+}
+
+#[test]
+fn test_custom_rules_parse_and_check() {
+    let config: Config = toml::from_str(&custom_rule("TEAM001", r"\bTODO\b")).unwrap();
+    assert_eq!(config.rules.custom.len(), 1);
+    assert_eq!(config.rules.custom[0].pattern, r"\bTODO\b");
+    assert!(config.rules.check_custom().is_ok());
+}
+
+#[test]
+fn test_custom_rule_invalid_regex_rejected() {
+    let config: Config = toml::from_str(&custom_rule("TEAM001", "(unclosed")).unwrap();
+    assert!(config.rules.check_custom().is_err());
+    let problems =
+        sql_query_analyzer::config::validate_config(&custom_rule("TEAM001", "(unclosed"));
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("rules.custom: Custom rule 'TEAM001': invalid pattern"));
+}
+
+#[test]
+fn test_custom_rule_builtin_id_rejected() {
+    let config: Config = toml::from_str(&custom_rule("PERF001", "x")).unwrap();
+    assert!(config.rules.check_custom().is_err());
+    let problems = sql_query_analyzer::config::validate_config(&custom_rule("perf001", "x"));
+    assert_eq!(
+        problems,
+        vec!["rules.custom: Custom rule 'perf001': ID is already in use"]
+    );
+}
+
+#[test]
+fn test_validate_config_accepts_custom_rule_ids() {
+    let content = format!(
+        "[rules]\ndisabled = [\"TEAM002\"]\n\n[rules.severity]\nTEAM001 = \"error\"\n\n{}{}",
+        custom_rule("TEAM001", "a"),
+        custom_rule("TEAM002", "b")
+    );
+    assert!(sql_query_analyzer::config::validate_config(&content).is_empty());
+}
+
+#[test]
+fn test_validate_config_reports_unknown_custom_rule_key() {
+    let content = custom_rule("TEAM001", "a") + "regex = \"b\"\n";
+    assert_eq!(
+        sql_query_analyzer::config::validate_config(&content),
+        vec!["unknown key 'rules.custom.regex'"]
+    );
+}
+
+fn style001_severity(rules: RulesConfig) -> Option<Severity> {
+    let queries = parse_queries("SELECT * FROM users LIMIT 10", SqlDialect::Generic).unwrap();
+    RuleRunner::with_config(rules)
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .find(|v| v.rule_id == "STYLE001")
+        .map(|v| v.severity)
+}
+
+#[test]
+fn test_strict_profile_promotes_style001() {
+    let rules = RulesConfig::default().over_profile(Profile::Strict);
+    assert!(style001_severity(rules).is_some_and(|s| s >= Severity::Warning));
+}
+
+#[test]
+fn test_relaxed_profile_disables_style001() {
+    let rules = RulesConfig::default().over_profile(Profile::Relaxed);
+    assert_eq!(style001_severity(rules), None);
+    let balanced = RulesConfig::default().over_profile(Profile::Balanced);
+    assert_eq!(style001_severity(balanced), Some(Severity::Info));
+}
+
+#[test]
+fn test_config_overrides_profile() {
+    let rules: RulesConfig = toml::from_str(
+        r#"
+        disabled = ["PERF001"]
+
+        [severity]
+        STYLE001 = "error"
+        "#
+    )
+    .unwrap();
+    let merged = rules.clone().over_profile(Profile::Relaxed);
+    assert!(merged.disabled.iter().any(|id| id == "PERF001"));
+    assert!(merged.disabled.iter().any(|id| id == "STYLE002"));
+    assert_eq!(style001_severity(merged), Some(Severity::Error));
+    assert_eq!(
+        style001_severity(rules.over_profile(Profile::Strict)),
+        Some(Severity::Error)
+    );
+}
+
+#[test]
+fn test_profile_rule_ids_exist() {
+    let catalog = rule_catalog();
+    for id in STRICT_PROMOTED {
+        let entry = catalog.iter().find(|entry| entry.id == *id).unwrap();
+        assert_eq!(entry.severity, Severity::Info, "{}", id);
+    }
+    for id in RELAXED_DISABLED {
+        assert!(catalog.iter().any(|entry| entry.id == *id), "{}", id);
+    }
+}
@@ -0,0 +1,200 @@
+// SPDX-FileCopyrightText: 2026 RAprogramm
+// SPDX-License-Identifier: MIT
+
+use sql_query_analyzer::{
+    config::{Config, CustomRuleConfig},
+    incremental::{IncrementalState, analysis_key},
+    query::{SqlDialect, parse_queries},
+    rules::{AnalysisReport, RuleRunner},
+    schema::Schema
+};
+use tempfile::TempDir;
+
+const SCHEMA: &str = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255));";
+const QUERIES: &str = "SELECT * FROM users;";
+
+fn key(queries: &str, config: &Config) -> String {
+    analysis_key(SCHEMA, queries, SqlDialect::Generic, config)
+}
+
+fn report(queries: &str) -> AnalysisReport {
+    let queries = parse_queries(queries, SqlDialect::Generic).unwrap();
+    RuleRunner::new().analyze(&queries)
+}
+
+#[test]
+fn test_unchanged_file_reuses_stored_report() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("state.json");
+    let config = Config::default();
+    let original = report(QUERIES);
+    assert!(!original.violations.is_empty());
+
+    let mut state = IncrementalState::load(&path);
+    assert!(
+        state
+            .lookup("queries.sql", &key(QUERIES, &config))
+            .is_none()
+    );
+    state.record("queries.sql", key(QUERIES, &config), &original);
+    state.save().unwrap();
+
+    let reloaded = IncrementalState::load(&path);
+    let stored = reloaded
+        .lookup("queries.sql", &key(QUERIES, &config))
+        .unwrap();
+    assert_eq!(stored.queries_count, original.queries_count);
+    assert_eq!(stored.violations.len(), original.violations.len());
+    for (stored, original) in stored.violations.iter().zip(&original.violations) {
+        assert_eq!(stored.rule_id, original.rule_id);
+        assert_eq!(stored.message, original.message);
+        assert_eq!(stored.severity, original.severity);
+        assert_eq!(stored.location, original.location);
+    }
+}
+
+#[test]
+fn test_schema_violations_are_stored() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("state.json");
+    let config = Config::default();
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, id INT);",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let queries = parse_queries(QUERIES, SqlDialect::Generic).unwrap();
+    let original =
+        RuleRunner::with_schema_and_config(schema, config.rules.clone()).analyze(&queries);
+    assert_eq!(original.schema_violations.len(), 1);
+
+    let mut state = IncrementalState::load(&path);
+    state.record("queries.sql", key(QUERIES, &config), &original);
+    state.save().unwrap();
+    let stored = IncrementalState::load(&path)
+        .lookup("queries.sql", &key(QUERIES, &config))
+        .unwrap();
+    assert_eq!(stored.schema_violations.len(), 1);
+    assert_eq!(stored.schema_violations[0].rule_id, "SCHEMA019");
+    assert_eq!(stored.violations.len(), original.violations.len());
+}
+
+#[test]
+fn test_changed_file_is_not_reused() {
+    let config = Config::default();
+    let mut state = IncrementalState::load(TempDir::new().unwrap().path().join("s.json"));
+    state.record("queries.sql", key(QUERIES, &config), &report(QUERIES));
+    let changed = "SELECT id FROM users;";
+    assert!(
+        state
+            .lookup("queries.sql", &key(changed, &config))
+            .is_none()
+    );
+    assert!(state.lookup("other.sql", &key(QUERIES, &config)).is_none());
+}
+
+#[test]
+fn test_rules_config_change_invalidates_key() {
+    let config = Config::default();
+    let mut disabled = Config::default();
+    disabled.rules.disabled.push("PERF001".to_string());
+    let mut escalated = Config::default();
+    escalated
+        .rules
+        .severity
+        .insert("STYLE001".to_string(), "error".to_string());
+    let base = key(QUERIES, &config);
+    assert_eq!(base, key(QUERIES, &Config::default()));
+    assert_ne!(base, key(QUERIES, &disabled));
+    assert_ne!(base, key(QUERIES, &escalated));
+    assert_ne!(
+        base,
+        analysis_key(SCHEMA, QUERIES, SqlDialect::PostgreSQL, &config)
+    );
+    assert_ne!(
+        base,
+        analysis_key(
+            "CREATE TABLE users (id INT);",
+            QUERIES,
+            SqlDialect::Generic,
+            &config
+        )
+    );
+}
+
+#[test]
+fn test_sensitive_variables_change_invalidates_key() {
+    let base = key(QUERIES, &Config::default());
+    let mut sensitive = Config::default();
+    sensitive
+        .rules
+        .sensitive_variables
+        .push("foo_var".to_string());
+    assert_ne!(base, key(QUERIES, &sensitive));
+    let mut reordered = Config::default();
+    reordered.rules.sensitive_variables = vec!["BAR_VAR".to_string(), "foo_var".to_string()];
+    let mut other = Config::default();
+    other.rules.sensitive_variables = vec!["Foo_Var".to_string(), "bar_var".to_string()];
+    assert_eq!(key(QUERIES, &reordered), key(QUERIES, &other));
+}
+
+#[test]
+fn test_custom_rules_change_invalidates_key() {
+    let todo = CustomRuleConfig {
+        id:         "CUSTOM001".to_string(),
+        name:       None,
+        pattern:    "(?i)TODO".to_string(),
+        severity:   "warning".to_string(),
+        category:   "style".to_string(),
+        message:    "Unresolved TODO".to_string(),
+        suggestion: None
+    };
+    let base = key(QUERIES, &Config::default());
+    let mut added = Config::default();
+    added.rules.custom.push(todo.clone());
+    assert_ne!(base, key(QUERIES, &added));
+    let edits = [
+        CustomRuleConfig {
+            pattern: "(?i)FIXME".to_string(),
+            ..todo.clone()
+        },
+        CustomRuleConfig {
+            severity: "error".to_string(),
+            ..todo.clone()
+        },
+        CustomRuleConfig {
+            category: "security".to_string(),
+            ..todo.clone()
+        },
+        CustomRuleConfig {
+            message: "Resolve the TODO".to_string(),
+            ..todo.clone()
+        },
+        CustomRuleConfig {
+            suggestion: Some("Remove the comment".to_string()),
+            ..todo.clone()
+        },
+        CustomRuleConfig {
+            id: "CUSTOM002".to_string(),
+            ..todo.clone()
+        }
+    ];
+    for edit in edits {
+        let mut edited = Config::default();
+        edited.rules.custom.push(edit.clone());
+        assert_ne!(key(QUERIES, &added), key(QUERIES, &edited), "{:?}", edit);
+    }
+}
+
+#[test]
+fn test_corrupt_state_file_is_empty() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("state.json");
+    std::fs::write(&path, "not json").unwrap();
+    let state = IncrementalState::load(&path);
+    assert!(
+        state
+            .lookup("queries.sql", &key(QUERIES, &Config::default()))
+            .is_none()
+    );
+}
@@ -0,0 +1,329 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "llm")]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering}
+    },
+    thread,
+    time::{Duration, Instant}
+};
+
+use sql_query_analyzer::{
+    config::RetryConfig,
+    llm::{LlmAuditLog, LlmCache, LlmClient, LlmProvider, audit::AuditEntry, split_batches},
+    output::{OutputFormat, OutputOptions, format_queries_summary_from},
+    query::{SqlDialect, parse_queries}
+};
+use tempfile::TempDir;
+
+/// Serve an Ollama-style response on a local port and panic on any request
+/// after the first. Returns the base URL and the request counter.
+fn mock_ollama_once(response: &'static str) -> (String, Arc<AtomicUsize>) {
+    mock_ollama(response, 1)
+}
+
+/// Serve an Ollama-style response on a local port and panic on any request
+/// after the first `limit`. Returns the base URL and the request counter.
+fn mock_ollama(response: &'static str, limit: usize) -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            if counter.fetch_add(1, Ordering::SeqCst) >= limit {
+                panic!("LLM endpoint called more than {} times", limit);
+            }
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':')
+                    && name.eq_ignore_ascii_case("content-length")
+                {
+                    content_length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let payload = format!("{{\"response\":\"{}\"}}", response);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                payload.len(),
+                payload
+            )
+            .unwrap();
+        }
+    });
+    (url, calls)
+}
+
+/// Accept connections on a local port and never answer. Returns the base URL
+/// and the connection counter.
+fn mock_silent_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&connections);
+    thread::spawn(move || {
+        let mut open = Vec::new();
+        for stream in listener.incoming() {
+            counter.fetch_add(1, Ordering::SeqCst);
+            open.push(stream.unwrap());
+        }
+    });
+    (url, connections)
+}
+
+fn client(base_url: &str, cache: LlmCache) -> LlmClient {
+    let provider = LlmProvider::Ollama {
+        base_url: base_url.to_string(),
+        model:    "llama3.2".to_string()
+    };
+    let retry = RetryConfig {
+        max_retries: 0,
+        ..RetryConfig::default()
+    };
+    LlmClient::with_retry_config(provider, retry).with_cache(cache)
+}
+
+#[tokio::test]
+async fn test_llm_cache_serves_identical_inputs() {
+    let dir = TempDir::new().unwrap();
+    let (url, calls) = mock_ollama_once("Add an index on users.email");
+    let cache = LlmCache::new(dir.path(), Duration::from_secs(3600));
+    let first = client(&url, cache.clone())
+        .analyze("schema", "queries")
+        .await
+        .unwrap();
+    let second = client(&url, cache)
+        .analyze("schema", "queries")
+        .await
+        .unwrap();
+    assert_eq!(first, "Add an index on users.email");
+    assert_eq!(second, first);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_llm_cache_key_depends_on_every_input() {
+    let key = LlmCache::key("prompt", "ollama", "llama3.2");
+    assert_eq!(key, LlmCache::key("prompt", "ollama", "llama3.2"));
+    assert_ne!(key, LlmCache::key("prompt2", "ollama", "llama3.2"));
+    assert_ne!(key, LlmCache::key("prompt", "openai", "llama3.2"));
+    assert_ne!(key, LlmCache::key("prompt", "ollama", "codellama"));
+}
+
+#[test]
+fn test_llm_cache_key_is_stable() {
+    assert_eq!(
+        LlmCache::key("prompt", "ollama", "llama3.2"),
+        "65d3613d08f7a2da"
+    );
+}
+
+#[test]
+fn test_llm_cache_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let cache = LlmCache::new(dir.path(), Duration::from_secs(3600));
+    assert!(cache.get("abc").is_none());
+    cache.put("abc", "response");
+    assert_eq!(cache.get("abc").as_deref(), Some("response"));
+}
+
+#[test]
+fn test_llm_cache_expired_entry_is_miss() {
+    let dir = TempDir::new().unwrap();
+    let cache = LlmCache::new(dir.path(), Duration::ZERO);
+    cache.put("abc", "response");
+    assert!(cache.get("abc").is_none());
+}
+
+#[tokio::test]
+async fn test_llm_request_timeout_is_applied_and_retried() {
+    let (url, connections) = mock_silent_server();
+    let provider = LlmProvider::Ollama {
+        base_url: url,
+        model:    "llama3.2".to_string()
+    };
+    let retry = RetryConfig {
+        max_retries: 1,
+        initial_delay_ms: 10,
+        timeout_secs: 1,
+        ..RetryConfig::default()
+    };
+    let started = Instant::now();
+    let err = LlmClient::with_retry_config(provider, retry)
+        .analyze("schema", "queries")
+        .await
+        .unwrap_err();
+    assert!(started.elapsed() < Duration::from_secs(30));
+    assert!(err.to_string().to_lowercase().contains("timeout"), "{err}");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_llm_audit_log_records_request_without_prompt() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let (url, _) = mock_ollama_once("Add an index on users.email");
+    let provider = LlmProvider::Ollama {
+        base_url: url,
+        model:    "llama3.2".to_string()
+    };
+    LlmClient::with_retry_config(provider, RetryConfig::default())
+        .with_audit_log(LlmAuditLog::new(&path))
+        .analyze("CREATE TABLE secret_table", "SELECT secret_column")
+        .await
+        .unwrap();
+    let log = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(log.lines().count(), 1);
+    assert!(!log.contains("secret_"), "{log}");
+    let entry: AuditEntry = serde_json::from_str(log.trim_end()).unwrap();
+    assert_eq!(entry.provider, "ollama");
+    assert_eq!(entry.model, "llama3.2");
+    assert_eq!(entry.status, "ok");
+    assert_eq!(entry.http_status, Some(200));
+    assert!(entry.prompt_bytes > 0);
+    assert!(entry.prompt.is_none());
+}
+
+#[tokio::test]
+async fn test_llm_audit_log_includes_prompt_on_request() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let (url, _) = mock_ollama_once("ok");
+    let provider = LlmProvider::Ollama {
+        base_url: url,
+        model:    "llama3.2".to_string()
+    };
+    LlmClient::with_retry_config(provider, RetryConfig::default())
+        .with_audit_log(LlmAuditLog::new(&path).with_prompt(true))
+        .analyze("CREATE TABLE users", "SELECT id FROM users")
+        .await
+        .unwrap();
+    let log = std::fs::read_to_string(&path).unwrap();
+    let entry: AuditEntry = serde_json::from_str(log.trim_end()).unwrap();
+    let prompt = entry.prompt.unwrap();
+    assert!(prompt.contains("SELECT id FROM users"));
+    assert_eq!(entry.prompt_bytes, prompt.len());
+}
+
+#[tokio::test]
+async fn test_llm_audit_log_records_every_attempt() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let (url, _) = mock_silent_server();
+    let provider = LlmProvider::Ollama {
+        base_url: url,
+        model:    "llama3.2".to_string()
+    };
+    let retry = RetryConfig {
+        max_retries: 1,
+        initial_delay_ms: 10,
+        timeout_secs: 1,
+        ..RetryConfig::default()
+    };
+    LlmClient::with_retry_config(provider, retry)
+        .with_audit_log(LlmAuditLog::new(&path))
+        .analyze("schema", "queries")
+        .await
+        .unwrap_err();
+    let log = std::fs::read_to_string(&path).unwrap();
+    let entries: Vec<AuditEntry> = log
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 2);
+    assert!(
+        entries
+            .iter()
+            .all(|e| e.status == "error" && e.http_status.is_none())
+    );
+}
+
+#[test]
+fn test_split_batches_respects_token_budget() {
+    let sql: String = (0..50)
+        .map(|i| format!("SELECT id, email FROM users WHERE id = {};\n", i))
+        .collect();
+    let queries = parse_queries(&sql, SqlDialect::Generic).unwrap();
+    let opts = OutputOptions {
+        colored: false,
+        ..OutputOptions::default()
+    };
+    let batches = split_batches(queries.len(), 400, |range| {
+        format_queries_summary_from(&queries[range.clone()], range.start + 1, &opts)
+    });
+    assert!(batches.len() > 1, "{}", batches.len());
+    assert_eq!(batches[0].queries.start, 0);
+    assert_eq!(batches.last().unwrap().queries.end, queries.len());
+    for pair in batches.windows(2) {
+        assert_eq!(pair[0].queries.end, pair[1].queries.start);
+    }
+    let second = &batches[1];
+    assert!(
+        second
+            .summary
+            .contains(&format!("Query #{} (SELECT):", second.queries.start + 1)),
+        "{}",
+        second.summary
+    );
+    let whole = split_batches(queries.len(), usize::MAX, |range| {
+        format_queries_summary_from(&queries[range], 1, &opts)
+    });
+    assert_eq!(whole.len(), 1);
+    assert_eq!(whole[0].queries, 0..queries.len());
+}
+
+#[tokio::test]
+async fn test_large_query_set_analyzed_in_batches() {
+    let sql: String = (0..30)
+        .map(|i| format!("SELECT * FROM orders WHERE user_id = {};\n", i))
+        .collect();
+    let queries = parse_queries(&sql, SqlDialect::Generic).unwrap();
+    let opts = OutputOptions {
+        format: OutputFormat::Text,
+        colored: false,
+        ..OutputOptions::default()
+    };
+    let batches = split_batches(queries.len(), 300, |range| {
+        format_queries_summary_from(&queries[range.clone()], range.start + 1, &opts)
+    });
+    assert!(batches.len() > 1);
+    let (url, calls) = mock_ollama("Use an index on user_id", batches.len());
+    let provider = LlmProvider::Ollama {
+        base_url: url,
+        model:    "llama3.2".to_string()
+    };
+    let analysis = LlmClient::with_retry_config(provider, RetryConfig::default())
+        .analyze_batches("schema", &batches)
+        .await
+        .unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), batches.len());
+    for batch in &batches {
+        let header = format!(
+            "=== Queries #{}-#{} ===\n\nUse an index on user_id",
+            batch.queries.start + 1,
+            batch.queries.end
+        );
+        assert!(analysis.contains(&header), "{}", analysis);
+    }
+    assert!(analysis.starts_with("=== Queries #1-#"));
+    assert!(analysis.ends_with(&format!(
+        "#{} ===\n\nUse an index on user_id",
+        queries.len()
+    )));
+}
@@ -0,0 +1,99 @@
+// SPDX-FileCopyrightText: 2025 RAprogramm
+// SPDX-License-Identifier: MIT
+
+use std::fs;
+
+use sql_query_analyzer::{
+    locale::Locale,
+    query::{SqlDialect, parse_queries},
+    rules::{AnalysisReport, RuleRunner}
+};
+use tempfile::TempDir;
+
+fn report(sql: &str) -> AnalysisReport {
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    RuleRunner::new().analyze(&queries)
+}
+
+fn message<'a>(report: &'a AnalysisReport, rule_id: &str) -> &'a str {
+    report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == rule_id)
+        .map(|v| v.message.as_str())
+        .unwrap()
+}
+
+#[test]
+fn test_french_catalog_translates_perf001() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("fr.toml");
+    fs::write(
+        &path,
+        r#"
+[messages.PERF001]
+message = "Requête {query} : SELECT * sans LIMIT peut renvoyer un nombre illimité de lignes"
+suggestion = "Ajoutez une clause LIMIT"
+"#
+    )
+    .unwrap();
+    let locale = Locale::load(&path).unwrap();
+    let mut report = report("SELECT * FROM users");
+    locale.translate(&mut report);
+    let perf001 = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF001")
+        .unwrap();
+    assert_eq!(
+        perf001.message,
+        "Requête 1 : SELECT * sans LIMIT peut renvoyer un nombre illimité de lignes"
+    );
+    assert_eq!(
+        perf001.suggestion.as_deref(),
+        Some("Ajoutez une clause LIMIT")
+    );
+}
+
+#[test]
+fn test_json_catalog_keeps_untranslated_rules() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("fr.json");
+    fs::write(
+        &path,
+        r#"{"messages": {"perf001": {"message": "[FR] {message}"}}}"#
+    )
+    .unwrap();
+    let english = report("SELECT * FROM users; DELETE FROM users");
+    let mut translated = english.clone();
+    Locale::load(&path).unwrap().translate(&mut translated);
+    assert_eq!(
+        message(&translated, "PERF001"),
+        format!("[FR] {}", message(&english, "PERF001"))
+    );
+    assert_eq!(message(&translated, "SEC002"), message(&english, "SEC002"));
+}
+
+#[test]
+fn test_resolve_english_is_identity() {
+    let english = report("SELECT * FROM users");
+    let mut translated = english.clone();
+    Locale::resolve("en").unwrap().translate(&mut translated);
+    assert_eq!(
+        message(&translated, "PERF001"),
+        message(&english, "PERF001")
+    );
+}
+
+#[test]
+fn test_resolve_unknown_language_errors() {
+    assert!(Locale::resolve("xx-does-not-exist").is_err());
+}
+
+#[test]
+fn test_invalid_catalog_errors() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("broken.toml");
+    fs::write(&path, "[messages.PERF001\nmessage = 1").unwrap();
+    assert!(Locale::load(&path).is_err());
+}
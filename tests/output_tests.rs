@@ -3,11 +3,15 @@
 
 use sql_query_analyzer::{
     output::{
-        AnalysisResult, OutputFormat, OutputOptions, format_analysis_result,
-        format_queries_summary, format_static_analysis
+        AnalysisResult, CatalogOutputFormat, OutputFormat, OutputOptions, ReportFormatter,
+        SchemaOutputFormat, SourcePaths, format_analysis_result, format_catalog,
+        format_json_schema, format_queries_summary, format_report_summary, format_static_analysis,
+        format_summary_line, validate_template
     },
     query::{Query, SqlDialect, parse_queries},
-    rules::{AnalysisReport, RuleCategory, Severity, Violation}
+    rules::{
+        AnalysisReport, ExitStatus, RuleCategory, RuleRunner, Severity, Violation, rule_catalog
+    }
 };
 
 fn sample_queries() -> Vec<Query> {
@@ -32,7 +36,9 @@ fn make_violation(
         severity,
         category: RuleCategory::Performance,
         query_index,
-        suggestion: suggestion.map(|s| s.to_string())
+        suggestion: suggestion.map(|s| s.to_string()),
+        location: None,
+        origin: None
     }
 }
 
@@ -54,9 +60,14 @@ fn test_output_options_default() {
 fn test_format_queries_summary_text() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("SQL Queries"));
@@ -67,9 +78,14 @@ fn test_format_queries_summary_text() {
 fn test_format_queries_summary_json() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Json,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Json,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.starts_with('['));
@@ -80,9 +96,14 @@ fn test_format_queries_summary_json() {
 fn test_format_queries_summary_yaml() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Yaml,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Yaml,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("users"));
@@ -92,9 +113,14 @@ fn test_format_queries_summary_yaml() {
 fn test_format_queries_summary_sarif() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Sarif,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Sarif,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.starts_with('['));
@@ -104,9 +130,14 @@ fn test_format_queries_summary_sarif() {
 fn test_format_queries_summary_with_verbose() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -116,9 +147,14 @@ fn test_format_queries_summary_with_verbose() {
 fn test_format_queries_summary_colored() {
     let queries = sample_queries();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -129,9 +165,14 @@ fn test_format_analysis_result_text() {
     let queries = sample_queries();
     let analysis = "Test analysis result";
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_analysis_result(&queries, analysis, &opts);
     assert!(output.contains("SQL Query Analysis"));
@@ -143,9 +184,14 @@ fn test_format_analysis_result_text_colored() {
     let queries = sample_queries();
     let analysis = "Test analysis";
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_analysis_result(&queries, analysis, &opts);
     assert!(output.contains("SQL Query Analysis"));
@@ -156,9 +202,14 @@ fn test_format_analysis_result_json() {
     let queries = sample_queries();
     let analysis = "JSON analysis";
     let opts = OutputOptions {
-        format:  OutputFormat::Json,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Json,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_analysis_result(&queries, analysis, &opts);
     assert!(output.contains("queries"));
@@ -170,9 +221,14 @@ fn test_format_analysis_result_yaml() {
     let queries = sample_queries();
     let analysis = "YAML analysis";
     let opts = OutputOptions {
-        format:  OutputFormat::Yaml,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Yaml,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_analysis_result(&queries, analysis, &opts);
     assert!(output.contains("queries"));
@@ -183,9 +239,14 @@ fn test_format_analysis_result_yaml() {
 fn test_format_static_analysis_no_violations() {
     let report = AnalysisReport::new(1, 1);
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("No issues found"));
@@ -195,9 +256,14 @@ fn test_format_static_analysis_no_violations() {
 fn test_format_static_analysis_no_violations_colored() {
     let report = AnalysisReport::new(1, 1);
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("No issues found"));
@@ -214,9 +280,14 @@ fn test_format_static_analysis_with_error() {
         Some("Add WHERE clause")
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("ERROR"));
@@ -236,9 +307,14 @@ fn test_format_static_analysis_with_warning() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("WARN"));
@@ -256,9 +332,14 @@ fn test_format_static_analysis_with_info() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("INFO"));
@@ -276,9 +357,14 @@ fn test_format_static_analysis_colored_error() {
         Some("Fix it")
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("SEC001"));
@@ -295,9 +381,14 @@ fn test_format_static_analysis_colored_warning() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("PERF001"));
@@ -314,9 +405,14 @@ fn test_format_static_analysis_colored_info() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("INFO001"));
@@ -333,15 +429,65 @@ fn test_format_static_analysis_json() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Json,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Json,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("violations"));
     assert!(output.contains("TEST001"));
 }
 
+#[test]
+fn test_format_static_analysis_compact_is_single_line() {
+    let mut report = AnalysisReport::new(2, 1);
+    report.add_violation(make_violation(
+        "TEST001",
+        "Test",
+        Severity::Warning,
+        0,
+        Some("Fix it")
+    ));
+    for format in [OutputFormat::Json, OutputFormat::Sarif] {
+        let opts = OutputOptions {
+            format,
+            colored: false,
+            verbose: false,
+            template: None,
+            compact: true,
+            min_severity: None,
+            exit: None,
+            sources: None
+        };
+        let output = format_static_analysis(&report, &opts);
+        assert!(!output.contains('\n'), "{}", output);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert!(parsed.is_object());
+        assert!(!format_report_summary(&report, &opts).contains('\n'));
+    }
+}
+
+#[test]
+fn test_format_summary_line() {
+    let mut report = AnalysisReport::new(12, 1);
+    for severity in [Severity::Error, Severity::Warning, Severity::Warning] {
+        report.add_violation(make_violation("TEST001", "Test", severity, 0, None));
+    }
+    assert_eq!(
+        format_summary_line(&report.summary(), 12, 2),
+        "sql-query-analyzer: 1 error, 2 warnings, 0 info across 12 queries (exit 2)"
+    );
+    assert_eq!(
+        format_summary_line(&AnalysisReport::new(1, 1).summary(), 1, 0),
+        "sql-query-analyzer: 0 errors, 0 warnings, 0 info across 1 query (exit 0)"
+    );
+}
+
 #[test]
 fn test_format_static_analysis_yaml() {
     let mut report = AnalysisReport::new(1, 1);
@@ -353,14 +499,154 @@ fn test_format_static_analysis_yaml() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Yaml,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Yaml,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("violations"));
 }
 
+#[test]
+fn test_format_static_analysis_sarif_uses_statement_location() {
+    let sql = "SELECT id FROM a;\nDELETE FROM b;";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let delete = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "SEC002")
+        .unwrap();
+    assert_eq!(delete.location.map(|span| span.line), Some(2));
+    let opts = OutputOptions {
+        format:       OutputFormat::Sarif,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_static_analysis(&report, &opts);
+    let sarif: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let region = sarif["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|r| r["ruleId"] == "SEC002")
+        .map(|r| r["locations"][0]["physicalLocation"]["region"].clone())
+        .unwrap();
+    assert_eq!(region["startLine"], 2);
+    assert_eq!(region["startColumn"], 1);
+}
+
+#[test]
+fn test_format_static_analysis_text_schema_section() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.schema_violations.push(make_violation(
+        "SCHEMA019",
+        "Table 'users' declares column 'id' 2 times",
+        Severity::Error,
+        0,
+        None
+    ));
+    let opts = OutputOptions {
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_static_analysis(&report, &opts);
+    assert!(output.contains("Found 1 error(s)"));
+    assert!(output.contains("Schema:\n  [ERROR] SCHEMA019"));
+    assert!(!output.contains("Query #"));
+}
+
+#[test]
+fn test_format_static_analysis_text_template() {
+    let mut report = AnalysisReport::new(2, 1);
+    let mut violation = make_violation(
+        "PERF001",
+        "Leading wildcard",
+        Severity::Warning,
+        1,
+        Some("Use full-text search")
+    );
+    violation.location =
+        parse_queries("SELECT 1;\nSELECT 2", SqlDialect::Generic).unwrap()[1].span;
+    report.add_violation(violation);
+    let opts = OutputOptions {
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     Some(
+            "[{severity}] {rule_id}:{line} q{query} {message} ({suggestion})".to_string()
+        ),
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_static_analysis(&report, &opts);
+    assert!(output.contains("[WARN] PERF001:2 q2 Leading wildcard (Use full-text search)\n"));
+    assert!(!output.contains("→"));
+}
+
+#[test]
+fn test_validate_template() {
+    assert!(
+        validate_template(
+            "{severity} {rule_id} {rule_name} {message} {query} {line} {suggestion}"
+        )
+        .is_ok()
+    );
+    assert!(validate_template("no placeholders").is_ok());
+    let err = validate_template("[{severity}] {rule}:{line}").unwrap_err();
+    assert!(err.to_string().contains("unknown placeholder '{rule}'"));
+    let err = validate_template("{message").unwrap_err();
+    assert!(err.to_string().contains("unclosed"));
+}
+
+#[test]
+fn test_format_static_analysis_sarif_schema_violation() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.schema_violations.push(make_violation(
+        "SCHEMA019",
+        "Duplicate column",
+        Severity::Error,
+        0,
+        None
+    ));
+    let opts = OutputOptions {
+        format:       OutputFormat::Sarif,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let sarif: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    let result = &sarif["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "SCHEMA019");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+        "schema.sql"
+    );
+}
+
 #[test]
 fn test_format_static_analysis_sarif() {
     let mut report = AnalysisReport::new(3, 1);
@@ -386,9 +672,14 @@ fn test_format_static_analysis_sarif() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Sarif,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Sarif,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("$schema"));
@@ -425,15 +716,93 @@ fn test_format_static_analysis_multiple_queries() {
         None
     ));
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_static_analysis(&report, &opts);
     assert!(output.contains("Query #1"));
     assert!(output.contains("Query #2"));
 }
 
+#[test]
+fn test_format_text_names_query_origin() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.add_violation(Violation {
+        origin: Some("list_users".to_string()),
+        ..make_violation("PERF001", "Issue", Severity::Warning, 0, None)
+    });
+    let opts = OutputOptions {
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_static_analysis(&report, &opts);
+    assert!(output.contains("Query #1 (list_users):\n"), "{}", output);
+}
+
+struct RuleIdFormatter;
+
+impl ReportFormatter for RuleIdFormatter {
+    fn format(&self, report: &AnalysisReport, _opts: &OutputOptions) -> String {
+        let ids: Vec<&str> = report.violations.iter().map(|v| v.rule_id).collect();
+        format!("{} queries: {}", report.queries_count, ids.join(","))
+    }
+}
+
+#[test]
+fn test_custom_report_formatter() {
+    let mut report = AnalysisReport::new(2, 1);
+    report.add_violation(make_violation(
+        "PERF001",
+        "Issue",
+        Severity::Warning,
+        0,
+        None
+    ));
+    report.add_violation(make_violation("SEC001", "Issue", Severity::Error, 1, None));
+    let formatter: &dyn ReportFormatter = &RuleIdFormatter;
+    let output = formatter.format(&report, &OutputOptions::default());
+    assert_eq!(output, "2 queries: PERF001,SEC001");
+}
+
+#[test]
+fn test_output_format_selects_builtin_formatter() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.add_violation(make_violation("SEC001", "Issue", Severity::Error, 0, None));
+    for format in [
+        OutputFormat::Text,
+        OutputFormat::Json,
+        OutputFormat::Yaml,
+        OutputFormat::Sarif
+    ] {
+        let opts = OutputOptions {
+            format,
+            colored: false,
+            verbose: false,
+            template: None,
+            compact: false,
+            min_severity: None,
+            exit: None,
+            sources: None
+        };
+        assert_eq!(
+            format.formatter().format(&report, &opts),
+            format_static_analysis(&report, &opts)
+        );
+    }
+}
+
 #[test]
 fn test_output_format_debug() {
     let format = OutputFormat::Text;
@@ -458,9 +827,14 @@ fn test_output_options_debug() {
 #[test]
 fn test_output_options_clone() {
     let opts = OutputOptions {
-        format:  OutputFormat::Yaml,
-        colored: false,
-        verbose: true
+        format:       OutputFormat::Yaml,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let cloned = opts.clone();
     assert!(matches!(cloned.format, OutputFormat::Yaml));
@@ -486,9 +860,14 @@ fn test_format_queries_with_ctes() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("CTEs"));
@@ -503,9 +882,14 @@ fn test_format_queries_with_joins() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("JOIN columns"));
@@ -515,9 +899,14 @@ fn test_format_queries_with_joins() {
 fn test_format_queries_with_order_by() {
     let queries = parse_queries("SELECT * FROM users ORDER BY name", SqlDialect::Generic).unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("ORDER BY columns"));
@@ -531,9 +920,14 @@ fn test_format_queries_with_group_by() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("GROUP BY columns"));
@@ -547,9 +941,14 @@ fn test_format_queries_with_having() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("HAVING columns"));
@@ -563,9 +962,14 @@ fn test_format_queries_with_limit_offset() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("LIMIT: 10"));
@@ -576,9 +980,14 @@ fn test_format_queries_with_limit_offset() {
 fn test_format_queries_with_distinct() {
     let queries = parse_queries("SELECT DISTINCT status FROM users", SqlDialect::Generic).unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("DISTINCT"));
@@ -592,9 +1001,14 @@ fn test_format_queries_with_union() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("UNION"));
@@ -608,9 +1022,14 @@ fn test_format_queries_with_subquery() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("subquery"));
@@ -620,9 +1039,14 @@ fn test_format_queries_with_subquery() {
 fn test_format_queries_verbose_low_complexity() {
     let queries = parse_queries("SELECT id FROM users", SqlDialect::Generic).unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Low"));
@@ -637,9 +1061,14 @@ fn test_format_queries_verbose_medium_complexity() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -657,9 +1086,14 @@ fn test_format_queries_verbose_high_complexity() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -675,9 +1109,14 @@ fn test_format_queries_verbose_colored_high() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -692,9 +1131,14 @@ fn test_format_queries_verbose_colored_medium() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: true,
-        verbose: true
+        format:       OutputFormat::Text,
+        colored:      true,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Complexity"));
@@ -708,9 +1152,14 @@ fn test_format_queries_with_window_functions() {
     )
     .unwrap();
     let opts = OutputOptions {
-        format:  OutputFormat::Text,
-        colored: false,
-        verbose: false
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
     };
     let output = format_queries_summary(&queries, &opts);
     assert!(output.contains("Window functions"));
@@ -737,3 +1186,334 @@ fn test_analysis_report_counts() {
     assert_eq!(report.warning_count(), 1);
     assert_eq!(report.info_count(), 3);
 }
+
+#[test]
+fn test_format_catalog_markdown() {
+    let output = format_catalog(&rule_catalog(), CatalogOutputFormat::Markdown);
+    assert!(output.starts_with("# Rule Catalog"));
+    assert!(output.contains("## Performance Rules"));
+    assert!(output.contains("## Schema-Aware Rules"));
+    assert!(output.contains("| `SEC001` |"));
+    assert!(output.contains("```sql"));
+}
+
+fn summary_report() -> AnalysisReport {
+    let mut report = AnalysisReport::new(2, 10);
+    report.add_violation(make_violation("PERF001", "Error", Severity::Error, 0, None));
+    report.add_violation(make_violation(
+        "PERF002",
+        "Warning",
+        Severity::Warning,
+        1,
+        None
+    ));
+    report.add_violation(make_violation(
+        "PERF003",
+        "Warning",
+        Severity::Warning,
+        1,
+        None
+    ));
+    report
+}
+
+#[test]
+fn test_format_report_summary_json() {
+    let opts = OutputOptions {
+        format:       OutputFormat::Json,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_report_summary(&summary_report(), &opts);
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert!(value.get("violations").is_none());
+    assert_eq!(value["errors"], 1);
+    assert_eq!(value["warnings"], 2);
+    assert_eq!(value["info"], 0);
+    assert_eq!(value["by_category"]["Performance"], 3);
+    assert_eq!(value["by_category"]["Security"], 0);
+    assert_eq!(value["highest_severity"], "Error");
+}
+
+#[test]
+fn test_format_report_summary_text() {
+    let opts = OutputOptions {
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_report_summary(&summary_report(), &opts);
+    assert!(output.contains("Errors: 1"));
+    assert!(output.contains("Warnings: 2"));
+    assert!(output.contains("Performance: 3"));
+    assert!(output.contains("Highest severity: ERROR"));
+    assert!(!output.contains("PERF001"));
+}
+
+#[test]
+fn test_format_report_summary_sarif_has_no_results() {
+    let opts = OutputOptions {
+        format:       OutputFormat::Sarif,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_report_summary(&summary_report(), &opts);
+    let value: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(value["runs"][0]["results"].as_array().unwrap().len(), 0);
+    assert_eq!(value["runs"][0]["properties"]["errors"], 1);
+}
+
+#[test]
+fn test_format_static_analysis_counts_unanalyzed() {
+    let mut report = AnalysisReport::new(3, 10);
+    report.unanalyzed = vec![0, 2];
+    let verbose = OutputOptions {
+        format:       OutputFormat::Text,
+        colored:      false,
+        verbose:      true,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let output = format_static_analysis(&report, &verbose);
+    assert!(output.contains("Skipped 2 unsupported statement(s): Query #1, #3"));
+    let quiet = OutputOptions {
+        verbose: false,
+        ..verbose
+    };
+    let output = format_static_analysis(&report, &quiet);
+    assert!(output.contains("Skipped 2 unsupported statement(s)\n"));
+    assert!(!output.contains("Query #1"));
+}
+
+/// Keys of `value` that `schema` lists under `properties`
+fn undeclared_keys(value: &serde_json::Value, schema: &serde_json::Value) -> Vec<String> {
+    value
+        .as_object()
+        .unwrap()
+        .keys()
+        .filter(|key| schema["properties"].get(key.as_str()).is_none())
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn test_json_output_includes_exit_code() {
+    let mut report = AnalysisReport::new(2, 10);
+    report.violations.push(make_violation(
+        "PERF001",
+        "Select star",
+        Severity::Warning,
+        0,
+        None
+    ));
+    report.violations.push(make_violation(
+        "SEC001",
+        "Delete without WHERE",
+        Severity::Error,
+        1,
+        None
+    ));
+    let opts = OutputOptions {
+        format: OutputFormat::Json,
+        ..OutputOptions::default()
+    };
+    let output: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    assert_eq!(output["exit_code"], 2);
+    assert_eq!(output["exit_reason"], "1 error found");
+    assert_eq!(output["queries_count"], 2);
+}
+
+#[test]
+fn test_json_output_uses_given_exit_status() {
+    let report = AnalysisReport::new(1, 1);
+    let opts = OutputOptions {
+        format: OutputFormat::Json,
+        exit: Some(ExitStatus {
+            exit_code:   1,
+            exit_reason: "3 warnings found".to_string()
+        }),
+        ..OutputOptions::default()
+    };
+    let output: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    assert_eq!(output["exit_code"], 1);
+    assert_eq!(output["exit_reason"], "3 warnings found");
+}
+
+#[test]
+fn test_github_annotations_one_command_per_violation() {
+    let queries = parse_queries(
+        "SELECT id FROM users;\nSELECT * FROM orders",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let opts = OutputOptions {
+        format: OutputFormat::GithubAnnotations,
+        sources: Some(SourcePaths {
+            schema:  "db/schema.sql".to_string(),
+            queries: "db/queries.sql".to_string()
+        }),
+        ..OutputOptions::default()
+    };
+    let output = format_static_analysis(&report, &opts);
+    let warning = output
+        .lines()
+        .find(|line| line.starts_with("::warning ") && line.contains("::PERF001: "))
+        .unwrap();
+    assert!(warning.starts_with("::warning file=db/queries.sql,line=2,col=1,title=PERF001 "));
+    assert_eq!(output.lines().count(), report.violations.len());
+    assert!(output.lines().all(|line| line.starts_with("::")));
+}
+
+#[test]
+fn test_github_annotations_levels_and_escaping() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.violations.push(make_violation(
+        "PERF003",
+        "100% of rows,\nscanned",
+        Severity::Info,
+        0,
+        Some("Use IN")
+    ));
+    report.schema_violations.push(make_violation(
+        "SCHEMA019",
+        "Duplicate index",
+        Severity::Error,
+        0,
+        None
+    ));
+    let opts = OutputOptions {
+        format: OutputFormat::GithubAnnotations,
+        ..OutputOptions::default()
+    };
+    let output = format_static_analysis(&report, &opts);
+    assert_eq!(
+        output,
+        "::notice file=queries.sql,title=PERF003 Test Rule::PERF003: 100%25 of rows,%0Ascanned%0ASuggestion: Use IN\n\
+         ::error file=schema.sql,title=SCHEMA019 Test Rule::SCHEMA019: Duplicate index"
+    );
+}
+
+#[test]
+fn test_sarif_output_has_no_exit_code() {
+    let report = AnalysisReport::new(1, 1);
+    let opts = OutputOptions {
+        format: OutputFormat::Sarif,
+        ..OutputOptions::default()
+    };
+    let output = format_static_analysis(&report, &opts);
+    assert!(!output.contains("exit_code"));
+    assert!(!output.contains("exit_reason"));
+}
+
+#[test]
+fn test_report_json_schema_describes_json_output() {
+    let schema: serde_json::Value =
+        serde_json::from_str(&format_json_schema(SchemaOutputFormat::Json)).unwrap();
+    assert_eq!(schema["properties"]["violations"]["type"], "array");
+    assert_eq!(
+        schema["properties"]["violations"]["items"]["$ref"],
+        "#/$defs/violation"
+    );
+    let mut report = AnalysisReport::new(2, 10);
+    report.violations.push(make_violation(
+        "PERF001",
+        "Select star",
+        Severity::Warning,
+        0,
+        Some("List columns")
+    ));
+    report.schema_violations.push(make_violation(
+        "SCHEMA019",
+        "Duplicate",
+        Severity::Error,
+        0,
+        None
+    ));
+    report.queries_per_second = Some(1000.0);
+    let opts = OutputOptions {
+        format: OutputFormat::Json,
+        ..OutputOptions::default()
+    };
+    let output: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    assert!(undeclared_keys(&output, &schema).is_empty());
+    let violation_schema = &schema["$defs"]["violation"];
+    assert!(undeclared_keys(&output["violations"][0], violation_schema).is_empty());
+    assert_eq!(
+        violation_schema["required"].as_array().unwrap().len(),
+        output["violations"][0].as_object().unwrap().len()
+    );
+}
+
+#[test]
+fn test_sarif_json_schema_describes_sarif_output() {
+    let schema: serde_json::Value =
+        serde_json::from_str(&format_json_schema(SchemaOutputFormat::Sarif)).unwrap();
+    assert_eq!(schema["properties"]["version"]["const"], "2.1.0");
+    let mut report = AnalysisReport::new(1, 10);
+    report.violations.push(make_violation(
+        "PERF001",
+        "Select star",
+        Severity::Info,
+        0,
+        None
+    ));
+    let opts = OutputOptions {
+        format: OutputFormat::Sarif,
+        ..OutputOptions::default()
+    };
+    let output: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    assert!(undeclared_keys(&output, &schema).is_empty());
+    let run = &output["runs"][0];
+    assert!(undeclared_keys(run, &schema["$defs"]["run"]).is_empty());
+    assert!(undeclared_keys(&run["results"][0], &schema["$defs"]["result"]).is_empty());
+    let levels = schema["$defs"]["result"]["properties"]["level"]["enum"]
+        .as_array()
+        .unwrap();
+    assert!(levels.contains(&run["results"][0]["level"]));
+}
+
+#[test]
+fn test_json_report_is_byte_identical_across_runs() {
+    let sql = "SELECT * FROM users WHERE LOWER(email) = 'a' OR name LIKE '%x' ORDER BY RAND();\n\
+               DELETE FROM orders;\n\
+               SELECT DISTINCT * FROM orders o, users u UNION SELECT * FROM users";
+    let queries = parse_queries(sql, SqlDialect::MySQL).unwrap();
+    let opts = OutputOptions {
+        format: OutputFormat::Json,
+        colored: false,
+        ..OutputOptions::default()
+    };
+    let first = format_static_analysis(&RuleRunner::new().analyze(&queries), &opts);
+    for _ in 0..5 {
+        let again = format_static_analysis(&RuleRunner::new().analyze(&queries), &opts);
+        assert_eq!(again, first);
+    }
+    let report = RuleRunner::new().analyze(&queries);
+    for pair in report.violations.windows(2) {
+        let key = |v: &Violation| (std::cmp::Reverse(v.severity), v.query_index, v.rule_id);
+        assert!(key(&pair[0]) <= key(&pair[1]), "{:?}", pair);
+    }
+}
@@ -1,7 +1,10 @@
 // SPDX-FileCopyrightText: 2025 RAprogramm
 // SPDX-License-Identifier: MIT
 
-use sql_query_analyzer::query::{QueryType, SqlDialect, parse_queries};
+use sql_query_analyzer::query::{
+    ColumnClause, ColumnWrite, LockMode, MergeAction, PlaceholderStyle, QueryType, SqlDialect,
+    StatementSplitter, WrittenLiteral, fingerprint, normalize, parse_queries, stream_queries
+};
 
 #[test]
 fn test_parse_simple_select() {
@@ -38,6 +41,17 @@ fn test_parse_multiple_queries() {
     assert_eq!(queries.len(), 2);
 }
 
+#[test]
+fn test_parse_records_statement_spans() {
+    let sql = "SELECT id FROM users;\n  DELETE FROM orders;";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let first = queries[0].span.unwrap();
+    let second = queries[1].span.unwrap();
+    assert_eq!((first.line, first.column, first.start), (1, 1, 0));
+    assert_eq!((second.line, second.column), (2, 3));
+    assert!(sql[second.start..second.end].starts_with("DELETE FROM orders"));
+}
+
 #[test]
 fn test_parse_insert() {
     let sql = "INSERT INTO users (id, name) VALUES (1, 'test')";
@@ -516,3 +530,535 @@ fn test_left_join_extracts_join_cols() {
     let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
     assert!(!queries[0].join_cols.is_empty());
 }
+
+#[test]
+fn test_like_patterns_extracted() {
+    let sql = "SELECT id FROM users WHERE name LIKE 'abc%' OR (email ILIKE '%@x.com')";
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    let patterns: Vec<&str> = queries[0]
+        .like_patterns
+        .iter()
+        .map(|p| p.as_str())
+        .collect();
+    assert_eq!(patterns, vec!["abc%", "%@x.com"]);
+}
+
+#[test]
+fn test_table_aliases_extracted() {
+    let sql = "SELECT u.id FROM users u JOIN orders ON u.id = orders.user_id";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let query = &queries[0];
+    assert_eq!(query.table_aliases.len(), 2);
+    assert_eq!(query.table_aliases[0].1.as_deref(), Some("u"));
+    assert_eq!(
+        query.tables_without_aliases().collect::<Vec<_>>(),
+        vec!["orders"]
+    );
+}
+
+#[test]
+fn test_insert_columns_and_arity_extracted() {
+    let sql = "INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b')";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let query = &queries[0];
+    assert_eq!(query.insert_columns.len(), 2);
+    assert_eq!(query.insert_values_arity, Some(2));
+}
+
+#[test]
+fn test_column_writes_extracted() {
+    let sql = "INSERT INTO users (id, name, age) VALUES (1, NULL, -3), (2, 'b', age + 1); \
+               UPDATE users SET name = 'c', age = age + 1, id = NULL WHERE id = 1";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let write = |column: &str, value: WrittenLiteral| ColumnWrite {
+        column: column.into(),
+        value
+    };
+    assert_eq!(
+        queries[0].column_writes,
+        vec![
+            write("id", WrittenLiteral::Number("1".into())),
+            write("name", WrittenLiteral::Null),
+            write("age", WrittenLiteral::Number("-3".into())),
+            write("id", WrittenLiteral::Number("2".into())),
+            write("name", WrittenLiteral::String("b".into()))
+        ]
+    );
+    assert_eq!(
+        queries[1].column_writes,
+        vec![
+            write("name", WrittenLiteral::String("c".into())),
+            write("id", WrittenLiteral::Null)
+        ]
+    );
+}
+
+#[test]
+fn test_projection_aggregates_extracted() {
+    let sql = "SELECT UPPER(name), COUNT(*), SUM(o.total) FROM users";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let query = &queries[0];
+    assert_eq!(query.projection_aggregates.as_slice(), ["COUNT", "SUM"]);
+    assert_eq!(query.projection_cols.as_slice(), ["name"]);
+}
+
+#[test]
+fn test_is_analyzable() {
+    let queries = parse_queries("BEGIN; SELECT 1; COMMIT;", SqlDialect::Generic).unwrap();
+    let analyzable: Vec<bool> = queries.iter().map(|q| q.is_analyzable()).collect();
+    assert_eq!(analyzable, vec![false, true, false]);
+}
+
+#[test]
+fn test_dollar_placeholder_is_parameterized() {
+    let queries =
+        parse_queries("SELECT id FROM users WHERE id = $1", SqlDialect::Generic).unwrap();
+    assert!(queries[0].is_parameterized());
+    assert_eq!(queries[0].placeholder_style, Some(PlaceholderStyle::Dollar));
+    assert_eq!(queries[0].placeholder_count, 1);
+}
+
+#[test]
+fn test_question_mark_placeholders_counted_individually() {
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE id = ? AND status = ?",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(
+        queries[0].placeholder_style,
+        Some(PlaceholderStyle::QuestionMark)
+    );
+    assert_eq!(queries[0].placeholder_count, 2);
+}
+
+#[test]
+fn test_named_colon_placeholder() {
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE id = :id OR parent_id = :id",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(queries[0].placeholder_style, Some(PlaceholderStyle::Colon));
+    assert_eq!(queries[0].placeholder_count, 1);
+}
+
+#[test]
+fn test_at_sign_placeholder_not_a_column() {
+    let queries =
+        parse_queries("SELECT id FROM users WHERE id = @p0", SqlDialect::Generic).unwrap();
+    assert_eq!(queries[0].placeholder_style, Some(PlaceholderStyle::AtSign));
+    assert!(!queries[0].where_cols.iter().any(|c| c.starts_with('@')));
+}
+
+#[test]
+fn test_literal_query_not_parameterized() {
+    let queries = parse_queries(
+        "SELECT id::text FROM users WHERE id = 1 AND name = ':x'",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert!(!queries[0].is_parameterized());
+    assert_eq!(queries[0].placeholder_style, None);
+}
+
+#[test]
+fn test_parse_locking_clause() {
+    let queries = parse_queries(
+        "SELECT * FROM jobs FOR UPDATE; SELECT * FROM jobs FOR SHARE; SELECT * FROM jobs",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert_eq!(queries[0].locking, Some(LockMode::Update));
+    assert_eq!(queries[1].locking, Some(LockMode::Share));
+    assert_eq!(queries[2].locking, None);
+}
+
+#[test]
+fn test_detect_clickhouse_dialect() {
+    let ddl = "CREATE TABLE events (ts DateTime CODEC(Delta, ZSTD), id UInt64) \
+               ENGINE = MergeTree ORDER BY ts";
+    assert_eq!(SqlDialect::detect(ddl), SqlDialect::ClickHouse);
+}
+
+#[test]
+fn test_detect_postgres_dialect() {
+    let sql = "CREATE TABLE users (id SERIAL PRIMARY KEY, data JSONB); \
+               SELECT id::text FROM users";
+    assert_eq!(SqlDialect::detect(sql), SqlDialect::PostgreSQL);
+}
+
+#[test]
+fn test_detect_mysql_dialect() {
+    let sql = "CREATE TABLE `users` (id INT AUTO_INCREMENT PRIMARY KEY) ENGINE = InnoDB";
+    assert_eq!(SqlDialect::detect(sql), SqlDialect::MySQL);
+}
+
+#[test]
+fn test_detect_ignores_markers_in_names_literals_and_comments() {
+    let sql = "CREATE TABLE t (serial_number INT, \"jsonb\" TEXT); \
+               -- cast with ::text\n\
+               SELECT serial_number FROM t WHERE note = 'ILIKE'";
+    assert_eq!(SqlDialect::detect(sql), SqlDialect::Generic);
+}
+
+#[test]
+fn test_detect_without_markers_is_generic() {
+    assert_eq!(
+        SqlDialect::detect("SELECT id FROM users WHERE id = 1"),
+        SqlDialect::Generic
+    );
+}
+
+#[test]
+fn test_parse_insert_select_source() {
+    let queries = parse_queries(
+        "INSERT INTO a (x) SELECT x FROM b WHERE y = 1",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let source = queries[0].insert_select.as_ref().unwrap();
+    assert_eq!(source.tables[0].as_str(), "b");
+    assert!(source.where_cols.iter().any(|c| c == "y"));
+    assert!(!source.projection_wildcard);
+    assert_eq!(queries[0].insert_values_arity, None);
+}
+
+#[test]
+fn test_parse_where_or_branches() {
+    let queries = parse_queries(
+        "SELECT id FROM t WHERE (a = 1 AND b = 2) OR (c = 3 OR a = 4)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let branches: Vec<Vec<&str>> = queries[0]
+        .where_or_branches
+        .iter()
+        .map(|cols| cols.iter().map(|c| c.as_str()).collect())
+        .collect();
+    assert_eq!(branches, vec![vec!["a", "b"], vec!["c"], vec!["a"]]);
+}
+
+#[test]
+fn test_parse_join_using() {
+    let queries = parse_queries(
+        "SELECT * FROM a JOIN b ON a.id = b.id JOIN c USING (id, kind)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let using = &queries[0].join_using;
+    assert_eq!(using.len(), 1);
+    assert_eq!(using[0].left, vec!["a", "b"]);
+    assert_eq!(using[0].right, "c");
+    assert_eq!(using[0].columns, vec!["id", "kind"]);
+}
+
+#[test]
+fn test_parse_into_outfile_attaches_to_its_statement() {
+    let sql =
+        "SELECT id FROM users;\nSELECT email FROM users INTO OUTFILE '/tmp/e.csv';\nSELECT 1";
+    let queries = parse_queries(sql, SqlDialect::MySQL).unwrap();
+    assert_eq!(queries.len(), 3);
+    assert!(queries[0].file_export.is_none());
+    let export = queries[1].file_export.as_ref().unwrap();
+    assert_eq!(export.form.as_str(), "INTO OUTFILE");
+    assert_eq!(export.target.as_str(), "/tmp/e.csv");
+    assert!(queries[2].file_export.is_none());
+    let span = queries[1].span.unwrap();
+    assert!(sql[span.start..span.end].starts_with("SELECT email"));
+}
+
+#[test]
+fn test_parse_copy_to_file() {
+    let queries = parse_queries(
+        "COPY users TO '/tmp/u.csv'; COPY users TO STDOUT",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert_eq!(queries[0].tables[0].as_str(), "users");
+    let export = queries[0].file_export.as_ref().unwrap();
+    assert_eq!(export.form.as_str(), "COPY TO");
+    assert_eq!(export.target.as_str(), "/tmp/u.csv");
+    assert!(queries[1].file_export.is_none());
+}
+
+#[test]
+fn test_parse_records_suppressed_rules() {
+    let sql = "SELECT 1;\n-- sqla:disable PERF001 SEC018\nSELECT * FROM t;\nSELECT 2";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    assert!(queries[0].suppressed_rules.is_empty());
+    assert_eq!(queries[1].suppressed_rules, vec!["PERF001", "SEC018"]);
+    assert!(queries[1].is_suppressed("perf001"));
+    assert!(!queries[1].is_suppressed("STYLE001"));
+    assert!(queries[2].suppressed_rules.is_empty());
+}
+
+#[test]
+fn test_parse_attaches_comments_to_statements() {
+    let sql = "-- TODO: paginate\nSELECT id /* inline */ FROM users;\nSELECT '-- not a comment';\nSELECT 2; -- trailing";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    assert_eq!(
+        queries[0].comments,
+        vec!["-- TODO: paginate", "/* inline */"]
+    );
+    assert!(queries[1].comments.is_empty());
+    assert_eq!(queries[2].comments, vec!["-- trailing"]);
+}
+
+#[test]
+fn test_parse_where_json_access() {
+    let queries = parse_queries(
+        "SELECT data ->> 'x' FROM events e WHERE e.data -> 'a' ->> 'b' = '1' AND tags ? 'k' AND id = 2",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    let accesses: Vec<(&str, &str, &str)> = queries[0]
+        .where_json_access
+        .iter()
+        .map(|a| (a.column.as_str(), a.operator.as_str(), a.expr.as_str()))
+        .collect();
+    assert_eq!(
+        accesses,
+        vec![
+            ("e.data", "->>", "e.data -> 'a' ->> 'b'"),
+            ("tags", "?", "tags ? 'k'")
+        ]
+    );
+    assert!(queries[0].where_json_access[0].is_extraction());
+    assert!(!queries[0].where_json_access[1].is_extraction());
+}
+
+#[test]
+fn test_parse_column_refs_by_clause() {
+    let queries = parse_queries(
+        "SELECT u.name FROM users u JOIN orders o USING (id) WHERE u.active = 1 \
+         GROUP BY u.name HAVING COUNT(o.total) > 1 ORDER BY u.name",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let refs: Vec<(String, ColumnClause)> = queries[0]
+        .column_refs
+        .iter()
+        .map(|r| (r.reference.to_string(), r.clause))
+        .collect();
+    assert_eq!(
+        refs,
+        vec![
+            ("u.name".to_string(), ColumnClause::Select),
+            ("id".to_string(), ColumnClause::Join),
+            ("u.active".to_string(), ColumnClause::Where),
+            ("u.name".to_string(), ColumnClause::GroupBy),
+            ("o.total".to_string(), ColumnClause::Having),
+            ("u.name".to_string(), ColumnClause::OrderBy)
+        ]
+    );
+}
+
+#[test]
+fn test_fingerprint_ignores_literal_values() {
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE email = 'a@b.c' AND age > 30;
+         select id  from users\n where email = 'x@y.z' and age > 41 -- note
+         ;SELECT id FROM users WHERE email = $1 AND age > ?",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(
+        normalize(&queries[0]),
+        "SELECT id FROM users WHERE email = ? AND age > ?"
+    );
+    assert_eq!(fingerprint(&queries[0]), fingerprint(&queries[1]));
+    assert_eq!(fingerprint(&queries[0]), fingerprint(&queries[2]));
+    assert_eq!(fingerprint(&queries[0]).len(), 16);
+}
+
+#[test]
+fn test_fingerprint_distinguishes_structure() {
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE email = 'a';
+         SELECT id FROM orders WHERE email = 'a';
+         SELECT id FROM users WHERE name = 'a';
+         SELECT id FROM users WHERE email = 'a' LIMIT 10",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let prints: Vec<String> = queries.iter().map(fingerprint).collect();
+    for (i, a) in prints.iter().enumerate() {
+        for b in &prints[i + 1..] {
+            assert_ne!(a, b);
+        }
+    }
+}
+
+#[test]
+fn test_normalize_keeps_calls_and_qualified_names() {
+    let queries = parse_queries(
+        "select count(*), u.name from users u where u.id in (1, 2) group by u.name",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(
+        normalize(&queries[0]),
+        "SELECT COUNT(*), u.name FROM users u WHERE u.id IN (?, ?) GROUP BY u.name"
+    );
+}
+
+#[test]
+fn test_normalized_sql_ignores_formatting_and_case() {
+    let queries = parse_queries(
+        "SELECT u.Name, COUNT(*) FROM Users u WHERE u.status = 'Active' AND u.id IN (1,2) GROUP BY u.Name;
+         select u.name ,count( * )
+           from users U -- owners only
+          where U.STATUS='Active' and u.ID in ( 1, 2 )
+          group by U.name",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let expected = "SELECT u.name, COUNT(*) FROM users u \
+                    WHERE u.status = 'Active' AND u.id IN (1, 2) GROUP BY u.name";
+    assert_eq!(queries[0].to_normalized_sql(), expected);
+    assert_eq!(queries[1].to_normalized_sql(), expected);
+}
+
+#[test]
+fn test_normalized_sql_keeps_quoted_identifiers_and_params() {
+    let queries = parse_queries(
+        "SELECT \"UserId\" FROM t WHERE a = :Name AND b = $1",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert_eq!(
+        queries[0].to_normalized_sql(),
+        "SELECT \"UserId\" FROM t WHERE a = :Name AND b = $1"
+    );
+}
+
+#[test]
+fn test_parse_merge() {
+    let queries = parse_queries(
+        "MERGE INTO users u USING contacts c ON u.email = c.email \
+         WHEN MATCHED AND c.unsubscribed = TRUE THEN DELETE \
+         WHEN NOT MATCHED THEN INSERT (email) VALUES (c.email)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(queries[0].query_type, QueryType::Merge);
+    assert!(queries[0].is_analyzable());
+    let merge = queries[0].merge.as_ref().unwrap();
+    assert_eq!(merge.source, "contacts c");
+    assert_eq!(merge.on_columns, vec!["email"]);
+    assert_eq!(merge.clauses.len(), 2);
+    assert_eq!(merge.clauses[0].kind, "MATCHED");
+    assert_eq!(merge.clauses[0].action, MergeAction::Delete);
+    assert_eq!(
+        merge.clauses[0].predicate.as_deref(),
+        Some("c.unsubscribed = true")
+    );
+    assert_eq!(merge.clauses[1].kind, "NOT MATCHED");
+    assert_eq!(merge.clauses[1].action, MergeAction::Insert);
+}
+
+#[test]
+fn test_parse_set_statements() {
+    let queries = parse_queries(
+        "SET GLOBAL local_infile = 1; SET @@session.sql_mode = ''; \
+         SET autocommit = 0, unique_checks = 0",
+        SqlDialect::MySQL
+    )
+    .unwrap();
+    assert_eq!(queries[0].query_type, QueryType::Set);
+    assert!(queries[0].is_analyzable());
+    let setting = &queries[0].session_settings[0];
+    assert_eq!(setting.name, "local_infile");
+    assert_eq!(setting.scope.as_deref(), Some("GLOBAL"));
+    assert_eq!(setting.value, "1");
+    let setting = &queries[1].session_settings[0];
+    assert_eq!(setting.name, "sql_mode");
+    assert_eq!(setting.scope.as_deref(), Some("SESSION"));
+    let names: Vec<&str> = queries[2]
+        .session_settings
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["autocommit", "unique_checks"]);
+
+    let queries = parse_queries(
+        "SET LOCAL search_path TO app, public",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    let setting = &queries[0].session_settings[0];
+    assert_eq!(setting.name, "search_path");
+    assert_eq!(setting.scope.as_deref(), Some("LOCAL"));
+    assert_eq!(setting.value, "app, public");
+}
+
+fn split(sql: &str, dialect: SqlDialect) -> Vec<String> {
+    StatementSplitter::new(sql.as_bytes(), dialect)
+        .map(|statement| statement.unwrap().sql.trim().to_string())
+        .collect()
+}
+
+#[test]
+fn test_splitter_ignores_quoted_and_commented_semicolons() {
+    let sql = "SELECT 'a;b', \"c;d\" FROM t; -- note; here\n\
+               /* block; comment */ SELECT 2;\n\
+               SELECT 3";
+    assert_eq!(
+        split(sql, SqlDialect::Generic),
+        vec![
+            "SELECT 'a;b', \"c;d\" FROM t;",
+            "-- note; here\n/* block; comment */ SELECT 2;",
+            "SELECT 3"
+        ]
+    );
+}
+
+#[test]
+fn test_splitter_dialect_quoting() {
+    let mysql = "SELECT 'it\\'s; fine' FROM `a;b`; # trailing; comment\nSELECT 2;";
+    assert_eq!(split(mysql, SqlDialect::MySQL).len(), 2);
+    let postgres = "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;\n\
+                    SELECT $1;";
+    assert_eq!(
+        split(postgres, SqlDialect::PostgreSQL),
+        vec![
+            "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql;",
+            "SELECT $1;"
+        ]
+    );
+}
+
+#[test]
+fn test_stream_queries_spans_refer_to_whole_input() {
+    let sql = "SELECT 1;\n  SELECT id FROM users; SELECT 2;";
+    let streamed: Vec<_> = stream_queries(sql.as_bytes(), SqlDialect::Generic)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    let parsed = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let spans = |queries: &[sql_query_analyzer::query::Query]| {
+        queries.iter().map(|q| q.span.unwrap()).collect::<Vec<_>>()
+    };
+    assert_eq!(spans(&streamed), spans(&parsed));
+    assert_eq!(streamed[1].span.unwrap().line, 2);
+    assert_eq!(streamed[1].span.unwrap().column, 3);
+}
+
+#[test]
+fn test_stream_queries_continues_after_parse_error() {
+    let sql = "SELECT 1;\nSELEC oops;\nSELECT 2;";
+    let results: Vec<_> = stream_queries(sql.as_bytes(), SqlDialect::Generic).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+}
+
+#[test]
+fn test_stream_queries_applies_file_directives_forward() {
+    let sql = "-- sqla:disable-file PERF001\nSELECT * FROM a;\nSELECT * FROM b;";
+    let queries: Vec<_> = stream_queries(sql.as_bytes(), SqlDialect::Generic)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(queries.iter().all(|q| q.is_suppressed("PERF001")));
+}
@@ -0,0 +1,91 @@
+// SPDX-FileCopyrightText: 2026 RAprogramm
+// SPDX-License-Identifier: MIT
+
+#![cfg(feature = "llm")]
+
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    sync::mpsc,
+    thread
+};
+
+use sql_query_analyzer::{
+    app::post_report,
+    query::{SqlDialect, parse_queries},
+    rules::RuleRunner
+};
+
+/// Accept one request, answer with `status`, and send back its lower-cased
+/// header lines and body.
+fn mock_endpoint(status: &'static str) -> (String, mpsc::Receiver<(Vec<String>, String)>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/reports", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut headers = Vec::new();
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            if line == "\r\n" {
+                break;
+            }
+            let line = line.trim_end().to_lowercase();
+            if let Some(value) = line.strip_prefix("content-length:") {
+                content_length = value.trim().parse().unwrap();
+            }
+            headers.push(line);
+        }
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).unwrap();
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+            status
+        )
+        .unwrap();
+        tx.send((headers, String::from_utf8(body).unwrap()))
+            .unwrap();
+    });
+    (url, rx)
+}
+
+#[tokio::test]
+async fn test_post_report_sends_json_and_headers() {
+    let (url, received) = mock_endpoint("200 OK");
+    let queries = parse_queries("SELECT * FROM users", SqlDialect::Generic).unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    post_report(&url, &["Authorization: Bearer secret".to_string()], &report)
+        .await
+        .unwrap();
+    let (headers, body) = received.recv().unwrap();
+    assert!(headers.iter().any(|h| h == "authorization: bearer secret"));
+    assert!(
+        headers
+            .iter()
+            .any(|h| h == "content-type: application/json")
+    );
+    let json: serde_json::Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(json["queries_count"], 1);
+    assert_eq!(
+        json["violations"].as_array().unwrap().len(),
+        report.violations.len()
+    );
+}
+
+#[tokio::test]
+async fn test_post_report_rejected_status_is_error() {
+    let (url, _received) = mock_endpoint("500 Internal Server Error");
+    let report = RuleRunner::new().analyze(&[]);
+    assert!(post_report(&url, &[], &report).await.is_err());
+}
+
+#[tokio::test]
+async fn test_post_report_malformed_header_is_error() {
+    let report = RuleRunner::new().analyze(&[]);
+    let result = post_report("http://127.0.0.1:9/", &["no-colon".to_string()], &report).await;
+    assert!(result.is_err());
+}
@@ -1,10 +1,15 @@
 // SPDX-FileCopyrightText: 2025 RAprogramm
 // SPDX-License-Identifier: MIT
 
+use std::collections::HashMap;
+
 use sql_query_analyzer::{
-    config::RulesConfig,
-    query::{SqlDialect, parse_queries},
-    rules::{RuleRunner, Severity},
+    config::{Config, RulesConfig},
+    output::{
+        CatalogOutputFormat, OutputFormat, OutputOptions, format_catalog, format_static_analysis
+    },
+    query::{SortDir, SqlDialect, parse_queries, stream_queries},
+    rules::{RuleCategory, RuleRunner, Severity, Violation, ViolationFilter, rule_catalog},
     schema::Schema
 };
 
@@ -19,6 +24,17 @@ fn analyze_query(sql: &str) -> Vec<String> {
         .collect()
 }
 
+fn analyze_clickhouse(sql: &str) -> Vec<String> {
+    let queries = parse_queries(sql, SqlDialect::ClickHouse).unwrap();
+    let runner = RuleRunner::new().with_dialect(SqlDialect::ClickHouse);
+    let report = runner.analyze(&queries);
+    report
+        .violations
+        .iter()
+        .map(|v| v.rule_id.to_string())
+        .collect()
+}
+
 fn analyze_with_schema(sql: &str, schema_sql: &str) -> Vec<String> {
     let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
     let schema = Schema::parse(schema_sql, SqlDialect::Generic).unwrap();
@@ -242,6 +258,51 @@ fn test_having_without_aggregate_flagged() {
     assert!(violations.contains(&"PERF018".to_string()));
 }
 
+#[test]
+fn test_aggregate_mixed_with_bare_column_flagged() {
+    let violations = analyze_query("SELECT name, COUNT(*) FROM users WHERE id > 0");
+    assert!(violations.contains(&"PERF040".to_string()));
+}
+
+#[test]
+fn test_pure_aggregate_ok() {
+    let violations = analyze_query("SELECT COUNT(*), MAX(created_at) FROM users WHERE id > 0");
+    assert!(!violations.contains(&"PERF040".to_string()));
+}
+
+#[test]
+fn test_grouped_aggregate_ok() {
+    let violations =
+        analyze_query("SELECT name, COUNT(*) FROM users WHERE id > 0 GROUP BY name LIMIT 10");
+    assert!(!violations.contains(&"PERF040".to_string()));
+}
+
+#[test]
+fn test_aggregate_arms_checked_separately() {
+    let violations = analyze_query(
+        "SELECT name FROM a WHERE id = 1 UNION ALL SELECT COUNT(*) FROM b WHERE id = 2"
+    );
+    assert!(!violations.contains(&"PERF040".to_string()));
+    let violations = analyze_query(
+        "SELECT id FROM a WHERE id = 1 UNION ALL SELECT name, COUNT(*) FROM b WHERE id = 2"
+    );
+    assert!(violations.contains(&"PERF040".to_string()));
+}
+
+#[test]
+fn test_group_by_in_literal_does_not_hide_aggregate_mix() {
+    let violations =
+        analyze_query("SELECT name, COUNT(*) FROM users WHERE note = 'GROUP BY' AND id > 0");
+    assert!(violations.contains(&"PERF040".to_string()));
+}
+
+#[test]
+fn test_window_function_not_aggregate() {
+    let violations =
+        analyze_query("SELECT name, COUNT(*) OVER () FROM users WHERE id > 0 LIMIT 10");
+    assert!(!violations.contains(&"PERF040".to_string()));
+}
+
 #[test]
 fn test_having_with_aggregate_ok() {
     let violations = analyze_query(
@@ -410,6 +471,13 @@ fn test_exec_string_flagged() {
     assert!(violations.contains(&"SEC007".to_string()));
 }
 
+#[test]
+fn test_parameterized_prepare_not_dynamic_sql() {
+    let violations =
+        analyze_query("PREPARE user_by_id (INT) AS SELECT id FROM users WHERE id = $1");
+    assert!(!violations.contains(&"SEC007".to_string()));
+}
+
 #[test]
 fn test_select_not_dynamic_sql() {
     let violations = analyze_query("SELECT id FROM users WHERE id = 1 LIMIT 5");
@@ -481,6 +549,55 @@ fn test_author_column_not_credential() {
     assert!(!violations.contains(&"SEC008".to_string()));
 }
 
+#[test]
+fn test_wildcard_only_like_pattern() {
+    let violations = analyze_query("SELECT id FROM users WHERE name LIKE '%%%' LIMIT 10");
+    assert!(violations.contains(&"SEC015".to_string()));
+}
+
+#[test]
+fn test_wildcard_heavy_like_pattern() {
+    let violations = analyze_query("SELECT id FROM users WHERE name LIKE 'a%_%_%' LIMIT 10");
+    assert!(violations.contains(&"SEC015".to_string()));
+}
+
+#[test]
+fn test_prefix_like_pattern_ok() {
+    let violations = analyze_query("SELECT id FROM users WHERE name LIKE 'abc%' LIMIT 10");
+    assert!(!violations.contains(&"SEC015".to_string()));
+}
+
+#[test]
+fn test_escaped_wildcards_not_pathological() {
+    let violations = analyze_query(r"SELECT id FROM users WHERE name LIKE 'a\%\%\%' LIMIT 10");
+    assert!(!violations.contains(&"SEC015".to_string()));
+}
+
+#[test]
+fn test_grant_all_to_public_with_grant_option() {
+    let violations = analyze_query("GRANT ALL ON users TO PUBLIC WITH GRANT OPTION");
+    assert!(violations.contains(&"SEC016".to_string()));
+}
+
+#[test]
+fn test_grant_with_grant_option_only() {
+    let violations = analyze_query("GRANT SELECT ON users TO analyst WITH GRANT OPTION");
+    assert!(violations.contains(&"SEC016".to_string()));
+}
+
+#[test]
+fn test_grant_to_any_host_account() {
+    let queries = parse_queries("GRANT SELECT ON users TO 'app'@'%'", SqlDialect::MySQL).unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    assert!(report.violations.iter().any(|v| v.rule_id == "SEC016"));
+}
+
+#[test]
+fn test_narrow_grant_not_broad() {
+    let violations = analyze_query("GRANT SELECT ON t TO app_ro");
+    assert!(!violations.contains(&"SEC016".to_string()));
+}
+
 #[test]
 fn test_update_without_where() {
     let violations = analyze_query("UPDATE users SET status = 'inactive'");
@@ -517,6 +634,112 @@ fn test_union_all_ok() {
     assert!(!violations.contains(&"PERF010".to_string()));
 }
 
+#[test]
+fn test_union_with_order_by_flagged() {
+    let violations = analyze_query("SELECT id FROM users UNION SELECT id FROM admins ORDER BY id");
+    assert!(violations.contains(&"PERF056".to_string()));
+}
+
+#[test]
+fn test_union_all_with_order_by_ok() {
+    let violations =
+        analyze_query("SELECT id FROM users UNION ALL SELECT id FROM admins ORDER BY 1");
+    assert!(!violations.contains(&"PERF056".to_string()));
+    let violations = analyze_query("SELECT id FROM users UNION SELECT id FROM admins");
+    assert!(!violations.contains(&"PERF056".to_string()));
+}
+
+#[test]
+fn test_distinct_limit_without_order_flagged() {
+    let violations = analyze_query("SELECT DISTINCT status FROM orders LIMIT 10");
+    assert!(violations.contains(&"PERF057".to_string()));
+    assert!(!violations.contains(&"SEC018".to_string()));
+}
+
+#[test]
+fn test_distinct_limit_with_order_ok() {
+    for sql in [
+        "SELECT DISTINCT status FROM orders ORDER BY status LIMIT 10",
+        "SELECT DISTINCT status FROM orders ORDER BY 1 LIMIT 10",
+        "SELECT DISTINCT status FROM orders",
+        "SELECT status FROM orders LIMIT 10"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF057".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_scalar_subquery_without_row_guarantee_flagged() {
+    for sql in [
+        "SELECT id FROM orders WHERE x = (SELECT y FROM t)",
+        "SELECT id FROM orders WHERE status = 'paid' AND (SELECT y FROM t) < total",
+        "SELECT id FROM orders WHERE NOT (x <> (SELECT y FROM t WHERE z = 1))",
+        "SELECT id FROM orders WHERE x = (SELECT y FROM t GROUP BY y)"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(violations.contains(&"PERF058".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_scalar_subquery_with_row_guarantee_ok() {
+    for sql in [
+        "SELECT id FROM orders WHERE x = (SELECT MAX(y) FROM t)",
+        "SELECT id FROM orders WHERE x = (SELECT COUNT(*) + 1 FROM t)",
+        "SELECT id FROM orders WHERE x = (SELECT y FROM t LIMIT 1)",
+        "SELECT id FROM orders WHERE x = (SELECT y FROM t FETCH FIRST 1 ROWS ONLY)",
+        "SELECT id FROM orders WHERE x = (SELECT 1)",
+        "SELECT id FROM orders WHERE x IN (SELECT y FROM t)",
+        "SELECT id FROM orders WHERE EXISTS (SELECT 1 FROM t WHERE t.y = orders.x)"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF058".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_scalar_subquery_on_unique_key_with_schema_ok() {
+    let schema = "CREATE TABLE orgs (id INT PRIMARY KEY, owner_id INT, slug TEXT); \
+                  CREATE UNIQUE INDEX orgs_slug ON orgs (slug);";
+    for sql in [
+        "SELECT id FROM users WHERE id = (SELECT owner_id FROM orgs WHERE id = 3)",
+        "SELECT id FROM users WHERE id = (SELECT o.owner_id FROM orgs o WHERE o.slug = 'acme')"
+    ] {
+        let violations = analyze_with_schema(sql, schema);
+        assert!(!violations.contains(&"PERF058".to_string()), "{}", sql);
+    }
+    let violations = analyze_with_schema(
+        "SELECT id FROM users WHERE id = (SELECT owner_id FROM orgs WHERE owner_id = 3)",
+        schema
+    );
+    assert!(violations.contains(&"PERF058".to_string()));
+}
+
+#[test]
+fn test_offset_without_limit_flagged() {
+    for sql in [
+        "SELECT id FROM orders ORDER BY id OFFSET 50",
+        "SELECT id FROM orders ORDER BY id OFFSET 50 ROWS"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(violations.contains(&"PERF059".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_offset_with_limit_not_perf059() {
+    for sql in [
+        "SELECT id FROM orders ORDER BY id LIMIT 10 OFFSET 50",
+        "SELECT id FROM orders ORDER BY id LIMIT $1 OFFSET 50",
+        "SELECT id FROM orders ORDER BY id OFFSET 50 ROWS FETCH FIRST 10 ROWS ONLY",
+        "SELECT id FROM orders ORDER BY id LIMIT 10"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF059".to_string()), "{}", sql);
+    }
+}
+
 #[test]
 fn test_distinct_with_order_by() {
     let violations = analyze_query("SELECT DISTINCT status FROM orders ORDER BY status");
@@ -577,6 +800,192 @@ fn test_numeric_column_comparison_ok() {
     assert!(!violations.contains(&"PERF015".to_string()));
 }
 
+#[test]
+fn test_insert_column_list_arity_mismatch() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20))";
+    let violations = analyze_with_schema(
+        "INSERT INTO orders (id, user_id) VALUES (1, 2, 'new')",
+        schema
+    );
+    assert!(violations.contains(&"SCHEMA013".to_string()));
+}
+
+#[test]
+fn test_insert_schema_arity_mismatch() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20))";
+    let violations = analyze_with_schema("INSERT INTO orders VALUES (1, 2)", schema);
+    assert!(violations.contains(&"SCHEMA013".to_string()));
+}
+
+#[test]
+fn test_insert_arity_matches() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20))";
+    let violations = analyze_with_schema("INSERT INTO orders (id, user_id) VALUES (1, 2)", schema);
+    assert!(!violations.contains(&"SCHEMA013".to_string()));
+    let violations = analyze_with_schema("INSERT INTO orders VALUES (1, 2, 'new')", schema);
+    assert!(!violations.contains(&"SCHEMA013".to_string()));
+}
+
+#[test]
+fn test_join_on_differing_collations_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255) COLLATE latin1_general_ci); \
+                  CREATE TABLE contacts (id INT PRIMARY KEY, email VARCHAR(255) COLLATE latin1_bin);";
+    let violations = analyze_with_schema(
+        "SELECT u.id FROM users u JOIN contacts c ON u.email = c.email LIMIT 10",
+        schema
+    );
+    assert!(violations.contains(&"PERF039".to_string()));
+}
+
+#[test]
+fn test_join_on_matching_collations_ok() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255) COLLATE latin1_bin); \
+                  CREATE TABLE contacts (id INT PRIMARY KEY, email VARCHAR(255) COLLATE latin1_bin);";
+    let violations = analyze_with_schema(
+        "SELECT u.id FROM users u JOIN contacts c ON u.email = c.email LIMIT 10",
+        schema
+    );
+    assert!(!violations.contains(&"PERF039".to_string()));
+}
+
+#[test]
+fn test_char_compared_with_nchar_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, code VARCHAR(10)); \
+                  CREATE TABLE legacy (id INT PRIMARY KEY, code NVARCHAR(10));";
+    let violations = analyze_with_schema(
+        "SELECT u.id FROM users u, legacy l WHERE u.code = l.code LIMIT 10",
+        schema
+    );
+    assert!(violations.contains(&"PERF039".to_string()));
+}
+
+#[test]
+fn test_group_by_unindexed_column_flagged() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20));";
+    let violations = analyze_with_schema(
+        "SELECT status, COUNT(*) FROM orders GROUP BY status",
+        schema
+    );
+    assert!(violations.contains(&"PERF041".to_string()));
+}
+
+#[test]
+fn test_group_by_indexed_column_ok() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20)); \
+                  CREATE INDEX idx_orders_status_user ON orders (status, user_id);";
+    let violations = analyze_with_schema(
+        "SELECT status, user_id, COUNT(*) FROM orders GROUP BY user_id, status",
+        schema
+    );
+    assert!(!violations.contains(&"PERF041".to_string()));
+}
+
+#[test]
+fn test_group_by_non_leading_index_column_flagged() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20)); \
+                  CREATE INDEX idx_orders_user_status ON orders (user_id, status);";
+    let violations = analyze_with_schema(
+        "SELECT status, COUNT(*) FROM orders GROUP BY status",
+        schema
+    );
+    assert!(violations.contains(&"PERF041".to_string()));
+}
+
+fn analyze_postgres_with_schema(sql: &str, schema_sql: &str) -> Vec<String> {
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    let schema = Schema::parse(schema_sql, SqlDialect::PostgreSQL).unwrap();
+    let runner = RuleRunner::with_schema_and_config(schema, RulesConfig::default());
+    let report = runner.analyze(&queries);
+    report
+        .violations
+        .iter()
+        .map(|v| v.rule_id.to_string())
+        .collect()
+}
+
+#[test]
+fn test_quoted_column_wrong_case_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, userid INT);";
+    let violations = analyze_postgres_with_schema("SELECT \"UserID\" FROM users", schema);
+    assert!(violations.contains(&"SCHEMA014".to_string()));
+}
+
+#[test]
+fn test_unquoted_column_folds_to_lowercase() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, userid INT);";
+    let violations = analyze_postgres_with_schema("SELECT UserID FROM users", schema);
+    assert!(!violations.contains(&"SCHEMA014".to_string()));
+}
+
+#[test]
+fn test_quoted_column_matching_quoted_declaration_ok() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, \"UserID\" INT);";
+    let violations = analyze_postgres_with_schema("SELECT \"UserID\" FROM users", schema);
+    assert!(!violations.contains(&"SCHEMA014".to_string()));
+}
+
+#[test]
+fn test_quoted_column_case_ignored_outside_postgres() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, userid INT);";
+    let violations = analyze_with_schema("SELECT \"UserID\" FROM users", schema);
+    assert!(!violations.contains(&"SCHEMA014".to_string()));
+}
+
+const ORDER_BY_SCHEMA: &str = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255)); \
+                               CREATE TABLE orders (order_id INT PRIMARY KEY, user_id INT, \
+                               status VARCHAR(20), created_at TIMESTAMP);";
+
+#[test]
+fn test_order_by_cross_table_flagged() {
+    let violations = analyze_with_schema(
+        "SELECT o.order_id FROM orders o JOIN users u ON u.id = o.user_id \
+         WHERE o.status = 'open' ORDER BY u.email",
+        ORDER_BY_SCHEMA
+    );
+    assert!(violations.contains(&"SCHEMA015".to_string()));
+}
+
+#[test]
+fn test_order_by_same_table_as_filter_ok() {
+    let violations = analyze_with_schema(
+        "SELECT o.order_id FROM orders o JOIN users u ON u.id = o.user_id \
+         WHERE o.status = 'open' ORDER BY o.created_at",
+        ORDER_BY_SCHEMA
+    );
+    assert!(!violations.contains(&"SCHEMA015".to_string()));
+}
+
+#[test]
+fn test_order_by_cross_table_with_index_ok() {
+    let schema = format!("{ORDER_BY_SCHEMA} CREATE INDEX idx_users_email ON users(email);");
+    let violations = analyze_with_schema(
+        "SELECT o.order_id FROM orders o JOIN users u ON u.id = o.user_id \
+         WHERE o.status = 'open' ORDER BY u.email",
+        &schema
+    );
+    assert!(!violations.contains(&"SCHEMA015".to_string()));
+}
+
+#[test]
+fn test_order_by_cross_table_with_shared_column_names_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255), \
+                  created_at TIMESTAMP); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, email VARCHAR(255), \
+                  created_at TIMESTAMP, total INT);";
+    let violations = analyze_with_schema(
+        "SELECT o.id FROM users u JOIN orders o ON o.user_id = u.id \
+         WHERE u.email = 'x' ORDER BY o.created_at",
+        schema
+    );
+    assert!(violations.contains(&"SCHEMA015".to_string()));
+    let violations = analyze_with_schema(
+        "SELECT o.id FROM users u JOIN orders o ON o.user_id = u.id \
+         WHERE u.email = 'x' ORDER BY u.created_at",
+        schema
+    );
+    assert!(!violations.contains(&"SCHEMA015".to_string()));
+}
+
 #[test]
 fn test_schema_missing_index() {
     let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255))";
@@ -626,7 +1035,8 @@ fn test_severity_override() {
     severity.insert("STYLE001".to_string(), "error".to_string());
     let config = RulesConfig {
         disabled: vec![],
-        severity
+        severity,
+        ..Default::default()
     };
     let runner = RuleRunner::with_config(config);
     let report = runner.analyze(&queries);
@@ -635,6 +1045,128 @@ fn test_severity_override() {
     assert_eq!(style_violation.unwrap().severity, Severity::Error);
 }
 
+fn perf001_as_security() -> RuleRunner {
+    let mut category = HashMap::new();
+    category.insert("PERF001".to_string(), "security".to_string());
+    RuleRunner::with_config(RulesConfig {
+        category,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_category_override_counts_in_summary() {
+    let queries = parse_queries("SELECT * FROM users", SqlDialect::Generic).unwrap();
+    let baseline = RuleRunner::new().analyze(&queries).category_summary();
+    let report = perf001_as_security().analyze(&queries);
+    let perf001 = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF001")
+        .unwrap();
+    assert_eq!(perf001.category, RuleCategory::Security);
+    let summary = report.category_summary();
+    assert_eq!(
+        summary[&RuleCategory::Security],
+        baseline[&RuleCategory::Security] + 1
+    );
+    assert_eq!(
+        summary[&RuleCategory::Performance],
+        baseline[&RuleCategory::Performance] - 1
+    );
+}
+
+#[test]
+fn test_category_override_kept_in_json_output() {
+    let queries = parse_queries("SELECT * FROM users", SqlDialect::Generic).unwrap();
+    let report = perf001_as_security().analyze(&queries);
+    let opts = OutputOptions {
+        format:       OutputFormat::Json,
+        colored:      false,
+        verbose:      false,
+        template:     None,
+        compact:      false,
+        min_severity: None,
+        exit:         None,
+        sources:      None
+    };
+    let json: serde_json::Value =
+        serde_json::from_str(&format_static_analysis(&report, &opts)).unwrap();
+    let perf001 = json["violations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|v| v["rule_id"] == "PERF001")
+        .unwrap();
+    assert_eq!(perf001["category"], "Security");
+}
+
+#[test]
+fn test_invalid_category_override_ignored() {
+    let queries = parse_queries("SELECT * FROM users", SqlDialect::Generic).unwrap();
+    let mut category = HashMap::new();
+    category.insert("PERF001".to_string(), "speed".to_string());
+    let runner = RuleRunner::with_config(RulesConfig {
+        category,
+        ..Default::default()
+    });
+    let report = runner.analyze(&queries);
+    let perf001 = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF001")
+        .unwrap();
+    assert_eq!(perf001.category, RuleCategory::Performance);
+}
+
+const TODO_RULE: &str = r#"
+[[custom]]
+id = "TEAM001"
+name = "TODO left in SQL"
+pattern = '(?i)\bTODO\b'
+severity = "warning"
+category = "style"
+message = "Statement still carries a TODO comment"
+suggestion = "Resolve the TODO before merging"
+"#;
+
+#[test]
+fn test_custom_regex_rule_flags_todo_comment() {
+    let config: RulesConfig = toml::from_str(TODO_RULE).unwrap();
+    let queries = parse_queries(
+        "-- TODO: narrow the filter\nSELECT id FROM users WHERE id = 1;\nSELECT id FROM orders WHERE id = 2",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::with_config(config).analyze(&queries);
+    let custom: Vec<_> = report
+        .violations
+        .iter()
+        .filter(|v| v.rule_id == "TEAM001")
+        .collect();
+    assert_eq!(custom.len(), 1);
+    assert_eq!(custom[0].query_index, 0);
+    assert_eq!(custom[0].rule_name, "TODO left in SQL");
+    assert_eq!(custom[0].severity, Severity::Warning);
+    assert_eq!(custom[0].category, RuleCategory::Style);
+    assert_eq!(custom[0].message, "Statement still carries a TODO comment");
+}
+
+#[test]
+fn test_custom_regex_rule_honours_disable_and_severity() {
+    let mut config: RulesConfig = toml::from_str(TODO_RULE).unwrap();
+    let queries = parse_queries("SELECT id FROM users /* todo */", SqlDialect::Generic).unwrap();
+    config
+        .severity
+        .insert("TEAM001".to_string(), "error".to_string());
+    let report = RuleRunner::with_config(config.clone()).analyze(&queries);
+    let violation = report.violations.iter().find(|v| v.rule_id == "TEAM001");
+    assert_eq!(violation.map(|v| v.severity), Some(Severity::Error));
+    config.disabled.push("team001".to_string());
+    let report = RuleRunner::with_config(config).analyze(&queries);
+    assert!(!report.violations.iter().any(|v| v.rule_id == "TEAM001"));
+}
+
 #[test]
 fn test_error_count() {
     let queries = parse_queries("DELETE FROM users", SqlDialect::Generic).unwrap();
@@ -778,6 +1310,34 @@ fn test_join_missing_alias() {
     assert!(violations.contains(&"STYLE002".to_string()));
 }
 
+#[test]
+fn test_join_partially_aliased() {
+    let violations =
+        analyze_query("SELECT u.id FROM users u JOIN orders ON u.id = orders.user_id LIMIT 10");
+    assert!(violations.contains(&"STYLE002".to_string()));
+}
+
+#[test]
+fn test_join_all_aliased_ok() {
+    let violations =
+        analyze_query("SELECT u.id FROM users u JOIN orders o ON u.id = o.user_id LIMIT 10");
+    assert!(!violations.contains(&"STYLE002".to_string()));
+}
+
+#[test]
+fn test_single_table_no_alias_ok() {
+    let violations = analyze_query("SELECT id FROM users WHERE id = 1 LIMIT 10");
+    assert!(!violations.contains(&"STYLE002".to_string()));
+}
+
+#[test]
+fn test_alias_keyword_in_literal_not_alias() {
+    let violations = analyze_query(
+        "SELECT users.id FROM users, orders WHERE users.id = orders.user_id AND users.name = ' AS x' LIMIT 10"
+    );
+    assert!(violations.contains(&"STYLE002".to_string()));
+}
+
 #[test]
 fn test_schema_join_column_missing_index() {
     let schema = r#"
@@ -869,3 +1429,1933 @@ fn test_drop_index_detected() {
     let violations = analyze_query("DROP INDEX idx_users_email");
     assert!(violations.contains(&"SEC004".to_string()));
 }
+
+const PREFIX_SCHEMA: &str = "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20), total INT); \
+                             CREATE INDEX idx_orders_user_status ON orders(user_id, status);";
+
+#[test]
+fn test_index_prefix_mismatch_detected() {
+    let violations =
+        analyze_with_schema("SELECT id FROM orders WHERE status = 'new'", PREFIX_SCHEMA);
+    assert!(violations.contains(&"PERF049".to_string()));
+}
+
+#[test]
+fn test_index_prefix_satisfied_in_any_order() {
+    let violations = analyze_with_schema(
+        "SELECT id FROM orders WHERE status = 'new' AND user_id = 7",
+        PREFIX_SCHEMA
+    );
+    assert!(!violations.contains(&"PERF049".to_string()));
+}
+
+#[test]
+fn test_index_prefix_leading_column_alone_ok() {
+    let violations = analyze_with_schema("SELECT id FROM orders WHERE user_id = ?", PREFIX_SCHEMA);
+    assert!(!violations.contains(&"PERF049".to_string()));
+}
+
+#[test]
+fn test_index_prefix_primary_key_filter_ok() {
+    let violations = analyze_with_schema(
+        "SELECT id FROM orders WHERE id = 1 AND status = 'new'",
+        PREFIX_SCHEMA
+    );
+    assert!(!violations.contains(&"PERF049".to_string()));
+}
+
+#[test]
+fn test_index_prefix_ignores_unindexed_and_or_filters() {
+    for sql in [
+        "SELECT id FROM orders WHERE total = 5",
+        "SELECT id FROM orders WHERE user_id = 1 OR status = 'new'",
+        "SELECT o.id FROM orders o WHERE o.status = o.user_id"
+    ] {
+        let violations = analyze_with_schema(sql, PREFIX_SCHEMA);
+        assert!(!violations.contains(&"PERF049".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_covering_index_suggested_for_projected_column() {
+    let violations =
+        analyze_with_schema("SELECT total FROM orders WHERE user_id = 7", PREFIX_SCHEMA);
+    assert!(violations.contains(&"SCHEMA021".to_string()));
+}
+
+#[test]
+fn test_covering_index_suggestion_lists_filter_then_projection() {
+    let schema = Schema::parse(PREFIX_SCHEMA, SqlDialect::Generic).unwrap();
+    let queries = parse_queries(
+        "SELECT o.id, o.total FROM orders o WHERE o.user_id = 7",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::schema_only(schema).analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "SCHEMA021")
+        .unwrap();
+    assert!(violation.message.contains("projected column(s) total;"));
+    assert_eq!(
+        violation.suggestion.as_deref(),
+        Some("CREATE INDEX idx_orders_covering ON orders(user_id, total)")
+    );
+}
+
+#[test]
+fn test_covering_index_not_suggested_when_covered() {
+    for sql in [
+        "SELECT status FROM orders WHERE user_id = 7",
+        "SELECT id, user_id, status FROM orders WHERE user_id = 7 AND status = 'new'",
+        "SELECT total FROM orders WHERE total > 5",
+        "SELECT total FROM orders WHERE id = 1",
+        "SELECT * FROM orders WHERE user_id = 7"
+    ] {
+        let violations = analyze_with_schema(sql, PREFIX_SCHEMA);
+        assert!(!violations.contains(&"SCHEMA021".to_string()), "{}", sql);
+    }
+}
+
+const JSON_SCHEMA: &str =
+    "CREATE TABLE events (id INT PRIMARY KEY, kind TEXT, data JSONB, tags TEXT[]);";
+
+fn postgres_schema_violations(sql: &str, schema_sql: &str) -> Vec<Violation> {
+    let schema = Schema::parse(schema_sql, SqlDialect::PostgreSQL).unwrap();
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    RuleRunner::schema_only(schema).analyze(&queries).violations
+}
+
+#[test]
+fn test_json_path_filter_without_index_detected() {
+    let violations = postgres_schema_violations(
+        "SELECT id FROM events WHERE data ->> 'status' = 'failed'",
+        JSON_SCHEMA
+    );
+    let violation = violations.iter().find(|v| v.rule_id == "PERF054").unwrap();
+    assert_eq!(violation.severity, Severity::Info);
+    assert_eq!(
+        violation.suggestion.as_deref(),
+        Some("CREATE INDEX idx_events_data_path ON events((data ->> 'status'))")
+    );
+}
+
+#[test]
+fn test_json_containment_without_gin_detected() {
+    for sql in [
+        "SELECT id FROM events e WHERE e.data @> '{\"status\": \"failed\"}'",
+        "SELECT id FROM events WHERE data ? 'status' AND kind = 'x'",
+        "SELECT id FROM events WHERE tags @> ARRAY['urgent']"
+    ] {
+        let violations = postgres_schema_violations(sql, JSON_SCHEMA);
+        let violation = violations.iter().find(|v| v.rule_id == "PERF054");
+        assert!(
+            violation.is_some_and(|v| v
+                .suggestion
+                .as_deref()
+                .is_some_and(|s| s.contains("USING GIN"))),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_json_access_with_supporting_index_ok() {
+    let schema = format!(
+        "{} CREATE INDEX idx_events_status ON events ((data ->> 'status')); \
+         CREATE INDEX idx_events_data ON events USING GIN (data);",
+        JSON_SCHEMA
+    );
+    for sql in [
+        "SELECT id FROM events WHERE data->>'status' = 'failed'",
+        "SELECT id FROM events WHERE data @> '{\"status\": \"failed\"}'"
+    ] {
+        let violations = postgres_schema_violations(sql, &schema);
+        assert!(
+            !violations.iter().any(|v| v.rule_id == "PERF054"),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_scalar_filter_not_json_access() {
+    let violations =
+        postgres_schema_violations("SELECT id FROM events WHERE kind = 'failed'", JSON_SCHEMA);
+    assert!(!violations.iter().any(|v| v.rule_id == "PERF054"));
+}
+
+fn lint_schema(schema_sql: &str) -> Vec<String> {
+    Schema::parse(schema_sql, SqlDialect::Generic)
+        .unwrap()
+        .lint()
+        .iter()
+        .map(|v| v.rule_id.to_string())
+        .collect()
+}
+
+#[test]
+fn test_duplicate_column_name_detected() {
+    let violations =
+        lint_schema("CREATE TABLE users (id INT PRIMARY KEY, email TEXT, EMAIL VARCHAR(255))");
+    assert_eq!(violations, vec!["SCHEMA019".to_string()]);
+}
+
+#[test]
+fn test_duplicate_column_name_clean_table() {
+    let violations =
+        lint_schema("CREATE TABLE users (id INT PRIMARY KEY, email TEXT, name VARCHAR(100))");
+    assert!(!violations.contains(&"SCHEMA019".to_string()));
+}
+
+#[test]
+fn test_duplicate_column_reported_once_per_name() {
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, id INT, id INT); \
+         CREATE TABLE orders (id INT PRIMARY KEY, id INT)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let violations = schema.lint();
+    assert_eq!(violations.len(), 2);
+    assert!(
+        violations
+            .iter()
+            .any(|v| v.message.contains("'id' 3 times"))
+    );
+}
+
+#[test]
+fn test_varchar_without_length_detected() {
+    let violations = lint_schema("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR)");
+    assert_eq!(violations, vec!["SCHEMA020".to_string()]);
+}
+
+#[test]
+fn test_varchar_with_length_not_schema020() {
+    let violations =
+        lint_schema("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(255), code CHAR(2))");
+    assert!(!violations.contains(&"SCHEMA020".to_string()));
+}
+
+#[test]
+fn test_varchar_without_length_dialects() {
+    let sql = "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR, code CHAR)";
+    let mysql = Schema::parse(sql, SqlDialect::MySQL).unwrap().lint();
+    assert_eq!(mysql.len(), 2);
+    assert!(mysql.iter().all(|v| v.severity == Severity::Warning));
+    let postgres = Schema::parse(sql, SqlDialect::PostgreSQL).unwrap().lint();
+    assert_eq!(postgres.len(), 1);
+    assert!(postgres[0].message.contains("'users.code'"));
+}
+
+#[test]
+fn test_missing_primary_key_detected() {
+    let violations = lint_schema("CREATE TABLE audit_entries (action VARCHAR(50), at TIMESTAMP)");
+    assert_eq!(violations, vec!["SCHEMA025".to_string()]);
+    let schema = Schema::parse("CREATE TABLE tags (name TEXT)", SqlDialect::Generic).unwrap();
+    let lint = &schema.lint()[0];
+    assert_eq!(lint.severity, Severity::Warning);
+    assert_eq!(lint.message, "Table 'tags' has no primary key");
+}
+
+#[test]
+fn test_declared_primary_key_not_schema025() {
+    for sql in [
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT)",
+        "CREATE TABLE members (team_id INT, user_id INT, PRIMARY KEY (team_id, user_id))"
+    ] {
+        assert!(
+            !lint_schema(sql).contains(&"SCHEMA025".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_clickhouse_sorting_key_satisfies_primary_key() {
+    let lint = |sql: &str| -> Vec<&'static str> {
+        Schema::parse(sql, SqlDialect::ClickHouse)
+            .unwrap()
+            .lint()
+            .iter()
+            .map(|v| v.rule_id)
+            .collect()
+    };
+    assert!(
+        lint("CREATE TABLE logs (id UInt64, ts DateTime) ENGINE = MergeTree ORDER BY (id, ts)")
+            .is_empty()
+    );
+    assert!(lint("CREATE TABLE buffer (id UInt64) ENGINE = Memory").is_empty());
+    assert_eq!(
+        lint("CREATE TABLE logs (id UInt64) ENGINE = MergeTree ORDER BY tuple()"),
+        vec!["SCHEMA025"]
+    );
+}
+
+#[test]
+fn test_schema_lints_in_report() {
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, id INT)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let queries = parse_queries("SELECT id FROM users WHERE id = 1", SqlDialect::Generic).unwrap();
+    let report = RuleRunner::with_schema_and_config(schema.clone(), RulesConfig::default())
+        .analyze(&queries);
+    assert_eq!(report.schema_violations.len(), 1);
+    assert_eq!(report.schema_violations[0].rule_id, "SCHEMA019");
+    assert_eq!(report.error_count(), 1);
+    let config = RulesConfig {
+        disabled: vec!["SCHEMA019".to_string()],
+        ..Default::default()
+    };
+    let report = RuleRunner::with_schema_and_config(schema, config).analyze(&queries);
+    assert!(report.schema_violations.is_empty());
+}
+
+#[test]
+fn test_out_of_domain_check_value_flagged() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, \
+                  status VARCHAR(20) CHECK (status IN ('new', 'paid', 'shipped')))";
+    let violations = analyze_with_schema("SELECT id FROM orders WHERE status = 'xyz'", schema);
+    assert!(violations.contains(&"SCHEMA022".to_string()));
+}
+
+#[test]
+fn test_in_domain_check_value_ok() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, \
+                  status VARCHAR(20) CHECK (status IN ('new', 'paid', 'shipped')))";
+    let violations = analyze_with_schema(
+        "SELECT id FROM orders WHERE status IN ('new', 'paid') AND id > 10",
+        schema
+    );
+    assert!(!violations.contains(&"SCHEMA022".to_string()));
+}
+
+#[test]
+fn test_out_of_domain_table_check_in_list_flagged() {
+    let schema = "CREATE TABLE orders (id INT PRIMARY KEY, status TEXT, \
+                  CONSTRAINT chk_status CHECK (status IN ('new', 'paid')))";
+    let violations = analyze_with_schema(
+        "SELECT id FROM orders WHERE status IN ('new', 'void')",
+        schema
+    );
+    assert!(violations.contains(&"SCHEMA022".to_string()));
+}
+
+#[test]
+fn test_out_of_domain_postgres_enum_type_flagged() {
+    let schema = "CREATE TYPE order_status AS ENUM ('new', 'paid'); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, status order_status)";
+    let flagged =
+        analyze_postgres_with_schema("SELECT id FROM orders WHERE status = 'New'", schema);
+    assert!(flagged.contains(&"SCHEMA022".to_string()));
+    let ok = analyze_postgres_with_schema("SELECT id FROM orders WHERE status = 'new'", schema);
+    assert!(!ok.contains(&"SCHEMA022".to_string()));
+}
+
+const NOT_NULL_SCHEMA: &str =
+    "CREATE TABLE orders (id INT PRIMARY KEY, user_id INT NOT NULL, note VARCHAR(50))";
+
+fn write_violations(sql: &str) -> Vec<Violation> {
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let schema = Schema::parse(NOT_NULL_SCHEMA, SqlDialect::Generic).unwrap();
+    RuleRunner::with_schema_and_config(schema, RulesConfig::default())
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .filter(|v| v.rule_id == "SCHEMA023")
+        .collect()
+}
+
+#[test]
+fn test_insert_null_into_not_null_column_flagged() {
+    let violations = write_violations("INSERT INTO orders (id, user_id) VALUES (1, NULL)");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].severity, Severity::Error);
+    assert!(violations[0].message.contains("orders.user_id is NOT NULL"));
+}
+
+#[test]
+fn test_valid_insert_not_flagged() {
+    let violations = write_violations(
+        "INSERT INTO orders (id, user_id, note) VALUES (1, 42, NULL), (2, '7', 'x')"
+    );
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_update_string_into_integer_column_flagged() {
+    let violations = write_violations("UPDATE orders SET user_id = 'n/a' WHERE id = 7");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].severity, Severity::Warning);
+}
+
+#[test]
+fn test_update_null_into_not_null_column_flagged() {
+    let violations = write_violations("UPDATE orders SET user_id = NULL WHERE id = 7");
+    assert_eq!(violations[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_null_into_primary_key_not_flagged() {
+    let violations = write_violations("INSERT INTO orders (id, user_id) VALUES (NULL, 1)");
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_repeated_null_rows_reported_once() {
+    let violations =
+        write_violations("INSERT INTO orders (id, user_id) VALUES (1, NULL), (2, NULL)");
+    assert_eq!(violations.len(), 1);
+}
+
+const DESC_INDEX_SCHEMA: &str = "CREATE TABLE events (id INT PRIMARY KEY, user_id INT, \
+                                 created_at TIMESTAMP, kind VARCHAR(20)); \
+                                 CREATE INDEX idx_events_created ON events (created_at DESC); \
+                                 CREATE INDEX idx_events_user_created ON events (user_id, created_at DESC); \
+                                 CREATE INDEX idx_events_kind ON events (kind)";
+
+fn direction_violations(sql: &str) -> Vec<Violation> {
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let schema = Schema::parse(DESC_INDEX_SCHEMA, SqlDialect::Generic).unwrap();
+    RuleRunner::with_schema_and_config(schema, RulesConfig::default())
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .filter(|v| v.rule_id == "SCHEMA024")
+        .collect()
+}
+
+#[test]
+fn test_desc_index_columns_parsed_with_direction() {
+    let schema = Schema::parse(DESC_INDEX_SCHEMA, SqlDialect::Generic).unwrap();
+    let index = &schema.tables["events"].indexes[1];
+    assert_eq!(index.columns, vec!["user_id", "created_at"]);
+    assert_eq!(
+        index.directions,
+        vec![
+            ("user_id".to_string(), SortDir::Asc),
+            ("created_at".to_string(), SortDir::Desc)
+        ]
+    );
+}
+
+#[test]
+fn test_order_against_desc_index_flagged() {
+    let violations = direction_violations("SELECT id FROM events ORDER BY created_at LIMIT 50");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].severity, Severity::Info);
+    assert_eq!(
+        violations[0].message,
+        "ORDER BY created_at ASC is the reverse of index idx_events_created (created_at DESC); \
+         it can only be read backward"
+    );
+    let mixed = direction_violations("SELECT e.id FROM events e ORDER BY e.user_id, e.created_at");
+    assert_eq!(mixed.len(), 1);
+    assert!(mixed[0].message.contains("mixes directions"));
+}
+
+#[test]
+fn test_order_matching_index_direction_not_flagged() {
+    for sql in [
+        "SELECT id FROM events ORDER BY created_at DESC LIMIT 50",
+        "SELECT id FROM events ORDER BY user_id, created_at DESC",
+        "SELECT id FROM events ORDER BY user_id ASC",
+        "SELECT id FROM events ORDER BY kind DESC",
+        "SELECT id FROM events ORDER BY id DESC",
+        "SELECT id FROM events ORDER BY LOWER(kind), created_at"
+    ] {
+        assert!(direction_violations(sql).is_empty(), "{}", sql);
+    }
+}
+
+const CAST_SCHEMA: &str = "CREATE TABLE accounts (id INT PRIMARY KEY, code VARCHAR(20), \
+                           balance NUMERIC(10, 2), owner_id SERIAL)";
+
+fn cast_violations(sql: &str) -> Vec<Violation> {
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    let schema = Schema::parse(CAST_SCHEMA, SqlDialect::PostgreSQL).unwrap();
+    RuleRunner::with_schema_and_config(schema, RulesConfig::default())
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .filter(|v| v.rule_id == "PERF060")
+        .collect()
+}
+
+#[test]
+fn test_cast_to_declared_type_flagged() {
+    let violations = cast_violations("SELECT CAST(id AS INT) FROM accounts");
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].severity, Severity::Info);
+    assert_eq!(
+        violations[0].message,
+        "CAST(id AS INT) casts accounts.id to INT, the type it is already declared as"
+    );
+    for sql in [
+        "SELECT id FROM accounts WHERE CAST(id AS INTEGER) = 7",
+        "SELECT a.id FROM accounts a WHERE a.code::CHARACTER VARYING(20) = 'x'",
+        "SELECT CAST(balance AS DECIMAL(10,2)) FROM accounts",
+        "SELECT id FROM accounts WHERE owner_id::int4 = 3",
+        "SELECT COALESCE(CAST(id AS INT), 0) FROM accounts"
+    ] {
+        assert_eq!(cast_violations(sql).len(), 1, "{}", sql);
+    }
+}
+
+#[test]
+fn test_cast_to_other_type_not_flagged() {
+    for sql in [
+        "SELECT CAST(code AS INT) FROM accounts",
+        "SELECT CAST(code AS VARCHAR(10)) FROM accounts",
+        "SELECT CAST(balance AS NUMERIC(12, 4)) FROM accounts",
+        "SELECT CAST(id AS BIGINT) FROM accounts",
+        "SELECT CAST(id + 1 AS INT) FROM accounts",
+        "SELECT CAST(missing AS INT) FROM accounts"
+    ] {
+        assert!(cast_violations(sql).is_empty(), "{}", sql);
+    }
+}
+
+const DISTINCT_ON_SCHEMA: &str = "CREATE TABLE readings (id SERIAL PRIMARY KEY, sensor_id INT, \
+                                  site_id INT, taken_at TIMESTAMP, value NUMERIC); \
+                                  CREATE INDEX idx_readings_sensor_taken ON readings (sensor_id, taken_at DESC)";
+
+fn distinct_on_violations(sql: &str) -> Vec<Violation> {
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    let schema = Schema::parse(DISTINCT_ON_SCHEMA, SqlDialect::PostgreSQL).unwrap();
+    RuleRunner::with_schema_and_config(schema, RulesConfig::default())
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .filter(|v| v.rule_id == "PERF061")
+        .collect()
+}
+
+#[test]
+fn test_distinct_on_columns_parsed() {
+    let queries = parse_queries(
+        "SELECT DISTINCT ON (r.sensor_id, site_id) id FROM readings r",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert_eq!(queries[0].distinct_on, vec!["r.sensor_id", "site_id"]);
+    let plain = parse_queries(
+        "SELECT DISTINCT sensor_id FROM readings",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    assert!(plain[0].distinct_on.is_empty());
+}
+
+#[test]
+fn test_distinct_on_unindexed_columns_flagged() {
+    let violations = distinct_on_violations(
+        "SELECT DISTINCT ON (site_id) site_id, value FROM readings ORDER BY site_id, taken_at DESC"
+    );
+    assert_eq!(violations.len(), 1);
+    assert_eq!(violations[0].severity, Severity::Info);
+    assert_eq!(
+        violations[0].message,
+        "DISTINCT ON (site_id) of table 'readings' has no index leading with these columns"
+    );
+    assert!(
+        violations[0]
+            .suggestion
+            .as_deref()
+            .unwrap()
+            .contains("ON readings(site_id, taken_at)")
+    );
+    assert_eq!(
+        distinct_on_violations("SELECT DISTINCT ON (site_id, sensor_id) id FROM readings").len(),
+        1
+    );
+}
+
+#[test]
+fn test_distinct_on_indexed_columns_not_flagged() {
+    for sql in [
+        "SELECT DISTINCT ON (sensor_id) sensor_id, value FROM readings \
+         ORDER BY sensor_id, taken_at DESC",
+        "SELECT DISTINCT ON (r.sensor_id, r.taken_at) r.value FROM readings r",
+        "SELECT DISTINCT ON (id) id, value FROM readings",
+        "SELECT DISTINCT ON (date_trunc('day', taken_at)) value FROM readings",
+        "SELECT DISTINCT site_id FROM readings"
+    ] {
+        assert!(distinct_on_violations(sql).is_empty(), "{}", sql);
+    }
+}
+
+#[test]
+fn test_catalog_json_includes_every_rule() {
+    let json = format_catalog(&rule_catalog(), CatalogOutputFormat::Json);
+    let entries: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    let runner = RuleRunner::with_schema_and_config(Schema::default(), RulesConfig::default())
+        .with_dialect(SqlDialect::ClickHouse);
+    assert_eq!(entries.len(), runner.analyze(&[]).rules_count);
+    let mut ids: Vec<&str> = entries.iter().map(|e| e["id"].as_str().unwrap()).collect();
+    for entry in &entries {
+        assert!(!entry["name"].as_str().unwrap().is_empty());
+    }
+    for id in ["PERF001", "PERF015", "STYLE004", "SEC008", "SCHEMA004"] {
+        assert!(ids.contains(&id), "catalog is missing {}", id);
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    assert_eq!(ids.len(), entries.len());
+}
+
+#[test]
+fn test_catalog_examples_trigger_their_rule() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, \
+                  email VARCHAR(255) COLLATE utf8mb4_general_ci, \
+                  phone VARCHAR(20), name VARCHAR(100), created_at TIMESTAMP, manager_id INT); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status VARCHAR(20) \
+                  CHECK (status IN ('new', 'paid', 'shipped'))); \
+                  CREATE INDEX idx_users_phone_name ON users(phone, name); \
+                  CREATE TABLE contacts (id INT PRIMARY KEY, email VARCHAR(255) COLLATE utf8mb4_bin); \
+                  CREATE TABLE events (id SERIAL PRIMARY KEY, occurred_at TIMESTAMP); \
+                  CREATE INDEX idx_events_occurred ON events(occurred_at DESC); \
+                  CREATE VIEW active_users AS SELECT id, name FROM users;";
+    for entry in rule_catalog() {
+        for example in &entry.examples {
+            let violations = if example.starts_with("CREATE TABLE") {
+                lint_schema(example)
+            } else if entry.id.starts_with("CH") {
+                analyze_clickhouse(example)
+            } else {
+                analyze_with_schema(example, schema)
+            };
+            assert!(
+                violations.contains(&entry.id.to_string()),
+                "{} example does not trigger it: {}",
+                entry.id,
+                example
+            );
+        }
+    }
+}
+
+fn filter_violation(rule_id: &'static str, severity: Severity, query_index: usize) -> Violation {
+    Violation {
+        rule_id,
+        rule_name: "Test",
+        message: String::new(),
+        severity,
+        category: RuleCategory::Security,
+        suggestion: None,
+        query_index,
+        location: None,
+        origin: None
+    }
+}
+
+#[test]
+fn test_select_rule_id_prefix() {
+    let filter = ViolationFilter::parse("rule_id^=SEC").unwrap();
+    assert!(filter.matches(&filter_violation("SEC001", Severity::Error, 0)));
+    assert!(filter.matches(&filter_violation("sec015", Severity::Warning, 0)));
+    assert!(!filter.matches(&filter_violation("PERF001", Severity::Error, 0)));
+}
+
+#[test]
+fn test_select_equality() {
+    let filter = ViolationFilter::parse("severity=error").unwrap();
+    assert!(filter.matches(&filter_violation("SEC001", Severity::Error, 0)));
+    assert!(!filter.matches(&filter_violation("SEC005", Severity::Warning, 0)));
+    let filter = ViolationFilter::parse("query=3").unwrap();
+    assert!(filter.matches(&filter_violation("SEC001", Severity::Error, 2)));
+    assert!(!filter.matches(&filter_violation("SEC001", Severity::Error, 3)));
+}
+
+#[test]
+fn test_select_combined() {
+    let filter =
+        ViolationFilter::parse("rule_id^=SEC, severity!=info, category=security").unwrap();
+    assert!(filter.matches(&filter_violation("SEC002", Severity::Error, 0)));
+    assert!(!filter.matches(&filter_violation("SEC002", Severity::Info, 0)));
+    assert!(!filter.matches(&filter_violation("PERF001", Severity::Error, 0)));
+}
+
+#[test]
+fn test_select_invalid_expressions() {
+    assert!(ViolationFilter::parse("").is_err());
+    assert!(ViolationFilter::parse("rule_id").is_err());
+    assert!(ViolationFilter::parse("color=red").is_err());
+    assert!(ViolationFilter::parse("severity=fatal").is_err());
+    assert!(ViolationFilter::parse("query=first").is_err());
+    assert!(ViolationFilter::parse("category^=sec").is_err());
+}
+
+#[test]
+fn test_unsupported_statements_reported() {
+    let queries = parse_queries("BEGIN; SELECT 1; COMMIT;", SqlDialect::Generic).unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    assert_eq!(report.unanalyzed, vec![0, 2]);
+    assert_eq!(report.summary().unanalyzed, 2);
+}
+
+#[test]
+fn test_timed_analysis_of_large_batch_reports_throughput() {
+    let sql = "SELECT id FROM users WHERE email = 'a@b.c' LIMIT 10;\n".repeat(1000);
+    let queries = parse_queries(&sql, SqlDialect::Generic).unwrap();
+    let report = RuleRunner::new().with_timings(true).analyze(&queries);
+    assert_eq!(report.queries_count, 1000);
+    assert!(report.queries_per_second.is_some_and(|qps| qps > 0.0));
+}
+
+#[test]
+fn test_timed_analysis_records_every_rule() {
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, email TEXT)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let config = RulesConfig {
+        disabled: vec!["PERF001".to_string()],
+        ..Default::default()
+    };
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE email = 'a@b.c';\nINSERT INTO users VALUES (1, 'x')",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::with_schema_and_config(schema, config)
+        .with_timings(true)
+        .analyze(&queries);
+    let lints = report.rules_count - report.rule_timings.len();
+    assert_eq!(lints, 3);
+    let ids: Vec<&str> = report.rule_timings.iter().map(|t| t.rule_id).collect();
+    for id in ["PERF002", "PERF052", "SEC001", "SCHEMA002"] {
+        assert!(ids.contains(&id), "missing timing for {}", id);
+    }
+    assert!(!ids.contains(&"PERF001"));
+    assert!(
+        report
+            .rule_timings
+            .windows(2)
+            .all(|pair| pair[0].nanos >= pair[1].nanos)
+    );
+    assert!(report.rule_timings.iter().any(|t| t.nanos > 0));
+}
+
+#[test]
+fn test_untimed_analysis_has_no_throughput() {
+    let queries = parse_queries("SELECT id FROM users LIMIT 10", SqlDialect::Generic).unwrap();
+    assert!(
+        RuleRunner::new()
+            .analyze(&queries)
+            .queries_per_second
+            .is_none()
+    );
+    assert!(RuleRunner::new().analyze(&queries).rule_timings.is_empty());
+}
+
+#[test]
+fn test_for_update_without_filter_flagged() {
+    let violations = analyze_query("SELECT * FROM jobs FOR UPDATE");
+    assert!(violations.contains(&"PERF042".to_string()));
+}
+
+#[test]
+fn test_for_update_with_where_ok() {
+    let violations = analyze_query("SELECT * FROM jobs WHERE id = 1 FOR UPDATE");
+    assert!(!violations.contains(&"PERF042".to_string()));
+}
+
+#[test]
+fn test_for_update_with_limit_ok() {
+    let violations =
+        analyze_query("SELECT * FROM jobs ORDER BY id LIMIT 10 FOR UPDATE SKIP LOCKED");
+    assert!(!violations.contains(&"PERF042".to_string()));
+}
+
+#[test]
+fn test_for_share_not_flagged() {
+    let violations = analyze_query("SELECT * FROM jobs FOR SHARE");
+    assert!(!violations.contains(&"PERF042".to_string()));
+}
+
+#[test]
+fn test_insert_select_star_flagged() {
+    let violations = analyze_query("INSERT INTO a SELECT * FROM b");
+    assert!(violations.contains(&"SEC017".to_string()));
+}
+
+#[test]
+fn test_insert_select_explicit_columns_ok() {
+    let violations = analyze_query("INSERT INTO a (x) SELECT x FROM b");
+    assert!(!violations.contains(&"SEC017".to_string()));
+}
+
+#[test]
+fn test_limit_without_order_by_flagged() {
+    let violations = analyze_query("SELECT * FROM t LIMIT 1");
+    assert!(violations.contains(&"SEC018".to_string()));
+}
+
+#[test]
+fn test_limit_with_order_by_ok() {
+    let violations = analyze_query("SELECT * FROM t ORDER BY id LIMIT 1");
+    assert!(!violations.contains(&"SEC018".to_string()));
+}
+
+#[test]
+fn test_limit_on_aggregate_without_group_by_ok() {
+    let violations = analyze_query("SELECT COUNT(*) FROM users LIMIT 1");
+    assert!(!violations.contains(&"SEC018".to_string()));
+    let violations = analyze_query("SELECT status, COUNT(*) FROM users GROUP BY status LIMIT 1");
+    assert!(violations.contains(&"SEC018".to_string()));
+}
+
+#[test]
+fn test_join_using_shared_column_ok() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status TEXT);";
+    let violations = analyze_with_schema("SELECT * FROM users JOIN orders USING (id)", schema);
+    assert!(!violations.contains(&"PERF046".to_string()));
+}
+
+#[test]
+fn test_join_using_column_missing_from_one_table() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, status TEXT);";
+    let violations = analyze_with_schema("SELECT * FROM users JOIN orders USING (status)", schema);
+    assert!(violations.contains(&"PERF046".to_string()));
+    let violations = analyze_with_schema(
+        "SELECT * FROM orders o JOIN users u USING (user_id)",
+        schema
+    );
+    assert!(violations.contains(&"PERF046".to_string()));
+}
+
+#[test]
+fn test_join_using_unknown_table_not_checked() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY);";
+    let violations = analyze_with_schema("SELECT * FROM users JOIN audit USING (ts)", schema);
+    assert!(!violations.contains(&"PERF046".to_string()));
+}
+
+#[test]
+fn test_nullable_join_column_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT);";
+    let violations = analyze_with_schema(
+        "SELECT o.id FROM orders o JOIN users u ON o.user_id = u.id",
+        schema
+    );
+    assert!(violations.contains(&"SCHEMA017".to_string()));
+}
+
+#[test]
+fn test_not_null_join_column_ok() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY); \
+                  CREATE TABLE orders (id INT PRIMARY KEY, user_id INT NOT NULL);";
+    let violations = analyze_with_schema(
+        "SELECT o.id FROM orders o JOIN users u ON o.user_id = u.id",
+        schema
+    );
+    assert!(!violations.contains(&"SCHEMA017".to_string()));
+}
+
+#[test]
+fn test_clickhouse_final_flagged() {
+    let violations = analyze_clickhouse("SELECT id FROM events FINAL WHERE id = 1");
+    assert!(violations.contains(&"CHPERF002".to_string()));
+}
+
+#[test]
+fn test_clickhouse_alias_named_final_ok() {
+    let violations = analyze_clickhouse("SELECT final.id FROM events AS final WHERE final.id = 1");
+    assert!(!violations.contains(&"CHPERF002".to_string()));
+}
+
+#[test]
+fn test_clickhouse_scan_without_prewhere_flagged() {
+    let violations = analyze_clickhouse("SELECT id, payload FROM events WHERE user_id = 7");
+    assert!(violations.contains(&"CHPERF001".to_string()));
+    assert!(!violations.contains(&"CHPERF002".to_string()));
+}
+
+#[test]
+fn test_clickhouse_prewhere_ok() {
+    let violations =
+        analyze_clickhouse("SELECT id, payload FROM events PREWHERE user_id = 7 WHERE id > 0");
+    assert!(!violations.contains(&"CHPERF001".to_string()));
+    let violations =
+        analyze_clickhouse("SELECT id, payload FROM events WHERE user_id = 7 LIMIT 10");
+    assert!(!violations.contains(&"CHPERF001".to_string()));
+}
+
+#[test]
+fn test_clickhouse_count_star_flagged() {
+    assert!(analyze_clickhouse("SELECT count(*) FROM events").contains(&"CHPERF003".to_string()));
+    assert!(!analyze_clickhouse("SELECT count() FROM events").contains(&"CHPERF003".to_string()));
+}
+
+#[test]
+fn test_clickhouse_rules_need_clickhouse_dialect() {
+    let violations = analyze_query("SELECT count(*) FROM events WHERE user_id = 7");
+    assert!(!violations.iter().any(|id| id.starts_with("CHPERF")));
+}
+
+#[test]
+fn test_clickhouse_rules_respect_disabled() {
+    let config = RulesConfig {
+        disabled: vec!["chperf001".into()],
+        ..Default::default()
+    };
+    let queries = parse_queries(
+        "SELECT id FROM events WHERE user_id = 7",
+        SqlDialect::ClickHouse
+    )
+    .unwrap();
+    let report = RuleRunner::with_config(config)
+        .with_dialect(SqlDialect::ClickHouse)
+        .analyze(&queries);
+    assert!(!report.violations.iter().any(|v| v.rule_id == "CHPERF001"));
+}
+
+#[test]
+fn test_analyze_static_without_schema() {
+    let report = sql_query_analyzer::analyze_static(
+        "SELECT * FROM users; DELETE FROM sessions",
+        None,
+        SqlDialect::Generic,
+        RulesConfig::default()
+    )
+    .unwrap();
+    assert_eq!(report.queries_count, 2);
+    assert!(report.violations.iter().any(|v| v.rule_id == "SEC002"));
+    assert!(
+        !report
+            .violations
+            .iter()
+            .any(|v| v.rule_id.starts_with("SCHEMA"))
+    );
+}
+
+#[test]
+fn test_analyze_static_with_schema() {
+    let report = sql_query_analyzer::analyze_static(
+        "SELECT id FROM users WHERE emial = 'a@b.c' LIMIT 10",
+        Some("CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255))"),
+        SqlDialect::Generic,
+        RulesConfig::default()
+    )
+    .unwrap();
+    assert!(report.violations.iter().any(|v| v.rule_id == "SCHEMA002"));
+}
+
+#[test]
+fn test_analyze_static_reports_parse_errors() {
+    let result = sql_query_analyzer::analyze_static(
+        "SELEC id FROM",
+        None,
+        SqlDialect::Generic,
+        RulesConfig::default()
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_self_join_on_primary_key_flagged() {
+    let violations =
+        analyze_query("SELECT u1.name FROM users u1 JOIN users u2 ON u1.id = u2.id LIMIT 10");
+    assert!(violations.contains(&"PERF043".to_string()));
+}
+
+#[test]
+fn test_self_join_on_manager_id_ok() {
+    let violations = analyze_query(
+        "SELECT e.name, m.name FROM employees e JOIN employees m ON e.manager_id = m.id LIMIT 10"
+    );
+    assert!(!violations.contains(&"PERF043".to_string()));
+}
+
+#[test]
+fn test_pairwise_self_join_on_key_inequality_ok() {
+    for op in ["<", "<>"] {
+        let violations = analyze_query(&format!(
+            "SELECT x.id, y.id FROM a x JOIN a y ON x.id {} y.id LIMIT 10",
+            op
+        ));
+        assert!(!violations.contains(&"PERF043".to_string()), "{}", op);
+    }
+}
+
+#[test]
+fn test_join_of_different_tables_on_id_ok() {
+    let violations =
+        analyze_query("SELECT u.name FROM users u JOIN accounts a ON u.id = a.id LIMIT 10");
+    assert!(!violations.contains(&"PERF043".to_string()));
+}
+
+#[test]
+fn test_or_across_different_columns_flagged() {
+    let violations = analyze_query("SELECT id FROM users WHERE a = 1 OR b = 2");
+    assert!(violations.contains(&"PERF044".to_string()));
+}
+
+#[test]
+fn test_or_on_same_column_not_perf044() {
+    let violations = analyze_query("SELECT id FROM users WHERE a = 1 OR a = 2");
+    assert!(!violations.contains(&"PERF044".to_string()));
+}
+
+#[test]
+fn test_or_nested_under_and_not_perf044() {
+    let violations =
+        analyze_query("SELECT id FROM users WHERE tenant_id = 1 AND (a = 1 OR b = 2)");
+    assert!(!violations.contains(&"PERF044".to_string()));
+}
+
+const DEPRECATED_SCHEMA: &str = "CREATE TABLE users (
+    id INT PRIMARY KEY,
+    email VARCHAR(255),
+    legacy_email VARCHAR(255) -- @deprecated Use users.email instead
+);
+CREATE TABLE orders (id INT PRIMARY KEY, user_id INT);";
+
+#[test]
+fn test_deprecated_column_read_flagged() {
+    let violations = analyze_with_schema(
+        "SELECT u.legacy_email FROM users u JOIN orders o ON o.user_id = u.id",
+        DEPRECATED_SCHEMA
+    );
+    assert!(violations.contains(&"SCHEMA016".to_string()));
+}
+
+#[test]
+fn test_deprecated_column_avoided_ok() {
+    let violations = analyze_with_schema("SELECT id, email FROM users", DEPRECATED_SCHEMA);
+    assert!(!violations.contains(&"SCHEMA016".to_string()));
+}
+
+#[test]
+fn test_deprecated_column_from_config_uses_note() {
+    let mut schema = Schema::parse(DEPRECATED_SCHEMA, SqlDialect::Generic).unwrap();
+    let entries = HashMap::from([(
+        "orders.user_id".to_string(),
+        "Join through order_owners".to_string()
+    )]);
+    schema.deprecate_columns(&entries);
+    let queries = parse_queries(
+        "UPDATE orders SET id = 1 WHERE user_id = 7",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report =
+        RuleRunner::with_schema_and_config(schema, RulesConfig::default()).analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "SCHEMA016")
+        .unwrap();
+    assert_eq!(violation.message, "Column 'orders.user_id' is deprecated");
+    assert_eq!(
+        violation.suggestion.as_deref(),
+        Some("Join through order_owners")
+    );
+}
+
+const EVENTS_SCHEMA: &str = "CREATE TABLE users (id INT PRIMARY KEY, email TEXT); \
+                             CREATE TABLE events (id INT PRIMARY KEY, user_id INT, payload TEXT);";
+
+fn analyze_with_large_events(sql: &str) -> Vec<String> {
+    let config: Config = toml::from_str("[schema]\nlarge_tables = [\"events\"]").unwrap();
+    let mut schema = Schema::parse(EVENTS_SCHEMA, SqlDialect::Generic).unwrap();
+    schema.mark_large_tables(&config.schema.large_tables);
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    RuleRunner::with_schema_and_config(schema, RulesConfig::default())
+        .analyze(&queries)
+        .violations
+        .iter()
+        .map(|v| v.rule_id.to_string())
+        .collect()
+}
+
+#[test]
+fn test_unfiltered_large_table_read_flagged() {
+    let violations = analyze_with_large_events("SELECT id, payload FROM events");
+    assert!(violations.contains(&"SEC022".to_string()));
+    let violations =
+        analyze_with_large_events("SELECT e.id FROM events e JOIN users u ON e.user_id = u.id");
+    assert!(violations.contains(&"SEC022".to_string()));
+}
+
+#[test]
+fn test_filtered_large_table_read_ok() {
+    for sql in [
+        "SELECT id, payload FROM events WHERE user_id = 42",
+        "SELECT e.id FROM events e JOIN users u ON e.user_id = u.id WHERE u.id = 42",
+        "SELECT id, payload FROM events LIMIT 100",
+        "SELECT id, email FROM users"
+    ] {
+        let violations = analyze_with_large_events(sql);
+        assert!(!violations.contains(&"SEC022".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_large_table_unmarked_ok() {
+    let violations = analyze_with_schema("SELECT id, payload FROM events", EVENTS_SCHEMA);
+    assert!(!violations.contains(&"SEC022".to_string()));
+}
+
+#[test]
+fn test_having_on_ungrouped_column_flagged() {
+    let violations =
+        analyze_query("SELECT a, COUNT(*) FROM t WHERE id > 0 GROUP BY a HAVING b = 1 LIMIT 10");
+    assert!(violations.contains(&"PERF045".to_string()));
+    assert!(!violations.contains(&"PERF018".to_string()));
+}
+
+#[test]
+fn test_having_aggregate_not_perf045() {
+    let violations = analyze_query(
+        "SELECT a, COUNT(*) FROM t WHERE id > 0 GROUP BY a HAVING COUNT(*) > 5 LIMIT 10"
+    );
+    assert!(!violations.contains(&"PERF045".to_string()));
+}
+
+#[test]
+fn test_having_grouped_column_or_alias_not_perf045() {
+    let violations = analyze_query(
+        "SELECT a, SUM(total) AS revenue FROM t WHERE id > 0 GROUP BY a \
+         HAVING a <> 'x' AND revenue > 100 LIMIT 10"
+    );
+    assert!(!violations.contains(&"PERF045".to_string()));
+}
+
+#[test]
+fn test_unpartitioned_window_flagged() {
+    let violations = analyze_query(
+        "SELECT id, ROW_NUMBER() OVER (ORDER BY created_at) FROM orders WHERE id > 0"
+    );
+    assert!(violations.contains(&"PERF047".to_string()));
+}
+
+#[test]
+fn test_partitioned_window_not_perf047() {
+    let violations = analyze_query(
+        "SELECT id, ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY created_at) FROM orders \
+         WHERE id > 0"
+    );
+    assert!(!violations.contains(&"PERF047".to_string()));
+}
+
+#[test]
+fn test_unpartitioned_window_with_limit_not_perf047() {
+    let violations = analyze_query(
+        "SELECT id, ROW_NUMBER() OVER (ORDER BY created_at) FROM orders WHERE id > 0 LIMIT 10"
+    );
+    assert!(!violations.contains(&"PERF047".to_string()));
+}
+
+#[test]
+fn test_window_partitioned_by_expression_not_perf047() {
+    let violations = analyze_query(
+        "SELECT id, RANK() OVER (PARTITION BY LOWER(status) ORDER BY id) FROM orders WHERE id > 0"
+    );
+    assert!(!violations.contains(&"PERF047".to_string()));
+}
+
+fn analyze_dialect(sql: &str, dialect: SqlDialect) -> Vec<Violation> {
+    let queries = parse_queries(sql, dialect).unwrap();
+    RuleRunner::new().analyze(&queries).violations
+}
+
+#[test]
+fn test_mysql_into_outfile_flagged() {
+    let violations = analyze_dialect(
+        "SELECT id, email FROM users WHERE id > 0 INTO OUTFILE '/tmp/users.csv' \
+         FIELDS TERMINATED BY ',' ENCLOSED BY '\"' LINES TERMINATED BY '\\n'",
+        SqlDialect::MySQL
+    );
+    let export = violations.iter().find(|v| v.rule_id == "SEC019").unwrap();
+    assert_eq!(export.severity, Severity::Error);
+    assert!(export.message.contains("INTO OUTFILE"));
+    assert!(export.message.contains("/tmp/users.csv"));
+}
+
+#[test]
+fn test_mysql_into_dumpfile_flagged() {
+    let violations = analyze_dialect(
+        "SELECT payload FROM blobs WHERE id = 1 INTO DUMPFILE '/var/lib/mysql-files/x.so'",
+        SqlDialect::MySQL
+    );
+    let export = violations.iter().find(|v| v.rule_id == "SEC019").unwrap();
+    assert!(export.message.contains("INTO DUMPFILE"));
+    assert!(export.message.contains("/var/lib/mysql-files/x.so"));
+}
+
+#[test]
+fn test_postgres_copy_to_file_flagged() {
+    let violations = analyze_dialect(
+        "COPY users TO '/tmp/users.csv' WITH CSV",
+        SqlDialect::PostgreSQL
+    );
+    let export = violations.iter().find(|v| v.rule_id == "SEC019").unwrap();
+    assert!(export.message.contains("COPY TO"));
+    assert!(export.message.contains("/tmp/users.csv"));
+}
+
+#[test]
+fn test_postgres_copy_to_stdout_and_from_file_not_sec019() {
+    let violations = analyze_dialect(
+        "COPY users TO STDOUT; COPY users FROM '/tmp/users.csv'",
+        SqlDialect::PostgreSQL
+    );
+    assert!(!violations.iter().any(|v| v.rule_id == "SEC019"));
+}
+
+#[test]
+fn test_copy_to_file_counted_as_analyzed() {
+    let queries = parse_queries(
+        "BEGIN; COPY users TO '/tmp/x.csv'; COMMIT;",
+        SqlDialect::PostgreSQL
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    assert!(
+        report
+            .violations
+            .iter()
+            .any(|v| v.rule_id == "SEC019" && v.query_index == 1)
+    );
+    assert_eq!(report.unanalyzed, vec![0, 2]);
+}
+
+#[test]
+fn test_plain_select_not_sec019() {
+    let violations = analyze_query("SELECT id FROM users WHERE id = 1");
+    assert!(!violations.contains(&"SEC019".to_string()));
+}
+
+#[test]
+fn test_inline_disable_suppresses_only_named_rule() {
+    let violations = analyze_query(
+        "SELECT id FROM users WHERE id = 1;\n\
+         -- sqla:disable PERF001\n\
+         SELECT * FROM orders;"
+    );
+    assert!(!violations.contains(&"PERF001".to_string()));
+    assert!(violations.contains(&"STYLE001".to_string()));
+}
+
+#[test]
+fn test_inline_disable_applies_to_next_statement_only() {
+    let violations = analyze_query(
+        "-- sqla:disable PERF001, STYLE001\n\
+         SELECT * FROM orders;\n\
+         SELECT * FROM users;"
+    );
+    assert_eq!(violations.iter().filter(|id| *id == "PERF001").count(), 1);
+    assert_eq!(violations.iter().filter(|id| *id == "STYLE001").count(), 1);
+}
+
+#[test]
+fn test_inline_disable_next_line() {
+    let queries = parse_queries(
+        "-- sqla:disable-next-line STYLE001\nSELECT * FROM orders;\n\nSELECT * FROM users;",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let style: Vec<usize> = report
+        .violations
+        .iter()
+        .filter(|v| v.rule_id == "STYLE001")
+        .map(|v| v.query_index)
+        .collect();
+    assert_eq!(style, vec![1]);
+}
+
+#[test]
+fn test_inline_disable_file() {
+    let violations =
+        analyze_query("SELECT * FROM orders;\n-- sqla:disable-file PERF001\nSELECT * FROM users;");
+    assert!(!violations.contains(&"PERF001".to_string()));
+    assert!(violations.contains(&"STYLE001".to_string()));
+
+    let violations =
+        analyze_query("-- sqla:disable-file\nSELECT * FROM orders; DELETE FROM users;");
+    assert!(violations.is_empty());
+}
+
+#[test]
+fn test_count_column_flagged() {
+    let violations = analyze_query("SELECT COUNT(id) FROM orders WHERE status = 'new'");
+    assert!(violations.contains(&"PERF048".to_string()));
+}
+
+#[test]
+fn test_count_star_not_perf048() {
+    let violations = analyze_query("SELECT COUNT(*) FROM orders WHERE status = 'new'");
+    assert!(!violations.contains(&"PERF048".to_string()));
+}
+
+#[test]
+fn test_count_distinct_not_perf048() {
+    let violations = analyze_query("SELECT COUNT(DISTINCT id) FROM orders WHERE status = 'new'");
+    assert!(!violations.contains(&"PERF048".to_string()));
+}
+
+#[test]
+fn test_count_qualified_column_in_expression_flagged() {
+    let queries = parse_queries(
+        "SELECT o.status, COUNT(o.user_id) * 2 FROM orders o WHERE o.id > 0 GROUP BY o.status",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF048")
+        .unwrap();
+    assert!(violation.message.contains("COUNT(o.user_id)"));
+}
+
+#[test]
+fn test_repeated_function_call_flagged() {
+    let violations =
+        analyze_query("SELECT expensive(x), expensive(x) + 1 FROM t WHERE id = 1 LIMIT 10");
+    assert!(violations.contains(&"PERF050".to_string()));
+}
+
+#[test]
+fn test_distinct_function_calls_not_perf050() {
+    for sql in [
+        "SELECT expensive(x), expensive(y) FROM t WHERE id = 1 LIMIT 10",
+        "SELECT SUM(total), SUM(total) / COUNT(*) FROM orders WHERE id > 0",
+        "SELECT NOW(), NOW() FROM t WHERE id = 1 LIMIT 10"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF050".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_repeated_nested_call_reported_once() {
+    let queries = parse_queries(
+        "SELECT LOWER(TRIM(email)), LENGTH(LOWER(TRIM(email))) FROM users WHERE id = 1",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let messages: Vec<&str> = report
+        .violations
+        .iter()
+        .filter(|v| v.rule_id == "PERF050")
+        .map(|v| v.message.as_str())
+        .collect();
+    assert_eq!(
+        messages,
+        vec!["LOWER(TRIM(email)) is computed more than once per row"]
+    );
+}
+
+#[test]
+fn test_unbounded_recursive_cte_flagged() {
+    let violations = analyze_query(
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq) SELECT n FROM seq"
+    );
+    assert!(violations.contains(&"PERF051".to_string()));
+}
+
+#[test]
+fn test_guarded_recursive_cte_not_perf051() {
+    for sql in [
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 100) SELECT n FROM seq",
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq LIMIT 100) SELECT n FROM seq",
+        "WITH RECURSIVE r AS (SELECT id FROM orgs WHERE id = 1 UNION ALL SELECT u.id FROM users u JOIN r ON u.org_id = r.id) SELECT id FROM r",
+        "WITH seq(n) AS (SELECT 1 UNION ALL SELECT 2) SELECT n FROM seq"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF051".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_batchable_inserts_flagged_once() {
+    let sql = (1..=5)
+        .map(|id| format!("INSERT INTO orders (id, status) VALUES ({}, 'new')", id))
+        .collect::<Vec<_>>()
+        .join(";\n");
+    let queries = parse_queries(&sql, SqlDialect::Generic).unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let found: Vec<&Violation> = report
+        .violations
+        .iter()
+        .filter(|v| v.rule_id == "PERF052")
+        .collect();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].query_index, 0);
+    assert!(
+        found[0]
+            .message
+            .contains("5 consecutive single-row INSERTs into orders")
+    );
+}
+
+#[test]
+fn test_mixed_table_inserts_not_perf052() {
+    for sql in [
+        "INSERT INTO orders VALUES (1); INSERT INTO users VALUES (1); INSERT INTO orders VALUES (2); INSERT INTO users VALUES (2)",
+        "INSERT INTO orders VALUES (1), (2); INSERT INTO orders VALUES (3), (4); INSERT INTO orders VALUES (5), (6)",
+        "INSERT INTO orders VALUES (1); INSERT INTO orders VALUES (2); SELECT id FROM orders WHERE id = 1; INSERT INTO orders VALUES (3)"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF052".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_unconditional_merge_delete_flagged() {
+    for sql in [
+        "MERGE INTO users u USING contacts c ON u.email = c.email WHEN MATCHED THEN DELETE",
+        "MERGE INTO users u USING contacts c ON 1 = 1 WHEN MATCHED AND c.active = FALSE THEN DELETE"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(violations.contains(&"SEC020".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_scoped_merge_not_sec020() {
+    for sql in [
+        "MERGE INTO users u USING contacts c ON u.email = c.email \
+         WHEN MATCHED AND c.unsubscribed = TRUE THEN DELETE \
+         WHEN NOT MATCHED THEN INSERT (email) VALUES (c.email)",
+        "MERGE INTO users u USING contacts c ON u.email = c.email \
+         WHEN MATCHED THEN UPDATE SET name = c.name"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"SEC020".to_string()), "{}", sql);
+    }
+}
+
+#[test]
+fn test_repeated_derived_table_flagged() {
+    let violations = analyze_query(
+        "SELECT a.id FROM (SELECT id, total FROM orders WHERE total > 100) a \
+         JOIN (select id, total from orders where total > 100) b ON a.id = b.id LIMIT 10"
+    );
+    assert!(violations.contains(&"PERF053".to_string()));
+}
+
+#[test]
+fn test_single_derived_table_not_perf053() {
+    for sql in [
+        "SELECT a.id FROM (SELECT id, total FROM orders WHERE total > 100) a WHERE a.id = 1",
+        "SELECT a.id FROM (SELECT id FROM orders WHERE total > 100) a \
+         JOIN (SELECT id FROM orders WHERE total > 200) b ON a.id = b.id LIMIT 10"
+    ] {
+        let violations = analyze_query(sql);
+        assert!(!violations.contains(&"PERF053".to_string()), "{}", sql);
+    }
+}
+
+fn sec021_severities(sql: &str, dialect: SqlDialect) -> Vec<Severity> {
+    let queries = parse_queries(sql, dialect).unwrap();
+    RuleRunner::new()
+        .analyze(&queries)
+        .violations
+        .iter()
+        .filter(|v| v.rule_id == "SEC021")
+        .map(|v| v.severity)
+        .collect()
+}
+
+#[test]
+fn test_sensitive_set_flagged() {
+    for (sql, dialect) in [
+        ("SET foreign_key_checks = 0", SqlDialect::MySQL),
+        ("SET SESSION sql_mode = ''", SqlDialect::MySQL),
+        ("SET @@session.sql_log_bin = 0", SqlDialect::MySQL),
+        ("SET local_infile = 1", SqlDialect::MySQL),
+        ("SET search_path TO evil, public", SqlDialect::PostgreSQL),
+        (
+            "SET LOCAL session_replication_role = replica",
+            SqlDialect::PostgreSQL
+        )
+    ] {
+        assert_eq!(
+            sec021_severities(sql, dialect),
+            vec![Severity::Warning],
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_global_local_infile_is_error() {
+    for sql in [
+        "SET GLOBAL local_infile = 1",
+        "SET @@global.local_infile = ON"
+    ] {
+        assert_eq!(
+            sec021_severities(sql, SqlDialect::MySQL),
+            vec![Severity::Error],
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_benign_set_not_sec021() {
+    for (sql, dialect) in [
+        ("SET NAMES utf8mb4", SqlDialect::MySQL),
+        ("SET @retries = 3", SqlDialect::MySQL),
+        ("SET statement_timeout = '5s'", SqlDialect::PostgreSQL),
+        ("SET TIME ZONE 'UTC'", SqlDialect::PostgreSQL),
+        ("SET foreign_key_checks = 1", SqlDialect::MySQL),
+        ("SET GLOBAL local_infile = 0", SqlDialect::MySQL),
+        ("SET @@session.sql_log_bin = ON", SqlDialect::MySQL),
+        ("SET sql_mode = DEFAULT", SqlDialect::MySQL),
+        (
+            "SET session_replication_role = origin",
+            SqlDialect::PostgreSQL
+        )
+    ] {
+        assert!(sec021_severities(sql, dialect).is_empty(), "{}", sql);
+    }
+}
+
+#[test]
+fn test_configured_sensitive_variable_flagged() {
+    let queries = parse_queries("SET statement_timeout = 0", SqlDialect::PostgreSQL).unwrap();
+    let config = RulesConfig {
+        sensitive_variables: vec!["Statement_Timeout".to_string()],
+        ..Default::default()
+    };
+    let report = RuleRunner::with_config(config).analyze(&queries);
+    assert!(report.violations.iter().any(|v| v.rule_id == "SEC021"));
+}
+
+fn sec023_messages(sql: &str, allowed: &[&str]) -> Vec<String> {
+    let queries = parse_queries(sql, SqlDialect::PostgreSQL).unwrap();
+    let config = RulesConfig {
+        allowed_schemas: allowed.iter().map(|s| s.to_string()).collect(),
+        ..Default::default()
+    };
+    RuleRunner::with_config(config)
+        .analyze(&queries)
+        .violations
+        .into_iter()
+        .filter(|v| v.rule_id == "SEC023")
+        .map(|v| v.message)
+        .collect()
+}
+
+#[test]
+fn test_three_part_name_flagged_against_allowlist() {
+    let messages = sec023_messages("SELECT * FROM prod.public.users", &["app"]);
+    assert_eq!(
+        messages,
+        vec!["Table 'prod.public.users' is referenced through database 'prod'"]
+    );
+    assert_eq!(
+        sec023_messages("SELECT * FROM prod.public.users", &[]).len(),
+        1
+    );
+}
+
+#[test]
+fn test_two_part_name_outside_allowlist_flagged() {
+    let sql = "SELECT i.id FROM billing.invoices i JOIN app.users u ON u.id = i.user_id";
+    let messages = sec023_messages(sql, &["App"]);
+    assert_eq!(
+        messages,
+        vec!["Table 'billing.invoices' is referenced through schema 'billing'"]
+    );
+}
+
+#[test]
+fn test_allowed_names_not_sec023() {
+    assert!(sec023_messages("SELECT id FROM public.users", &[]).is_empty());
+    assert!(sec023_messages("SELECT id FROM users", &["app"]).is_empty());
+    assert!(sec023_messages("SELECT id FROM app.users", &["app"]).is_empty());
+    assert!(sec023_messages("SELECT id FROM prod.app.users", &["prod.app"]).is_empty());
+}
+
+#[test]
+fn test_quoted_dotted_schema_is_one_part() {
+    assert!(sec023_messages("SELECT id FROM \"my.schema\".users", &[]).is_empty());
+    assert!(sec023_messages("SELECT id FROM \"my.schema\".users", &["my.schema"]).is_empty());
+    let messages = sec023_messages("SELECT id FROM \"my.schema\".users", &["app"]);
+    assert_eq!(
+        messages,
+        vec!["Table '\"my.schema\".users' is referenced through schema 'my.schema'"]
+    );
+}
+
+/// Generates `remaining` statements on demand and counts the bytes handed out
+struct SyntheticDump {
+    remaining: usize,
+    buffered:  Vec<u8>,
+    served:    std::rc::Rc<std::cell::Cell<usize>>
+}
+
+impl std::io::Read for SyntheticDump {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.buffered.is_empty() && self.remaining > 0 {
+            self.remaining -= 1;
+            self.buffered = format!(
+                "SELECT * FROM events_{} WHERE note = 'a;b';\n",
+                self.remaining
+            )
+            .into_bytes();
+        }
+        let len = buf.len().min(self.buffered.len());
+        buf[..len].copy_from_slice(&self.buffered[..len]);
+        self.buffered.drain(..len);
+        self.served.set(self.served.get() + len);
+        Ok(len)
+    }
+}
+
+#[test]
+fn test_analyze_stream_yields_before_reading_all_input() {
+    let statements = 200_000;
+    let served = std::rc::Rc::new(std::cell::Cell::new(0));
+    let dump = SyntheticDump {
+        remaining: statements,
+        buffered:  Vec::new(),
+        served:    served.clone()
+    };
+    let runner = RuleRunner::new();
+    let mut results = runner.analyze_stream(stream_queries(
+        std::io::BufReader::new(dump),
+        SqlDialect::Generic
+    ));
+
+    let first = results.next().unwrap().unwrap();
+    assert!(first.iter().any(|v| v.rule_id == "STYLE001"));
+    assert!(first.iter().all(|v| v.query_index == 0));
+    assert!(
+        served.get() < 64 * 1024,
+        "read {} bytes up front",
+        served.get()
+    );
+
+    let rest = results.take(999).filter(|r| r.is_ok()).count();
+    assert_eq!(rest, 999);
+    assert!(served.get() < 128 * 1024, "read {} bytes", served.get());
+}
+
+#[test]
+fn test_check_query_applies_config() {
+    let queries = parse_queries(
+        "-- sqla:disable STYLE001
+SELECT * FROM users",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let mut severity = HashMap::new();
+    severity.insert("PERF001".to_string(), "error".to_string());
+    let runner = RuleRunner::with_config(RulesConfig {
+        severity,
+        ..Default::default()
+    });
+    let violations = runner.check_query(&queries[0], 0);
+    assert!(!violations.iter().any(|v| v.rule_id == "STYLE001"));
+    let perf001 = violations.iter().find(|v| v.rule_id == "PERF001").unwrap();
+    assert_eq!(perf001.severity, Severity::Error);
+    assert_eq!(perf001.location, queries[0].span);
+}
+
+#[test]
+fn test_between_on_wrapped_column_flagged() {
+    let queries = parse_queries(
+        "SELECT id FROM orders WHERE status = 'paid' AND DATE(ts) BETWEEN '2024-01-01' AND '2024-01-31'",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF055")
+        .expect("PERF055");
+    assert_eq!(
+        violation.suggestion.as_deref(),
+        Some("Use a half-open range on the bare column: ts >= '2024-01-01' AND ts < '2024-02-01'")
+    );
+    for sql in [
+        "SELECT id FROM orders WHERE CAST(ts AS DATE) BETWEEN '2024-12-01' AND '2024-12-31'",
+        "SELECT id FROM users WHERE LOWER(name) BETWEEN 'a' AND 'm' LIMIT 10"
+    ] {
+        assert!(
+            analyze_query(sql).contains(&"PERF055".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_between_on_bare_column_not_perf055() {
+    for sql in [
+        "SELECT id FROM orders WHERE ts BETWEEN '2024-01-01' AND '2024-01-31'",
+        "SELECT id FROM orders WHERE DATE(ts) NOT BETWEEN '2024-01-01' AND '2024-01-31'",
+        "SELECT id FROM orders WHERE ts BETWEEN DATE('2024-01-01') AND NOW()"
+    ] {
+        assert!(
+            !analyze_query(sql).contains(&"PERF055".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_insert_into_serial_column_flagged() {
+    let schema = "CREATE TABLE events (id SERIAL PRIMARY KEY, occurred_at TIMESTAMP)";
+    let queries = parse_queries(
+        "INSERT INTO events (id, occurred_at) VALUES (1, NOW())",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let schema = Schema::parse(schema, SqlDialect::Generic).unwrap();
+    let report =
+        RuleRunner::with_schema_and_config(schema, RulesConfig::default()).analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "SCHEMA026")
+        .expect("SCHEMA026");
+    assert_eq!(
+        violation.message,
+        "INSERT writes column 'events.id', which the database generates"
+    );
+}
+
+#[test]
+fn test_insert_omitting_generated_column_not_flagged() {
+    let schema = "CREATE TABLE events (id SERIAL PRIMARY KEY, occurred_at TIMESTAMP)";
+    for sql in [
+        "INSERT INTO events (occurred_at) VALUES (NOW())",
+        "INSERT INTO events VALUES (1, NOW())"
+    ] {
+        assert!(
+            !analyze_with_schema(sql, schema).contains(&"SCHEMA026".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_insert_into_identity_and_auto_increment_columns_flagged() {
+    let identity = "CREATE TABLE orders (id INT GENERATED ALWAYS AS IDENTITY, \
+                    total INT, doubled INT GENERATED ALWAYS AS (total * 2) STORED)";
+    for sql in [
+        "INSERT INTO orders (id, total) VALUES (1, 10)",
+        "INSERT INTO orders (total, doubled) VALUES (10, 20)"
+    ] {
+        assert!(
+            analyze_with_schema(sql, identity).contains(&"SCHEMA026".to_string()),
+            "{}",
+            sql
+        );
+    }
+    let queries = parse_queries(
+        "INSERT INTO users (id, name) VALUES (1, 'a')",
+        SqlDialect::MySQL
+    )
+    .unwrap();
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT AUTO_INCREMENT PRIMARY KEY, name VARCHAR(50))",
+        SqlDialect::MySQL
+    )
+    .unwrap();
+    let report =
+        RuleRunner::with_schema_and_config(schema, RulesConfig::default()).analyze(&queries);
+    assert!(report.violations.iter().any(|v| v.rule_id == "SCHEMA026"));
+}
+
+#[test]
+fn test_report_statistics_over_mixed_queries() {
+    let queries = parse_queries(
+        "SELECT * FROM users; \
+         BEGIN; \
+         SELECT * FROM orders; \
+         SELECT u.id, COUNT(*) FROM users u JOIN orders o ON u.id = o.user_id \
+         WHERE o.total > 10 GROUP BY u.id LIMIT 10; \
+         DELETE FROM orders;",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let stats = report.to_statistics(&queries);
+    assert_eq!(stats.queries, 5);
+    assert_eq!(stats.analyzed, 4);
+    assert_eq!(stats.unsupported, 1);
+    assert_eq!(
+        stats.by_severity.values().sum::<usize>(),
+        report.violations.len()
+    );
+    assert_eq!(stats.by_category, report.category_summary());
+    let top: Vec<(&str, usize)> = stats
+        .top_rules
+        .iter()
+        .take(3)
+        .map(|rule| (rule.rule_id, rule.count))
+        .collect();
+    assert_eq!(top, [("PERF001", 2), ("PERF011", 2), ("STYLE001", 2)]);
+    assert_eq!(stats.max_complexity, 11);
+    assert!((stats.avg_complexity - 3.5).abs() < f64::EPSILON);
+}
+
+#[test]
+fn test_report_statistics_of_empty_run() {
+    let stats = RuleRunner::new().analyze(&[]).to_statistics(&[]);
+    assert_eq!(stats.queries, 0);
+    assert!(stats.top_rules.is_empty());
+    assert_eq!(stats.by_severity[&Severity::Error], 0);
+    assert_eq!(stats.avg_complexity, 0.0);
+    assert_eq!(stats.max_complexity, 0);
+}
+
+#[test]
+fn test_order_by_in_cte_without_limit_flagged() {
+    let queries = parse_queries(
+        "WITH recent AS (SELECT id, total FROM orders ORDER BY created_at DESC) \
+         SELECT id FROM recent WHERE total > 100",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let report = RuleRunner::new().analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF062")
+        .expect("PERF062");
+    assert_eq!(
+        violation.message,
+        "CTE 'recent' is sorted with ORDER BY but has no LIMIT"
+    );
+}
+
+#[test]
+fn test_order_by_in_cte_with_limit_not_flagged() {
+    for sql in [
+        "WITH top_orders AS (SELECT id, total FROM orders ORDER BY total DESC LIMIT 10) \
+         SELECT id FROM top_orders",
+        "WITH top_orders AS (SELECT id FROM orders ORDER BY total DESC FETCH FIRST 5 ROWS ONLY) \
+         SELECT id FROM top_orders",
+        "WITH recent AS (SELECT id, total FROM orders WHERE total > 100) \
+         SELECT id FROM recent ORDER BY total LIMIT 10",
+        "WITH RECURSIVE seq(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 10 ORDER BY n) \
+         SELECT n FROM seq"
+    ] {
+        assert!(
+            !analyze_query(sql).contains(&"PERF062".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_view_filter_reported_instead_of_missing_index() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255), name VARCHAR(100)); \
+                  CREATE VIEW active_users AS SELECT id, email AS contact FROM users";
+    let queries = parse_queries(
+        "SELECT id FROM active_users WHERE contact = 'a' ORDER BY contact",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let schema = Schema::parse(schema, SqlDialect::Generic).unwrap();
+    let report =
+        RuleRunner::with_schema_and_config(schema, RulesConfig::default()).analyze(&queries);
+    let ids: Vec<&str> = report.violations.iter().map(|v| v.rule_id).collect();
+    assert!(!ids.contains(&"SCHEMA001"), "{:?}", ids);
+    assert!(!ids.contains(&"SCHEMA003"), "{:?}", ids);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "SCHEMA027")
+        .expect("SCHEMA027");
+    assert_eq!(
+        violation.message,
+        "'active_users' is a view without indexes of its own; using contact relies on its base tables"
+    );
+}
+
+#[test]
+fn test_table_filter_not_reported_as_view() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255)); \
+                  CREATE VIEW active_users AS SELECT * FROM users";
+    let violations = analyze_with_schema("SELECT id FROM users WHERE email = 'a'", schema);
+    assert!(violations.contains(&"SCHEMA001".to_string()));
+    assert!(!violations.contains(&"SCHEMA027".to_string()));
+}
+
+#[test]
+fn test_group_by_primary_key_with_dependent_column_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(100))";
+    let queries = parse_queries(
+        "SELECT id, name, COUNT(*) FROM users GROUP BY id, name",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let schema = Schema::parse(schema, SqlDialect::Generic).unwrap();
+    let report =
+        RuleRunner::with_schema_and_config(schema, RulesConfig::default()).analyze(&queries);
+    let violation = report
+        .violations
+        .iter()
+        .find(|v| v.rule_id == "PERF063")
+        .expect("PERF063");
+    assert_eq!(
+        violation.message,
+        "GROUP BY contains the primary key (id) of 'users'; the other columns (name) add nothing to the grouping"
+    );
+}
+
+#[test]
+fn test_group_by_without_full_primary_key_not_flagged() {
+    let schema = "CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(100)); \
+                  CREATE TABLE members (team_id INT, user_id INT, role VARCHAR(20), \
+                  PRIMARY KEY (team_id, user_id))";
+    for sql in [
+        "SELECT name, COUNT(*) FROM users GROUP BY name",
+        "SELECT id, COUNT(*) FROM users GROUP BY id",
+        "SELECT team_id, role, COUNT(*) FROM members GROUP BY team_id, role",
+        "SELECT u.id, o.status FROM users u JOIN orders o ON o.user_id = u.id GROUP BY u.id, o.status"
+    ] {
+        assert!(
+            !analyze_with_schema(sql, schema).contains(&"PERF063".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
+
+#[test]
+fn test_group_by_composite_key_and_distinct_flagged() {
+    let schema = "CREATE TABLE members (team_id INT, user_id INT, role VARCHAR(20), \
+                  PRIMARY KEY (team_id, user_id))";
+    for sql in [
+        "SELECT team_id, user_id, role FROM members GROUP BY team_id, user_id, role",
+        "SELECT DISTINCT team_id, user_id FROM members GROUP BY user_id, team_id"
+    ] {
+        assert!(
+            analyze_with_schema(sql, schema).contains(&"PERF063".to_string()),
+            "{}",
+            sql
+        );
+    }
+}
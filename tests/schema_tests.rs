@@ -1,7 +1,10 @@
 // SPDX-FileCopyrightText: 2025 RAprogramm
 // SPDX-License-Identifier: MIT
 
-use sql_query_analyzer::{query::SqlDialect, schema::Schema};
+use sql_query_analyzer::{
+    query::{ColumnClause, SqlDialect, parse_queries},
+    schema::Schema
+};
 
 #[test]
 fn test_parse_simple_table() {
@@ -204,16 +207,59 @@ fn test_table_info_debug() {
 fn test_column_info_debug() {
     use sql_query_analyzer::schema::ColumnInfo;
     let col = ColumnInfo {
-        name:        "test".to_string(),
-        data_type:   "INT".to_string(),
-        is_nullable: true,
-        is_primary:  false,
-        codec:       None
+        name:           "test".to_string(),
+        data_type:      "INT".to_string(),
+        is_nullable:    true,
+        is_primary:     false,
+        codec:          None,
+        collation:      None,
+        deprecated:     None,
+        allowed_values: None,
+        is_generated:   false
     };
     let debug = format!("{:?}", col);
     assert!(debug.contains("test"));
 }
 
+#[test]
+fn test_column_is_generated() {
+    let sql = "CREATE TABLE events (id BIGSERIAL PRIMARY KEY, seq INT GENERATED BY DEFAULT AS IDENTITY, \
+               total INT, doubled INT GENERATED ALWAYS AS (total * 2) STORED)";
+    let schema = Schema::parse(sql, SqlDialect::PostgreSQL).unwrap();
+    let generated: Vec<_> = schema.tables["events"]
+        .columns
+        .iter()
+        .filter(|c| c.is_generated)
+        .map(|c| c.name.as_str())
+        .collect();
+    assert_eq!(generated, ["id", "seq", "doubled"]);
+}
+
+#[test]
+fn test_view_columns_parsed() {
+    let sql = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255) NOT NULL); \
+               CREATE VIEW contacts AS SELECT u.email AS address, LOWER(email) AS folded, id FROM users u; \
+               CREATE VIEW renamed (key_id) AS SELECT id FROM users";
+    let schema = Schema::parse(sql, SqlDialect::Generic).unwrap();
+    let contacts = &schema.tables["contacts"];
+    assert!(contacts.is_view);
+    assert!(!schema.tables["users"].is_view);
+    let columns: Vec<(&str, &str, bool)> = contacts
+        .columns
+        .iter()
+        .map(|c| (c.name.as_str(), c.data_type.as_str(), c.is_primary))
+        .collect();
+    assert_eq!(
+        columns,
+        [
+            ("address", "VARCHAR(255)", false),
+            ("folded", "", false),
+            ("id", "INT", false)
+        ]
+    );
+    assert_eq!(schema.tables["renamed"].columns[0].name, "key_id");
+}
+
 #[test]
 fn test_column_info_codec_default_none() {
     let sql = "CREATE TABLE users (id INT PRIMARY KEY)";
@@ -222,13 +268,24 @@ fn test_column_info_codec_default_none() {
     assert!(users.columns[0].codec.is_none());
 }
 
+#[test]
+fn test_column_collation_parsed() {
+    let sql = "CREATE TABLE users (id INT PRIMARY KEY, email VARCHAR(255) COLLATE utf8mb4_bin)";
+    let schema = Schema::parse(sql, SqlDialect::Generic).unwrap();
+    let users = &schema.tables["users"];
+    assert!(users.columns[0].collation.is_none());
+    assert_eq!(users.columns[1].collation.as_deref(), Some("utf8mb4_bin"));
+}
+
 #[test]
 fn test_index_info_debug() {
-    use sql_query_analyzer::schema::IndexInfo;
+    use sql_query_analyzer::{query::SortDir, schema::IndexInfo};
     let idx = IndexInfo {
-        name:      "idx_test".to_string(),
-        columns:   vec!["col1".to_string()],
-        is_unique: false
+        name:       "idx_test".to_string(),
+        columns:    vec!["col1".to_string()],
+        directions: vec![("col1".to_string(), SortDir::Asc)],
+        is_unique:  false,
+        method:     None
     };
     let debug = format!("{:?}", idx);
     assert!(debug.contains("idx_test"));
@@ -254,6 +311,22 @@ fn test_table_info_clickhouse_fields_default_none() {
     assert!(users.cluster.is_none());
 }
 
+#[test]
+fn test_table_primary_key_constraint() {
+    let sql = "CREATE TABLE members (team_id INT, user_id INT, PRIMARY KEY (team_id, user_id)); \
+               CREATE TABLE teams (id INT, name TEXT, PRIMARY KEY (id))";
+    let schema = Schema::parse(sql, SqlDialect::Generic).unwrap();
+    let members = &schema.tables["members"];
+    assert_eq!(
+        members.primary_key,
+        Some(vec!["team_id".to_string(), "user_id".to_string()])
+    );
+    assert!(members.columns.iter().all(|c| !c.is_primary));
+    let teams = &schema.tables["teams"];
+    assert_eq!(teams.primary_key, Some(vec!["id".to_string()]));
+    assert!(teams.columns[0].is_primary);
+}
+
 #[test]
 fn test_clickhouse_engine_extraction() {
     let sql = "CREATE TABLE logs (id UInt64) ENGINE = MergeTree ORDER BY id";
@@ -377,3 +450,123 @@ fn test_mysql_inline_key_constraint() {
     assert_eq!(orders.indexes.len(), 1);
     assert_eq!(orders.indexes[0].name, "idx_user");
 }
+
+#[test]
+fn test_parse_deprecated_column_comments() {
+    let sql = "CREATE TABLE users (
+        id INT PRIMARY KEY,
+        -- @deprecated
+        nickname VARCHAR(50),
+        legacy_email VARCHAR(255) -- @deprecated Use email
+    )";
+    let schema = Schema::parse(sql, SqlDialect::Generic).unwrap();
+    let columns = &schema.tables["users"].columns;
+    assert_eq!(columns[0].deprecated, None);
+    assert_eq!(columns[1].deprecated.as_deref(), Some(""));
+    assert_eq!(columns[2].deprecated.as_deref(), Some("Use email"));
+}
+
+#[test]
+fn test_parse_enum_and_check_domains() {
+    let sql = "CREATE TABLE tickets (
+        id INT PRIMARY KEY,
+        priority ENUM('low', 'high'),
+        state VARCHAR(10) CHECK (state IN ('open', 'closed')),
+        note TEXT
+    )";
+    let schema = Schema::parse(sql, SqlDialect::MySQL).unwrap();
+    let columns = &schema.tables["tickets"].columns;
+    assert_eq!(columns[0].allowed_values, None);
+    assert_eq!(
+        columns[1].allowed_values,
+        Some(vec!["low".to_string(), "high".to_string()])
+    );
+    assert_eq!(
+        columns[2].allowed_values,
+        Some(vec!["open".to_string(), "closed".to_string()])
+    );
+    assert_eq!(columns[3].allowed_values, None);
+}
+
+const IMPACT_SCHEMA: &str = "
+    CREATE TABLE users (id INT PRIMARY KEY, email TEXT, name TEXT);
+    CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, email TEXT, total INT);
+";
+
+#[test]
+fn test_find_column_usage_finds_referencing_queries() {
+    let schema = Schema::parse(IMPACT_SCHEMA, SqlDialect::Generic).unwrap();
+    let queries = parse_queries(
+        "SELECT id FROM users WHERE email = 'a@b.c';
+         SELECT name FROM users ORDER BY id;
+         SELECT u.email, o.total FROM users u JOIN orders o ON o.user_id = u.id;
+         UPDATE users SET email = NULL WHERE id = 1;
+         SELECT email FROM orders;",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(
+        schema.find_column_usage("users", "email", &queries),
+        vec![0, 2, 3]
+    );
+    assert_eq!(
+        schema.find_column_usage("orders", "email", &queries),
+        vec![4]
+    );
+    assert_eq!(
+        schema.column_usage("users", "email", &queries[0]),
+        vec![ColumnClause::Where]
+    );
+    assert_eq!(
+        schema.column_usage("users", "email", &queries[3]),
+        vec![ColumnClause::Set]
+    );
+    assert_eq!(
+        schema.column_usage("users", "id", &queries[2]),
+        vec![ColumnClause::Join]
+    );
+}
+
+#[test]
+fn test_find_column_usage_sees_nested_queries() {
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, org_id INT, email TEXT);
+         CREATE TABLE orgs (id INT PRIMARY KEY, email TEXT);",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let queries = parse_queries(
+        "SELECT id FROM orgs WHERE id IN (SELECT org_id FROM users WHERE email = 'x');
+         WITH c AS (SELECT email FROM users) SELECT email FROM c;
+         SELECT id FROM orgs WHERE EXISTS (SELECT 1 FROM users u WHERE u.email = orgs.email);
+         SELECT d.email FROM (SELECT email FROM users) d;
+         SELECT id FROM orgs WHERE id IN (SELECT org_id FROM users)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert_eq!(
+        schema.find_column_usage("users", "email", &queries),
+        vec![0, 1, 2, 3]
+    );
+    assert_eq!(schema.find_column_usage("orgs", "email", &queries), vec![2]);
+    assert_eq!(
+        schema.column_usage("users", "email", &queries[1]),
+        vec![ColumnClause::Select]
+    );
+}
+
+#[test]
+fn test_find_column_usage_ignores_unrelated_and_ambiguous_queries() {
+    let schema = Schema::parse(IMPACT_SCHEMA, SqlDialect::Generic).unwrap();
+    let queries = parse_queries(
+        "SELECT total FROM orders WHERE user_id = 1;
+         SELECT email FROM users JOIN orders ON orders.user_id = users.id",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    assert!(
+        schema
+            .find_column_usage("users", "email", &queries)
+            .is_empty()
+    );
+}
@@ -0,0 +1,92 @@
+// SPDX-FileCopyrightText: 2026 RAprogramm
+// SPDX-License-Identifier: MIT
+
+use sql_query_analyzer::{
+    output::{StatsOutputFormat, format_usage_stats},
+    query::{SqlDialect, parse_queries},
+    schema::Schema,
+    stats::{UsageCount, usage_stats}
+};
+
+fn count(name: &str, count: usize) -> UsageCount {
+    UsageCount {
+        name: name.to_string(),
+        count
+    }
+}
+
+const QUERIES: &str = "
+    SELECT id, email FROM users WHERE id = 1;
+    SELECT email FROM users WHERE email LIKE 'a%' ORDER BY email;
+    SELECT u.name, o.total FROM users u JOIN orders o ON o.user_id = u.id;
+    UPDATE orders SET total = 0 WHERE status = 'void';
+";
+
+#[test]
+fn test_usage_stats_counts_tables_per_statement() {
+    let queries = parse_queries(QUERIES, SqlDialect::Generic).unwrap();
+    let stats = usage_stats(&queries, None);
+    assert_eq!(stats.queries, 4);
+    assert_eq!(stats.tables, vec![count("users", 3), count("orders", 2)]);
+}
+
+#[test]
+fn test_usage_stats_counts_columns_once_per_statement() {
+    let queries = parse_queries(QUERIES, SqlDialect::Generic).unwrap();
+    let stats = usage_stats(&queries, None);
+    assert_eq!(
+        stats.columns,
+        vec![
+            count("users.email", 2),
+            count("users.id", 2),
+            count("orders.status", 1),
+            count("orders.total", 1),
+            count("orders.user_id", 1),
+            count("users.name", 1)
+        ]
+    );
+}
+
+#[test]
+fn test_usage_stats_json_and_text() {
+    let queries = parse_queries("SELECT id FROM users", SqlDialect::Generic).unwrap();
+    let stats = usage_stats(&queries, None);
+    let json: serde_json::Value =
+        serde_json::from_str(&format_usage_stats(&stats, StatsOutputFormat::Json)).unwrap();
+    assert_eq!(json["tables"][0]["name"], "users");
+    assert_eq!(json["columns"][0]["count"], 1);
+    let text = format_usage_stats(&stats, StatsOutputFormat::Text);
+    assert!(text.contains("Usage across 1 queries"));
+    assert!(text.contains("users.id  1"));
+}
+
+#[test]
+fn test_usage_stats_attributes_join_columns() {
+    let sql = "SELECT u.id, created_at FROM users u JOIN orders o ON o.user_id = u.id \
+               WHERE total > 10 ORDER BY u.created_at;\n\
+               SELECT id, created_at FROM users WHERE id = 1";
+    let queries = parse_queries(sql, SqlDialect::Generic).unwrap();
+    let schema = Schema::parse(
+        "CREATE TABLE users (id INT PRIMARY KEY, created_at TIMESTAMP); \
+         CREATE TABLE orders (id INT PRIMARY KEY, user_id INT, total INT)",
+        SqlDialect::Generic
+    )
+    .unwrap();
+    let stats = usage_stats(&queries, Some(&schema));
+    assert_eq!(
+        stats.columns,
+        vec![
+            count("users.created_at", 2),
+            count("users.id", 2),
+            count("orders.total", 1),
+            count("orders.user_id", 1)
+        ]
+    );
+    let names: Vec<String> = usage_stats(&queries, None)
+        .columns
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    assert!(names.contains(&"users.id".to_string()), "{:?}", names);
+    assert!(!names.contains(&"id".to_string()), "{:?}", names);
+}
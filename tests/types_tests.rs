@@ -34,6 +34,20 @@ fn test_severity_equality() {
     assert_ne!(Severity::Info, Severity::Warning);
 }
 
+#[test]
+fn test_severity_from_str() {
+    assert_eq!("error".parse::<Severity>().unwrap(), Severity::Error);
+    assert_eq!("warn".parse::<Severity>().unwrap(), Severity::Warning);
+    assert_eq!("WARNING".parse::<Severity>().unwrap(), Severity::Warning);
+    assert_eq!("Info".parse::<Severity>().unwrap(), Severity::Info);
+}
+
+#[test]
+fn test_severity_from_str_invalid() {
+    let err = "critical".parse::<Severity>().unwrap_err();
+    assert!(err.to_string().contains("Invalid severity 'critical'"));
+}
+
 #[test]
 fn test_severity_copy() {
     let s = Severity::Error;
@@ -95,7 +109,9 @@ fn test_violation_creation() {
         severity:    Severity::Warning,
         category:    RuleCategory::Performance,
         suggestion:  Some("Fix it".to_string()),
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     };
     assert_eq!(v.rule_id, "TEST001");
     assert_eq!(v.rule_name, "Test Rule");
@@ -111,7 +127,9 @@ fn test_violation_without_suggestion() {
         severity:    Severity::Info,
         category:    RuleCategory::Style,
         suggestion:  None,
-        query_index: 1
+        query_index: 1,
+        location:    None,
+        origin:      None
     };
     assert!(v.suggestion.is_none());
 }
@@ -125,7 +143,9 @@ fn test_violation_clone() {
         severity:    Severity::Error,
         category:    RuleCategory::Security,
         suggestion:  None,
-        query_index: 2
+        query_index: 2,
+        location:    None,
+        origin:      None
     };
     let cloned = v.clone();
     assert_eq!(cloned.rule_id, v.rule_id);
@@ -141,7 +161,9 @@ fn test_violation_debug() {
         severity:    Severity::Warning,
         category:    RuleCategory::Performance,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     };
     let debug = format!("{:?}", v);
     assert!(debug.contains("TEST004"));
@@ -202,7 +224,9 @@ fn test_analysis_report_add_violation() {
         severity:    Severity::Warning,
         category:    RuleCategory::Performance,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     });
     assert_eq!(report.violations.len(), 1);
 }
@@ -217,7 +241,9 @@ fn test_analysis_report_counts() {
         severity:    Severity::Error,
         category:    RuleCategory::Security,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     });
     report.add_violation(Violation {
         rule_id:     "W1",
@@ -226,7 +252,9 @@ fn test_analysis_report_counts() {
         severity:    Severity::Warning,
         category:    RuleCategory::Performance,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     });
     report.add_violation(Violation {
         rule_id:     "I1",
@@ -235,13 +263,78 @@ fn test_analysis_report_counts() {
         severity:    Severity::Info,
         category:    RuleCategory::Style,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     });
     assert_eq!(report.error_count(), 1);
     assert_eq!(report.warning_count(), 1);
     assert_eq!(report.info_count(), 1);
 }
 
+#[test]
+fn test_analysis_report_category_summary() {
+    let mut report = AnalysisReport::new(1, 1);
+    report.add_violation(Violation {
+        rule_id:     "S1",
+        rule_name:   "Security",
+        message:     "Security".to_string(),
+        severity:    Severity::Error,
+        category:    RuleCategory::Security,
+        suggestion:  None,
+        query_index: 0,
+        location:    None,
+        origin:      None
+    });
+    let summary = report.category_summary();
+    assert_eq!(summary[&RuleCategory::Security], 1);
+    assert_eq!(summary[&RuleCategory::Performance], 0);
+    assert_eq!(summary[&RuleCategory::Style], 0);
+    assert_eq!(report.summary().highest_severity, Some(Severity::Error));
+    assert_eq!(AnalysisReport::new(0, 0).highest_severity(), None);
+}
+
+fn report_with(severities: &[Severity]) -> AnalysisReport {
+    let mut report = AnalysisReport::new(1, 1);
+    for &severity in severities {
+        report.add_violation(Violation {
+            rule_id: "R1",
+            rule_name: "Rule",
+            message: String::new(),
+            severity,
+            category: RuleCategory::Style,
+            suggestion: None,
+            query_index: 0,
+            location: None,
+            origin: None
+        });
+    }
+    report
+}
+
+#[test]
+fn test_exit_status_reasons() {
+    let clean = AnalysisReport::new(1, 1).exit_status(Severity::Warning);
+    assert_eq!(clean.exit_code, 0);
+    assert_eq!(clean.exit_reason, "no violations found");
+    let errors = report_with(&[Severity::Error, Severity::Error, Severity::Warning]);
+    let status = errors.exit_status(Severity::Warning);
+    assert_eq!(status.exit_code, 2);
+    assert_eq!(status.exit_reason, "2 errors found");
+    let mixed = report_with(&[Severity::Warning, Severity::Warning, Severity::Info]);
+    assert_eq!(
+        mixed.exit_status(Severity::Info).exit_reason,
+        "2 warnings and 1 info violation found"
+    );
+    assert_eq!(
+        mixed.exit_status(Severity::Warning).exit_reason,
+        "2 warnings found"
+    );
+    let below = mixed.exit_status(Severity::Error);
+    assert_eq!(below.exit_code, 0);
+    assert_eq!(below.exit_reason, "no violations at or above error");
+}
+
 #[test]
 fn test_analysis_report_clone() {
     let mut report = AnalysisReport::new(2, 3);
@@ -252,7 +345,9 @@ fn test_analysis_report_clone() {
         severity:    Severity::Warning,
         category:    RuleCategory::Performance,
         suggestion:  None,
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     });
     let cloned = report.clone();
     assert_eq!(cloned.violations.len(), report.violations.len());
@@ -288,7 +383,9 @@ fn test_violation_serialize() {
         severity:    Severity::Warning,
         category:    RuleCategory::Style,
         suggestion:  Some("Suggestion".to_string()),
-        query_index: 0
+        query_index: 0,
+        location:    None,
+        origin:      None
     };
     let json = serde_json::to_string(&v).unwrap();
     assert!(json.contains("SER001"));